@@ -1,10 +1,13 @@
 use crate::app::state::ContextMenuTarget;
 use crate::models::{
-    BodyFormat, BodyViewMode, EnvironmentOption, FormDataParamType, HttpMethod, RequestTab,
-    Response, ResponseTab,
+    BodyFormat, BodyViewMode, CodeLanguage, CollectionExportFormat, ConditionOperator,
+    ConditionSource, EnvironmentOption, ExtractionSource, FlowReport, FlowTarget,
+    FormDataParamType, HttpMethod, RequestTab, Response, ResponseTab, SchemaSource, VariableScope,
 };
 use crate::ui::toast::Toast;
 use iced::widget::text_editor;
+use std::collections::HashMap;
+use std::path::PathBuf;
 use uuid::Uuid;
 
 #[derive(Debug, Clone)]
@@ -12,6 +15,7 @@ pub enum Message {
     // Request editing
     MethodSelected(HttpMethod),
     UrlChanged(String),
+    ClearInsecure, // Verify TLS certificates again after a `curl -k` import
     EnvironmentOptionSelected(EnvironmentOption),
     AiFill,
     SendRequest,
@@ -23,24 +27,49 @@ pub enum Message {
     HeaderValueChanged(usize, String),
     AddHeader,
     RemoveHeader(usize),
+    ToggleHeaderSecret(usize), // Keep a header value in the secret store or in the request
 
     // Query Params
     ParamKeyChanged(usize, String),
     ParamValueChanged(usize, String),
     AddParam,
     RemoveParam(usize),
+    ToggleParamSecret(usize), // Keep a query parameter value in the secret store or in the request
 
     // Cookies
     CookieKeyChanged(usize, String),
     CookieValueChanged(usize, String),
     AddCookie,
     RemoveCookie(usize),
+    ToggleCookieSecret(usize), // Keep a cookie value in the secret store or in the request
 
     // Auth
     AuthKeyChanged(usize, String),
     AuthValueChanged(usize, String),
     AddAuthField,
     RemoveAuthField(usize),
+    ToggleAuthSecret(usize), // Keep an auth field in the secret store or in the request
+
+    // Response extraction rules
+    ExtractionVariableChanged(usize, String),
+    ExtractionSourceChanged(usize, ExtractionSource),
+    ExtractionExpressionChanged(usize, String),
+    ExtractionScopeChanged(usize, VariableScope),
+    AddExtraction,
+    RemoveExtraction(usize),
+
+    // Response schema
+    ToggleResponseSchema,
+    SchemaSourceSelected(SchemaSource),
+    SchemaEditorAction(text_editor::Action), // Text editor action for the inline schema
+    SchemaFileChanged(String),
+    BrowseSchemaFile,
+    SchemaFileSelected(Option<String>),
+
+    // Snapshot ignore rules of the pinned example
+    SnapshotIgnoreChanged(usize, String),
+    AddSnapshotIgnore,
+    RemoveSnapshotIgnore(usize),
 
     // Body
     BodyChanged(String),
@@ -140,4 +169,134 @@ pub enum Message {
     // Keyboard shortcuts
     ShowShortcutsDialog,
     CloseShortcutsDialog,
+
+    // Collection runner
+    ShowRunnerDialog(Vec<usize>), // Open the runner for a collection or folder
+    CloseRunnerDialog,
+    RunnerIterationsChanged(String),
+    RunnerDelayChanged(String),
+    ToggleRunnerStopOnFailure,
+    SelectRunnerDataFile,
+    RunnerDataFileLoaded(Result<(String, Vec<HashMap<String, String>>), String>), // (path, rows) or error
+    ClearRunnerDataFile,
+    StartCollectionRun,
+    CollectionRunCompleted(crate::models::RunReport),
+    SaveRunReport,
+
+    // Blast mode (load testing)
+    ShowBlastDialog(Vec<usize>), // Open the load test for a saved request
+    CloseBlastDialog,
+    BlastConcurrencyChanged(String),
+    BlastUseDuration(bool),
+    BlastTotalRequestsChanged(String),
+    BlastDurationChanged(String),
+    BlastRampUpChanged(String),
+    BlastRateLimitChanged(String),
+    StartBlast,
+    StopBlast,
+    BlastEvent(crate::blast::BlastEvent),
+    ExportBlastReport(crate::models::BlastExportFormat),
+
+    // Code generation
+    ShowCodeDialog(Option<Vec<usize>>), // "Copy as..." for a saved request, None for the open one
+    CloseCodeDialog,
+    CodeLanguageSelected(CodeLanguage),
+    ToggleCodeResolveVariables,
+    CopyGeneratedCode,
+
+    // Import
+    ImportFile, // Pick a file exported from another tool
+    ImportFileLoaded(Result<crate::models::ImportFile, String>),
+    CloseImportReport,
+    ToggleHarDomain(String), // Include or leave out a domain of a HAR archive
+    ToggleHarResourceType(String), // Include or leave out a resource type of a HAR archive
+    ConfirmHarImport,
+    CloseHarImport,
+    OpenHttpFile,          // Open a `.http` file as a collection kept in sync with it
+    ReloadHttpFile(usize), // Read the `.http` file of a collection again
+
+    // Export
+    ShowExportDialog(Vec<usize>), // Collection or folder path
+    CloseExportDialog,
+    ExportFormatSelected(CollectionExportFormat),
+    ExportCollection(Vec<usize>, CollectionExportFormat),
+    ExportHistoryHar, // Save the request history as a HAR archive
+
+    // Flows
+    AddNewFlow(Vec<usize>), // Add a flow under a collection or folder
+    OpenFlow(Vec<usize>),   // Open the flow editor
+    CloseFlowEditor,
+    SaveFlow,
+    AddFlowStep,
+    RemoveFlowStep(usize),
+    MoveFlowStepUp(usize),
+    FlowStepEdited(usize, FlowStepEdit),
+    RunFlow,
+    FlowCompleted(FlowReport),
+
+    // Mock server
+    SaveResponseExample, // Pin the current response as the request's example
+    ShowMockServerDialog(Vec<usize>), // Open the mock server for a collection
+    CloseMockServerDialog,
+    MockPortChanged(String),
+    MockLatencyChanged(String),
+    StartMockServer,
+    StopMockServer,
+    MockServerEvent(crate::mock_server::MockEvent),
+    ClearMockHits,
+
+    // External changes
+    CheckStorage,     // Reload collections changed on disk since the last check
+    KeepMyChanges,    // Resolve the first reload conflict with the edits made here
+    TakeTheirChanges, // Resolve the first reload conflict with the files on disk
+    ToggleReloadDiff,
+
+    // Recovery of unreadable files
+    RestoreBackup(PathBuf, PathBuf), // Put a backup (second) in place of an unreadable file (first)
+    OpenRawFile(PathBuf),            // Open an unreadable file with the system's default program
+    CloseRecoveryDialog,
+
+    // Secrets
+    ToggleSecretVariable(String), // Mark a variable of the current environment secret or not
+    SecretsPassphraseChanged(String),
+    UnlockSecrets,
+    CloseUnlockSecrets,
+    SetSecretsPassphrase, // Encrypt the secret store with the entered passphrase, or stop if empty
+
+    // History
+    ShowHistory(bool), // Whether the sidebar lists the history instead of collections
+    HistoryQueryChanged(String),
+    OpenHistoryEntry(Uuid), // Re-open a sent request in a new tab
+    SaveHistoryEntry(Uuid), // Save a sent request into the selected collection
+    HistoryRetentionChanged(String),
+    PurgeHistory, // Delete entries older than the retention period
+    ClearHistory,
+
+    // Undo
+    Undo, // Go back to before the last collection or request edit
+    Redo,
+}
+
+/// Edit applied to one step in the flow editor
+#[derive(Debug, Clone)]
+pub enum FlowStepEdit {
+    Request(Uuid),
+    Wait(String),
+    ToggleLoop,
+    LoopCondition(ConditionEdit),
+    MaxAttempts(String),
+    Interval(String),
+    ToggleBranch,
+    BranchCondition(ConditionEdit),
+    OnTrue(FlowTarget),
+    OnFalse(FlowTarget),
+}
+
+/// Edit applied to a loop or branch condition
+#[derive(Debug, Clone)]
+pub enum ConditionEdit {
+    Source(ConditionSource),
+    Variable(String),
+    Operator(ConditionOperator),
+    Value(String),
 }
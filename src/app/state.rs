@@ -1,15 +1,26 @@
 use crate::ai_client::AiClient;
 use crate::app::Message;
+use crate::config::HistoryConfig;
 use crate::i18n::{I18n, Language, Translations};
 use crate::models::{
-    AiConfig, BodyFormat, BodyType, BodyViewMode, Collection, CollectionItem, Environment,
-    Request, RequestTab, Response, ResponseTab, ShortcutRegistry,
+    AiConfig, BlastReport, BlastSummary, BodyFormat, BodyType, BodyViewMode, CodeLanguage,
+    Collection, CollectionExportFormat, CollectionItem, Environment, EnvironmentVariables,
+    ExtractionRule, Flow, FlowReport, HarArchive, HarFilter, HistoryEntry, ImportReport, MockHit,
+    ReloadConflict, Request, RequestTab, Response, ResponseSchema, ResponseTab, RunReport,
+    SchemaViolation, ShortcutRegistry, SnapshotDiff,
 };
+use crate::secrets::SecretStore;
+use crate::storage::LoadFailure;
 use crate::ui::toast::Toast;
 use crate::utils::navigation;
+use crate::utils::undo::UndoStack;
 use iced::widget::{text_editor, Id};
 use iced::{event, keyboard, mouse, Element, Event, Subscription};
 use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+use std::time::Duration;
 use uuid::Uuid;
 
 #[derive(Debug, Clone)]
@@ -23,6 +34,28 @@ pub struct RequestTabItem {
     pub parent_path: Option<Vec<usize>>, // Parent path for saving new requests
 }
 
+/// What an edit changed, as it was before, for undo and redo
+#[derive(Debug, Clone)]
+pub enum EditSnapshot {
+    /// A request, saved in a collection or open unsaved in its tab
+    Request(Request),
+    /// The collections a tree edit touched, with the unsaved request of the
+    /// tab that was active, shown again when going back
+    Collections {
+        collections: Vec<CollectionSnapshot>,
+        draft: Option<Request>,
+        tab: Option<Uuid>,
+    },
+}
+
+/// A collection and where it was, `None` for one the edit added
+#[derive(Debug, Clone)]
+pub struct CollectionSnapshot {
+    pub id: Uuid,
+    pub index: usize,
+    pub collection: Option<Collection>,
+}
+
 #[derive(Debug, Clone)]
 pub struct DragState {
     pub dragging_tab_index: usize,
@@ -44,6 +77,7 @@ pub struct TabPressState {
 pub enum ContextMenuTarget {
     Request,
     Folder,
+    Flow,
     Collection,
     EmptyArea, // Empty area in request list for creating new collections
 }
@@ -56,6 +90,95 @@ pub struct ContextMenu {
     pub target: ContextMenuTarget,
 }
 
+#[derive(Debug, Clone)]
+pub struct RunnerState {
+    pub path: Vec<usize>,          // Path of the collection or folder being run
+    pub name: String,              // Display name of the run target
+    pub iterations: String,        // Iteration count as typed by the user
+    pub delay_ms: String,          // Delay between requests as typed by the user
+    pub stop_on_failure: bool,     // Abort the run at the first failing request
+    pub data_file: Option<String>, // Path of the CSV/JSON data file, if attached
+    pub data_rows: Vec<HashMap<String, String>>, // Rows loaded from the data file
+    pub running: bool,             // Whether the run is in progress
+    pub report: Option<RunReport>, // Report of the last finished run
+}
+
+#[derive(Debug, Clone)]
+pub struct BlastState {
+    pub request: Request,       // Request being load tested, variables resolved
+    pub concurrency: String,    // Worker count as typed by the user
+    pub use_duration: bool,     // Limit by duration instead of request count
+    pub total_requests: String, // Request count limit as typed by the user
+    pub duration_secs: String,  // Duration limit as typed by the user
+    pub ramp_up_secs: String,   // Ramp-up time as typed by the user
+    pub rate_limit: String,     // Requests per second cap, empty for none
+    pub running: bool,          // Whether the run is in progress
+    pub stop: Arc<AtomicBool>,  // Signals the workers to stop early
+    pub summary: Option<BlastSummary>, // Latest live or final statistics
+    pub report: Option<BlastReport>, // Report of the last finished run
+}
+
+#[derive(Debug, Clone)]
+pub struct CodeDialogState {
+    pub request: Request, // Request as written, placeholders intact
+    pub variables: HashMap<String, String>, // Active variables when the dialog opened
+    pub language: CodeLanguage, // Selected target language
+    pub resolve_variables: bool, // Inline variable values instead of placeholders
+}
+
+impl CodeDialogState {
+    /// Snippet for the selected language and variable mode
+    pub fn code(&self) -> String {
+        if self.resolve_variables {
+            let resolved = crate::utils::variables::resolve_request(&self.request, &self.variables);
+            crate::utils::codegen::generate(&resolved, self.language)
+        } else {
+            crate::utils::codegen::generate(&self.request, self.language)
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ExportDialogState {
+    pub path: Vec<usize>, // Path of the collection or folder being exported
+    pub name: String,     // Its name, shown in the dialog and used for the file name
+    pub format: CollectionExportFormat, // Selected export format
+}
+
+#[derive(Debug, Clone)]
+pub struct HarImportState {
+    pub archive: HarArchive, // Entries read from the archive
+    pub filter: HarFilter,   // Domains and resource types left out
+}
+
+#[derive(Debug, Clone)]
+pub struct ReloadConflictState {
+    pub collection: Collection, // Collection read from disk, other unsaved edits applied
+    pub conflicts: Vec<ReloadConflict>, // Requests edited here that also changed on disk
+    pub show_diff: bool,        // Whether the diff of the conflicting requests is shown
+}
+
+#[derive(Debug, Clone)]
+pub struct FlowEditorState {
+    pub path: Vec<usize>,           // Path of the flow item in the sidebar
+    pub flow: Flow,                 // Working copy, written back on save
+    pub requests: Vec<Request>,     // Requests of the collection that steps can use
+    pub running: bool,              // Whether the flow is executing
+    pub report: Option<FlowReport>, // Log of the last execution
+}
+
+#[derive(Debug, Clone)]
+pub struct MockServerState {
+    pub collection_id: Uuid,   // Collection being served
+    pub name: String,          // Display name of the collection
+    pub port: String,          // Port as typed by the user
+    pub latency_ms: String,    // Added latency as typed by the user
+    pub running: bool,         // Whether the server is listening
+    pub stop: Arc<AtomicBool>, // Signals the server to stop
+    pub hits: Vec<MockHit>,    // Incoming requests, oldest first
+    pub show_dialog: bool,     // The server keeps running while the dialog is hidden
+}
+
 pub struct Requiem {
     pub collections: Vec<Collection>,
     pub selected_collection: Option<usize>,
@@ -103,6 +226,37 @@ pub struct Requiem {
     pub window_height: f32,            // Current window height for accurate vertical split calculation
     pub shortcut_registry: ShortcutRegistry, // Keyboard shortcut registry
     pub show_shortcuts_dialog: bool,   // Whether to show shortcuts help dialog
+    pub environment_variables: EnvironmentVariables, // Persisted variables per environment
+    pub runtime_variables: HashMap<String, String>, // Variables captured for this session only
+    pub pending_extractions: Vec<ExtractionRule>, // Extraction rules of the in-flight request
+    pub pending_schema: Option<ResponseSchema>, // Schema of the in-flight request
+    pub schema_result: Option<Result<Vec<SchemaViolation>, String>>, // Validation of the current response
+    pub schema_content: text_editor::Content, // Text editor content for the inline schema
+    pub snapshot_diff: Option<SnapshotDiff>, // Diff against the pinned example
+    pub runner: Option<RunnerState>, // Collection runner dialog state
+    pub blast: Option<BlastState>, // Blast (load test) dialog state
+    pub code_dialog: Option<CodeDialogState>, // "Copy as..." code generation dialog state
+    pub import_report: Option<ImportReport>, // Outcome of the last import, shown until dismissed
+    pub export_dialog: Option<ExportDialogState>, // Collection export dialog state
+    pub last_export_format: CollectionExportFormat, // Preselected when the export dialog opens
+    pub har_import: Option<HarImportState>, // HAR entries waiting for the user to filter them
+    pub history: Vec<HistoryEntry>, // Sent requests kept under the retention policy, oldest first
+    pub pending_history: Option<HistoryEntry>, // History entry of the in-flight request
+    pub history_config: HistoryConfig, // What is kept of sent requests, and for how long
+    pub show_history: bool,         // Whether the sidebar lists the history instead of collections
+    pub history_query: String,      // Search in the history sidebar
+    pub undo: UndoStack<EditSnapshot>, // Collection and request edits that can be undone
+    pub recording_edit: bool, // Whether an edit is being recorded, so nested updates are part of it
+    pub flow_editor: Option<FlowEditorState>, // Flow editor dialog state
+    pub mock_server: Option<MockServerState>, // Mock server state and hit log
+    pub saved_collections: HashMap<Uuid, Collection>, // Collections as last read from or written to disk
+    pub storage_fingerprint: u64, // Fingerprint of the save directory when last checked
+    pub reload_conflicts: Vec<ReloadConflictState>, // External edits waiting for the user to resolve them
+    pub load_failures: Vec<LoadFailure>,            // Collection files that could not be read
+    pub show_recovery_dialog: bool, // Whether to show the recovery screen for unreadable files
+    pub secrets: SecretStore,       // Secret values kept out of collection and config files
+    pub secrets_passphrase: String, // Passphrase typed to unlock or encrypt the secret store
+    pub show_unlock_secrets: bool,  // Whether to ask for the passphrase of the secret store
 }
 
 impl Requiem {
@@ -116,23 +270,50 @@ impl Requiem {
 
         // Use save directory and AI config from config
         let save_directory = config.save_directory.clone();
-        let ai_config = config.ai_config.clone();
+        let mut ai_config = config.ai_config.clone();
+
+        // Secret values live apart from collections and config, possibly encrypted
+        let secrets = SecretStore::load(&save_directory, None).unwrap_or_else(|e| {
+            tracing::error!("Failed to load secrets: {}", e);
+            SecretStore::unreadable()
+        });
+        crate::secrets::resolve_ai_config(&mut ai_config, &secrets);
 
         // Try to load collections from disk
-        let collections = match crate::storage::load_collections(&save_directory) {
-            Ok(loaded_collections) => {
-                if loaded_collections.is_empty() {
-                    tracing::info!("No saved collections found, starting with empty collections");
-                } else {
-                    tracing::info!("Loaded {} collections from disk", loaded_collections.len());
+        let (mut collections, load_failures) =
+            match crate::storage::load_collections_with_failures(&save_directory) {
+                Ok((loaded_collections, failures)) => {
+                    if loaded_collections.is_empty() {
+                        tracing::info!(
+                            "No saved collections found, starting with empty collections"
+                        );
+                    } else {
+                        tracing::info!("Loaded {} collections from disk", loaded_collections.len());
+                    }
+                    (loaded_collections, failures)
                 }
-                loaded_collections
-            }
-            Err(e) => {
-                tracing::error!("Failed to load collections: {}", e);
-                vec![]
-            }
-        };
+                Err(e) => {
+                    tracing::error!("Failed to load collections: {}", e);
+                    (vec![], vec![])
+                }
+            };
+
+        for collection in &mut collections {
+            crate::secrets::resolve_collection(collection, &secrets);
+        }
+        let saved_collections = collections.iter().map(|c| (c.id, c.clone())).collect();
+        let storage_fingerprint =
+            crate::storage::fingerprint(&save_directory, &linked_files(&collections));
+
+        let mut environment_variables =
+            match crate::storage::load_environment_variables(&save_directory) {
+                Ok(variables) => variables,
+                Err(e) => {
+                    tracing::error!("Failed to load environment variables: {}", e);
+                    EnvironmentVariables::default()
+                }
+            };
+        crate::secrets::resolve_environments(&mut environment_variables, &secrets);
 
         // Get first request for initial tab (if available)
         let (open_tabs, selected_request, selected_collection) =
@@ -172,7 +353,7 @@ impl Requiem {
             text_editor::Content::new()
         };
 
-        Self {
+        let mut state = Self {
             collections,
             selected_collection,
             selected_request,
@@ -219,7 +400,47 @@ impl Requiem {
             window_height: 800.0,          // Default window height, will be updated
             shortcut_registry: ShortcutRegistry::new(),
             show_shortcuts_dialog: false,
+            environment_variables,
+            runtime_variables: HashMap::new(),
+            pending_extractions: vec![],
+            pending_schema: None,
+            schema_result: None,
+            schema_content: text_editor::Content::new(),
+            snapshot_diff: None,
+            runner: None,
+            blast: None,
+            code_dialog: None,
+            import_report: None,
+            export_dialog: None,
+            last_export_format: CollectionExportFormat::default(),
+            har_import: None,
+            history: Vec::new(),
+            pending_history: None,
+            history_config: config.history,
+            show_history: false,
+            history_query: String::new(),
+            undo: UndoStack::default(),
+            recording_edit: false,
+            flow_editor: None,
+            mock_server: None,
+            saved_collections,
+            storage_fingerprint,
+            reload_conflicts: vec![],
+            show_recovery_dialog: !load_failures.is_empty(),
+            load_failures,
+            show_unlock_secrets: secrets.is_locked(),
+            secrets,
+            secrets_passphrase: String::new(),
+        };
+
+        // API keys saved before there was a secret store move into it
+        if crate::secrets::has_plain_api_key(&state.ai_config) && !state.secrets.is_locked() {
+            if let Err(e) = state.save_ai_config() {
+                tracing::error!("Failed to move the API key to the secret store: {}", e);
+            }
         }
+        state.reload_history();
+        state
     }
 
     pub fn get_current_request(&self) -> Option<&Request> {
@@ -271,6 +492,21 @@ impl Requiem {
         })
     }
 
+    /// Variables visible to requests: the active environment, overridden by runtime values
+    pub fn active_variables(&self) -> HashMap<String, String> {
+        let mut variables = self
+            .environment_variables
+            .get(self.current_environment)
+            .cloned()
+            .unwrap_or_default();
+        variables.extend(
+            self.runtime_variables
+                .iter()
+                .map(|(k, v)| (k.clone(), v.clone())),
+        );
+        variables
+    }
+
     pub fn get_item_by_path(&self, path: &[usize]) -> Option<&CollectionItem> {
         navigation::get_item_by_path(&self.collections, path)
     }
@@ -280,9 +516,16 @@ impl Requiem {
     }
 
     /// Save a specific collection to disk
-    pub fn save_collection(&self, collection_index: usize) -> Result<(), String> {
+    pub fn save_collection(&mut self, collection_index: usize) -> Result<(), String> {
         if let Some(collection) = self.collections.get(collection_index) {
-            crate::storage::save_collection(&self.save_directory, collection)
+            let stored = crate::secrets::extract_collection(collection, &mut self.secrets)?;
+            self.secrets.save(&self.save_directory)?;
+            crate::storage::save_collection(&self.save_directory, &stored)?;
+            self.saved_collections
+                .insert(collection.id, collection.clone());
+            // Our own writes are not external changes
+            self.storage_fingerprint = self.fingerprint_storage();
+            Ok(())
         } else {
             Err(format!(
                 "Collection at index {} not found",
@@ -291,17 +534,42 @@ impl Requiem {
         }
     }
 
+    /// Save the environment variables, keeping secret ones in the secret store
+    pub fn save_environment_variables(&mut self) -> Result<(), String> {
+        let stored =
+            crate::secrets::extract_environments(&self.environment_variables, &mut self.secrets)?;
+        self.secrets.save(&self.save_directory)?;
+        crate::storage::save_environment_variables(&self.save_directory, &stored)
+    }
+
+    /// Save the AI settings, keeping the API key in the secret store
+    pub fn save_ai_config(&mut self) -> Result<(), String> {
+        let ai_config = crate::secrets::extract_ai_config(&self.ai_config, &mut self.secrets)?;
+        self.secrets.save(&self.save_directory)?;
+        crate::config::Config::load()
+            .set_ai_config(ai_config)
+            .map_err(|e| e.to_string())
+    }
+
     /// Save all collections to disk
-    pub fn save_all_collections(&self) -> Result<(), String> {
-        for collection in &self.collections {
-            crate::storage::save_collection(&self.save_directory, collection)?;
+    pub fn save_all_collections(&mut self) -> Result<(), String> {
+        for index in 0..self.collections.len() {
+            self.save_collection(index)?;
         }
         Ok(())
     }
 
     /// Delete a collection from disk
-    pub fn delete_collection_file(&self, collection_id: &uuid::Uuid) -> Result<(), String> {
-        crate::storage::delete_collection(&self.save_directory, collection_id)
+    pub fn delete_collection_file(&mut self, collection_id: &uuid::Uuid) -> Result<(), String> {
+        crate::storage::delete_collection(&self.save_directory, collection_id)?;
+        self.saved_collections.remove(collection_id);
+        self.storage_fingerprint = self.fingerprint_storage();
+        Ok(())
+    }
+
+    /// Fingerprint of the save directory and the `.http` files linked to collections
+    pub fn fingerprint_storage(&self) -> u64 {
+        crate::storage::fingerprint(&self.save_directory, &linked_files(&self.collections))
     }
 
     /// View function for iced application
@@ -311,7 +579,11 @@ impl Requiem {
 
     /// Subscription function for iced application
     pub fn subscription(&self) -> Subscription<Message> {
-        event::listen_with(Self::handle_event)
+        Subscription::batch([
+            event::listen_with(Self::handle_event),
+            // Poll for collections edited by other programs
+            iced::time::every(Duration::from_secs(2)).map(|_| Message::CheckStorage),
+        ])
     }
 
     /// Handle events for the application
@@ -330,6 +602,17 @@ impl Requiem {
             return Some(Message::WindowResized(size.width, size.height));
         }
 
+        // Text inputs have no undo of their own, so undo and redo also work
+        // while one has focus
+        if let Event::Keyboard(keyboard::Event::KeyPressed { key, modifiers, .. }) = &event {
+            use crate::models::ShortcutAction;
+            if let Some(action @ (ShortcutAction::Undo | ShortcutAction::Redo)) =
+                ShortcutRegistry::new().find_action(key, modifiers)
+            {
+                return Self::shortcut_to_message(action);
+            }
+        }
+
         // Only handle other events if not captured by widgets
         if matches!(status, event::Status::Captured) {
             return None;
@@ -387,11 +670,21 @@ impl Requiem {
             ShortcutAction::ManageEnvironments => Some(Message::ShowEnvironmentDialog),
             ShortcutAction::CopyResponse => Some(Message::CopyResponseBody),
             ShortcutAction::FormatJson => Some(Message::FormatRequestBodyJson),
+            ShortcutAction::Undo => Some(Message::Undo),
+            ShortcutAction::Redo => Some(Message::Redo),
             _ => None, // Other actions not yet mapped
         }
     }
 }
 
+/// `.http` files that collections are kept in sync with
+fn linked_files(collections: &[Collection]) -> Vec<PathBuf> {
+    collections
+        .iter()
+        .filter_map(|c| c.http_file.as_ref().map(|link| link.path.clone()))
+        .collect()
+}
+
 impl Default for Requiem {
     fn default() -> Self {
         Self::new()
@@ -1,8 +1,22 @@
+mod blast;
+mod codegen;
 mod collection;
+mod export;
+mod flow;
+mod history;
+mod import;
 mod key_value;
+mod mock;
+mod recovery;
+mod reload;
 mod request;
+mod runner;
+mod schema;
+mod secrets;
+mod snapshot;
 mod tabs;
 mod ui;
+mod undo;
 
 use iced::Task;
 
@@ -11,10 +25,18 @@ use super::state::Requiem;
 
 impl Requiem {
     pub fn update(&mut self, message: Message) -> Task<Message> {
+        match undo::edit_of(&message) {
+            Some(edit) if !self.recording_edit => self.record_edit(edit, message),
+            _ => self.dispatch(message),
+        }
+    }
+
+    fn dispatch(&mut self, message: Message) -> Task<Message> {
         match message {
             // ============ Request Operations ============
             Message::MethodSelected(method) => self.handle_method_selected(method),
             Message::UrlChanged(url) => self.handle_url_changed(url),
+            Message::ClearInsecure => self.handle_clear_insecure(),
             Message::AiFill => self.handle_ai_fill(),
             Message::SendRequest => self.handle_send_request(),
             Message::CancelRequest => self.handle_cancel_request(),
@@ -45,21 +67,52 @@ impl Requiem {
             Message::HeaderValueChanged(idx, value) => self.handle_header_value_changed(idx, value),
             Message::AddHeader => self.handle_add_header(),
             Message::RemoveHeader(idx) => self.handle_remove_header(idx),
+            Message::ToggleHeaderSecret(idx) => self.handle_toggle_header_secret(idx),
 
             Message::ParamKeyChanged(idx, key) => self.handle_param_key_changed(idx, key),
             Message::ParamValueChanged(idx, value) => self.handle_param_value_changed(idx, value),
             Message::AddParam => self.handle_add_param(),
             Message::RemoveParam(idx) => self.handle_remove_param(idx),
+            Message::ToggleParamSecret(idx) => self.handle_toggle_param_secret(idx),
 
             Message::CookieKeyChanged(idx, key) => self.handle_cookie_key_changed(idx, key),
             Message::CookieValueChanged(idx, value) => self.handle_cookie_value_changed(idx, value),
             Message::AddCookie => self.handle_add_cookie(),
             Message::RemoveCookie(idx) => self.handle_remove_cookie(idx),
+            Message::ToggleCookieSecret(idx) => self.handle_toggle_cookie_secret(idx),
 
             Message::AuthKeyChanged(idx, key) => self.handle_auth_key_changed(idx, key),
             Message::AuthValueChanged(idx, value) => self.handle_auth_value_changed(idx, value),
             Message::AddAuthField => self.handle_add_auth_field(),
             Message::RemoveAuthField(idx) => self.handle_remove_auth_field(idx),
+            Message::ToggleAuthSecret(idx) => self.handle_toggle_auth_secret(idx),
+
+            Message::ExtractionVariableChanged(idx, variable) => {
+                self.handle_extraction_variable_changed(idx, variable)
+            }
+            Message::ExtractionSourceChanged(idx, source) => {
+                self.handle_extraction_source_changed(idx, source)
+            }
+            Message::ExtractionExpressionChanged(idx, expression) => {
+                self.handle_extraction_expression_changed(idx, expression)
+            }
+            Message::ExtractionScopeChanged(idx, scope) => {
+                self.handle_extraction_scope_changed(idx, scope)
+            }
+            Message::AddExtraction => self.handle_add_extraction(),
+            Message::RemoveExtraction(idx) => self.handle_remove_extraction(idx),
+
+            Message::ToggleResponseSchema => self.handle_toggle_response_schema(),
+            Message::SchemaSourceSelected(source) => self.handle_schema_source_selected(source),
+            Message::SchemaEditorAction(action) => self.handle_schema_editor_action(action),
+            Message::SchemaFileChanged(path) => self.handle_schema_file_changed(path),
+            Message::BrowseSchemaFile => self.handle_browse_schema_file(),
+            Message::SchemaFileSelected(path) => self.handle_schema_file_selected(path),
+            Message::SnapshotIgnoreChanged(idx, rule) => {
+                self.handle_snapshot_ignore_changed(idx, rule)
+            }
+            Message::AddSnapshotIgnore => self.handle_add_snapshot_ignore(),
+            Message::RemoveSnapshotIgnore(idx) => self.handle_remove_snapshot_ignore(idx),
 
             // ============ Collection Operations ============
             Message::SelectRequest(path) => self.handle_select_request(path),
@@ -134,6 +187,118 @@ impl Requiem {
             // ============ Keyboard Shortcuts ============
             Message::ShowShortcutsDialog => self.handle_show_shortcuts_dialog(),
             Message::CloseShortcutsDialog => self.handle_close_shortcuts_dialog(),
+
+            // ============ Collection Runner ============
+            Message::ShowRunnerDialog(path) => self.handle_show_runner_dialog(path),
+            Message::CloseRunnerDialog => self.handle_close_runner_dialog(),
+            Message::RunnerIterationsChanged(value) => self.handle_runner_iterations_changed(value),
+            Message::RunnerDelayChanged(value) => self.handle_runner_delay_changed(value),
+            Message::ToggleRunnerStopOnFailure => self.handle_toggle_runner_stop_on_failure(),
+            Message::SelectRunnerDataFile => self.handle_select_runner_data_file(),
+            Message::RunnerDataFileLoaded(result) => self.handle_runner_data_file_loaded(result),
+            Message::ClearRunnerDataFile => self.handle_clear_runner_data_file(),
+            Message::StartCollectionRun => self.handle_start_collection_run(),
+            Message::CollectionRunCompleted(report) => self.handle_collection_run_completed(report),
+            Message::SaveRunReport => self.handle_save_run_report(),
+
+            // ============ Blast Mode ============
+            Message::ShowBlastDialog(path) => self.handle_show_blast_dialog(path),
+            Message::CloseBlastDialog => self.handle_close_blast_dialog(),
+            Message::BlastConcurrencyChanged(value) => self.handle_blast_concurrency_changed(value),
+            Message::BlastUseDuration(use_duration) => self.handle_blast_use_duration(use_duration),
+            Message::BlastTotalRequestsChanged(value) => {
+                self.handle_blast_total_requests_changed(value)
+            }
+            Message::BlastDurationChanged(value) => self.handle_blast_duration_changed(value),
+            Message::BlastRampUpChanged(value) => self.handle_blast_ramp_up_changed(value),
+            Message::BlastRateLimitChanged(value) => self.handle_blast_rate_limit_changed(value),
+            Message::StartBlast => self.handle_start_blast(),
+            Message::StopBlast => self.handle_stop_blast(),
+            Message::BlastEvent(event) => self.handle_blast_event(event),
+            Message::ExportBlastReport(format) => self.handle_export_blast_report(format),
+
+            // ============ Code Generation ============
+            Message::ShowCodeDialog(path) => self.handle_show_code_dialog(path),
+            Message::CloseCodeDialog => self.handle_close_code_dialog(),
+            Message::CodeLanguageSelected(language) => self.handle_code_language_selected(language),
+            Message::ToggleCodeResolveVariables => self.handle_toggle_code_resolve_variables(),
+            Message::CopyGeneratedCode => self.handle_copy_generated_code(),
+
+            // ============ Import ============
+            Message::ImportFile => self.handle_import_file(),
+            Message::ImportFileLoaded(result) => self.handle_import_file_loaded(result),
+            Message::CloseImportReport => self.handle_close_import_report(),
+            Message::ToggleHarDomain(domain) => self.handle_toggle_har_domain(domain),
+            Message::ToggleHarResourceType(resource_type) => {
+                self.handle_toggle_har_resource_type(resource_type)
+            }
+            Message::ConfirmHarImport => self.handle_confirm_har_import(),
+            Message::CloseHarImport => self.handle_close_har_import(),
+            Message::OpenHttpFile => self.handle_open_http_file(),
+            Message::ReloadHttpFile(index) => self.handle_reload_http_file(index),
+
+            // ============ Export ============
+            Message::ShowExportDialog(path) => self.handle_show_export_dialog(path),
+            Message::CloseExportDialog => self.handle_close_export_dialog(),
+            Message::ExportFormatSelected(format) => self.handle_export_format_selected(format),
+            Message::ExportCollection(path, format) => self.handle_export_collection(path, format),
+            Message::ExportHistoryHar => self.handle_export_history_har(),
+
+            // ============ Flows ============
+            Message::AddNewFlow(parent_path) => self.handle_add_new_flow(parent_path),
+            Message::OpenFlow(path) => self.handle_open_flow(path),
+            Message::CloseFlowEditor => self.handle_close_flow_editor(),
+            Message::SaveFlow => self.handle_save_flow(),
+            Message::AddFlowStep => self.handle_add_flow_step(),
+            Message::RemoveFlowStep(index) => self.handle_remove_flow_step(index),
+            Message::MoveFlowStepUp(index) => self.handle_move_flow_step_up(index),
+            Message::FlowStepEdited(index, edit) => self.handle_flow_step_edited(index, edit),
+            Message::RunFlow => self.handle_run_flow(),
+            Message::FlowCompleted(report) => self.handle_flow_completed(report),
+
+            // ============ Mock Server ============
+            Message::SaveResponseExample => self.handle_save_response_example(),
+            Message::ShowMockServerDialog(path) => self.handle_show_mock_server_dialog(path),
+            Message::CloseMockServerDialog => self.handle_close_mock_server_dialog(),
+            Message::MockPortChanged(value) => self.handle_mock_port_changed(value),
+            Message::MockLatencyChanged(value) => self.handle_mock_latency_changed(value),
+            Message::StartMockServer => self.handle_start_mock_server(),
+            Message::StopMockServer => self.handle_stop_mock_server(),
+            Message::MockServerEvent(event) => self.handle_mock_server_event(event),
+            Message::ClearMockHits => self.handle_clear_mock_hits(),
+
+            // ============ External Changes ============
+            Message::CheckStorage => self.handle_check_storage(),
+            Message::KeepMyChanges => self.handle_keep_my_changes(),
+            Message::TakeTheirChanges => self.handle_take_their_changes(),
+            Message::ToggleReloadDiff => self.handle_toggle_reload_diff(),
+
+            // ============ Recovery ============
+            Message::RestoreBackup(path, backup) => self.handle_restore_backup(path, backup),
+            Message::OpenRawFile(path) => self.handle_open_raw_file(path),
+            Message::CloseRecoveryDialog => self.handle_close_recovery_dialog(),
+
+            // ============ Secrets ============
+            Message::ToggleSecretVariable(name) => self.handle_toggle_secret_variable(name),
+            Message::SecretsPassphraseChanged(value) => {
+                self.handle_secrets_passphrase_changed(value)
+            }
+            Message::UnlockSecrets => self.handle_unlock_secrets(),
+            Message::CloseUnlockSecrets => self.handle_close_unlock_secrets(),
+            Message::SetSecretsPassphrase => self.handle_set_secrets_passphrase(),
+
+            // ============ History ============
+            Message::ShowHistory(show) => self.handle_show_history(show),
+            Message::HistoryQueryChanged(query) => self.handle_history_query_changed(query),
+            Message::OpenHistoryEntry(id) => self.handle_open_history_entry(id),
+            Message::SaveHistoryEntry(id) => self.handle_save_history_entry(id),
+            Message::HistoryRetentionChanged(value) => self.handle_history_retention_changed(value),
+            Message::PurgeHistory => self.handle_purge_history(),
+            Message::ClearHistory => self.handle_clear_history(),
+
+            // ============ Undo ============
+            Message::Undo => self.handle_undo(),
+            Message::Redo => self.handle_redo(),
         }
     }
 }
@@ -0,0 +1,227 @@
+use iced::Task;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tracing::{error, info};
+
+use crate::blast::{self, BlastEvent};
+use crate::models::{BlastConfig, BlastExportFormat, BlastLimit, CollectionItem};
+use crate::ui::toast::Toast;
+use crate::utils::variables;
+
+use super::super::message::Message;
+use super::super::state::{BlastState, Requiem};
+
+/// Keep only digits so numeric inputs always parse
+fn digits_only(value: &str) -> bool {
+    value.chars().all(|c| c.is_ascii_digit())
+}
+
+impl Requiem {
+    /// Open the load test dialog for a saved request
+    pub fn handle_show_blast_dialog(&mut self, path: Vec<usize>) -> Task<Message> {
+        self.context_menu = None;
+
+        let Some(CollectionItem::Request(request)) = self.get_item_by_path(&path) else {
+            return Task::none();
+        };
+        let request = variables::resolve_request(request, &self.active_variables());
+
+        info!("Opening blast dialog for '{}'", request.name);
+        let defaults = BlastConfig::default();
+        self.blast = Some(BlastState {
+            request,
+            concurrency: defaults.concurrency.to_string(),
+            use_duration: false,
+            total_requests: "1000".to_string(),
+            duration_secs: "30".to_string(),
+            ramp_up_secs: defaults.ramp_up_secs.to_string(),
+            rate_limit: String::new(),
+            running: false,
+            stop: Arc::new(AtomicBool::new(false)),
+            summary: None,
+            report: None,
+        });
+        Task::none()
+    }
+
+    /// Close the load test dialog, stopping a run in progress
+    pub fn handle_close_blast_dialog(&mut self) -> Task<Message> {
+        if let Some(state) = self.blast.take() {
+            state.stop.store(true, Ordering::Relaxed);
+        }
+        Task::none()
+    }
+
+    pub fn handle_blast_concurrency_changed(&mut self, value: String) -> Task<Message> {
+        if let Some(state) = &mut self.blast {
+            if digits_only(&value) {
+                state.concurrency = value;
+            }
+        }
+        Task::none()
+    }
+
+    pub fn handle_blast_use_duration(&mut self, use_duration: bool) -> Task<Message> {
+        if let Some(state) = &mut self.blast {
+            state.use_duration = use_duration;
+        }
+        Task::none()
+    }
+
+    pub fn handle_blast_total_requests_changed(&mut self, value: String) -> Task<Message> {
+        if let Some(state) = &mut self.blast {
+            if digits_only(&value) {
+                state.total_requests = value;
+            }
+        }
+        Task::none()
+    }
+
+    pub fn handle_blast_duration_changed(&mut self, value: String) -> Task<Message> {
+        if let Some(state) = &mut self.blast {
+            if digits_only(&value) {
+                state.duration_secs = value;
+            }
+        }
+        Task::none()
+    }
+
+    pub fn handle_blast_ramp_up_changed(&mut self, value: String) -> Task<Message> {
+        if let Some(state) = &mut self.blast {
+            if digits_only(&value) {
+                state.ramp_up_secs = value;
+            }
+        }
+        Task::none()
+    }
+
+    pub fn handle_blast_rate_limit_changed(&mut self, value: String) -> Task<Message> {
+        if let Some(state) = &mut self.blast {
+            if digits_only(&value) {
+                state.rate_limit = value;
+            }
+        }
+        Task::none()
+    }
+
+    /// Start the load test and stream its progress into the dialog
+    pub fn handle_start_blast(&mut self) -> Task<Message> {
+        let Some(state) = &mut self.blast else {
+            return Task::none();
+        };
+        if state.running {
+            return Task::none();
+        }
+
+        let limit = if state.use_duration {
+            BlastLimit::Duration(state.duration_secs.parse().unwrap_or(30).max(1))
+        } else {
+            BlastLimit::Requests(state.total_requests.parse().unwrap_or(1000).max(1))
+        };
+        let config = BlastConfig {
+            concurrency: state.concurrency.parse().unwrap_or(10).clamp(1, 10_000),
+            limit,
+            ramp_up_secs: state.ramp_up_secs.parse().unwrap_or(0),
+            rate_limit: state.rate_limit.parse().ok().filter(|rate| *rate > 0),
+        };
+
+        state.running = true;
+        state.summary = None;
+        state.report = None;
+        state.stop = Arc::new(AtomicBool::new(false));
+
+        Task::run(
+            blast::run(state.request.clone(), config, state.stop.clone()),
+            Message::BlastEvent,
+        )
+    }
+
+    /// Ask the workers to stop; the final report still arrives
+    pub fn handle_stop_blast(&mut self) -> Task<Message> {
+        if let Some(state) = &self.blast {
+            info!("Stopping blast run");
+            state.stop.store(true, Ordering::Relaxed);
+        }
+        Task::none()
+    }
+
+    /// Apply a progress update or the final report
+    pub fn handle_blast_event(&mut self, event: BlastEvent) -> Task<Message> {
+        let Some(state) = &mut self.blast else {
+            return Task::none();
+        };
+
+        match event {
+            BlastEvent::Progress(summary) => {
+                state.summary = Some(summary);
+                Task::none()
+            }
+            BlastEvent::Finished(report) => {
+                state.running = false;
+                state.summary = Some(report.summary.clone());
+                let message = format!(
+                    "{} {} · {:.1} rps · {} {}",
+                    report.summary.total,
+                    self.translations.get("blast_requests"),
+                    report.summary.rps,
+                    report.summary.errors,
+                    self.translations.get("blast_errors")
+                );
+                state.report = Some(report);
+                Task::done(Message::ShowToast(Toast::info(message)))
+            }
+        }
+    }
+
+    /// Export the last blast report as JSON or CSV
+    pub fn handle_export_blast_report(&mut self, format: BlastExportFormat) -> Task<Message> {
+        let Some(report) = self.blast.as_ref().and_then(|s| s.report.as_ref()) else {
+            return Task::none();
+        };
+
+        let (content, extension) = match format {
+            BlastExportFormat::Json => (
+                serde_json::to_string_pretty(report).map_err(|e| e.to_string()),
+                "json",
+            ),
+            BlastExportFormat::Csv => (blast::samples_to_csv(&report.samples), "csv"),
+        };
+        let content = match content {
+            Ok(content) => content,
+            Err(e) => {
+                error!("Failed to export blast report: {}", e);
+                return Task::done(Message::ShowToast(Toast::error(format!(
+                    "Failed to export report: {}",
+                    e
+                ))));
+            }
+        };
+
+        let file_name = format!("{}-blast.{}", report.request_name, extension);
+        Task::perform(
+            async move {
+                use rfd::AsyncFileDialog;
+                let handle = AsyncFileDialog::new()
+                    .set_title("Export Blast Report")
+                    .set_file_name(&file_name)
+                    .add_filter(extension.to_uppercase(), &[extension])
+                    .save_file()
+                    .await?;
+                Some(
+                    std::fs::write(handle.path(), content)
+                        .map(|_| handle.path().to_string_lossy().to_string())
+                        .map_err(|e| e.to_string()),
+                )
+            },
+            |result| match result {
+                Some(Ok(path)) => {
+                    Message::ShowToast(Toast::success(format!("Report saved to {}", path)))
+                }
+                Some(Err(e)) => {
+                    Message::ShowToast(Toast::error(format!("Failed to export report: {}", e)))
+                }
+                None => Message::HideContextMenu,
+            },
+        )
+    }
+}
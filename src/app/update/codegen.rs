@@ -0,0 +1,75 @@
+use iced::Task;
+use tracing::info;
+
+use crate::models::{CodeLanguage, CollectionItem};
+use crate::ui::toast::Toast;
+
+use super::super::message::Message;
+use super::super::state::{CodeDialogState, Requiem};
+
+impl Requiem {
+    /// Open "Copy as..." for a saved request, or for the request being edited
+    pub fn handle_show_code_dialog(&mut self, path: Option<Vec<usize>>) -> Task<Message> {
+        self.context_menu = None;
+
+        let request = match &path {
+            Some(path) => match self.get_item_by_path(path) {
+                Some(CollectionItem::Request(request)) => request.clone(),
+                _ => return Task::none(),
+            },
+            None => match self.get_current_request() {
+                Some(request) => request.clone(),
+                None => return Task::none(),
+            },
+        };
+
+        info!("Opening code generation for '{}'", request.name);
+        let language = self
+            .code_dialog
+            .as_ref()
+            .map(|state| state.language)
+            .unwrap_or_default();
+        self.code_dialog = Some(CodeDialogState {
+            request,
+            variables: self.active_variables(),
+            language,
+            resolve_variables: false,
+        });
+        Task::none()
+    }
+
+    pub fn handle_close_code_dialog(&mut self) -> Task<Message> {
+        self.code_dialog = None;
+        Task::none()
+    }
+
+    pub fn handle_code_language_selected(&mut self, language: CodeLanguage) -> Task<Message> {
+        if let Some(state) = &mut self.code_dialog {
+            state.language = language;
+        }
+        Task::none()
+    }
+
+    pub fn handle_toggle_code_resolve_variables(&mut self) -> Task<Message> {
+        if let Some(state) = &mut self.code_dialog {
+            state.resolve_variables = !state.resolve_variables;
+        }
+        Task::none()
+    }
+
+    /// Copy the snippet shown in the dialog to the clipboard
+    pub fn handle_copy_generated_code(&mut self) -> Task<Message> {
+        let Some(state) = &self.code_dialog else {
+            return Task::none();
+        };
+        let message = format!(
+            "{} ({})",
+            self.translations.get("code_copied"),
+            state.language
+        );
+        Task::batch([
+            iced::clipboard::write(state.code()).discard(),
+            Task::done(Message::ShowToast(Toast::success(message))),
+        ])
+    }
+}
@@ -3,7 +3,7 @@ use iced::Task;
 use tracing::{debug, error, info};
 use uuid::Uuid;
 
-use crate::models::{self, CollectionItem, Folder};
+use crate::models::{self, CollectionItem, Flow, Folder};
 
 use super::super::message::Message;
 use super::super::state::Requiem;
@@ -30,9 +30,14 @@ impl Requiem {
             let _ = self.update(Message::ConfirmRename);
         }
 
-        let new_request = models::Request::default();
-        let request_id = new_request.id;
-        let request_name = new_request.name.clone();
+        self.open_draft(models::Request::default(), parent_path);
+        Task::none()
+    }
+
+    /// Open an unsaved request in a new tab, to be saved under `parent_path`
+    pub fn open_draft(&mut self, request: models::Request, parent_path: Vec<usize>) {
+        let request_id = request.id;
+        let request_name = request.name.clone();
 
         // Determine the parent path for the new request
         // If parent_path is empty (e.g., from keyboard shortcut), use intelligent defaults
@@ -49,6 +54,7 @@ impl Requiem {
                     name: "My Collection".to_string(),
                     items: vec![],
                     expanded: true,
+                    http_file: None,
                 };
                 self.collections.push(default_collection);
                 vec![0]
@@ -57,13 +63,20 @@ impl Requiem {
             parent_path
         };
 
+        let body_text = match &request.body {
+            models::BodyType::Json(s) | models::BodyType::Xml(s) | models::BodyType::Text(s) => {
+                s.clone()
+            }
+            _ => String::new(),
+        };
+
         let new_tab = super::super::state::RequestTabItem {
             id: request_id,
             name: request_name.clone(),
             request_path: None,
             is_modified: false,
             is_new: true,
-            draft_request: Some(new_request),
+            draft_request: Some(request),
             parent_path: Some(actual_parent_path.clone()),
         };
 
@@ -75,9 +88,8 @@ impl Requiem {
         self.context_menu = None;
 
         // Sync request body content
-        self.request_body_content = iced::widget::text_editor::Content::new();
-
-        Task::none()
+        self.request_body_content = iced::widget::text_editor::Content::with_text(&body_text);
+        self.sync_schema_content();
     }
 
     /// Add new folder to collection
@@ -126,6 +138,46 @@ impl Requiem {
         ])
     }
 
+    /// Add new flow to collection or folder
+    pub fn handle_add_new_flow(&mut self, parent_path: Vec<usize>) -> Task<Message> {
+        // Auto-save rename if in progress
+        if self.renaming_item.is_some() {
+            let _ = self.update(Message::ConfirmRename);
+        }
+
+        let flow_name = "New Flow".to_string();
+        let new_flow = CollectionItem::Flow(Flow::new(flow_name.clone()));
+
+        let collection_idx = parent_path[0];
+        let mut new_flow_path = parent_path.clone();
+
+        if parent_path.len() == 1 {
+            if let Some(collection) = self.collections.get_mut(collection_idx) {
+                collection.items.push(new_flow);
+                new_flow_path.push(collection.items.len() - 1);
+            }
+        } else {
+            if let Some(CollectionItem::Folder(folder)) = self.get_item_by_path_mut(&parent_path) {
+                folder.items.push(new_flow);
+                new_flow_path.push(folder.items.len() - 1);
+            }
+        }
+
+        if let Err(e) = self.save_collection(collection_idx) {
+            error!("Failed to save collection after adding flow: {}", e);
+        }
+
+        self.context_menu = None;
+
+        // Set renaming state to auto-edit the new flow name
+        self.renaming_item = Some((new_flow_path, flow_name.clone(), flow_name));
+
+        Task::batch([
+            focus(self.rename_input_id.clone()),
+            select_all(self.rename_input_id.clone()),
+        ])
+    }
+
     /// Delete item from collection
     pub fn handle_delete_item(&mut self, path: Vec<usize>) -> Task<Message> {
         // Auto-save rename if in progress
@@ -254,6 +306,10 @@ impl Requiem {
                         folder.name = new_name.clone();
                         Some(folder.id)
                     }
+                    CollectionItem::Flow(flow) => {
+                        flow.name = new_name.clone();
+                        Some(flow.id)
+                    }
                 }
             } else {
                 None
@@ -326,6 +382,7 @@ impl Requiem {
             name: default_name.clone(),
             items: vec![],
             expanded: true,
+            http_file: None,
         };
         self.collections.push(new_collection);
         let new_coll_idx = self.collections.len() - 1;
@@ -354,6 +411,7 @@ impl Requiem {
             self.get_item_by_path(&path).map(|item| match item {
                 CollectionItem::Request(req) => req.name.clone(),
                 CollectionItem::Folder(folder) => folder.name.clone(),
+                CollectionItem::Flow(flow) => flow.name.clone(),
             })
         }
         .unwrap_or_default();
@@ -514,10 +572,11 @@ impl Requiem {
 
         let collection_index = path[0];
         if let Some(collection) = self.collections.get(collection_index) {
-            let collection_path = crate::storage::get_collection_path(&self.save_directory, &collection.id);
+            // Folders have a directory of their own; requests open the one holding them
+            let item_dir =
+                crate::storage::item_dir(&self.save_directory, collection, &path[1..]);
 
-            // Get the directory containing the collection file
-            if let Some(dir_path) = collection_path.parent() {
+            if let Some(dir_path) = item_dir {
                 let dir_str = dir_path.to_string_lossy().to_string();
 
                 // Use platform-specific command to open file manager
@@ -540,7 +599,7 @@ impl Requiem {
                     }
                 }
             } else {
-                error!("Failed to get parent directory for collection path");
+                error!("Collection directory not found: {}", collection.name);
             }
         } else {
             error!("Collection not found at index: {}", collection_index);
@@ -0,0 +1,176 @@
+use iced::Task;
+use tracing::error;
+
+use crate::models::{CollectionExportFormat, CollectionItem};
+use crate::secrets;
+use crate::ui::toast::Toast;
+use crate::utils::{docs, har, http_file, insomnia, openapi, postman};
+
+use super::super::message::Message;
+use super::super::state::{ExportDialogState, Requiem};
+
+impl Requiem {
+    /// Open the export dialog for a collection or folder
+    pub fn handle_show_export_dialog(&mut self, path: Vec<usize>) -> Task<Message> {
+        self.context_menu = None;
+        let Some((name, _)) = self.export_source(&path) else {
+            return Task::none();
+        };
+        self.export_dialog = Some(ExportDialogState {
+            path,
+            name,
+            format: self.last_export_format,
+        });
+        Task::none()
+    }
+
+    pub fn handle_close_export_dialog(&mut self) -> Task<Message> {
+        self.export_dialog = None;
+        Task::none()
+    }
+
+    pub fn handle_export_format_selected(
+        &mut self,
+        format: CollectionExportFormat,
+    ) -> Task<Message> {
+        if let Some(dialog) = &mut self.export_dialog {
+            dialog.format = format;
+        }
+        Task::none()
+    }
+
+    /// Save a collection or folder in a format other tools can import
+    pub fn handle_export_collection(
+        &mut self,
+        path: Vec<usize>,
+        format: CollectionExportFormat,
+    ) -> Task<Message> {
+        self.export_dialog = None;
+        self.last_export_format = format;
+        let Some((name, items)) = self.export_source(&path) else {
+            return Task::none();
+        };
+        // Exported files leave the save directory, so secrets stay behind
+        let items = &secrets::redact_items(items);
+
+        let failed = self.translations.get("export_failed").to_string();
+        let json = |value: serde_json::Value| serde_json::to_string_pretty(&value);
+        let content = match format {
+            CollectionExportFormat::Postman => json(postman::export_collection(&name, items)),
+            CollectionExportFormat::Insomnia => json(insomnia::export(&name, items)),
+            CollectionExportFormat::OpenApi => json(openapi::export_document(&name, items)),
+            CollectionExportFormat::Http => {
+                // Define the variables the requests use, so the file runs on its own
+                let environment = self.current_environment;
+                let mut values = self
+                    .environment_variables
+                    .get(environment)
+                    .cloned()
+                    .unwrap_or_default();
+                values.retain(|name, _| !self.environment_variables.is_secret(environment, name));
+                let variables = http_file::referenced_variables(items, &values);
+                Ok(http_file::export(items, &variables))
+            }
+            CollectionExportFormat::Html => Ok(docs::html(&name, items)),
+            CollectionExportFormat::Markdown => Ok(docs::markdown(&name, items)),
+        };
+        let content = match content {
+            Ok(content) => content,
+            Err(e) => {
+                error!("Failed to export {}: {}", name, e);
+                return Task::done(Message::ShowToast(Toast::error(format!(
+                    "{}: {}",
+                    failed, e
+                ))));
+            }
+        };
+
+        let saved = self.translations.get("export_saved").to_string();
+        let file_name = format!("{}{}", name, format.file_suffix());
+        let extension = format.extension();
+        Task::perform(
+            async move {
+                use rfd::AsyncFileDialog;
+                let handle = AsyncFileDialog::new()
+                    .set_title("Export")
+                    .set_file_name(&file_name)
+                    .add_filter(extension.to_uppercase(), &[extension])
+                    .save_file()
+                    .await?;
+                Some(
+                    std::fs::write(handle.path(), content)
+                        .map(|_| handle.path().to_string_lossy().to_string())
+                        .map_err(|e| e.to_string()),
+                )
+            },
+            move |result| match result {
+                Some(Ok(path)) => Message::ShowToast(Toast::success(format!("{} {}", saved, path))),
+                Some(Err(e)) => Message::ShowToast(Toast::error(format!("{}: {}", failed, e))),
+                None => Message::HideContextMenu,
+            },
+        )
+    }
+
+    /// Save the requests sent this session as a HAR archive
+    pub fn handle_export_history_har(&mut self) -> Task<Message> {
+        self.context_menu = None;
+        if self.history.is_empty() {
+            return Task::done(Message::ShowToast(Toast::error(
+                self.translations.get("export_history_empty"),
+            )));
+        }
+
+        let failed = self.translations.get("export_failed").to_string();
+        let entries: Vec<_> = self.history.iter().map(secrets::redact_history).collect();
+        // Secrets may also have been sent through variables, outside secret fields
+        let mut archive = har::export_history(&entries);
+        secrets::mask_json(&mut archive);
+        let content = match serde_json::to_string_pretty(&archive) {
+            Ok(content) => content,
+            Err(e) => {
+                error!("Failed to export history: {}", e);
+                return Task::done(Message::ShowToast(Toast::error(format!(
+                    "{}: {}",
+                    failed, e
+                ))));
+            }
+        };
+
+        let saved = self.translations.get("export_saved").to_string();
+        Task::perform(
+            async move {
+                use rfd::AsyncFileDialog;
+                let handle = AsyncFileDialog::new()
+                    .set_title("Export history")
+                    .set_file_name("requiem-history.har")
+                    .add_filter("HAR", &["har"])
+                    .save_file()
+                    .await?;
+                Some(
+                    std::fs::write(handle.path(), content)
+                        .map(|_| handle.path().to_string_lossy().to_string())
+                        .map_err(|e| e.to_string()),
+                )
+            },
+            move |result| match result {
+                Some(Ok(path)) => Message::ShowToast(Toast::success(format!("{} {}", saved, path))),
+                Some(Err(e)) => Message::ShowToast(Toast::error(format!("{}: {}", failed, e))),
+                None => Message::HideContextMenu,
+            },
+        )
+    }
+
+    /// Name and items of the collection or folder at `path`
+    fn export_source(&self, path: &[usize]) -> Option<(String, &Vec<CollectionItem>)> {
+        match path {
+            [index] => {
+                let collection = self.collections.get(*index)?;
+                Some((collection.name.clone(), &collection.items))
+            }
+            _ => match self.get_item_by_path(path)? {
+                CollectionItem::Folder(folder) => Some((folder.name.clone(), &folder.items)),
+                _ => None,
+            },
+        }
+    }
+}
@@ -0,0 +1,260 @@
+use iced::Task;
+use tracing::{error, info};
+
+use crate::models::{
+    CollectionItem, FlowBranch, FlowCondition, FlowLoop, FlowReport, FlowStep, FlowTarget,
+};
+use crate::ui::toast::Toast;
+use crate::utils::navigation;
+
+use super::super::message::{ConditionEdit, FlowStepEdit, Message};
+use super::super::state::{FlowEditorState, Requiem};
+
+/// Apply a condition edit from the flow editor
+fn apply_condition_edit(condition: &mut FlowCondition, edit: ConditionEdit) {
+    match edit {
+        ConditionEdit::Source(source) => condition.source = source,
+        ConditionEdit::Variable(variable) => condition.variable = variable,
+        ConditionEdit::Operator(operator) => condition.operator = operator,
+        ConditionEdit::Value(value) => condition.value = value,
+    }
+}
+
+/// Parse a numeric input, treating an empty field as zero
+fn parse_number<T: std::str::FromStr + Default>(value: &str) -> Option<T> {
+    if value.is_empty() {
+        Some(T::default())
+    } else {
+        value.parse().ok()
+    }
+}
+
+impl Requiem {
+    /// Open the flow editor for a flow item
+    pub fn handle_open_flow(&mut self, path: Vec<usize>) -> Task<Message> {
+        self.context_menu = None;
+
+        let Some(CollectionItem::Flow(flow)) = self.get_item_by_path(&path) else {
+            return Task::none();
+        };
+        let flow = flow.clone();
+
+        // Steps may use any request of the same collection
+        let requests = self
+            .collections
+            .get(path[0])
+            .map(|c| navigation::collect_requests(&c.items))
+            .unwrap_or_default();
+
+        info!("Opening flow editor for '{}'", flow.name);
+        self.flow_editor = Some(FlowEditorState {
+            path,
+            flow,
+            requests,
+            running: false,
+            report: None,
+        });
+        Task::none()
+    }
+
+    /// Close the flow editor, discarding unsaved changes
+    pub fn handle_close_flow_editor(&mut self) -> Task<Message> {
+        self.flow_editor = None;
+        Task::none()
+    }
+
+    /// Write the edited flow back into its collection
+    pub fn handle_save_flow(&mut self) -> Task<Message> {
+        let Some(editor) = &self.flow_editor else {
+            return Task::none();
+        };
+        let path = editor.path.clone();
+        let edited = editor.flow.clone();
+
+        // The sidebar may have changed since the editor was opened
+        let updated = match self.get_item_by_path_mut(&path) {
+            Some(CollectionItem::Flow(flow)) if flow.id == edited.id => {
+                flow.steps = edited.steps;
+                true
+            }
+            _ => false,
+        };
+        if !updated {
+            return Task::done(Message::ShowToast(Toast::error(
+                self.translations.get("flow_not_found").to_string(),
+            )));
+        }
+
+        match self.save_collection(path[0]) {
+            Ok(()) => Task::done(Message::ShowToast(Toast::success(
+                self.translations.get("flow_saved").to_string(),
+            ))),
+            Err(e) => {
+                error!("Failed to save flow: {}", e);
+                Task::done(Message::ShowToast(Toast::error(format!(
+                    "Failed to save flow: {}",
+                    e
+                ))))
+            }
+        }
+    }
+
+    /// Append a step using the first available request
+    pub fn handle_add_flow_step(&mut self) -> Task<Message> {
+        let Some(editor) = &mut self.flow_editor else {
+            return Task::none();
+        };
+        let Some(request) = editor.requests.first() else {
+            return Task::done(Message::ShowToast(Toast::warning(
+                self.translations.get("flow_no_requests").to_string(),
+            )));
+        };
+        editor.flow.steps.push(FlowStep::new(request.id));
+        Task::none()
+    }
+
+    pub fn handle_remove_flow_step(&mut self, index: usize) -> Task<Message> {
+        if let Some(editor) = &mut self.flow_editor {
+            if index < editor.flow.steps.len() {
+                let removed = editor.flow.steps.remove(index);
+
+                // Jumps to the removed step fall back to the next step
+                for step in &mut editor.flow.steps {
+                    if let Some(branch) = &mut step.branch {
+                        for target in [&mut branch.on_true, &mut branch.on_false] {
+                            if *target == FlowTarget::Step(removed.id) {
+                                *target = FlowTarget::Next;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        Task::none()
+    }
+
+    pub fn handle_move_flow_step_up(&mut self, index: usize) -> Task<Message> {
+        if let Some(editor) = &mut self.flow_editor {
+            if index > 0 && index < editor.flow.steps.len() {
+                editor.flow.steps.swap(index - 1, index);
+            }
+        }
+        Task::none()
+    }
+
+    /// Apply an edit to one step of the flow being edited
+    pub fn handle_flow_step_edited(&mut self, index: usize, edit: FlowStepEdit) -> Task<Message> {
+        let Some(step) = self
+            .flow_editor
+            .as_mut()
+            .and_then(|editor| editor.flow.steps.get_mut(index))
+        else {
+            return Task::none();
+        };
+
+        match edit {
+            FlowStepEdit::Request(request_id) => step.request_id = request_id,
+            FlowStepEdit::Wait(value) => {
+                if let Some(wait_ms) = parse_number(&value) {
+                    step.wait_ms = wait_ms;
+                }
+            }
+            FlowStepEdit::ToggleLoop => {
+                step.repeat = match step.repeat {
+                    Some(_) => None,
+                    None => Some(FlowLoop::default()),
+                };
+            }
+            FlowStepEdit::LoopCondition(edit) => {
+                if let Some(repeat) = &mut step.repeat {
+                    apply_condition_edit(&mut repeat.until, edit);
+                }
+            }
+            FlowStepEdit::MaxAttempts(value) => {
+                if let (Some(repeat), Some(max_attempts)) = (&mut step.repeat, parse_number(&value))
+                {
+                    repeat.max_attempts = max_attempts;
+                }
+            }
+            FlowStepEdit::Interval(value) => {
+                if let (Some(repeat), Some(interval_ms)) = (&mut step.repeat, parse_number(&value))
+                {
+                    repeat.interval_ms = interval_ms;
+                }
+            }
+            FlowStepEdit::ToggleBranch => {
+                step.branch = match step.branch {
+                    Some(_) => None,
+                    None => Some(FlowBranch::default()),
+                };
+            }
+            FlowStepEdit::BranchCondition(edit) => {
+                if let Some(branch) = &mut step.branch {
+                    apply_condition_edit(&mut branch.condition, edit);
+                }
+            }
+            FlowStepEdit::OnTrue(target) => {
+                if let Some(branch) = &mut step.branch {
+                    branch.on_true = target;
+                }
+            }
+            FlowStepEdit::OnFalse(target) => {
+                if let Some(branch) = &mut step.branch {
+                    branch.on_false = target;
+                }
+            }
+        }
+        Task::none()
+    }
+
+    /// Execute the flow as currently edited
+    pub fn handle_run_flow(&mut self) -> Task<Message> {
+        let variables = self.active_variables();
+        let Some(editor) = &mut self.flow_editor else {
+            return Task::none();
+        };
+        if editor.running || editor.flow.steps.is_empty() {
+            return Task::none();
+        }
+
+        editor.running = true;
+        editor.report = None;
+
+        Task::perform(
+            crate::runner::run_flow(
+                editor.flow.clone(),
+                editor.requests.clone(),
+                variables,
+                self.save_directory.clone().into(),
+            ),
+            Message::FlowCompleted,
+        )
+    }
+
+    /// Show the step-by-step log of a finished flow
+    pub fn handle_flow_completed(&mut self, report: FlowReport) -> Task<Message> {
+        info!("Flow '{}' finished, passed: {}", report.name, report.passed);
+
+        let toast = if report.passed {
+            Toast::success(format!(
+                "{}: {}",
+                report.name,
+                self.translations.get("flow_passed")
+            ))
+        } else {
+            Toast::error(format!(
+                "{}: {}",
+                report.name,
+                self.translations.get("flow_failed")
+            ))
+        };
+
+        let Some(editor) = &mut self.flow_editor else {
+            return Task::none();
+        };
+        editor.running = false;
+        editor.report = Some(report);
+
+        Task::done(Message::ShowToast(toast))
+    }
+}
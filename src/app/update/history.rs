@@ -0,0 +1,126 @@
+use iced::Task;
+use tracing::{error, info};
+use uuid::Uuid;
+
+use crate::models::BodyViewMode;
+use crate::secrets;
+use crate::storage;
+use crate::ui::toast::Toast;
+use crate::utils::history;
+
+use super::super::message::Message;
+use super::super::state::Requiem;
+
+impl Requiem {
+    /// Apply the retention policy and load what is left of the history
+    pub fn reload_history(&mut self) {
+        let today = chrono::Local::now().date_naive();
+        if let Some(start) = history::retention_start(today, self.history_config.retention_days) {
+            if let Err(e) = storage::purge_history(&self.save_directory, &start) {
+                error!("Failed to purge history: {}", e);
+            }
+        }
+        self.history = storage::load_history(&self.save_directory).unwrap_or_else(|e| {
+            error!("Failed to load history: {}", e);
+            Vec::new()
+        });
+    }
+
+    pub fn handle_show_history(&mut self, show: bool) -> Task<Message> {
+        self.show_history = show;
+        Task::none()
+    }
+
+    pub fn handle_history_query_changed(&mut self, query: String) -> Task<Message> {
+        self.history_query = query;
+        Task::none()
+    }
+
+    /// Open a sent request in a new tab, showing the response it got
+    pub fn handle_open_history_entry(&mut self, id: Uuid) -> Task<Message> {
+        // Auto-save rename if in progress
+        if self.renaming_item.is_some() {
+            let _ = self.update(Message::ConfirmRename);
+        }
+
+        let Some(entry) = self.history.iter().find(|entry| entry.id == id).cloned() else {
+            return Task::none();
+        };
+        // Secret references are keyed by the original request, so resolve
+        // them before the copy gets an id of its own
+        let mut request = entry.request;
+        secrets::resolve_request(&mut request, &self.secrets);
+        request.id = Uuid::new_v4();
+        self.open_draft(request, vec![]);
+
+        let body = entry
+            .response
+            .as_ref()
+            .map(|response| response.body.clone())
+            .unwrap_or_default();
+        self.response = entry.response;
+        self.raw_response_body = body.clone();
+        self.response_body_content = iced::widget::text_editor::Content::with_text(&body);
+        self.active_body_view_mode = BodyViewMode::Raw;
+        self.error_message = entry.error;
+        self.schema_result = None;
+        self.refresh_snapshot_diff();
+
+        info!("Opened history entry {}", id);
+        Task::none()
+    }
+
+    /// Save a sent request into the selected collection, or the first one
+    pub fn handle_save_history_entry(&mut self, id: Uuid) -> Task<Message> {
+        if !self.history.iter().any(|entry| entry.id == id) {
+            return Task::none();
+        }
+        let _ = self.handle_open_history_entry(id);
+        self.handle_save_request()
+    }
+
+    pub fn handle_history_retention_changed(&mut self, value: String) -> Task<Message> {
+        let days = if value.trim().is_empty() {
+            0
+        } else {
+            match value.trim().parse::<u32>() {
+                Ok(days) => days,
+                Err(_) => return Task::none(),
+            }
+        };
+        self.history_config.retention_days = days;
+        if let Err(e) =
+            crate::config::Config::load().set_history_config(self.history_config.clone())
+        {
+            error!("Failed to save config: {}", e);
+            return Task::done(Message::ShowToast(Toast::error(e.to_string())));
+        }
+        Task::none()
+    }
+
+    /// Delete entries older than the retention period now, rather than on
+    /// the next start
+    pub fn handle_purge_history(&mut self) -> Task<Message> {
+        let before = self.history.len();
+        self.reload_history();
+        let removed = before.saturating_sub(self.history.len());
+        info!("Purged {} history entries", removed);
+        Task::done(Message::ShowToast(Toast::success(format!(
+            "{}: {}",
+            self.translations.get("history_purged"),
+            removed
+        ))))
+    }
+
+    pub fn handle_clear_history(&mut self) -> Task<Message> {
+        if let Err(e) = storage::clear_history(&self.save_directory) {
+            error!("Failed to clear history: {}", e);
+            return Task::done(Message::ShowToast(Toast::error(e)));
+        }
+        self.history.clear();
+        info!("Cleared history");
+        Task::done(Message::ShowToast(Toast::success(
+            self.translations.get("history_cleared"),
+        )))
+    }
+}
@@ -0,0 +1,241 @@
+use iced::Task;
+use tracing::{error, info};
+
+use crate::models::{HarFilter, ImportFile, ImportResult};
+use crate::ui::toast::Toast;
+use crate::utils::{har, http_file, import};
+
+use super::super::message::Message;
+use super::super::state::{HarImportState, Requiem};
+
+impl Requiem {
+    /// Pick a file exported from another tool and import it
+    pub fn handle_import_file(&mut self) -> Task<Message> {
+        self.context_menu = None;
+        Task::perform(
+            async {
+                use rfd::AsyncFileDialog;
+                let handle = AsyncFileDialog::new()
+                    .set_title("Import")
+                    .add_filter(
+                        "JSON / YAML / HAR / HTTP",
+                        &["json", "yaml", "yml", "har", "http", "rest"],
+                    )
+                    .pick_file()
+                    .await?;
+                let contents = std::fs::read_to_string(handle.path())
+                    .map_err(|e| format!("Failed to read {}: {}", handle.path().display(), e));
+                Some(contents.and_then(|contents| import::read_file(handle.path(), &contents)))
+            },
+            |result| match result {
+                Some(result) => Message::ImportFileLoaded(result),
+                None => Message::HideContextMenu,
+            },
+        )
+    }
+
+    /// Pick a `.http` file and open it as a collection that stays in sync with it
+    pub fn handle_open_http_file(&mut self) -> Task<Message> {
+        self.context_menu = None;
+        Task::perform(
+            async {
+                use rfd::AsyncFileDialog;
+                let handle = AsyncFileDialog::new()
+                    .set_title("Open .http file")
+                    .add_filter("HTTP", &["http", "rest"])
+                    .pick_file()
+                    .await?;
+                Some(http_file::open(handle.path()).map(ImportFile::Ready))
+            },
+            |result| match result {
+                Some(result) => Message::ImportFileLoaded(result),
+                None => Message::HideContextMenu,
+            },
+        )
+    }
+
+    /// Read the `.http` file of a linked collection again
+    pub fn handle_reload_http_file(&mut self, index: usize) -> Task<Message> {
+        self.context_menu = None;
+        let Some(collection) = self.collections.get_mut(index) else {
+            return Task::none();
+        };
+        match crate::storage::sync_http_file(collection) {
+            Ok(true) => {
+                self.close_collection_tabs(index);
+                if let Err(e) = self.save_collection(index) {
+                    error!("Failed to save reloaded collection: {}", e);
+                }
+                Task::done(Message::ShowToast(Toast::success(
+                    self.translations.get("http_file_reloaded"),
+                )))
+            }
+            Ok(false) => Task::done(Message::ShowToast(Toast::success(
+                self.translations.get("http_file_unchanged"),
+            ))),
+            Err(e) => {
+                error!("Failed to reload .http file: {}", e);
+                Task::done(Message::ShowToast(Toast::error(e)))
+            }
+        }
+    }
+
+    /// Close the tabs of a collection whose items were replaced, as their
+    /// paths may now point elsewhere
+    pub(super) fn close_collection_tabs(&mut self, index: usize) {
+        self.open_tabs.retain(|tab| {
+            let path = tab.request_path.as_ref().or(tab.parent_path.as_ref());
+            path.and_then(|path| path.first()) != Some(&index)
+        });
+        if self
+            .active_tab_index
+            .is_some_and(|active| active >= self.open_tabs.len())
+        {
+            self.active_tab_index = self.open_tabs.len().checked_sub(1);
+        }
+        if self
+            .selected_request
+            .as_ref()
+            .is_some_and(|path| path.first() == Some(&index))
+        {
+            self.selected_request = None;
+            self.response = None;
+        }
+    }
+
+    /// Import the file, or let the user filter the entries of a HAR archive first
+    pub fn handle_import_file_loaded(
+        &mut self,
+        result: Result<ImportFile, String>,
+    ) -> Task<Message> {
+        match result {
+            Ok(ImportFile::Ready(result)) => self.apply_import(result),
+            Ok(ImportFile::Har(archive)) => {
+                if archive.entries.is_empty() {
+                    return Task::done(Message::ShowToast(Toast::error(
+                        self.translations.get("har_import_empty"),
+                    )));
+                }
+                self.har_import = Some(HarImportState {
+                    filter: HarFilter::for_archive(&archive),
+                    archive,
+                });
+                Task::none()
+            }
+            Err(e) => {
+                error!("Import failed: {}", e);
+                return Task::done(Message::ShowToast(Toast::error(format!(
+                    "{}: {}",
+                    self.translations.get("import_failed"),
+                    e
+                ))));
+            }
+        }
+    }
+
+    pub fn handle_toggle_har_domain(&mut self, domain: String) -> Task<Message> {
+        if let Some(state) = &mut self.har_import {
+            state.filter.toggle_domain(&domain);
+        }
+        Task::none()
+    }
+
+    pub fn handle_toggle_har_resource_type(&mut self, resource_type: String) -> Task<Message> {
+        if let Some(state) = &mut self.har_import {
+            state.filter.toggle_type(&resource_type);
+        }
+        Task::none()
+    }
+
+    /// Import the HAR entries left after filtering
+    pub fn handle_confirm_har_import(&mut self) -> Task<Message> {
+        let Some(state) = self.har_import.take() else {
+            return Task::none();
+        };
+        let result = har::import_archive(&state.archive, &state.filter);
+        if result.report.requests == 0 {
+            self.har_import = Some(state);
+            return Task::done(Message::ShowToast(Toast::error(
+                self.translations.get("har_import_nothing"),
+            )));
+        }
+        self.apply_import(result)
+    }
+
+    pub fn handle_close_har_import(&mut self) -> Task<Message> {
+        self.har_import = None;
+        Task::none()
+    }
+
+    /// Add the imported collection and variables, then show the report
+    fn apply_import(&mut self, result: ImportResult) -> Task<Message> {
+        info!(
+            "Imported {} '{}' with {} issues",
+            result.report.source,
+            result.report.name,
+            result.report.issues.len()
+        );
+
+        let mut report = result.report;
+        if let Some(collection) = result.collection {
+            // Opening a linked file again reloads its collection instead of adding another
+            let linked = collection.http_file.as_ref().and_then(|link| {
+                self.collections.iter().position(|c| {
+                    c.http_file
+                        .as_ref()
+                        .is_some_and(|other| other.path == link.path)
+                })
+            });
+            if let Some(index) = linked {
+                self.selected_collection = Some(index);
+                return self.handle_reload_http_file(index);
+            }
+
+            let existing = self
+                .collections
+                .iter()
+                .position(|c| c.name == collection.name)
+                .filter(|_| result.merge);
+            let index = match existing {
+                Some(index) => {
+                    import::merge_collection(&mut self.collections[index], collection, &mut report);
+                    index
+                }
+                None => {
+                    self.collections.push(collection);
+                    self.collections.len() - 1
+                }
+            };
+            self.selected_collection = Some(index);
+            if let Err(e) = self.save_collection(index) {
+                error!("Failed to save imported collection: {}", e);
+            }
+        }
+
+        if !result.variables.is_empty() {
+            let environment = result.environment.unwrap_or(self.current_environment);
+            let existing = self
+                .environment_variables
+                .get(environment)
+                .cloned()
+                .unwrap_or_default();
+            for (key, value) in result.variables {
+                // Collection variables are defaults; environment values take precedence
+                if result.overwrite || !existing.contains_key(&key) {
+                    self.environment_variables.set(environment, key, value);
+                }
+            }
+            if let Err(e) = self.save_environment_variables() {
+                error!("Failed to save environment variables: {}", e);
+            }
+        }
+
+        self.import_report = Some(report);
+        Task::none()
+    }
+
+    pub fn handle_close_import_report(&mut self) -> Task<Message> {
+        self.import_report = None;
+        Task::none()
+    }
+}
@@ -42,6 +42,15 @@ impl Requiem {
         Task::none()
     }
 
+    pub fn handle_toggle_header_secret(&mut self, idx: usize) -> Task<Message> {
+        if let Some(request) = self.get_current_request_mut() {
+            if let Some(header) = request.headers.get_mut(idx) {
+                header.secret = !header.secret;
+            }
+        }
+        Task::none()
+    }
+
     // ============ Query Parameters ============
 
     pub fn handle_param_key_changed(&mut self, idx: usize, key: String) -> Task<Message> {
@@ -78,6 +87,15 @@ impl Requiem {
         Task::none()
     }
 
+    pub fn handle_toggle_param_secret(&mut self, idx: usize) -> Task<Message> {
+        if let Some(request) = self.get_current_request_mut() {
+            if let Some(param) = request.query_params.get_mut(idx) {
+                param.secret = !param.secret;
+            }
+        }
+        Task::none()
+    }
+
     // ============ Cookies ============
 
     pub fn handle_cookie_key_changed(&mut self, idx: usize, key: String) -> Task<Message> {
@@ -114,6 +132,15 @@ impl Requiem {
         Task::none()
     }
 
+    pub fn handle_toggle_cookie_secret(&mut self, idx: usize) -> Task<Message> {
+        if let Some(request) = self.get_current_request_mut() {
+            if let Some(cookie) = request.cookies.get_mut(idx) {
+                cookie.secret = !cookie.secret;
+            }
+        }
+        Task::none()
+    }
+
     // ============ Authentication ============
 
     pub fn handle_auth_key_changed(&mut self, idx: usize, key: String) -> Task<Message> {
@@ -149,4 +176,87 @@ impl Requiem {
         }
         Task::none()
     }
+
+    pub fn handle_toggle_auth_secret(&mut self, idx: usize) -> Task<Message> {
+        if let Some(request) = self.get_current_request_mut() {
+            if let Some(auth_field) = request.auth.get_mut(idx) {
+                auth_field.secret = !auth_field.secret;
+            }
+        }
+        Task::none()
+    }
+
+    // ============ Extraction Rules ============
+
+    pub fn handle_extraction_variable_changed(
+        &mut self,
+        idx: usize,
+        variable: String,
+    ) -> Task<Message> {
+        if let Some(request) = self.get_current_request_mut() {
+            if let Some(rule) = request.extractions.get_mut(idx) {
+                rule.variable = variable;
+            }
+        }
+        Task::none()
+    }
+
+    pub fn handle_extraction_source_changed(
+        &mut self,
+        idx: usize,
+        source: models::ExtractionSource,
+    ) -> Task<Message> {
+        if let Some(request) = self.get_current_request_mut() {
+            if let Some(rule) = request.extractions.get_mut(idx) {
+                rule.source = source;
+            }
+        }
+        Task::none()
+    }
+
+    pub fn handle_extraction_expression_changed(
+        &mut self,
+        idx: usize,
+        expression: String,
+    ) -> Task<Message> {
+        if let Some(request) = self.get_current_request_mut() {
+            if let Some(rule) = request.extractions.get_mut(idx) {
+                rule.expression = expression;
+            }
+        }
+        Task::none()
+    }
+
+    pub fn handle_extraction_scope_changed(
+        &mut self,
+        idx: usize,
+        scope: models::VariableScope,
+    ) -> Task<Message> {
+        if let Some(request) = self.get_current_request_mut() {
+            if let Some(rule) = request.extractions.get_mut(idx) {
+                rule.scope = scope;
+            }
+        }
+        Task::none()
+    }
+
+    pub fn handle_add_extraction(&mut self) -> Task<Message> {
+        if let Some(request) = self.get_current_request_mut() {
+            request.extractions.push(models::ExtractionRule::new(
+                "",
+                models::ExtractionSource::JsonPath,
+                "",
+            ));
+        }
+        Task::none()
+    }
+
+    pub fn handle_remove_extraction(&mut self, idx: usize) -> Task<Message> {
+        if let Some(request) = self.get_current_request_mut() {
+            if idx < request.extractions.len() {
+                request.extractions.remove(idx);
+            }
+        }
+        Task::none()
+    }
 }
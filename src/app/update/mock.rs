@@ -0,0 +1,219 @@
+use iced::Task;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tracing::{error, info};
+
+use crate::mock_server::{self, MockEvent};
+use crate::models::{MockServerConfig, ResponseExample};
+use crate::ui::toast::Toast;
+use crate::utils::navigation;
+
+use super::super::message::Message;
+use super::super::state::{MockServerState, Requiem};
+
+/// Hits kept in the log; older ones are dropped
+const MAX_HITS: usize = 500;
+
+impl Requiem {
+    /// Pin the current response as the example of the active request
+    ///
+    /// Later responses are compared with it in the Diff tab and by the runner.
+    pub fn handle_save_response_example(&mut self) -> Task<Message> {
+        let Some(response) = &self.response else {
+            return Task::none();
+        };
+        let mut example = ResponseExample::from_response(response);
+
+        let is_draft = self
+            .active_tab_index
+            .and_then(|idx| self.open_tabs.get(idx))
+            .is_some_and(|tab| tab.is_new);
+        let Some(request) = self.get_current_request_mut() else {
+            return Task::none();
+        };
+        // Re-pinning keeps the ignore rules written for the old example
+        if let Some(previous) = request.example.take() {
+            example.ignore = previous.ignore;
+        }
+        request.example = Some(example);
+        self.refresh_snapshot_diff();
+
+        // Drafts keep the example until the request itself is saved
+        if !is_draft {
+            if let Some(collection_idx) = self.selected_request.as_ref().map(|p| p[0]) {
+                if let Err(e) = self.save_collection(collection_idx) {
+                    error!("Failed to save example: {}", e);
+                    return Task::done(Message::ShowToast(Toast::error(format!(
+                        "Failed to save example: {}",
+                        e
+                    ))));
+                }
+            }
+        }
+
+        Task::done(Message::ShowToast(Toast::success(
+            self.translations.get("mock_example_saved").to_string(),
+        )))
+    }
+
+    /// Open the mock server dialog for a collection
+    pub fn handle_show_mock_server_dialog(&mut self, path: Vec<usize>) -> Task<Message> {
+        self.context_menu = None;
+
+        let Some(collection) = path.first().and_then(|idx| self.collections.get(*idx)) else {
+            return Task::none();
+        };
+
+        if let Some(state) = &mut self.mock_server {
+            if state.running {
+                // Only one server runs at a time; show the one that is running
+                state.show_dialog = true;
+                if state.collection_id != collection.id {
+                    return Task::done(Message::ShowToast(Toast::warning(format!(
+                        "{}: {}",
+                        self.translations.get("mock_already_running"),
+                        state.name
+                    ))));
+                }
+                return Task::none();
+            }
+        }
+
+        let defaults = MockServerConfig::default();
+        self.mock_server = Some(MockServerState {
+            collection_id: collection.id,
+            name: collection.name.clone(),
+            port: defaults.port.to_string(),
+            latency_ms: defaults.latency_ms.to_string(),
+            running: false,
+            stop: Arc::new(AtomicBool::new(false)),
+            hits: vec![],
+            show_dialog: true,
+        });
+        Task::none()
+    }
+
+    /// Hide the dialog; a running server keeps serving in the background
+    pub fn handle_close_mock_server_dialog(&mut self) -> Task<Message> {
+        match &mut self.mock_server {
+            Some(state) if state.running => state.show_dialog = false,
+            _ => self.mock_server = None,
+        }
+        Task::none()
+    }
+
+    pub fn handle_mock_port_changed(&mut self, value: String) -> Task<Message> {
+        if let Some(state) = &mut self.mock_server {
+            if !state.running && value.len() <= 5 && value.chars().all(|c| c.is_ascii_digit()) {
+                state.port = value;
+            }
+        }
+        Task::none()
+    }
+
+    pub fn handle_mock_latency_changed(&mut self, value: String) -> Task<Message> {
+        if let Some(state) = &mut self.mock_server {
+            if !state.running && value.chars().all(|c| c.is_ascii_digit()) {
+                state.latency_ms = value;
+            }
+        }
+        Task::none()
+    }
+
+    /// Start serving the saved requests of the collection
+    ///
+    /// Routes are read once at start, so edits made afterwards need a restart.
+    pub fn handle_start_mock_server(&mut self) -> Task<Message> {
+        let Some(state) = &mut self.mock_server else {
+            return Task::none();
+        };
+        if state.running {
+            return Task::none();
+        }
+        let Some(collection) = self
+            .collections
+            .iter()
+            .find(|c| c.id == state.collection_id)
+        else {
+            return Task::none();
+        };
+
+        let routes = mock_server::routes(&navigation::collect_requests(&collection.items));
+        let config = MockServerConfig {
+            port: state
+                .port
+                .parse()
+                .unwrap_or(MockServerConfig::default().port),
+            latency_ms: state.latency_ms.parse().unwrap_or(0),
+        };
+
+        info!(
+            "Starting mock server for '{}' with {} routes",
+            state.name,
+            routes.len()
+        );
+        state.running = true;
+        state.hits.clear();
+        state.stop = Arc::new(AtomicBool::new(false));
+
+        Task::run(
+            mock_server::serve(routes, config, state.stop.clone()),
+            Message::MockServerEvent,
+        )
+    }
+
+    /// Ask the server to stop; `Stopped` arrives once the port is released
+    pub fn handle_stop_mock_server(&mut self) -> Task<Message> {
+        self.context_menu = None;
+        if let Some(state) = &self.mock_server {
+            info!("Stopping mock server for '{}'", state.name);
+            state.stop.store(true, Ordering::Relaxed);
+        }
+        Task::none()
+    }
+
+    /// Apply a server lifecycle change or log a hit
+    pub fn handle_mock_server_event(&mut self, event: MockEvent) -> Task<Message> {
+        let Some(state) = &mut self.mock_server else {
+            return Task::none();
+        };
+
+        match event {
+            MockEvent::Started(port) => {
+                state.port = port.to_string();
+                Task::done(Message::ShowToast(Toast::success(format!(
+                    "{} http://127.0.0.1:{}",
+                    self.translations.get("mock_listening"),
+                    port
+                ))))
+            }
+            MockEvent::Hit(hit) => {
+                state.hits.push(hit);
+                if state.hits.len() > MAX_HITS {
+                    state.hits.remove(0);
+                }
+                Task::none()
+            }
+            MockEvent::Failed(message) => {
+                state.running = false;
+                Task::done(Message::ShowToast(Toast::error(message)))
+            }
+            MockEvent::Stopped => {
+                state.running = false;
+                if !state.show_dialog {
+                    self.mock_server = None;
+                }
+                Task::done(Message::ShowToast(Toast::info(
+                    self.translations.get("mock_stopped").to_string(),
+                )))
+            }
+        }
+    }
+
+    pub fn handle_clear_mock_hits(&mut self) -> Task<Message> {
+        if let Some(state) = &mut self.mock_server {
+            state.hits.clear();
+        }
+        Task::none()
+    }
+}
@@ -0,0 +1,46 @@
+use std::path::PathBuf;
+
+use iced::Task;
+use tracing::{error, info};
+
+use crate::ui::toast::Toast;
+
+use super::super::message::Message;
+use super::super::state::Requiem;
+
+impl Requiem {
+    /// Put a backup in place of a file that could not be read, then load it
+    pub fn handle_restore_backup(&mut self, path: PathBuf, backup: PathBuf) -> Task<Message> {
+        if let Err(e) = crate::storage::restore_backup(&self.save_directory, &backup, &path) {
+            error!("Failed to restore backup: {}", e);
+            return Task::done(Message::ShowToast(Toast::error(e)));
+        }
+        Task::batch([
+            self.handle_check_storage(),
+            Task::done(Message::ShowToast(Toast::success(
+                self.translations.get("recovery_restored"),
+            ))),
+        ])
+    }
+
+    /// Open a file that could not be read with the system's default program
+    pub fn handle_open_raw_file(&mut self, path: PathBuf) -> Task<Message> {
+        #[cfg(target_os = "linux")]
+        let command = "xdg-open";
+        #[cfg(target_os = "macos")]
+        let command = "open";
+        #[cfg(target_os = "windows")]
+        let command = "explorer";
+
+        match std::process::Command::new(command).arg(&path).spawn() {
+            Ok(_) => info!("Opened file: {:?}", path),
+            Err(e) => error!("Failed to open file: {}", e),
+        }
+        Task::none()
+    }
+
+    pub fn handle_close_recovery_dialog(&mut self) -> Task<Message> {
+        self.show_recovery_dialog = false;
+        Task::none()
+    }
+}
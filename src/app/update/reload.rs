@@ -0,0 +1,293 @@
+use std::collections::HashSet;
+
+use iced::widget::text_editor;
+use iced::Task;
+use tracing::{error, info};
+
+use crate::models::{BodyType, Collection, Request};
+use crate::ui::toast::Toast;
+use crate::utils::{navigation, reload};
+
+use super::super::message::Message;
+use super::super::state::{ReloadConflictState, RequestTabItem, Requiem};
+
+impl Requiem {
+    /// Reload collections that other programs changed in the save directory
+    ///
+    /// Collections read from disk are compared with their last saved
+    /// version, so our own writes and unsaved edits are never mistaken for
+    /// external changes.
+    pub fn handle_check_storage(&mut self) -> Task<Message> {
+        let fingerprint = self.fingerprint_storage();
+        if fingerprint == self.storage_fingerprint {
+            return Task::none();
+        }
+        self.storage_fingerprint = fingerprint;
+
+        let loaded = match crate::storage::load_collections_with_failures(&self.save_directory) {
+            Ok((loaded, failures)) => {
+                // Files fixed in the meantime drop off the recovery screen
+                self.load_failures = failures;
+                if self.load_failures.is_empty() {
+                    self.show_recovery_dialog = false;
+                }
+                loaded
+            }
+            Err(e) => {
+                error!("Failed to reload collections: {}", e);
+                return Task::none();
+            }
+        };
+
+        let mut reloaded = 0;
+        let mut on_disk = HashSet::new();
+        for mut disk in loaded {
+            crate::secrets::resolve_collection(&mut disk, &self.secrets);
+            on_disk.insert(disk.id);
+            let saved = self.saved_collections.get(&disk.id);
+            if saved.is_some_and(|saved| reload::same(saved, &disk)) {
+                continue;
+            }
+            let Some(index) = self.collections.iter().position(|c| c.id == disk.id) else {
+                info!("Collection '{}' was added on disk", disk.name);
+                self.saved_collections.insert(disk.id, disk.clone());
+                self.collections.push(disk);
+                reloaded += 1;
+                continue;
+            };
+
+            info!("Collection '{}' changed on disk", disk.name);
+            // Without a saved version there are no unsaved edits to keep
+            let saved = saved
+                .cloned()
+                .unwrap_or_else(|| self.collections[index].clone());
+            let (merged, conflicts) = reload::merge(&saved, &self.collections[index], disk.clone());
+            self.saved_collections.insert(disk.id, disk);
+            self.reload_conflicts
+                .retain(|state| state.collection.id != merged.id);
+            if conflicts.is_empty() {
+                self.replace_collection(merged, vec![]);
+                reloaded += 1;
+            } else {
+                self.reload_conflicts.push(ReloadConflictState {
+                    collection: merged,
+                    conflicts,
+                    show_diff: false,
+                });
+            }
+        }
+
+        // While any file cannot be read, a missing collection may merely be
+        // unreadable; one with unsaved edits stays and is written back on the
+        // next save
+        let removed: Vec<usize> = self
+            .collections
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| {
+                self.load_failures.is_empty()
+                    && !on_disk.contains(&c.id)
+                    && self
+                        .saved_collections
+                        .get(&c.id)
+                        .is_some_and(|saved| !reload::has_edits(saved, c))
+            })
+            .map(|(index, _)| index)
+            .collect();
+        for index in removed.into_iter().rev() {
+            info!(
+                "Collection '{}' was deleted on disk",
+                self.collections[index].name
+            );
+            self.saved_collections.remove(&self.collections[index].id);
+            self.remove_collection(index);
+            reloaded += 1;
+        }
+
+        if reloaded == 0 {
+            return Task::none();
+        }
+        // Undoing an edit made before would write over the changes on disk
+        self.undo.clear();
+        self.sync_request_editor();
+        Task::done(Message::ShowToast(Toast::success(
+            self.translations.get("storage_reloaded"),
+        )))
+    }
+
+    /// Resolve the first conflict with the edits made here, which stay unsaved
+    pub fn handle_keep_my_changes(&mut self) -> Task<Message> {
+        if self.reload_conflicts.is_empty() {
+            return Task::none();
+        }
+        let ReloadConflictState {
+            mut collection,
+            conflicts,
+            ..
+        } = self.reload_conflicts.remove(0);
+
+        let mut drafts = Vec::new();
+        for conflict in conflicts {
+            // Requests deleted on disk stay open as unsaved requests
+            if !reload::replace_request(&mut collection.items, &conflict.mine) {
+                drafts.push(conflict.mine);
+            }
+        }
+        self.replace_collection(collection, drafts);
+        self.undo.clear();
+        self.sync_request_editor();
+        Task::none()
+    }
+
+    /// Resolve the first conflict with the requests as they are on disk
+    pub fn handle_take_their_changes(&mut self) -> Task<Message> {
+        if self.reload_conflicts.is_empty() {
+            return Task::none();
+        }
+        let state = self.reload_conflicts.remove(0);
+        self.replace_collection(state.collection, vec![]);
+        self.undo.clear();
+        self.sync_request_editor();
+        Task::none()
+    }
+
+    pub fn handle_toggle_reload_diff(&mut self) -> Task<Message> {
+        if let Some(state) = self.reload_conflicts.first_mut() {
+            state.show_diff = !state.show_diff;
+        }
+        Task::none()
+    }
+
+    /// Swap in a collection read from disk, moving tabs along with their requests
+    ///
+    /// Tabs of requests that no longer exist are closed, except for those of
+    /// `drafts`, which stay open as unsaved requests.
+    pub(super) fn replace_collection(&mut self, collection: Collection, mut drafts: Vec<Request>) {
+        let Some(index) = self.collections.iter().position(|c| c.id == collection.id) else {
+            return;
+        };
+        let old = std::mem::replace(&mut self.collections[index], collection);
+        let new = self.collections[index].items.clone();
+        let remap = |path: &[usize]| -> Option<Vec<usize>> {
+            if path.first() != Some(&index) {
+                return Some(path.to_vec());
+            }
+            let mut remapped = vec![index];
+            remapped.extend(reload::remap_path(&old.items, &new, &path[1..])?);
+            Some(remapped)
+        };
+
+        let active_id = self
+            .active_tab_index
+            .and_then(|active| self.open_tabs.get(active))
+            .map(|tab| tab.id);
+        self.open_tabs.retain_mut(|tab| {
+            if let Some(path) = tab.request_path.clone() {
+                if let Some(path) = remap(&path) {
+                    if let Some(name) = navigation::get_item_name(&self.collections, &path) {
+                        tab.name = name;
+                    }
+                    tab.request_path = Some(path);
+                    return true;
+                }
+                let Some(position) = drafts.iter().position(|draft| draft.id == tab.id) else {
+                    return false;
+                };
+                tab.request_path = None;
+                tab.is_new = true;
+                tab.draft_request = Some(drafts.remove(position));
+                tab.parent_path = Some(vec![index]);
+            } else if let Some(parent_path) = tab.parent_path.clone() {
+                // Unsaved requests move to the collection root when their folder is gone
+                tab.parent_path = Some(remap(&parent_path).unwrap_or_else(|| vec![index]));
+            }
+            true
+        });
+        // Requests whose tab was already closed get a tab of their own
+        for draft in drafts {
+            self.open_tabs.push(RequestTabItem {
+                id: draft.id,
+                name: draft.name.clone(),
+                request_path: None,
+                is_modified: false,
+                is_new: true,
+                draft_request: Some(draft),
+                parent_path: Some(vec![index]),
+            });
+        }
+        self.active_tab_index = active_id
+            .and_then(|id| self.open_tabs.iter().position(|tab| tab.id == id))
+            .or_else(|| self.open_tabs.len().checked_sub(1));
+        self.remap_paths(remap);
+    }
+
+    /// Drop a collection deleted on disk, shifting paths into later collections
+    pub(super) fn remove_collection(&mut self, index: usize) {
+        self.close_collection_tabs(index);
+        self.collections.remove(index);
+        let shift = |path: &[usize]| -> Option<Vec<usize>> {
+            let mut path = path.to_vec();
+            match path.first_mut() {
+                Some(first) if *first == index => return None,
+                Some(first) if *first > index => *first -= 1,
+                _ => {}
+            }
+            Some(path)
+        };
+
+        for tab in &mut self.open_tabs {
+            tab.request_path = tab.request_path.take().and_then(|path| shift(&path));
+            tab.parent_path = tab.parent_path.take().and_then(|path| shift(&path));
+        }
+        self.selected_collection = self
+            .selected_collection
+            .and_then(|selected| shift(&[selected]))
+            .map(|path| path[0]);
+        self.remap_paths(shift);
+    }
+
+    /// Point the selection and open dialogs at where their items are now,
+    /// closing the dialogs whose item is gone
+    pub(super) fn remap_paths(&mut self, remap: impl Fn(&[usize]) -> Option<Vec<usize>>) {
+        self.selected_request = self.selected_request.take().and_then(|path| remap(&path));
+        if self.selected_request.is_none() {
+            self.selected_request = self
+                .active_tab_index
+                .and_then(|active| self.open_tabs.get(active))
+                .and_then(|tab| tab.request_path.clone());
+        }
+        self.context_menu = self.context_menu.take().and_then(|mut menu| {
+            menu.path = remap(&menu.path)?;
+            Some(menu)
+        });
+        self.renaming_item = self
+            .renaming_item
+            .take()
+            .and_then(|(path, original, current)| Some((remap(&path)?, original, current)));
+        self.runner = self.runner.take().and_then(|mut runner| {
+            runner.path = remap(&runner.path)?;
+            Some(runner)
+        });
+        self.export_dialog = self.export_dialog.take().and_then(|mut dialog| {
+            dialog.path = remap(&dialog.path)?;
+            Some(dialog)
+        });
+        self.flow_editor = self.flow_editor.take().and_then(|mut editor| {
+            editor.path = remap(&editor.path)?;
+            Some(editor)
+        });
+    }
+
+    /// Show the active request again after it changed on disk
+    pub(super) fn sync_request_editor(&mut self) {
+        let body_text = match self.get_current_request().map(|request| &request.body) {
+            Some(BodyType::Json(s) | BodyType::Xml(s) | BodyType::Text(s)) => s.clone(),
+            _ => String::new(),
+        };
+        if self.request_body_content.text() != body_text {
+            self.request_body_content = text_editor::Content::with_text(&body_text);
+        }
+        self.sync_schema_content();
+        self.refresh_snapshot_diff();
+    }
+}
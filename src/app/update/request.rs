@@ -16,13 +16,77 @@ impl Requiem {
     }
 
     /// Handle URL changes
+    ///
+    /// A curl command pasted into the URL bar replaces the request instead.
     pub fn handle_url_changed(&mut self, url: String) -> Task<Message> {
+        // Typing adds one character at a time; a paste adds many at once
+        let pasted = self
+            .get_current_request()
+            .is_some_and(|request| url.chars().count() > request.url.chars().count() + 1);
+        if pasted && crate::utils::curl::is_curl_command(&url) {
+            match crate::utils::curl::parse(&url) {
+                Ok(imported) => return self.apply_curl_import(imported),
+                Err(e) => {
+                    error!("Failed to import curl command: {}", e);
+                    if let Some(request) = self.get_current_request_mut() {
+                        request.url = url;
+                    }
+                    return Task::done(Message::ShowToast(crate::ui::toast::Toast::error(
+                        format!("{}: {}", self.translations.get("curl_import_failed"), e),
+                    )));
+                }
+            }
+        }
+
         if let Some(request) = self.get_current_request_mut() {
             request.url = url;
         }
         Task::none()
     }
 
+    /// Replace the current request with one parsed from a curl command
+    fn apply_curl_import(&mut self, imported: models::Request) -> Task<Message> {
+        info!(
+            "Imported curl command: {} {}",
+            imported.method.as_str(),
+            imported.url
+        );
+        let Some(request) = self.get_current_request_mut() else {
+            return Task::none();
+        };
+
+        // Keep the identity, name and checks of the request being edited
+        *request = models::Request {
+            id: request.id,
+            name: request.name.clone(),
+            description: std::mem::take(&mut request.description),
+            extractions: std::mem::take(&mut request.extractions),
+            example: request.example.take(),
+            schema: request.schema.take(),
+            imported: request.imported.take(),
+            ..imported
+        };
+        let body_text = match &request.body {
+            models::BodyType::Json(s) | models::BodyType::Xml(s) | models::BodyType::Text(s) => {
+                s.clone()
+            }
+            _ => String::new(),
+        };
+        self.request_body_content = iced::widget::text_editor::Content::with_text(&body_text);
+
+        Task::done(Message::ShowToast(crate::ui::toast::Toast::success(
+            self.translations.get("curl_imported").to_string(),
+        )))
+    }
+
+    /// Turn TLS certificate verification back on for the current request
+    pub fn handle_clear_insecure(&mut self) -> Task<Message> {
+        if let Some(request) = self.get_current_request_mut() {
+            request.insecure = false;
+        }
+        Task::none()
+    }
+
     /// Handle AI Fill action
     pub fn handle_ai_fill(&mut self) -> Task<Message> {
         info!("AI Fill triggered - opening dialog");
@@ -34,6 +98,7 @@ impl Requiem {
         info!("Cancelling current request");
         self.loading = false;
         self.current_request_id = None; // Clear the request ID to ignore future responses
+        self.pending_history = None;
         Task::none()
     }
 
@@ -49,6 +114,20 @@ impl Requiem {
             let request_id = uuid::Uuid::new_v4();
             self.loading = true;
             self.current_request_id = Some(request_id);
+            self.pending_extractions = request.extractions.clone();
+            self.pending_schema = request.schema.clone();
+
+            // Resolve {{variable}} placeholders before sending
+            let request =
+                crate::utils::variables::resolve_request(&request, &self.active_variables());
+            self.pending_history = Some(models::HistoryEntry {
+                id: request_id,
+                sent_at: chrono::Local::now().to_rfc3339(),
+                request: request.clone(),
+                response: None,
+                error: None,
+                body_omitted: false,
+            });
 
             Task::perform(
                 async move {
@@ -79,6 +158,7 @@ impl Requiem {
 
         self.loading = false;
         self.current_request_id = None; // Clear the current request ID
+        self.record_history(&result);
 
         match result {
             Ok(ref response) => {
@@ -92,16 +172,101 @@ impl Requiem {
                     iced::widget::text_editor::Content::with_text(&response.body);
                 self.active_body_view_mode = crate::models::BodyViewMode::Raw;
                 self.error_message = None; // Clear any previous error
+                let save_dir = std::path::Path::new(&self.save_directory);
+                self.schema_result = self.pending_schema.take().map(|schema| {
+                    crate::utils::json_schema::check(&schema, &response.body, save_dir)
+                });
+                self.refresh_snapshot_diff();
+
+                let rules = std::mem::take(&mut self.pending_extractions);
+                return self.apply_extractions(&rules, response);
             }
             Err(ref e) => {
                 error!("Request failed: {}", e);
                 self.error_message = Some(e.clone()); // Store error message
                 self.response = None; // Clear response on error
+                self.pending_extractions.clear();
+                self.pending_schema = None;
+                self.schema_result = None;
+                self.snapshot_diff = None;
             }
         }
         Task::none()
     }
 
+    /// Complete the history entry of the finished request and store it
+    fn record_history(&mut self, result: &Result<models::Response, String>) {
+        let Some(mut entry) = self.pending_history.take() else {
+            return;
+        };
+        match result {
+            Ok(response) => entry.response = Some(response.clone()),
+            Err(e) => entry.error = Some(e.clone()),
+        }
+        if let Some(response) = entry.response.as_mut() {
+            if response.body.len() > self.history_config.max_body_bytes {
+                response.body.clear();
+                entry.body_omitted = true;
+            }
+        }
+        // Secret fields are stored as references to the secret store
+        let stored = crate::secrets::redact_history(&entry);
+        if let Err(e) = crate::storage::append_history(&self.save_directory, &stored) {
+            error!("Failed to save history: {}", e);
+        }
+        self.history.push(entry);
+    }
+
+    /// Run extraction rules against a response and store the captured variables
+    pub fn apply_extractions(
+        &mut self,
+        rules: &[models::ExtractionRule],
+        response: &models::Response,
+    ) -> Task<Message> {
+        let mut failures = Vec::new();
+        let mut environment_changed = false;
+
+        for (rule, result) in crate::utils::extractor::extract_all(rules, response) {
+            let variable = rule.variable.trim().to_string();
+            match result {
+                Ok(value) => {
+                    debug!("Extracted variable '{}' ({:?})", variable, rule.scope);
+                    match rule.scope {
+                        models::VariableScope::Runtime => {
+                            self.runtime_variables.insert(variable, value);
+                        }
+                        models::VariableScope::Environment => {
+                            // Drop any runtime override so the new value takes effect
+                            self.runtime_variables.remove(&variable);
+                            self.environment_variables
+                                .set(self.current_environment, variable, value);
+                            environment_changed = true;
+                        }
+                    }
+                }
+                Err(e) => {
+                    error!("Failed to extract variable '{}': {}", variable, e);
+                    failures.push(variable);
+                }
+            }
+        }
+
+        if environment_changed {
+            if let Err(e) = self.save_environment_variables() {
+                error!("Failed to save environment variables: {}", e);
+            }
+        }
+
+        if failures.is_empty() {
+            Task::none()
+        } else {
+            Task::done(Message::ShowToast(crate::ui::toast::Toast::warning(format!(
+                "Failed to extract: {}",
+                failures.join(", ")
+            ))))
+        }
+    }
+
     /// Handle body content changes
     pub fn handle_body_changed(&mut self, body: String) -> Task<Message> {
         if let Some(request) = self.get_current_request_mut() {
@@ -0,0 +1,248 @@
+use iced::Task;
+use std::collections::HashMap;
+use tracing::{error, info};
+
+use crate::models::{RunOptions, RunReport};
+use crate::ui::toast::Toast;
+use crate::utils::{data_file, navigation};
+
+use super::super::message::Message;
+use super::super::state::{Requiem, RunnerState};
+
+impl Requiem {
+    /// Open the collection runner for a collection or folder
+    pub fn handle_show_runner_dialog(&mut self, path: Vec<usize>) -> Task<Message> {
+        self.context_menu = None;
+
+        let Some(name) = navigation::get_item_name(&self.collections, &path) else {
+            return Task::none();
+        };
+
+        info!("Opening collection runner for '{}'", name);
+        self.runner = Some(RunnerState {
+            path,
+            name,
+            iterations: "1".to_string(),
+            delay_ms: "0".to_string(),
+            stop_on_failure: false,
+            data_file: None,
+            data_rows: vec![],
+            running: false,
+            report: None,
+        });
+        Task::none()
+    }
+
+    /// Close the collection runner dialog
+    pub fn handle_close_runner_dialog(&mut self) -> Task<Message> {
+        // Keep the dialog while a run is in flight so its report is not lost
+        if self.runner.as_ref().is_some_and(|r| !r.running) {
+            self.runner = None;
+        }
+        Task::none()
+    }
+
+    /// Update the iteration count input
+    pub fn handle_runner_iterations_changed(&mut self, value: String) -> Task<Message> {
+        if let Some(runner) = &mut self.runner {
+            if value.chars().all(|c| c.is_ascii_digit()) {
+                runner.iterations = value;
+            }
+        }
+        Task::none()
+    }
+
+    /// Update the delay input
+    pub fn handle_runner_delay_changed(&mut self, value: String) -> Task<Message> {
+        if let Some(runner) = &mut self.runner {
+            if value.chars().all(|c| c.is_ascii_digit()) {
+                runner.delay_ms = value;
+            }
+        }
+        Task::none()
+    }
+
+    /// Toggle stop on first failure
+    pub fn handle_toggle_runner_stop_on_failure(&mut self) -> Task<Message> {
+        if let Some(runner) = &mut self.runner {
+            runner.stop_on_failure = !runner.stop_on_failure;
+        }
+        Task::none()
+    }
+
+    /// Pick a CSV or JSON data file for data-driven iterations
+    pub fn handle_select_runner_data_file(&mut self) -> Task<Message> {
+        Task::perform(
+            async {
+                use rfd::AsyncFileDialog;
+                let handle = AsyncFileDialog::new()
+                    .set_title("Select Data File")
+                    .add_filter("Data", &["csv", "json"])
+                    .pick_file()
+                    .await?;
+                let path = handle.path().to_path_buf();
+                Some(data_file::load(&path).map(|rows| (path.to_string_lossy().to_string(), rows)))
+            },
+            |result| match result {
+                Some(result) => Message::RunnerDataFileLoaded(result),
+                None => Message::HideContextMenu,
+            },
+        )
+    }
+
+    /// Attach the loaded data rows to the runner
+    pub fn handle_runner_data_file_loaded(
+        &mut self,
+        result: Result<(String, Vec<HashMap<String, String>>), String>,
+    ) -> Task<Message> {
+        let Some(runner) = &mut self.runner else {
+            return Task::none();
+        };
+
+        match result {
+            Ok((path, rows)) => {
+                info!("Loaded {} data rows from {}", rows.len(), path);
+                if rows.is_empty() {
+                    return Task::done(Message::ShowToast(Toast::warning(
+                        self.translations.get("runner_data_empty").to_string(),
+                    )));
+                }
+                runner.data_file = Some(path);
+                runner.data_rows = rows;
+                Task::none()
+            }
+            Err(e) => {
+                error!("Failed to load data file: {}", e);
+                Task::done(Message::ShowToast(Toast::error(e)))
+            }
+        }
+    }
+
+    /// Detach the data file and go back to a fixed iteration count
+    pub fn handle_clear_runner_data_file(&mut self) -> Task<Message> {
+        if let Some(runner) = &mut self.runner {
+            runner.data_file = None;
+            runner.data_rows.clear();
+        }
+        Task::none()
+    }
+
+    /// Start running every request under the selected collection or folder
+    pub fn handle_start_collection_run(&mut self) -> Task<Message> {
+        let variables = self.active_variables();
+        let Some(runner) = &mut self.runner else {
+            return Task::none();
+        };
+        if runner.running {
+            return Task::none();
+        }
+
+        let requests = navigation::get_items_by_path(&self.collections, &runner.path)
+            .map(navigation::collect_requests)
+            .unwrap_or_default();
+        if requests.is_empty() {
+            return Task::done(Message::ShowToast(Toast::warning(
+                self.translations.get("runner_no_requests").to_string(),
+            )));
+        }
+
+        let options = RunOptions {
+            iterations: runner.iterations.parse().unwrap_or(1).max(1),
+            delay_ms: runner.delay_ms.parse().unwrap_or(0),
+            stop_on_failure: runner.stop_on_failure,
+            data_file: runner.data_file.clone(),
+        };
+
+        runner.running = true;
+        runner.report = None;
+
+        let name = runner.name.clone();
+        let data_rows = runner.data_rows.clone();
+        let save_dir = self.save_directory.clone().into();
+        Task::perform(
+            crate::runner::run_requests(name, requests, variables, options, data_rows, save_dir),
+            Message::CollectionRunCompleted,
+        )
+    }
+
+    /// Store the report of a finished run
+    pub fn handle_collection_run_completed(&mut self, report: RunReport) -> Task<Message> {
+        info!(
+            "Run '{}' finished: {} passed, {} failed",
+            report.name,
+            report.passed_count(),
+            report.failed_count()
+        );
+
+        let toast = if report.failed_count() == 0 && !report.aborted {
+            Toast::success(format!(
+                "{}: {} {}",
+                report.name,
+                report.passed_count(),
+                self.translations.get("runner_passed")
+            ))
+        } else {
+            Toast::error(format!(
+                "{}: {} {}",
+                report.name,
+                report.failed_count(),
+                self.translations.get("runner_failed")
+            ))
+        };
+
+        match &mut self.runner {
+            Some(runner) => {
+                runner.running = false;
+                runner.report = Some(report);
+            }
+            None => return Task::none(),
+        }
+
+        Task::done(Message::ShowToast(toast))
+    }
+
+    /// Export the last run report as JSON
+    pub fn handle_save_run_report(&mut self) -> Task<Message> {
+        let Some(report) = self.runner.as_ref().and_then(|r| r.report.clone()) else {
+            return Task::none();
+        };
+
+        let json = match serde_json::to_string_pretty(&report) {
+            Ok(json) => json,
+            Err(e) => {
+                error!("Failed to serialize run report: {}", e);
+                return Task::done(Message::ShowToast(Toast::error(format!(
+                    "Failed to save report: {}",
+                    e
+                ))));
+            }
+        };
+
+        let file_name = format!("{}-report.json", report.name);
+        Task::perform(
+            async move {
+                use rfd::AsyncFileDialog;
+                let handle = AsyncFileDialog::new()
+                    .set_title("Save Run Report")
+                    .set_file_name(&file_name)
+                    .add_filter("JSON", &["json"])
+                    .save_file()
+                    .await?;
+                Some(
+                    std::fs::write(handle.path(), json)
+                        .map(|_| handle.path().to_string_lossy().to_string())
+                        .map_err(|e| e.to_string()),
+                )
+            },
+            |result| match result {
+                Some(Ok(path)) => {
+                    Message::ShowToast(Toast::success(format!("Report saved to {}", path)))
+                }
+                Some(Err(e)) => {
+                    Message::ShowToast(Toast::error(format!("Failed to save report: {}", e)))
+                }
+                None => Message::HideContextMenu,
+            },
+        )
+    }
+}
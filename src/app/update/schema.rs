@@ -0,0 +1,103 @@
+use iced::widget::text_editor;
+use iced::Task;
+use std::path::Path;
+
+use crate::models::{ResponseSchema, SchemaSource};
+use crate::utils::json_schema;
+
+use super::super::message::Message;
+use super::super::state::Requiem;
+
+impl Requiem {
+    /// Load the inline schema of the current request into the schema editor
+    pub fn sync_schema_content(&mut self) {
+        let inline = self
+            .get_current_request()
+            .and_then(|request| request.schema.as_ref())
+            .map(|schema| schema.inline.clone())
+            .unwrap_or_default();
+        // Only recreate content if the text actually changed
+        if self.schema_content.text() != inline {
+            self.schema_content = text_editor::Content::with_text(&inline);
+        }
+    }
+
+    /// Attach or detach the response schema of the current request
+    pub fn handle_toggle_response_schema(&mut self) -> Task<Message> {
+        if let Some(request) = self.get_current_request_mut() {
+            request.schema = match request.schema {
+                Some(_) => None,
+                None => Some(ResponseSchema::default()),
+            };
+        }
+        self.sync_schema_content();
+        Task::none()
+    }
+
+    pub fn handle_schema_source_selected(&mut self, source: SchemaSource) -> Task<Message> {
+        if let Some(schema) = self
+            .get_current_request_mut()
+            .and_then(|request| request.schema.as_mut())
+        {
+            schema.source = source;
+        }
+        Task::none()
+    }
+
+    /// Handle inline schema editor actions
+    pub fn handle_schema_editor_action(&mut self, action: text_editor::Action) -> Task<Message> {
+        let is_edit = matches!(action, text_editor::Action::Edit(_));
+        self.schema_content.perform(action);
+
+        // Navigation and selection do not change the schema
+        if is_edit {
+            let text = self.schema_content.text();
+            if let Some(schema) = self
+                .get_current_request_mut()
+                .and_then(|request| request.schema.as_mut())
+            {
+                schema.inline = text;
+            }
+        }
+        Task::none()
+    }
+
+    pub fn handle_schema_file_changed(&mut self, path: String) -> Task<Message> {
+        if let Some(schema) = self
+            .get_current_request_mut()
+            .and_then(|request| request.schema.as_mut())
+        {
+            schema.file = path;
+        }
+        Task::none()
+    }
+
+    /// Open file picker for the schema file
+    pub fn handle_browse_schema_file(&mut self) -> Task<Message> {
+        Task::perform(
+            async {
+                rfd::AsyncFileDialog::new()
+                    .add_filter("JSON Schema", &["json"])
+                    .pick_file()
+                    .await
+            },
+            |result| {
+                Message::SchemaFileSelected(
+                    result.map(|file| file.path().to_string_lossy().to_string()),
+                )
+            },
+        )
+    }
+
+    /// Keep picked files relative to the save directory, so the collection
+    /// works wherever it is opened from
+    pub fn handle_schema_file_selected(&mut self, path: Option<String>) -> Task<Message> {
+        match path {
+            Some(path) => {
+                let path = json_schema::relative_path(&path, Path::new(&self.save_directory));
+                self.handle_schema_file_changed(path)
+            }
+            None => Task::none(),
+        }
+    }
+}
@@ -0,0 +1,102 @@
+use iced::Task;
+use tracing::{error, info};
+
+use crate::secrets::{self, SecretStore};
+use crate::ui::toast::Toast;
+
+use super::super::message::Message;
+use super::super::state::Requiem;
+
+impl Requiem {
+    /// Keep a variable of the current environment in the secret store or not
+    pub fn handle_toggle_secret_variable(&mut self, name: String) -> Task<Message> {
+        let environment = self.current_environment;
+        let secret = !self.environment_variables.is_secret(environment, &name);
+        self.environment_variables
+            .set_secret(environment, &name, secret);
+        if let Err(e) = self.save_environment_variables() {
+            error!("Failed to save environment variables: {}", e);
+            self.environment_variables
+                .set_secret(environment, &name, !secret);
+            return Task::done(Message::ShowToast(Toast::error(e)));
+        }
+        Task::none()
+    }
+
+    pub fn handle_secrets_passphrase_changed(&mut self, value: String) -> Task<Message> {
+        self.secrets_passphrase = value;
+        Task::none()
+    }
+
+    /// Open the encrypted secret store and fill in the values it holds
+    pub fn handle_unlock_secrets(&mut self) -> Task<Message> {
+        let passphrase = std::mem::take(&mut self.secrets_passphrase);
+        self.secrets = match SecretStore::load(&self.save_directory, Some(&passphrase)) {
+            Ok(store) => store,
+            Err(e) => {
+                error!("Failed to unlock secrets: {}", e);
+                return Task::done(Message::ShowToast(Toast::error(e)));
+            }
+        };
+        self.show_unlock_secrets = false;
+        info!("Unlocked secrets");
+
+        // Everything read while locked still holds references
+        for collection in self
+            .collections
+            .iter_mut()
+            .chain(self.saved_collections.values_mut())
+        {
+            secrets::resolve_collection(collection, &self.secrets);
+        }
+        for request in self
+            .open_tabs
+            .iter_mut()
+            .filter_map(|tab| tab.draft_request.as_mut())
+        {
+            secrets::resolve_request(request, &self.secrets);
+        }
+        secrets::resolve_environments(&mut self.environment_variables, &self.secrets);
+        secrets::resolve_ai_config(&mut self.ai_config, &self.secrets);
+        if secrets::has_plain_api_key(&self.ai_config) {
+            if let Err(e) = self.save_ai_config() {
+                error!("Failed to move the API key to the secret store: {}", e);
+            }
+        }
+
+        Task::done(Message::ShowToast(Toast::success(
+            self.translations.get("secrets_unlocked"),
+        )))
+    }
+
+    /// Carry on without secrets; references stay as they are until unlocked
+    pub fn handle_close_unlock_secrets(&mut self) -> Task<Message> {
+        self.show_unlock_secrets = false;
+        self.secrets_passphrase.clear();
+        Task::none()
+    }
+
+    /// Encrypt the secret store with the entered passphrase, or store it in
+    /// plain text when none is entered
+    pub fn handle_set_secrets_passphrase(&mut self) -> Task<Message> {
+        let passphrase = std::mem::take(&mut self.secrets_passphrase);
+        let done = if passphrase.is_empty() {
+            "secrets_decrypted"
+        } else {
+            "secrets_encrypted"
+        };
+        let result = self
+            .secrets
+            .set_passphrase(Some(passphrase))
+            .and_then(|_| self.secrets.save(&self.save_directory));
+        match result {
+            Ok(()) => Task::done(Message::ShowToast(Toast::success(
+                self.translations.get(done),
+            ))),
+            Err(e) => {
+                error!("Failed to change the secrets passphrase: {}", e);
+                Task::done(Message::ShowToast(Toast::error(e)))
+            }
+        }
+    }
+}
@@ -0,0 +1,55 @@
+use iced::Task;
+
+use crate::utils::snapshot;
+
+use super::super::message::Message;
+use super::super::state::Requiem;
+
+impl Requiem {
+    /// Compare the current response with the example of the current request
+    pub fn refresh_snapshot_diff(&mut self) {
+        let example = self
+            .get_current_request()
+            .and_then(|request| request.example.as_ref());
+        self.snapshot_diff = match (example, &self.response) {
+            (Some(example), Some(response)) => Some(snapshot::compare(example, response)),
+            _ => None,
+        };
+    }
+
+    pub fn handle_snapshot_ignore_changed(&mut self, idx: usize, rule: String) -> Task<Message> {
+        if let Some(example) = self
+            .get_current_request_mut()
+            .and_then(|request| request.example.as_mut())
+        {
+            if let Some(existing) = example.ignore.get_mut(idx) {
+                *existing = rule;
+            }
+        }
+        self.refresh_snapshot_diff();
+        Task::none()
+    }
+
+    pub fn handle_add_snapshot_ignore(&mut self) -> Task<Message> {
+        if let Some(example) = self
+            .get_current_request_mut()
+            .and_then(|request| request.example.as_mut())
+        {
+            example.ignore.push(String::new());
+        }
+        Task::none()
+    }
+
+    pub fn handle_remove_snapshot_ignore(&mut self, idx: usize) -> Task<Message> {
+        if let Some(example) = self
+            .get_current_request_mut()
+            .and_then(|request| request.example.as_mut())
+        {
+            if idx < example.ignore.len() {
+                example.ignore.remove(idx);
+            }
+        }
+        self.refresh_snapshot_diff();
+        Task::none()
+    }
+}
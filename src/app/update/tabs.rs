@@ -27,6 +27,9 @@ impl Requiem {
                         iced::widget::text_editor::Content::with_text(&body_text);
                 }
             }
+        } else if tab == models::RequestTab::Schema {
+            self.sync_schema_content();
+            self.refresh_snapshot_diff();
         }
 
         Task::none()
@@ -95,6 +98,8 @@ impl Requiem {
                     iced::widget::text_editor::Content::with_text(&body_text);
             }
         }
+        self.sync_schema_content();
+        self.refresh_snapshot_diff();
 
         Task::none()
     }
@@ -142,6 +147,8 @@ impl Requiem {
                     }
                 }
             }
+            self.sync_schema_content();
+            self.refresh_snapshot_diff();
         }
         Task::none()
     }
@@ -174,6 +181,8 @@ impl Requiem {
                     }
                 }
             }
+            self.sync_schema_content();
+            self.refresh_snapshot_diff();
         }
         Task::none()
     }
@@ -257,6 +266,8 @@ impl Requiem {
                         }
                     }
                 }
+                self.sync_schema_content();
+                self.refresh_snapshot_diff();
             }
         }
 
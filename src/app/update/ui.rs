@@ -3,6 +3,7 @@ use serde::{Deserialize, Serialize};
 use tracing::{error, info};
 
 use crate::models::EnvironmentOption;
+use crate::secrets::SecretStore;
 
 use super::super::message::Message;
 use super::super::state::{ContextMenu, ContextMenuTarget, DragState, Requiem};
@@ -151,6 +152,23 @@ impl Requiem {
     pub fn handle_save_directory_changed(&mut self, path: String) -> Task<Message> {
         self.save_directory = path.clone();
         info!("Save directory changed to: {}", self.save_directory);
+        // Collections here are not saved in the new directory yet; the next
+        // check for external changes loads the ones that are
+        self.saved_collections.clear();
+        // Each directory has its own secret store
+        self.secrets = SecretStore::load(&self.save_directory, None).unwrap_or_else(|e| {
+            error!("Failed to load secrets: {}", e);
+            SecretStore::unreadable()
+        });
+        self.show_unlock_secrets = self.secrets.is_locked();
+        // The API key follows into the new store
+        if !self.secrets.is_locked() {
+            if let Err(e) = self.save_ai_config() {
+                error!("Failed to save config: {}", e);
+            }
+        }
+        self.reload_history();
+        self.undo.clear();
 
         if let Err(e) = crate::config::Config::load().set_save_directory(path) {
             error!("Failed to save config: {}", e);
@@ -198,7 +216,7 @@ impl Requiem {
         info!("AI engine changed to: {:?}", engine);
         self.ai_config.engine = engine;
 
-        if let Err(e) = crate::config::Config::load().set_ai_config(self.ai_config.clone()) {
+        if let Err(e) = self.save_ai_config() {
             error!("Failed to save config: {}", e);
         }
 
@@ -210,7 +228,7 @@ impl Requiem {
         info!("AI API URL changed to: {}", url);
         self.ai_config.openai_config.api_url = url;
 
-        if let Err(e) = crate::config::Config::load().set_ai_config(self.ai_config.clone()) {
+        if let Err(e) = self.save_ai_config() {
             error!("Failed to save config: {}", e);
         }
 
@@ -222,7 +240,7 @@ impl Requiem {
         info!("AI API key changed");
         self.ai_config.openai_config.api_key = key;
 
-        if let Err(e) = crate::config::Config::load().set_ai_config(self.ai_config.clone()) {
+        if let Err(e) = self.save_ai_config() {
             error!("Failed to save config: {}", e);
         }
 
@@ -234,7 +252,7 @@ impl Requiem {
         info!("AI model changed to: {}", model);
         self.ai_config.openai_config.model = model;
 
-        if let Err(e) = crate::config::Config::load().set_ai_config(self.ai_config.clone()) {
+        if let Err(e) = self.save_ai_config() {
             error!("Failed to save config: {}", e);
         }
 
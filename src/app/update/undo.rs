@@ -0,0 +1,428 @@
+use iced::Task;
+use tracing::{error, info};
+use uuid::Uuid;
+
+use crate::models::{Collection, CollectionItem, Request};
+use crate::ui::toast::Toast;
+use crate::utils::reload;
+use crate::utils::undo::Step;
+
+use super::super::message::Message;
+use super::super::state::{CollectionSnapshot, EditSnapshot, RequestTabItem, Requiem};
+
+/// What a message changes that can be undone
+pub(super) enum Edit {
+    /// The open request; edits of the same field in a row, such as typing,
+    /// are undone together
+    Request(Option<String>),
+    /// The collection tree, which is saved as soon as it changes; imports
+    /// are left out, as which collection they change is not known up front
+    Collection,
+}
+
+/// The edit a message makes, if any
+pub(super) fn edit_of(message: &Message) -> Option<Edit> {
+    let field = |name: String| Some(Edit::Request(Some(name)));
+    match message {
+        Message::UrlChanged(_) => field("url".to_string()),
+        Message::HeaderKeyChanged(i, _) => field(format!("header-key-{}", i)),
+        Message::HeaderValueChanged(i, _) => field(format!("header-value-{}", i)),
+        Message::ParamKeyChanged(i, _) => field(format!("param-key-{}", i)),
+        Message::ParamValueChanged(i, _) => field(format!("param-value-{}", i)),
+        Message::CookieKeyChanged(i, _) => field(format!("cookie-key-{}", i)),
+        Message::CookieValueChanged(i, _) => field(format!("cookie-value-{}", i)),
+        Message::AuthKeyChanged(i, _) => field(format!("auth-key-{}", i)),
+        Message::AuthValueChanged(i, _) => field(format!("auth-value-{}", i)),
+        Message::ExtractionVariableChanged(i, _) => field(format!("extraction-variable-{}", i)),
+        Message::ExtractionExpressionChanged(i, _) => field(format!("extraction-expression-{}", i)),
+        Message::SnapshotIgnoreChanged(i, _) => field(format!("snapshot-ignore-{}", i)),
+        Message::FormDataKeyChanged(i, _) => field(format!("form-key-{}", i)),
+        Message::FormDataValueChanged(i, _) => field(format!("form-value-{}", i)),
+        Message::SchemaFileChanged(_) => field("schema-file".to_string()),
+        Message::BodyChanged(_) => field("body".to_string()),
+        Message::RequestBodyAction(action) if action.is_edit() => field("body".to_string()),
+        Message::SchemaEditorAction(action) if action.is_edit() => field("schema".to_string()),
+
+        Message::MethodSelected(_)
+        | Message::ClearInsecure
+        | Message::AddHeader
+        | Message::RemoveHeader(_)
+        | Message::ToggleHeaderSecret(_)
+        | Message::AddParam
+        | Message::RemoveParam(_)
+        | Message::ToggleParamSecret(_)
+        | Message::AddCookie
+        | Message::RemoveCookie(_)
+        | Message::ToggleCookieSecret(_)
+        | Message::AddAuthField
+        | Message::RemoveAuthField(_)
+        | Message::ToggleAuthSecret(_)
+        | Message::ExtractionSourceChanged(..)
+        | Message::ExtractionScopeChanged(..)
+        | Message::AddExtraction
+        | Message::RemoveExtraction(_)
+        | Message::ToggleResponseSchema
+        | Message::SchemaSourceSelected(_)
+        | Message::SchemaFileSelected(_)
+        | Message::AddSnapshotIgnore
+        | Message::RemoveSnapshotIgnore(_)
+        | Message::BodyFormatChanged(_)
+        | Message::FormDataTypeChanged(..)
+        | Message::FormDataFileSelected(..)
+        | Message::AddFormDataField
+        | Message::RemoveFormDataField(_)
+        | Message::FormatRequestBodyJson
+        | Message::SaveResponseExample
+        | Message::AiFillCompleted(Ok(_)) => Some(Edit::Request(None)),
+
+        Message::AddNewCollection
+        | Message::AddNewFolder(_)
+        | Message::AddNewFlow(_)
+        | Message::DeleteItem(_)
+        | Message::RenameItem(..)
+        | Message::ConfirmRename
+        | Message::SaveRequest
+        | Message::SaveFlow => Some(Edit::Collection),
+
+        _ => None,
+    }
+}
+
+impl Requiem {
+    /// Handle a message that edits collections or requests, keeping what it
+    /// changes as it was before
+    pub(super) fn record_edit(&mut self, edit: Edit, message: Message) -> Task<Message> {
+        match edit {
+            Edit::Request(field) => self.record_request_edit(field, message),
+            Edit::Collection => self.record_collection_edit(message),
+        }
+    }
+
+    fn record_request_edit(&mut self, field: Option<String>, message: Message) -> Task<Message> {
+        let Some(id) = self.get_current_request().map(|request| request.id) else {
+            return self.dispatch(message);
+        };
+        // Typing on in the same field only extends the last step, so nothing
+        // needs to be kept
+        let group = field.map(|field| format!("{}/{}", id, field));
+        if self.undo.continues(group.as_deref()) {
+            return self.dispatch(message);
+        }
+
+        let before = self.get_current_request().cloned();
+        let task = self.dispatch_recording(message);
+        if let Some(before) = before {
+            let changed = self
+                .find_request(before.id)
+                .is_none_or(|after| !reload::same(after, &before));
+            if changed {
+                self.undo
+                    .record(EditSnapshot::Request(before), false, group);
+            }
+        }
+        task
+    }
+
+    fn record_collection_edit(&mut self, message: Message) -> Task<Message> {
+        // A rename still being typed is confirmed, and undone, on its own
+        if self.renaming_item.is_some()
+            && !matches!(message, Message::ConfirmRename | Message::RenameItem(..))
+        {
+            let _ = self.update(Message::ConfirmRename);
+        }
+
+        let target = self
+            .edit_target(&message)
+            .and_then(|index| Some((index, self.collections.get(index)?.clone())));
+        let ids: Vec<Uuid> = self.collections.iter().map(|c| c.id).collect();
+        let draft = self.active_tab().and_then(|tab| tab.draft_request.clone());
+        let tab = self.active_tab().map(|tab| tab.id);
+
+        let task = self.dispatch_recording(message);
+
+        let mut collections = Vec::new();
+        if let Some((index, before)) = target {
+            let unchanged = self
+                .collections
+                .iter()
+                .find(|c| c.id == before.id)
+                .is_some_and(|after| reload::same(after, &before));
+            if !unchanged {
+                collections.push(CollectionSnapshot {
+                    id: before.id,
+                    index,
+                    collection: Some(before),
+                });
+            }
+        }
+        for (index, collection) in self.collections.iter().enumerate() {
+            if !ids.contains(&collection.id) {
+                collections.push(CollectionSnapshot {
+                    id: collection.id,
+                    index,
+                    collection: None,
+                });
+            }
+        }
+        if !collections.is_empty() {
+            let snapshot = EditSnapshot::Collections {
+                collections,
+                draft,
+                tab,
+            };
+            self.undo.record(snapshot, true, None);
+        }
+        task
+    }
+
+    /// Handle a message as part of the edit being recorded, so the updates
+    /// it makes itself are not recorded apart
+    fn dispatch_recording(&mut self, message: Message) -> Task<Message> {
+        self.recording_edit = true;
+        let task = self.dispatch(message);
+        self.recording_edit = false;
+        task
+    }
+
+    /// Index of the existing collection a tree edit changes
+    fn edit_target(&self, message: &Message) -> Option<usize> {
+        match message {
+            Message::AddNewFolder(path)
+            | Message::AddNewFlow(path)
+            | Message::DeleteItem(path)
+            | Message::RenameItem(path, _) => path.first().copied(),
+            Message::ConfirmRename => self.renaming_item.as_ref()?.0.first().copied(),
+            Message::SaveRequest => {
+                let tab = self.active_tab()?;
+                let path = match tab.is_new {
+                    true => tab.parent_path.as_ref(),
+                    false => tab.request_path.as_ref(),
+                };
+                path?.first().copied()
+            }
+            Message::SaveFlow => self.flow_editor.as_ref()?.path.first().copied(),
+            _ => None,
+        }
+    }
+
+    fn active_tab(&self) -> Option<&RequestTabItem> {
+        self.active_tab_index
+            .and_then(|index| self.open_tabs.get(index))
+    }
+
+    /// The request with `id`, unsaved in its tab or saved in a collection
+    fn find_request(&self, id: Uuid) -> Option<&Request> {
+        if let Some(tab) = self.open_tabs.iter().find(|tab| tab.is_new && tab.id == id) {
+            return tab.draft_request.as_ref();
+        }
+        self.collections
+            .iter()
+            .enumerate()
+            .find_map(|(index, collection)| {
+                let mut path = vec![index];
+                path.extend(reload::find_item(&collection.items, id)?);
+                match self.get_item_by_path(&path)? {
+                    CollectionItem::Request(request) => Some(request),
+                    _ => None,
+                }
+            })
+    }
+
+    /// How what `snapshot` covers is now, to come back to
+    fn current_state(&self, snapshot: &EditSnapshot) -> EditSnapshot {
+        match snapshot {
+            EditSnapshot::Request(request) => {
+                EditSnapshot::Request(self.find_request(request.id).unwrap_or(request).clone())
+            }
+            EditSnapshot::Collections {
+                collections, tab, ..
+            } => EditSnapshot::Collections {
+                collections: collections
+                    .iter()
+                    .map(|snapshot| {
+                        let index = self.collections.iter().position(|c| c.id == snapshot.id);
+                        CollectionSnapshot {
+                            id: snapshot.id,
+                            index: index.unwrap_or(snapshot.index),
+                            collection: index.map(|index| self.collections[index].clone()),
+                        }
+                    })
+                    .collect(),
+                draft: self
+                    .open_tabs
+                    .iter()
+                    .find(|open_tab| Some(open_tab.id) == *tab)
+                    .and_then(|open_tab| open_tab.draft_request.clone()),
+                tab: *tab,
+            },
+        }
+    }
+
+    pub fn handle_undo(&mut self) -> Task<Message> {
+        let Some(current) = self.undo.next_undo().map(|state| self.current_state(state)) else {
+            return Task::none();
+        };
+        match self.undo.undo(current) {
+            Some(step) => self.restore(step),
+            None => Task::none(),
+        }
+    }
+
+    pub fn handle_redo(&mut self) -> Task<Message> {
+        let Some(current) = self.undo.next_redo().map(|state| self.current_state(state)) else {
+            return Task::none();
+        };
+        match self.undo.redo(current) {
+            Some(step) => self.restore(step),
+            None => Task::none(),
+        }
+    }
+
+    /// Put back what a step covers, in whichever tab it was edited, saving
+    /// collections again if the edit was saved
+    fn restore(&mut self, step: Step<EditSnapshot>) -> Task<Message> {
+        let (tab, errors) = match step.state {
+            EditSnapshot::Request(request) => {
+                let id = request.id;
+                self.restore_request(request);
+                (Some(id), Vec::new())
+            }
+            EditSnapshot::Collections {
+                collections,
+                draft,
+                tab,
+            } => (
+                tab,
+                self.restore_collections(collections, draft, step.saved),
+            ),
+        };
+
+        if let Some(index) = tab.and_then(|id| self.open_tabs.iter().position(|t| t.id == id)) {
+            self.active_tab_index = Some(index);
+            self.selected_request = self.open_tabs[index].request_path.clone();
+            if let Some(path) = &self.selected_request {
+                self.selected_collection = Some(path[0]);
+            }
+        }
+        self.sync_request_editor();
+        info!("Restored collections and requests");
+
+        if errors.is_empty() {
+            return Task::none();
+        }
+        for e in &errors {
+            error!("Failed to save after undo: {}", e);
+        }
+        Task::done(Message::ShowToast(Toast::error(errors.join("\n"))))
+    }
+
+    fn restore_request(&mut self, request: Request) {
+        if let Some(tab) = self
+            .open_tabs
+            .iter_mut()
+            .find(|tab| tab.is_new && tab.id == request.id)
+        {
+            tab.name = request.name.clone();
+            tab.draft_request = Some(request);
+            return;
+        }
+        for collection in &mut self.collections {
+            if reload::replace_request(&mut collection.items, &request) {
+                break;
+            }
+        }
+    }
+
+    /// Put collections back, returning the errors of saving them
+    fn restore_collections(
+        &mut self,
+        collections: Vec<CollectionSnapshot>,
+        draft: Option<Request>,
+        saved: bool,
+    ) -> Vec<String> {
+        let mut errors = Vec::new();
+        for snapshot in collections {
+            let current = self.collections.iter().position(|c| c.id == snapshot.id);
+            let index = match (current, snapshot.collection) {
+                // Added by the edit
+                (Some(index), None) => {
+                    self.remove_collection(index);
+                    if saved {
+                        if let Err(e) = self.delete_collection_file(&snapshot.id) {
+                            errors.push(e);
+                        }
+                    }
+                    continue;
+                }
+                (None, None) => continue,
+                (Some(index), Some(collection)) => {
+                    // A request the edit saved goes back to its tab unsaved
+                    let drafts = draft
+                        .iter()
+                        .filter(|draft| {
+                            self.open_tabs
+                                .iter()
+                                .any(|tab| tab.id == draft.id && !tab.is_new)
+                        })
+                        .cloned()
+                        .collect();
+                    self.replace_collection(collection, drafts);
+                    index
+                }
+                // Deleted by the edit
+                (None, Some(collection)) => {
+                    let index = snapshot.index.min(self.collections.len());
+                    self.insert_collection(index, collection);
+                    index
+                }
+            };
+            if saved {
+                if let Err(e) = self.save_collection(index) {
+                    errors.push(e);
+                }
+            }
+        }
+
+        // Unsaved tabs follow their request into the collection it is
+        // saved in again
+        for open_tab in self.open_tabs.iter_mut().filter(|tab| tab.is_new) {
+            let saved_path = self.collections.iter().enumerate().find_map(|(index, c)| {
+                let mut path = vec![index];
+                path.extend(reload::find_item(&c.items, open_tab.id)?);
+                Some(path)
+            });
+            if let Some(path) = saved_path {
+                open_tab.request_path = Some(path);
+                open_tab.is_new = false;
+                open_tab.draft_request = None;
+                open_tab.parent_path = None;
+            }
+        }
+        errors
+    }
+
+    /// Put a collection back where it was, shifting paths into later collections
+    fn insert_collection(&mut self, index: usize, collection: Collection) {
+        self.collections.insert(index, collection);
+        let shift = |path: &[usize]| -> Option<Vec<usize>> {
+            let mut path = path.to_vec();
+            if let Some(first) = path.first_mut().filter(|first| **first >= index) {
+                *first += 1;
+            }
+            Some(path)
+        };
+
+        for tab in &mut self.open_tabs {
+            tab.request_path = tab.request_path.take().and_then(|path| shift(&path));
+            tab.parent_path = tab.parent_path.take().and_then(|path| shift(&path));
+        }
+        self.selected_collection = self.selected_collection.map(|selected| {
+            if selected >= index {
+                selected + 1
+            } else {
+                selected
+            }
+        });
+        self.remap_paths(shift);
+    }
+}
@@ -0,0 +1,495 @@
+use futures::channel::mpsc;
+use futures::{Stream, StreamExt};
+use reqwest::Client;
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::{error, info};
+
+use crate::http_client;
+use crate::models::{
+    BlastConfig, BlastLimit, BlastReport, BlastSample, BlastSecond, BlastSummary, Request,
+};
+
+/// How often live statistics are published while a run is in progress
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(500);
+
+/// How often waiting workers check whether the run should stop
+const STOP_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Samples kept for the report; later requests only count in the statistics,
+/// so long duration runs do not grow memory without bound
+pub const MAX_SAMPLES: usize = 100_000;
+
+/// Updates published by a blast run
+#[derive(Debug, Clone)]
+pub enum BlastEvent {
+    Progress(BlastSummary),
+    Finished(BlastReport),
+}
+
+/// Start a load test of `request` and stream its progress
+///
+/// The stream yields a `Progress` summary every half second and ends with a
+/// single `Finished` report. Setting `stop` ends the run early.
+pub fn run(
+    request: Request,
+    config: BlastConfig,
+    stop: Arc<AtomicBool>,
+) -> impl Stream<Item = BlastEvent> {
+    let (sender, receiver) = mpsc::unbounded();
+    let engine = futures::stream::once(execute(request, config, stop, sender))
+        .filter_map(|_| async { None });
+    futures::stream::select(engine, receiver)
+}
+
+async fn execute(
+    request: Request,
+    config: BlastConfig,
+    stop: Arc<AtomicBool>,
+    sender: mpsc::UnboundedSender<BlastEvent>,
+) {
+    let started_at = chrono::Local::now().to_rfc3339();
+    let start = Instant::now();
+    let recorder = Arc::new(Mutex::new(Recorder::default()));
+
+    info!(
+        "Blasting {} {} with {} workers",
+        request.method.as_str(),
+        request.url,
+        config.concurrency
+    );
+
+    match http_client::build_client(request.insecure) {
+        Ok(client) => {
+            let issued = Arc::new(AtomicU64::new(0));
+            let concurrency = config.concurrency.max(1);
+            let mut workers = Vec::new();
+
+            for index in 0..concurrency {
+                let worker = Worker {
+                    client: client.clone(),
+                    request: request.clone(),
+                    config: config.clone(),
+                    start,
+                    issued: issued.clone(),
+                    recorder: recorder.clone(),
+                    stop: stop.clone(),
+                };
+                let delay = Duration::from_secs(config.ramp_up_secs) * index / concurrency;
+                workers.push(tokio::spawn(worker.run(delay)));
+            }
+
+            let mut all_done = std::pin::pin!(futures::future::join_all(workers));
+            let mut ticker = tokio::time::interval(PROGRESS_INTERVAL);
+            loop {
+                tokio::select! {
+                    _ = &mut all_done => break,
+                    _ = ticker.tick() => {
+                        let summary = recorder
+                            .lock()
+                            .unwrap()
+                            .stats
+                            .summary(start.elapsed().as_millis() as u64);
+                        let _ = sender.unbounded_send(BlastEvent::Progress(summary));
+                    }
+                }
+            }
+        }
+        Err(e) => {
+            error!("Failed to create HTTP client for blast: {}", e);
+        }
+    }
+
+    let elapsed_ms = start.elapsed().as_millis() as u64;
+    let Recorder { samples, stats } = std::mem::take(&mut *recorder.lock().unwrap());
+    let summary = stats.summary(elapsed_ms);
+    info!(
+        "Blast finished: {} requests, {} errors, {:.1} rps",
+        summary.total, summary.errors, summary.rps
+    );
+
+    let _ = sender.unbounded_send(BlastEvent::Finished(BlastReport {
+        request_name: request.name.clone(),
+        method: request.method,
+        url: request.url.clone(),
+        started_at,
+        config,
+        summary,
+        stopped: stop.load(Ordering::Relaxed),
+        samples,
+    }));
+}
+
+/// Samples of a run together with their running statistics
+#[derive(Default)]
+struct Recorder {
+    samples: Vec<BlastSample>,
+    stats: BlastStats,
+}
+
+impl Recorder {
+    fn record(&mut self, sample: BlastSample) {
+        self.stats.record(&sample);
+        if self.samples.len() < MAX_SAMPLES {
+            self.samples.push(sample);
+        }
+    }
+}
+
+/// One concurrent sender; all workers share the request counter and recorder
+struct Worker {
+    client: Client,
+    request: Request,
+    config: BlastConfig,
+    start: Instant,
+    issued: Arc<AtomicU64>,
+    recorder: Arc<Mutex<Recorder>>,
+    stop: Arc<AtomicBool>,
+}
+
+impl Worker {
+    async fn run(self, delay: Duration) {
+        if !self.wait_until(Instant::now() + delay).await {
+            return;
+        }
+
+        loop {
+            let sequence = self.issued.fetch_add(1, Ordering::Relaxed);
+            if !self.may_send(sequence) {
+                break;
+            }
+
+            // Rate limiting schedules request N at N / rate seconds after the start
+            if let Some(rate) = self.config.rate_limit.filter(|rate| *rate > 0) {
+                let due = self.start + Duration::from_secs_f64(sequence as f64 / rate as f64);
+                if !self.wait_until(due).await || !self.may_send(sequence) {
+                    break;
+                }
+            }
+
+            let sent = Instant::now();
+            let result = http_client::execute_with_client(&self.client, &self.request).await;
+            let completed = Instant::now();
+
+            let (status, error) = match result {
+                Ok(response) => (Some(response.status), None),
+                Err(e) => (None, Some(e.to_string())),
+            };
+            self.recorder.lock().unwrap().record(BlastSample {
+                offset_ms: completed.duration_since(self.start).as_millis() as u64,
+                latency_ms: completed.duration_since(sent).as_millis() as u64,
+                status,
+                error,
+            });
+        }
+    }
+
+    /// Sleep until `due`, or return false as soon as the run is stopped
+    async fn wait_until(&self, due: Instant) -> bool {
+        let stopped = async {
+            let mut ticker = tokio::time::interval(STOP_POLL_INTERVAL);
+            while !self.stop.load(Ordering::Relaxed) {
+                ticker.tick().await;
+            }
+        };
+        tokio::select! {
+            _ = tokio::time::sleep_until(tokio::time::Instant::from_std(due)) => true,
+            _ = stopped => false,
+        }
+    }
+
+    fn may_send(&self, sequence: u64) -> bool {
+        if self.stop.load(Ordering::Relaxed) {
+            return false;
+        }
+        match self.config.limit {
+            BlastLimit::Requests(total) => sequence < total,
+            BlastLimit::Duration(secs) => self.start.elapsed() < Duration::from_secs(secs),
+        }
+    }
+}
+
+/// Latency counts keyed by millisecond, percentiles are read off the cumulative counts
+#[derive(Debug, Clone, Default)]
+struct Histogram {
+    counts: BTreeMap<u64, u64>,
+    total: u64,
+}
+
+impl Histogram {
+    fn record(&mut self, latency_ms: u64) {
+        *self.counts.entry(latency_ms).or_default() += 1;
+        self.total += 1;
+    }
+
+    /// Nearest-rank percentile of the recorded latencies
+    fn percentile(&self, p: f64) -> u64 {
+        if self.total == 0 {
+            return 0;
+        }
+        let rank = (((p / 100.0) * self.total as f64).ceil() as u64).clamp(1, self.total);
+        let mut seen = 0;
+        for (latency, count) in &self.counts {
+            seen += count;
+            if seen >= rank {
+                return *latency;
+            }
+        }
+        0
+    }
+}
+
+/// Per-second counters behind the timeline
+#[derive(Debug, Clone, Default)]
+struct SecondStats {
+    errors: u64,
+    latencies: Histogram,
+}
+
+/// Running totals of a blast run, updated as each sample arrives so that
+/// live progress never has to walk the full sample list
+#[derive(Debug, Clone, Default)]
+struct BlastStats {
+    errors: u64,
+    min_ms: u64,
+    max_ms: u64,
+    sum_ms: u64,
+    latencies: Histogram,
+    status_counts: BTreeMap<String, u64>,
+    error_counts: BTreeMap<String, u64>,
+    seconds: BTreeMap<u64, SecondStats>,
+}
+
+impl BlastStats {
+    fn record(&mut self, sample: &BlastSample) {
+        if self.latencies.total == 0 {
+            self.min_ms = sample.latency_ms;
+        }
+        self.min_ms = self.min_ms.min(sample.latency_ms);
+        self.max_ms = self.max_ms.max(sample.latency_ms);
+        self.sum_ms += sample.latency_ms;
+        self.latencies.record(sample.latency_ms);
+
+        let key = match sample.status {
+            Some(status) => status.to_string(),
+            None => "error".to_string(),
+        };
+        *self.status_counts.entry(key).or_default() += 1;
+        if let Some(error) = &sample.error {
+            *self.error_counts.entry(error.clone()).or_default() += 1;
+        }
+
+        let second = self.seconds.entry(sample.offset_ms / 1000).or_default();
+        second.latencies.record(sample.latency_ms);
+        if sample.is_error() {
+            self.errors += 1;
+            second.errors += 1;
+        }
+    }
+
+    /// Totals, latency percentiles and a per-second timeline
+    fn summary(&self, elapsed_ms: u64) -> BlastSummary {
+        let total = self.latencies.total;
+        let mut summary = BlastSummary {
+            elapsed_ms,
+            total,
+            ..Default::default()
+        };
+        if total == 0 {
+            return summary;
+        }
+
+        summary.errors = self.errors;
+        summary.rps = if elapsed_ms > 0 {
+            total as f64 * 1000.0 / elapsed_ms as f64
+        } else {
+            0.0
+        };
+        summary.min_ms = self.min_ms;
+        summary.max_ms = self.max_ms;
+        summary.mean_ms = self.sum_ms / total;
+        summary.p50_ms = self.latencies.percentile(50.0);
+        summary.p90_ms = self.latencies.percentile(90.0);
+        summary.p99_ms = self.latencies.percentile(99.0);
+        summary.status_counts = self.status_counts.clone();
+        summary.error_counts = self.error_counts.clone();
+
+        let empty = SecondStats::default();
+        let last_second = self.seconds.keys().next_back().copied().unwrap_or(0);
+        summary.timeline = (0..=last_second)
+            .map(|second| {
+                let stats = self.seconds.get(&second).unwrap_or(&empty);
+                BlastSecond {
+                    second,
+                    requests: stats.latencies.total,
+                    errors: stats.errors,
+                    p50_ms: stats.latencies.percentile(50.0),
+                    p99_ms: stats.latencies.percentile(99.0),
+                }
+            })
+            .collect();
+
+        summary
+    }
+}
+
+/// Render samples as CSV, one line per request
+pub fn samples_to_csv(samples: &[BlastSample]) -> Result<String, String> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer
+        .write_record(["offset_ms", "latency_ms", "status", "error"])
+        .map_err(|e| e.to_string())?;
+    for sample in samples {
+        writer
+            .write_record([
+                sample.offset_ms.to_string(),
+                sample.latency_ms.to_string(),
+                sample.status.map(|s| s.to_string()).unwrap_or_default(),
+                sample.error.clone().unwrap_or_default(),
+            ])
+            .map_err(|e| e.to_string())?;
+    }
+    let bytes = writer.into_inner().map_err(|e| e.to_string())?;
+    String::from_utf8(bytes).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::HttpMethod;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    fn sample(offset_ms: u64, latency_ms: u64, status: Option<u16>) -> BlastSample {
+        BlastSample {
+            offset_ms,
+            latency_ms,
+            status,
+            error: status.is_none().then(|| "connection refused".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_summarize() {
+        let samples: Vec<BlastSample> = (1..=100)
+            .map(|i| sample(i * 20, i, if i % 10 == 0 { None } else { Some(200) }))
+            .collect();
+        let mut stats = BlastStats::default();
+        for sample in &samples {
+            stats.record(sample);
+        }
+        let summary = stats.summary(2000);
+
+        assert_eq!(summary.total, 100);
+        assert_eq!(summary.errors, 10);
+        assert_eq!(summary.rps, 50.0);
+        assert_eq!(summary.min_ms, 1);
+        assert_eq!(summary.max_ms, 100);
+        assert_eq!(summary.mean_ms, 50);
+        assert_eq!(summary.p50_ms, 50);
+        assert_eq!(summary.p90_ms, 90);
+        assert_eq!(summary.p99_ms, 99);
+        assert_eq!(summary.status_counts["200"], 90);
+        assert_eq!(summary.status_counts["error"], 10);
+        assert_eq!(summary.error_counts["connection refused"], 10);
+        assert_eq!(summary.timeline.len(), 3);
+        assert_eq!(summary.timeline[0].requests, 49);
+        assert_eq!(summary.timeline[0].p50_ms, 25);
+    }
+
+    #[test]
+    fn test_recorder_keeps_a_bounded_number_of_samples() {
+        let mut recorder = Recorder::default();
+        for i in 0..=MAX_SAMPLES as u64 {
+            recorder.record(sample(i, 1, Some(200)));
+        }
+        assert_eq!(recorder.samples.len(), MAX_SAMPLES);
+        assert_eq!(recorder.stats.summary(1000).total, MAX_SAMPLES as u64 + 1);
+    }
+
+    #[test]
+    fn test_samples_to_csv() {
+        let csv = samples_to_csv(&[sample(5, 3, Some(200)), sample(9, 7, None)]).unwrap();
+        assert_eq!(
+            csv,
+            "offset_ms,latency_ms,status,error\n5,3,200,\n9,7,,connection refused\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_run_against_local_server() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                tokio::spawn(async move {
+                    let mut buf = [0u8; 1024];
+                    let _ = socket.read(&mut buf).await;
+                    let _ = socket
+                        .write_all(
+                            b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\nConnection: close\r\n\r\nok",
+                        )
+                        .await;
+                });
+            }
+        });
+
+        let request = Request {
+            method: HttpMethod::GET,
+            url: format!("http://{}/", addr),
+            ..Default::default()
+        };
+        let config = BlastConfig {
+            concurrency: 4,
+            limit: BlastLimit::Requests(20),
+            ramp_up_secs: 0,
+            rate_limit: None,
+        };
+
+        let events: Vec<BlastEvent> = run(request, config, Arc::new(AtomicBool::new(false)))
+            .collect()
+            .await;
+        let Some(BlastEvent::Finished(report)) = events.last() else {
+            panic!("run did not finish");
+        };
+        assert_eq!(report.summary.total, 20);
+        assert_eq!(report.summary.errors, 0);
+        assert_eq!(report.summary.status_counts["200"], 20);
+    }
+
+    #[tokio::test]
+    async fn test_stop_interrupts_waiting_workers() {
+        // Nothing listens, so requests fail fast; the workers spend the run
+        // waiting for the ramp-up and the rate limit
+        let request = Request {
+            method: HttpMethod::GET,
+            url: "http://127.0.0.1:9/".to_string(),
+            ..Default::default()
+        };
+        let config = BlastConfig {
+            concurrency: 2,
+            limit: BlastLimit::Requests(100),
+            ramp_up_secs: 60,
+            rate_limit: Some(1),
+        };
+        let stop = Arc::new(AtomicBool::new(false));
+        let stopper = stop.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(300)).await;
+            stopper.store(true, Ordering::Relaxed);
+        });
+
+        let events = tokio::time::timeout(
+            Duration::from_secs(5),
+            run(request, config, stop).collect::<Vec<_>>(),
+        )
+        .await
+        .expect("run did not stop");
+        let Some(BlastEvent::Finished(report)) = events.last() else {
+            panic!("run did not finish");
+        };
+        assert!(report.stopped);
+        assert!(report.summary.total < 100);
+    }
+}
@@ -0,0 +1,831 @@
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+
+use crate::config::Config;
+use crate::http_client;
+use crate::models::{
+    Collection, CollectionItem, Environment, Request, Response, RunOptions, RunReport,
+};
+use crate::secrets::{self, SecretStore};
+use crate::storage;
+use crate::utils::{data_file, navigation, report, variables};
+
+const USAGE: &str = "\
+Usage: requiem <command> [options]
+
+Commands:
+  run <collection>    Run every request of a saved collection
+  send <path>         Send one saved request, e.g. \"My API/Users/Get user\"
+  send --file <file>  Send a request stored as JSON
+  help                Show this message
+
+Common options:
+  -e, --env <name>        Environment: development, testing, staging, production
+  -v, --var <key=value>   Set a variable, overriding the environment (repeatable)
+      --dir <path>        Collections directory instead of the configured one
+
+Run options:
+  -f, --folder <path>     Only run a folder, e.g. \"Auth/Login\"
+  -n, --iterations <n>    Run the sequence n times
+      --delay <ms>        Pause between two requests
+      --data <file>       CSV or JSON data file, one iteration per row
+      --bail              Stop at the first failed request
+      --junit <file>      Write a JUnit XML report
+      --json <file>       Write a JSON report
+
+Send options:
+      --file <file>       Read the request from a JSON file instead
+      --json              Print the response as JSON
+
+A request file needs only a URL, e.g. {\"method\": \"POST\", \"url\": \"...\"};
+other fields take the names of a saved request file and are optional.
+
+`run` exits with 1 when a request failed, `send` when no response arrived;
+both exit with 2 on usage or loading errors.
+
+Secrets encrypted with a passphrase are unlocked with REQUIEM_PASSPHRASE.
+";
+
+/// Environment variable holding the passphrase of an encrypted secret store
+const PASSPHRASE_VAR: &str = "REQUIEM_PASSPHRASE";
+
+/// Exit status when a request failed or the run was aborted
+const EXIT_FAILED: i32 = 1;
+/// Exit status for bad arguments or collections that cannot be loaded
+const EXIT_ERROR: i32 = 2;
+
+/// Whether the arguments name a command, so the GUI must not start
+pub fn is_command(args: &[String]) -> bool {
+    matches!(
+        args.first().map(String::as_str),
+        Some("run" | "send" | "help" | "--help" | "-h")
+    )
+}
+
+/// Run a command and return the process exit status
+pub fn dispatch(args: &[String]) -> i32 {
+    let Some((command, rest)) = args.split_first() else {
+        print!("{}", USAGE);
+        return EXIT_ERROR;
+    };
+
+    let result = match command.as_str() {
+        "run" => parse_run_args(rest).map(|args| (args.common.help, Command::Run(args))),
+        "send" => parse_send_args(rest).map(|args| (args.common.help, Command::Send(args))),
+        _ => {
+            print!("{}", USAGE);
+            return 0;
+        }
+    };
+
+    match result {
+        Ok((true, _)) => {
+            print!("{}", USAGE);
+            0
+        }
+        Ok((false, Command::Run(mut args))) => {
+            args.common.passphrase = std::env::var(PASSPHRASE_VAR).ok();
+            run(args)
+        }
+        Ok((false, Command::Send(mut args))) => {
+            args.common.passphrase = std::env::var(PASSPHRASE_VAR).ok();
+            send(args)
+        }
+        Err(e) => {
+            eprintln!("error: {}\n\n{}", e, USAGE);
+            EXIT_ERROR
+        }
+    }
+}
+
+enum Command {
+    Run(RunArgs),
+    Send(SendArgs),
+}
+
+/// Options understood by every command
+#[derive(Debug, Default)]
+struct CommonArgs {
+    environment: Environment,
+    variables: Vec<(String, String)>,
+    dir: Option<String>,
+    help: bool,
+    /// Unlocks an encrypted secret store, taken from `REQUIEM_PASSPHRASE`
+    passphrase: Option<String>,
+}
+
+/// Arguments of `requiem run`
+#[derive(Debug, Default)]
+struct RunArgs {
+    common: CommonArgs,
+    collection: String,
+    folder: Option<String>,
+    options: RunOptions,
+    junit: Option<PathBuf>,
+    json: Option<PathBuf>,
+}
+
+/// Arguments of `requiem send`
+#[derive(Debug, Default)]
+struct SendArgs {
+    common: CommonArgs,
+    /// `Collection/Folder/Request`
+    path: Option<String>,
+    file: Option<PathBuf>,
+    json: bool,
+}
+
+/// One command line argument, with the value of `--flag=value` split off
+struct Arg<'a> {
+    raw: &'a String,
+    flag: &'a str,
+    inline: Option<&'a str>,
+}
+
+/// Walks the arguments, handing out flag values as they are asked for
+struct ArgIter<'a> {
+    args: std::slice::Iter<'a, String>,
+}
+
+impl<'a> ArgIter<'a> {
+    fn new(args: &'a [String]) -> Self {
+        Self { args: args.iter() }
+    }
+
+    fn next(&mut self) -> Option<Arg<'a>> {
+        let raw = self.args.next()?;
+        Some(match raw.split_once('=') {
+            Some((flag, value)) if raw.starts_with("--") => Arg {
+                raw,
+                flag,
+                inline: Some(value),
+            },
+            _ => Arg {
+                raw,
+                flag: raw,
+                inline: None,
+            },
+        })
+    }
+
+    fn value(&mut self, arg: &Arg<'a>) -> Result<String, String> {
+        arg.inline
+            .or_else(|| self.args.next().map(String::as_str))
+            .map(str::to_string)
+            .ok_or_else(|| format!("{} needs a value", arg.flag))
+    }
+}
+
+/// Apply an option shared by every command; false when the flag is not one
+fn parse_common<'a>(
+    common: &mut CommonArgs,
+    arg: &Arg<'a>,
+    args: &mut ArgIter<'a>,
+) -> Result<bool, String> {
+    match arg.flag {
+        "-h" | "--help" => common.help = true,
+        "-e" | "--env" => common.environment = args.value(arg)?.parse()?,
+        "-v" | "--var" => {
+            let pair = args.value(arg)?;
+            let (key, value) = pair
+                .split_once('=')
+                .ok_or_else(|| format!("Invalid variable '{}', expected key=value", pair))?;
+            common
+                .variables
+                .push((key.trim().to_string(), value.to_string()));
+        }
+        "--dir" => common.dir = Some(args.value(arg)?),
+        _ => return Ok(false),
+    }
+    Ok(true)
+}
+
+fn parse_run_args(args: &[String]) -> Result<RunArgs, String> {
+    let mut parsed = RunArgs::default();
+    let mut collection = None;
+    let mut args = ArgIter::new(args);
+
+    while let Some(arg) = args.next() {
+        if parse_common(&mut parsed.common, &arg, &mut args)? {
+            continue;
+        }
+        match arg.flag {
+            "-f" | "--folder" => parsed.folder = Some(args.value(&arg)?),
+            "-n" | "--iterations" => {
+                let count = args.value(&arg)?;
+                parsed.options.iterations = count
+                    .parse::<u32>()
+                    .ok()
+                    .filter(|n| *n > 0)
+                    .ok_or_else(|| format!("Invalid iteration count '{}'", count))?;
+            }
+            "--delay" => {
+                let delay = args.value(&arg)?;
+                parsed.options.delay_ms = delay
+                    .parse()
+                    .map_err(|_| format!("Invalid delay '{}'", delay))?;
+            }
+            "--data" => parsed.options.data_file = Some(args.value(&arg)?),
+            "--bail" => parsed.options.stop_on_failure = true,
+            "--junit" => parsed.junit = Some(PathBuf::from(args.value(&arg)?)),
+            "--json" => parsed.json = Some(PathBuf::from(args.value(&arg)?)),
+            flag if flag.starts_with('-') => return Err(format!("Unknown option '{}'", flag)),
+            _ if collection.is_none() => collection = Some(arg.raw.clone()),
+            _ => return Err(format!("Unexpected argument '{}'", arg.raw)),
+        }
+    }
+
+    match collection {
+        Some(collection) => parsed.collection = collection,
+        None if parsed.common.help => {}
+        None => return Err("Missing collection name".to_string()),
+    }
+    Ok(parsed)
+}
+
+fn parse_send_args(args: &[String]) -> Result<SendArgs, String> {
+    let mut parsed = SendArgs::default();
+    let mut args = ArgIter::new(args);
+
+    while let Some(arg) = args.next() {
+        if parse_common(&mut parsed.common, &arg, &mut args)? {
+            continue;
+        }
+        match arg.flag {
+            "--file" => parsed.file = Some(PathBuf::from(args.value(&arg)?)),
+            "--json" => parsed.json = true,
+            flag if flag.starts_with('-') => return Err(format!("Unknown option '{}'", flag)),
+            _ if parsed.path.is_none() => parsed.path = Some(arg.raw.clone()),
+            _ => return Err(format!("Unexpected argument '{}'", arg.raw)),
+        }
+    }
+
+    match (&parsed.path, &parsed.file) {
+        (Some(_), Some(_)) => Err("Give either a request path or --file, not both".to_string()),
+        (None, None) if !parsed.common.help => Err("Missing request path".to_string()),
+        _ => Ok(parsed),
+    }
+}
+
+/// Collections directory and the variables of the selected environment
+fn load_context(common: &CommonArgs) -> Result<(String, HashMap<String, String>), String> {
+    let dir = common
+        .dir
+        .clone()
+        .unwrap_or_else(|| Config::load().save_directory);
+    let mut environments = storage::load_environment_variables(&dir)?;
+    if secrets::environment_has_references(&environments, common.environment) {
+        let store = load_secrets(&dir, common.passphrase.as_deref())?;
+        secrets::resolve_environments(&mut environments, &store);
+    }
+    let mut variables: HashMap<String, String> = environments
+        .get(common.environment)
+        .cloned()
+        .unwrap_or_default();
+    variables.extend(common.variables.iter().cloned());
+    Ok((dir, variables))
+}
+
+/// Secret store of a collections directory, unlocked with `passphrase`
+fn load_secrets(dir: &str, passphrase: Option<&str>) -> Result<SecretStore, String> {
+    let store = SecretStore::load(dir, passphrase)?;
+    if store.is_locked() {
+        return Err(format!(
+            "Secrets in {} are encrypted; set {} to the passphrase",
+            dir, PASSPHRASE_VAR
+        ));
+    }
+    Ok(store)
+}
+
+/// Fill in the secrets of the requests about to be sent
+///
+/// The store is only read when one of them refers to it, so commands that
+/// need no secrets work without the passphrase.
+fn resolve_secrets(common: &CommonArgs, dir: &str, requests: &mut [Request]) -> Result<(), String> {
+    if !requests.iter().any(secrets::has_references) {
+        return Ok(());
+    }
+    let store = load_secrets(dir, common.passphrase.as_deref())?;
+    for request in requests {
+        secrets::resolve_request(request, &store);
+    }
+    Ok(())
+}
+
+fn runtime() -> Result<tokio::runtime::Runtime, String> {
+    tokio::runtime::Runtime::new().map_err(|e| format!("Failed to start async runtime: {}", e))
+}
+
+/// Find a collection by id or by name, ignoring case
+fn find_collection<'a>(collections: &'a [Collection], query: &str) -> Option<&'a Collection> {
+    collections
+        .iter()
+        .find(|c| c.id.to_string() == query)
+        .or_else(|| {
+            collections
+                .iter()
+                .find(|c| c.name.eq_ignore_ascii_case(query))
+        })
+}
+
+fn missing_collection(collections: &[Collection], query: &str, dir: &str) -> String {
+    let names: Vec<&str> = collections.iter().map(|c| c.name.as_str()).collect();
+    format!(
+        "No collection named '{}' in {} (available: {})",
+        query,
+        dir,
+        if names.is_empty() {
+            "none".to_string()
+        } else {
+            names.join(", ")
+        }
+    )
+}
+
+/// Resolve a `/`-separated folder path below a collection
+fn find_folder<'a>(items: &'a [CollectionItem], path: &str) -> Option<&'a [CollectionItem]> {
+    path.split('/')
+        .filter(|segment| !segment.trim().is_empty())
+        .try_fold(items, |items, segment| {
+            items.iter().find_map(|item| match item {
+                CollectionItem::Folder(folder)
+                    if folder.name.eq_ignore_ascii_case(segment.trim()) =>
+                {
+                    Some(folder.items.as_slice())
+                }
+                _ => None,
+            })
+        })
+}
+
+fn run(args: RunArgs) -> i32 {
+    match execute(args) {
+        Ok(report) if report.failed_count() == 0 && !report.aborted => 0,
+        Ok(_) => EXIT_FAILED,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            EXIT_ERROR
+        }
+    }
+}
+
+fn execute(args: RunArgs) -> Result<RunReport, String> {
+    let (dir, variables) = load_context(&args.common)?;
+    let collections = storage::read_collections(&dir)?;
+    let collection = find_collection(&collections, &args.collection)
+        .ok_or_else(|| missing_collection(&collections, &args.collection, &dir))?;
+
+    let (name, items) = match &args.folder {
+        Some(folder) => (
+            format!("{} / {}", collection.name, folder),
+            find_folder(&collection.items, folder)
+                .ok_or_else(|| format!("No folder '{}' in '{}'", folder, collection.name))?,
+        ),
+        None => (collection.name.clone(), collection.items.as_slice()),
+    };
+    let mut requests = navigation::collect_requests(items);
+    if requests.is_empty() {
+        return Err(format!("'{}' has no requests to run", name));
+    }
+    resolve_secrets(&args.common, &dir, &mut requests)?;
+
+    let data_rows = match &args.options.data_file {
+        Some(path) => data_file::load(std::path::Path::new(path))?,
+        None => vec![],
+    };
+
+    println!(
+        "Running '{}' ({}): {} requests",
+        name,
+        args.common.environment.code(),
+        requests.len()
+    );
+
+    let report = runtime()?.block_on(crate::runner::run_requests(
+        name,
+        requests,
+        variables,
+        args.options,
+        data_rows,
+        PathBuf::from(&dir),
+    ));
+
+    print!("{}", format_summary(&report));
+
+    if let Some(path) = &args.json {
+        let json = serde_json::to_string_pretty(&report)
+            .map_err(|e| format!("Failed to serialize report: {}", e))?;
+        std::fs::write(path, json)
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    }
+    if let Some(path) = &args.junit {
+        std::fs::write(path, report::junit_xml(&report))
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    }
+
+    Ok(report)
+}
+
+/// Find a saved request from a `Collection/Folder/Request` path
+fn find_request<'a>(
+    collections: &'a [Collection],
+    path: &str,
+    dir: &str,
+) -> Result<&'a Request, String> {
+    let segments: Vec<&str> = path
+        .split('/')
+        .map(str::trim)
+        .filter(|segment| !segment.is_empty())
+        .collect();
+    let [collection_name, folders @ .., request_name] = segments.as_slice() else {
+        return Err(format!(
+            "Invalid request path '{}', expected Collection/Request",
+            path
+        ));
+    };
+
+    let collection = find_collection(collections, collection_name)
+        .ok_or_else(|| missing_collection(collections, collection_name, dir))?;
+    let items = find_folder(&collection.items, &folders.join("/"))
+        .ok_or_else(|| format!("No folder '{}' in '{}'", folders.join("/"), collection.name))?;
+
+    items
+        .iter()
+        .find_map(|item| match item {
+            CollectionItem::Request(request)
+                if request.name.eq_ignore_ascii_case(request_name)
+                    || request.id.to_string() == *request_name =>
+            {
+                Some(request)
+            }
+            _ => None,
+        })
+        .ok_or_else(|| format!("No request named '{}' in '{}'", request_name, path))
+}
+
+fn send(args: SendArgs) -> i32 {
+    let request = match load_request(&args) {
+        Ok(request) => request,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return EXIT_ERROR;
+        }
+    };
+
+    let response = runtime().and_then(|runtime| {
+        runtime
+            .block_on(http_client::execute_request(&request))
+            .map_err(|e| e.to_string())
+    });
+    match response {
+        Ok(response) if args.json => {
+            println!("{}", format_response_json(&response));
+            0
+        }
+        Ok(response) => {
+            print!("{}", format_response(&response));
+            0
+        }
+        Err(e) => {
+            eprintln!("error: {}", e);
+            EXIT_FAILED
+        }
+    }
+}
+
+/// The request to send, with environment variables substituted
+fn load_request(args: &SendArgs) -> Result<Request, String> {
+    let (dir, variables) = load_context(&args.common)?;
+
+    let mut request = match (&args.file, &args.path) {
+        (Some(file), _) => {
+            let content = std::fs::read_to_string(file)
+                .map_err(|e| format!("Failed to read {}: {}", file.display(), e))?;
+            parse_request_file(&content)
+                .map_err(|e| format!("Failed to parse {}: {}", file.display(), e))?
+        }
+        (None, Some(path)) => {
+            let collections = storage::read_collections(&dir)?;
+            find_request(&collections, path, &dir)?.clone()
+        }
+        (None, None) => return Err("Missing request path".to_string()),
+    };
+    resolve_secrets(&args.common, &dir, std::slice::from_mut(&mut request))?;
+
+    Ok(variables::resolve_request(&request, &variables))
+}
+
+/// A hand-written request file, where fields other than the URL may be left
+/// out and take their defaults
+fn parse_request_file(content: &str) -> Result<Request, String> {
+    let value: serde_json::Value = serde_json::from_str(content).map_err(|e| e.to_string())?;
+    let serde_json::Value::Object(fields) = value else {
+        return Err("expected a JSON object".to_string());
+    };
+    if !fields.contains_key("url") {
+        return Err("missing field `url`".to_string());
+    }
+    let mut request = serde_json::to_value(Request::default()).map_err(|e| e.to_string())?;
+    if let serde_json::Value::Object(defaults) = &mut request {
+        defaults.extend(fields);
+    }
+    serde_json::from_value(request).map_err(|e| e.to_string())
+}
+
+/// Status line, headers sorted by name, a blank line and the body
+fn format_response(response: &Response) -> String {
+    let headers: BTreeMap<&String, &String> = response.headers.iter().collect();
+    let mut out = format!("{}\n", response.status_text);
+    for (key, value) in headers {
+        out.push_str(&format!("{}: {}\n", key, value));
+    }
+    out.push('\n');
+    out.push_str(&response.body);
+    if !response.body.ends_with('\n') {
+        out.push('\n');
+    }
+    out
+}
+
+fn format_response_json(response: &Response) -> String {
+    let headers: BTreeMap<&String, &String> = response.headers.iter().collect();
+    let value = serde_json::json!({
+        "status": response.status,
+        "status_text": response.status_text,
+        "headers": headers,
+        "body": response.body,
+        "time_ms": response.time_ms as u64,
+        "size_bytes": response.size_bytes,
+    });
+    serde_json::to_string_pretty(&value).unwrap_or_default()
+}
+
+/// Human readable result, one line per request and the failures below it
+fn format_summary(report: &RunReport) -> String {
+    let mut out = String::new();
+    let groups = report.iterations();
+    let numbered = groups.len() > 1;
+
+    for group in groups {
+        if numbered {
+            out.push_str(&format!("\nIteration {}\n", group.iteration));
+        }
+        for result in group.results {
+            let mark = if result.passed() { "✓" } else { "✗" };
+            let outcome = match (&result.error, result.status) {
+                (Some(e), _) => format!("error: {}", e),
+                (None, Some(status)) => format!("{}  {}ms", status, result.time_ms),
+                (None, None) => format!("{}ms", result.time_ms),
+            };
+            out.push_str(&format!(
+                "  {} {} {}  {}\n",
+                mark,
+                result.method.as_str(),
+                result.name,
+                outcome
+            ));
+            for assertion in result.assertions.iter().filter(|a| !a.passed) {
+                out.push_str(&format!(
+                    "      {}: {}\n",
+                    assertion.name,
+                    assertion.message.as_deref().unwrap_or("failed")
+                ));
+            }
+        }
+    }
+
+    out.push_str(&format!(
+        "\n{} passed, {} failed in {}ms{}\n",
+        report.passed_count(),
+        report.failed_count(),
+        report.duration_ms,
+        if report.aborted {
+            " (stopped at first failure)"
+        } else {
+            ""
+        }
+    ));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{AssertionResult, HttpMethod, RequestRunResult};
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn test_parse_run_args() {
+        let parsed = parse_run_args(&args(
+            "API --env staging -v token=abc -v host=x=y --iterations=3 --bail --junit out.xml",
+        ))
+        .unwrap();
+        assert_eq!(parsed.collection, "API");
+        assert_eq!(parsed.common.environment, Environment::Staging);
+        assert_eq!(
+            parsed.common.variables,
+            vec![
+                ("token".to_string(), "abc".to_string()),
+                ("host".to_string(), "x=y".to_string())
+            ]
+        );
+        assert_eq!(parsed.options.iterations, 3);
+        assert!(parsed.options.stop_on_failure);
+        assert_eq!(parsed.junit, Some(PathBuf::from("out.xml")));
+
+        assert!(parse_run_args(&args("")).is_err());
+        assert!(parse_run_args(&args("API --env mars")).is_err());
+        assert!(parse_run_args(&args("API --iterations 0")).is_err());
+        assert!(parse_run_args(&args("API --junit")).is_err());
+        assert!(parse_run_args(&args("--help")).unwrap().common.help);
+    }
+
+    #[test]
+    fn test_parse_send_args() {
+        let parsed = parse_send_args(&args("API/Users/Get --json --env=prod")).unwrap();
+        assert_eq!(parsed.path.as_deref(), Some("API/Users/Get"));
+        assert!(parsed.json);
+        assert_eq!(parsed.common.environment, Environment::Production);
+
+        let parsed = parse_send_args(&args("--file req.json")).unwrap();
+        assert_eq!(parsed.file, Some(PathBuf::from("req.json")));
+
+        assert!(parse_send_args(&args("")).is_err());
+        assert!(parse_send_args(&args("API/Get --file req.json")).is_err());
+    }
+
+    #[test]
+    fn test_parse_request_file() {
+        let request = parse_request_file(
+            r#"{
+                "method": "POST",
+                "url": "https://example.com/users",
+                "headers": [{"key": "Accept", "value": "application/json", "enabled": true}]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(request.method, HttpMethod::POST);
+        assert_eq!(request.url, "https://example.com/users");
+        assert_eq!(request.headers[0].key, "Accept");
+        assert!(request.query_params.is_empty());
+
+        let request = parse_request_file(r#"{"url": "https://example.com"}"#).unwrap();
+        assert_eq!(request.method, HttpMethod::GET);
+
+        assert!(parse_request_file(r#"{"method": "GET"}"#).is_err());
+        assert!(parse_request_file(r#"{"url": "/", "method": "FETCH"}"#).is_err());
+        assert!(parse_request_file("[]").is_err());
+    }
+
+    #[test]
+    fn test_find_request() {
+        let request = |name: &str| {
+            CollectionItem::Request(Request {
+                name: name.to_string(),
+                ..Default::default()
+            })
+        };
+        let collections = vec![Collection {
+            id: uuid::Uuid::new_v4(),
+            name: "My API".to_string(),
+            items: vec![
+                request("Health"),
+                CollectionItem::Folder(crate::models::Folder {
+                    id: uuid::Uuid::new_v4(),
+                    name: "Users".to_string(),
+                    items: vec![request("Get user")],
+                    expanded: true,
+                }),
+            ],
+            expanded: true,
+            http_file: None,
+        }];
+
+        let found = find_request(&collections, "My API/Users/Get user", "dir").unwrap();
+        assert_eq!(found.name, "Get user");
+        assert!(find_request(&collections, "my api/health", "dir").is_ok());
+        assert!(find_request(&collections, "My API/Get user", "dir").is_err());
+        assert!(find_request(&collections, "My API", "dir").is_err());
+    }
+
+    #[test]
+    fn test_execute_against_saved_collection() {
+        let dir = std::env::temp_dir().join(format!("requiem-cli-{}", uuid::Uuid::new_v4()));
+        let dir_str = dir.to_string_lossy().to_string();
+
+        let request = crate::models::Request {
+            name: "Unreachable".to_string(),
+            url: "http://127.0.0.1:9/health".to_string(),
+            ..Default::default()
+        };
+        let collection = Collection {
+            id: uuid::Uuid::new_v4(),
+            name: "Smoke".to_string(),
+            items: vec![CollectionItem::Request(request)],
+            expanded: true,
+            http_file: None,
+        };
+        storage::save_collection(&dir_str, &collection).unwrap();
+
+        // An encrypted store only needs unlocking for requests that use it
+        let mut store = SecretStore::default();
+        store
+            .set_passphrase(Some("passphrase".to_string()))
+            .unwrap();
+        store.set("other/password", "hunter2".to_string()).unwrap();
+        store.save(&dir_str).unwrap();
+
+        let report_path = dir.join("report.xml");
+        let parsed = RunArgs {
+            common: CommonArgs {
+                dir: Some(dir_str.clone()),
+                ..Default::default()
+            },
+            collection: "smoke".to_string(),
+            junit: Some(report_path.clone()),
+            ..Default::default()
+        };
+        let report = execute(parsed).unwrap();
+        assert_eq!(report.failed_count(), 1);
+        assert!(std::fs::read_to_string(&report_path)
+            .unwrap()
+            .contains("<error message="));
+
+        let missing = RunArgs {
+            common: CommonArgs {
+                dir: Some(dir_str.clone()),
+                ..Default::default()
+            },
+            collection: "nope".to_string(),
+            ..Default::default()
+        };
+        assert!(execute(missing).unwrap_err().contains("available: Smoke"));
+
+        let mut password = crate::models::KeyValue::new("password", "secret:other/password");
+        password.secret = true;
+        let locked = Collection {
+            id: uuid::Uuid::new_v4(),
+            name: "Locked".to_string(),
+            items: vec![CollectionItem::Request(Request {
+                auth: vec![password],
+                ..Default::default()
+            })],
+            expanded: true,
+            http_file: None,
+        };
+        storage::save_collection(&dir_str, &locked).unwrap();
+        let locked = |passphrase: Option<&str>| RunArgs {
+            common: CommonArgs {
+                dir: Some(dir_str.clone()),
+                passphrase: passphrase.map(str::to_string),
+                ..Default::default()
+            },
+            collection: "locked".to_string(),
+            ..Default::default()
+        };
+        assert!(execute(locked(None)).unwrap_err().contains(PASSPHRASE_VAR));
+        assert!(execute(locked(Some("wrong"))).is_err());
+        // Unlocked, the request is sent and fails for its empty URL instead
+        assert_eq!(
+            execute(locked(Some("passphrase"))).unwrap().failed_count(),
+            1
+        );
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_format_summary() {
+        let report = RunReport {
+            name: "API".to_string(),
+            started_at: String::new(),
+            duration_ms: 42,
+            options: RunOptions::default(),
+            results: vec![RequestRunResult {
+                iteration: 1,
+                request_id: uuid::Uuid::new_v4(),
+                name: "Login".to_string(),
+                method: HttpMethod::POST,
+                url: String::new(),
+                status: Some(500),
+                time_ms: 12,
+                assertions: vec![AssertionResult::fail(
+                    "Status code is successful",
+                    "Got 500 Internal Server Error",
+                )],
+                error: None,
+            }],
+            aborted: false,
+            data_rows: vec![],
+        };
+
+        assert_eq!(
+            format_summary(&report),
+            "  ✗ POST Login  500  12ms\n      Status code is successful: Got 500 Internal Server Error\n\n0 passed, 1 failed in 42ms\n"
+        );
+    }
+}
@@ -15,6 +15,28 @@ pub struct Config {
     /// AI configuration
     #[serde(default)]
     pub ai_config: AiConfig,
+    /// Request history settings
+    #[serde(default)]
+    pub history: HistoryConfig,
+}
+
+/// What is kept of sent requests, and for how long
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HistoryConfig {
+    /// Days entries are kept; 0 keeps them forever
+    pub retention_days: u32,
+    /// Largest response body stored with an entry, in bytes; 0 stores none
+    pub max_body_bytes: usize,
+}
+
+impl Default for HistoryConfig {
+    fn default() -> Self {
+        Self {
+            retention_days: 30,
+            max_body_bytes: 256 * 1024,
+        }
+    }
 }
 
 impl Default for Config {
@@ -27,6 +49,7 @@ impl Default for Config {
             language: Language::default(),
             save_directory,
             ai_config: AiConfig::default(),
+            history: HistoryConfig::default(),
         }
     }
 }
@@ -113,4 +136,13 @@ impl Config {
         self.ai_config = ai_config;
         self.save()
     }
+
+    /// Update history settings and save
+    pub fn set_history_config(
+        &mut self,
+        history: HistoryConfig,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        self.history = history;
+        self.save()
+    }
 }
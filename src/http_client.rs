@@ -5,13 +5,28 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::time::Instant;
 
+/// Build the HTTP client used for sending requests
+///
+/// `insecure` accepts invalid TLS certificates, for self-signed dev servers.
+pub fn build_client(insecure: bool) -> Result<Client> {
+    Ok(Client::builder()
+        .timeout(std::time::Duration::from_secs(30))
+        .danger_accept_invalid_certs(insecure)
+        .build()?)
+}
+
 /// Execute an HTTP request
 pub async fn execute_request(request: &Request) -> Result<Response> {
+    let client = build_client(request.insecure)?;
+    execute_with_client(&client, request).await
+}
+
+/// Execute an HTTP request with an existing client, reusing its connection pool
+pub async fn execute_with_client(client: &Client, request: &Request) -> Result<Response> {
     let start = Instant::now();
 
-    let client = Client::builder()
-        .timeout(std::time::Duration::from_secs(30))
-        .build()?;
+    // Auth fields and cookies go out as headers and query parameters
+    let request = &request.with_credentials();
 
     // Build URL with query parameters
     let mut url = reqwest::Url::parse(&request.url)?;
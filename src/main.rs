@@ -8,10 +8,15 @@
 
 mod ai_client;
 mod app;
+mod blast;
+mod cli;
 mod config;
 mod http_client;
 mod i18n;
+mod mock_server;
 mod models;
+mod runner;
+mod secrets;
 mod storage;
 mod ui;
 mod utils;
@@ -21,6 +26,7 @@ use iced::{Size, Task, Theme};
 use tracing::info;
 
 use app::Requiem;
+use secrets::MaskedWriter;
 
 // Embed logo at compile time
 const LOGO_BYTES: &[u8] = include_bytes!("resources/logo.png");
@@ -64,6 +70,13 @@ pub fn main() -> iced::Result {
         }
     }));
 
+    // Commands run headless and exit without opening a window
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if cli::is_command(&args) {
+        init_cli_logging();
+        std::process::exit(cli::dispatch(&args));
+    }
+
     // Initialize logging with file output
     init_logging();
 
@@ -143,7 +156,17 @@ fn get_log_dir() -> Result<std::path::PathBuf, Box<dyn std::error::Error>> {
     Ok(log_dir)
 }
 
+/// Log only to stderr so command output stays clean; quiet unless RUST_LOG is set
+fn init_cli_logging() {
+    tracing_subscriber::fmt()
+        .with_writer(|| MaskedWriter(std::io::stderr()))
+        .with_env_filter(tracing_subscriber::EnvFilter::from_default_env())
+        .init();
+}
+
 /// Initialize logging with both stdout and file output
+///
+/// Secret values are masked in both.
 fn init_logging() {
     let env_filter = tracing_subscriber::EnvFilter::from_default_env()
         .add_directive(tracing::Level::INFO.into());
@@ -166,13 +189,16 @@ fn init_logging() {
             use tracing_subscriber::util::SubscriberInitExt;
 
             let file_appender = tracing_subscriber::fmt::layer()
-                .with_writer(move || file.try_clone().unwrap())
+                .with_writer(move || MaskedWriter(file.try_clone().unwrap()))
                 .with_target(true)
                 .with_ansi(false);
 
             tracing_subscriber::registry()
                 .with(env_filter)
-                .with(tracing_subscriber::fmt::layer())
+                .with(
+                    tracing_subscriber::fmt::layer()
+                        .with_writer(|| MaskedWriter(std::io::stdout())),
+                )
                 .with(file_appender)
                 .init();
             return;
@@ -180,5 +206,8 @@ fn init_logging() {
     }
 
     // Fallback to console-only logging
-    tracing_subscriber::fmt().with_env_filter(env_filter).init();
+    tracing_subscriber::fmt()
+        .with_writer(|| MaskedWriter(std::io::stdout()))
+        .with_env_filter(env_filter)
+        .init();
 }
@@ -0,0 +1,527 @@
+use futures::channel::mpsc;
+use futures::{Stream, StreamExt};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tracing::{error, info};
+
+use crate::models::{MockHit, MockServerConfig, Request, ResponseExample};
+use crate::utils::{curl, variables};
+
+/// How often the accept loop checks whether the server should stop
+const STOP_POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Incoming requests larger than this are rejected
+const MAX_REQUEST_BYTES: usize = 1024 * 1024;
+
+/// Updates published by a running mock server
+#[derive(Debug, Clone)]
+pub enum MockEvent {
+    /// Listening on the given port
+    Started(u16),
+    Hit(MockHit),
+    Failed(String),
+    Stopped,
+}
+
+/// One segment of a route path
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    Literal(String),
+    /// `:id`, `{id}` or `{{id}}`, captured as a path parameter
+    Param(String),
+}
+
+/// A saved request turned into a route of the mock server
+#[derive(Debug, Clone)]
+pub struct MockRoute {
+    method: String,
+    segments: Vec<Segment>,
+    name: String,
+    example: Option<ResponseExample>,
+}
+
+impl MockRoute {
+    fn literal_count(&self) -> usize {
+        self.segments
+            .iter()
+            .filter(|s| matches!(s, Segment::Literal(_)))
+            .count()
+    }
+
+    /// Path parameters when `path` matches this route
+    fn captures(&self, path: &[&str]) -> Option<HashMap<String, String>> {
+        if path.len() != self.segments.len() {
+            return None;
+        }
+
+        let mut params = HashMap::new();
+        for (segment, actual) in self.segments.iter().zip(path) {
+            match segment {
+                Segment::Literal(literal) if literal == actual => {}
+                Segment::Literal(_) => return None,
+                Segment::Param(name) => {
+                    params.insert(name.clone(), actual.to_string());
+                }
+            }
+        }
+        Some(params)
+    }
+}
+
+/// Build one route per saved request
+pub fn routes(requests: &[Request]) -> Vec<MockRoute> {
+    requests
+        .iter()
+        .map(|request| MockRoute {
+            method: request.method.as_str().to_string(),
+            segments: split_path(url_path(&request.url))
+                .into_iter()
+                .map(parse_segment)
+                .collect(),
+            name: request.name.clone(),
+            example: request.example.clone(),
+        })
+        .collect()
+}
+
+/// Path part of a saved request URL
+///
+/// Handles full URLs, `{{baseUrl}}/path` style URLs where the variable stands
+/// for scheme and host, and bare paths.
+fn url_path(url: &str) -> &str {
+    let url = url.trim().split(['?', '#']).next().unwrap_or_default();
+
+    if let Some(scheme_end) = url.find("://") {
+        let rest = &url[scheme_end + 3..];
+        rest.find('/').map(|i| &rest[i..]).unwrap_or("")
+    } else if url.starts_with("{{") {
+        url.find("}}").map(|i| &url[i + 2..]).unwrap_or("")
+    } else if url.starts_with('/') {
+        url
+    } else {
+        url.find('/').map(|i| &url[i..]).unwrap_or("")
+    }
+}
+
+fn split_path(path: &str) -> Vec<&str> {
+    path.split('/').filter(|s| !s.is_empty()).collect()
+}
+
+fn parse_segment(segment: &str) -> Segment {
+    if let Some(name) = segment.strip_prefix(':') {
+        return Segment::Param(name.to_string());
+    }
+    if let Some(name) = segment
+        .strip_prefix("{{")
+        .and_then(|s| s.strip_suffix("}}"))
+        .or_else(|| segment.strip_prefix('{').and_then(|s| s.strip_suffix('}')))
+    {
+        return Segment::Param(name.trim().to_string());
+    }
+    Segment::Literal(segment.to_string())
+}
+
+/// Response produced for one incoming request
+#[derive(Debug)]
+struct MockResponse {
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: String,
+    matched: Option<String>,
+}
+
+impl MockResponse {
+    fn error(status: u16, message: String, matched: Option<String>) -> Self {
+        Self {
+            status,
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            body: serde_json::json!({ "error": message }).to_string(),
+            matched,
+        }
+    }
+}
+
+/// Pick the route for `method` and `target` and render its example
+///
+/// When several routes match, the one with the most literal segments wins,
+/// so `/users/me` takes precedence over `/users/:id`.
+fn respond(routes: &[MockRoute], method: &str, target: &str) -> MockResponse {
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let segments = split_path(path);
+
+    let matched = routes
+        .iter()
+        .filter(|route| route.method.eq_ignore_ascii_case(method))
+        .filter_map(|route| route.captures(&segments).map(|params| (route, params)))
+        .max_by_key(|(route, _)| route.literal_count());
+
+    let Some((route, params)) = matched else {
+        if method.eq_ignore_ascii_case("OPTIONS") {
+            // CORS preflight from a browser app
+            return MockResponse {
+                status: 204,
+                headers: vec![
+                    (
+                        "Access-Control-Allow-Methods".to_string(),
+                        "GET, POST, PUT, PATCH, DELETE, HEAD, OPTIONS".to_string(),
+                    ),
+                    ("Access-Control-Allow-Headers".to_string(), "*".to_string()),
+                ],
+                body: String::new(),
+                matched: None,
+            };
+        }
+        return MockResponse::error(404, format!("No mock for {} {}", method, path), None);
+    };
+
+    let Some(example) = &route.example else {
+        return MockResponse::error(
+            501,
+            format!("No example saved for '{}'", route.name),
+            Some(route.name.clone()),
+        );
+    };
+
+    let mut context = template_context(&params, query);
+    let headers = example
+        .headers
+        .iter()
+        .filter(|h| h.enabled && !is_hop_header(&h.key))
+        .map(|h| (h.key.clone(), variables::substitute(&h.value, &context)))
+        .collect();
+    // Values from the request must not break out of the strings of a JSON body
+    if is_json(example) {
+        for value in context.values_mut() {
+            let quoted = serde_json::Value::from(value.as_str()).to_string();
+            *value = quoted[1..quoted.len() - 1].to_string();
+        }
+    }
+
+    MockResponse {
+        status: example.status,
+        headers,
+        body: variables::substitute(&example.body, &context),
+        matched: Some(route.name.clone()),
+    }
+}
+
+/// Values available to `{{...}}` placeholders in example headers and bodies
+fn template_context(params: &HashMap<String, String>, query: &str) -> HashMap<String, String> {
+    let mut context = HashMap::new();
+    for (name, value) in params {
+        context.insert(format!("path.{}", name), value.clone());
+    }
+    for pair in query.split('&').filter(|p| !p.is_empty()) {
+        let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+        context.insert(
+            format!("query.{}", curl::percent_decode(name)),
+            curl::percent_decode(value),
+        );
+    }
+
+    let now = chrono::Local::now();
+    context.insert("$uuid".to_string(), uuid::Uuid::new_v4().to_string());
+    context.insert("$timestamp".to_string(), now.timestamp().to_string());
+    context.insert("$isoTimestamp".to_string(), now.to_rfc3339());
+    context.insert(
+        "$randomInt".to_string(),
+        (uuid::Uuid::new_v4().as_u128() % 1000).to_string(),
+    );
+    context
+}
+
+/// Whether an example's body is JSON, going by its content type
+fn is_json(example: &ResponseExample) -> bool {
+    example.headers.iter().any(|h| {
+        h.enabled
+            && h.key.eq_ignore_ascii_case("content-type")
+            && h.value.to_ascii_lowercase().contains("json")
+    })
+}
+
+/// Headers describing the original transfer, recomputed when serving
+fn is_hop_header(name: &str) -> bool {
+    [
+        "content-length",
+        "transfer-encoding",
+        "connection",
+        "content-encoding",
+    ]
+    .iter()
+    .any(|h| name.eq_ignore_ascii_case(h))
+}
+
+/// Serve `routes` on localhost until `stop` is set, streaming hits
+///
+/// The stream starts with `Started` (or `Failed` if the port is taken) and
+/// ends with `Stopped`.
+pub fn serve(
+    routes: Vec<MockRoute>,
+    config: MockServerConfig,
+    stop: Arc<AtomicBool>,
+) -> impl Stream<Item = MockEvent> {
+    let (sender, receiver) = mpsc::unbounded();
+    let server =
+        futures::stream::once(listen(routes, config, stop, sender)).filter_map(|_| async { None });
+    futures::stream::select(server, receiver)
+}
+
+async fn listen(
+    routes: Vec<MockRoute>,
+    config: MockServerConfig,
+    stop: Arc<AtomicBool>,
+    sender: mpsc::UnboundedSender<MockEvent>,
+) {
+    let listener = match TcpListener::bind(("127.0.0.1", config.port)).await {
+        Ok(listener) => listener,
+        Err(e) => {
+            error!(
+                "Mock server failed to listen on port {}: {}",
+                config.port, e
+            );
+            let _ = sender.unbounded_send(MockEvent::Failed(format!(
+                "Cannot listen on port {}: {}",
+                config.port, e
+            )));
+            return;
+        }
+    };
+    let port = listener
+        .local_addr()
+        .map(|addr| addr.port())
+        .unwrap_or(config.port);
+
+    info!("Mock server listening on http://127.0.0.1:{}", port);
+    let _ = sender.unbounded_send(MockEvent::Started(port));
+
+    let routes = Arc::new(routes);
+    while !stop.load(Ordering::Relaxed) {
+        let Ok(accepted) = tokio::time::timeout(STOP_POLL_INTERVAL, listener.accept()).await else {
+            continue;
+        };
+        match accepted {
+            Ok((stream, _)) => {
+                tokio::spawn(handle_connection(
+                    stream,
+                    routes.clone(),
+                    config.latency_ms,
+                    sender.clone(),
+                ));
+            }
+            Err(e) => error!("Mock server failed to accept a connection: {}", e),
+        }
+    }
+
+    info!("Mock server on port {} stopped", port);
+    let _ = sender.unbounded_send(MockEvent::Stopped);
+}
+
+async fn handle_connection(
+    mut stream: TcpStream,
+    routes: Arc<Vec<MockRoute>>,
+    latency_ms: u64,
+    sender: mpsc::UnboundedSender<MockEvent>,
+) {
+    let Some((method, target)) = read_request(&mut stream).await else {
+        return;
+    };
+
+    let response = respond(&routes, &method, &target);
+    if latency_ms > 0 {
+        tokio::time::sleep(Duration::from_millis(latency_ms)).await;
+    }
+
+    let reason = reqwest::StatusCode::from_u16(response.status)
+        .ok()
+        .and_then(|s| s.canonical_reason())
+        .unwrap_or("");
+    let mut head = format!("HTTP/1.1 {} {}\r\n", response.status, reason);
+    for (key, value) in &response.headers {
+        head.push_str(&format!("{}: {}\r\n", key, value));
+    }
+    if !response
+        .headers
+        .iter()
+        .any(|(key, _)| key.eq_ignore_ascii_case("access-control-allow-origin"))
+    {
+        head.push_str("Access-Control-Allow-Origin: *\r\n");
+    }
+    head.push_str(&format!(
+        "Content-Length: {}\r\nConnection: close\r\n\r\n",
+        response.body.len()
+    ));
+
+    let written = async {
+        stream.write_all(head.as_bytes()).await?;
+        if !method.eq_ignore_ascii_case("HEAD") {
+            stream.write_all(response.body.as_bytes()).await?;
+        }
+        stream.flush().await
+    };
+    if let Err(e) = written.await {
+        error!("Mock server failed to write response: {}", e);
+    }
+
+    let _ = sender.unbounded_send(MockEvent::Hit(MockHit {
+        time: chrono::Local::now().format("%H:%M:%S").to_string(),
+        method,
+        path: target,
+        status: response.status,
+        matched: response.matched,
+    }));
+}
+
+/// Read the request head and body, returning method and target
+async fn read_request(stream: &mut TcpStream) -> Option<(String, String)> {
+    let mut buffer = Vec::new();
+    let mut chunk = [0u8; 4096];
+
+    let head_end = loop {
+        if let Some(pos) = buffer.windows(4).position(|w| w == b"\r\n\r\n") {
+            break pos + 4;
+        }
+        if buffer.len() > MAX_REQUEST_BYTES {
+            return None;
+        }
+        let read = stream.read(&mut chunk).await.ok()?;
+        if read == 0 {
+            return None;
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+    };
+
+    let head = String::from_utf8_lossy(&buffer[..head_end]).to_string();
+    let mut lines = head.lines();
+    let mut request_line = lines.next()?.split_whitespace();
+    let method = request_line.next()?.to_string();
+    let target = request_line.next()?.to_string();
+
+    // Drain the body so the client does not see a reset connection
+    let content_length = lines
+        .filter_map(|line| line.split_once(':'))
+        .find(|(name, _)| name.trim().eq_ignore_ascii_case("content-length"))
+        .and_then(|(_, value)| value.trim().parse::<usize>().ok())
+        .unwrap_or(0)
+        .min(MAX_REQUEST_BYTES);
+    let mut remaining = content_length.saturating_sub(buffer.len() - head_end);
+    while remaining > 0 {
+        let read = stream.read(&mut chunk).await.ok()?;
+        if read == 0 {
+            break;
+        }
+        remaining = remaining.saturating_sub(read);
+    }
+
+    Some((method, target))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{HttpMethod, KeyValue};
+
+    fn request(method: HttpMethod, name: &str, url: &str, body: Option<&str>) -> Request {
+        Request {
+            name: name.to_string(),
+            method,
+            url: url.to_string(),
+            example: body.map(|body| ResponseExample {
+                status: 200,
+                headers: vec![KeyValue::new("Content-Type", "application/json")],
+                body: body.to_string(),
+                saved_at: String::new(),
+                ignore: vec![],
+            }),
+            ..Request::default()
+        }
+    }
+
+    #[test]
+    fn test_url_path() {
+        assert_eq!(url_path("https://api.example.com/users?x=1"), "/users");
+        assert_eq!(url_path("{{baseUrl}}/users/:id"), "/users/:id");
+        assert_eq!(url_path("localhost:8080/health"), "/health");
+        assert_eq!(url_path("https://api.example.com"), "");
+    }
+
+    #[test]
+    fn test_respond() {
+        let routes = routes(&[
+            request(
+                HttpMethod::GET,
+                "Get user",
+                "{{baseUrl}}/users/:id",
+                Some(r#"{"id":"{{path.id}}","v":"{{query.verbose}}"}"#),
+            ),
+            request(
+                HttpMethod::GET,
+                "Me",
+                "https://api.example.com/users/me",
+                Some(r#"{"me":true}"#),
+            ),
+            request(HttpMethod::POST, "Create user", "/users", None),
+        ]);
+
+        let user = respond(&routes, "GET", "/users/42?verbose=1");
+        assert_eq!(user.status, 200);
+        assert_eq!(user.body, r#"{"id":"42","v":"1"}"#);
+        assert_eq!(user.matched.as_deref(), Some("Get user"));
+
+        // Query values are decoded, then escaped for the JSON body
+        let quoted = respond(&routes, "GET", "/users/42?verbose=say+%22hi%22%5C");
+        assert_eq!(quoted.body, r#"{"id":"42","v":"say \"hi\"\\"}"#);
+        let value: serde_json::Value = serde_json::from_str(&quoted.body).unwrap();
+        assert_eq!(value["v"], r#"say "hi"\"#);
+
+        let me = respond(&routes, "GET", "/users/me");
+        assert_eq!(me.matched.as_deref(), Some("Me"));
+
+        let create = respond(&routes, "POST", "/users");
+        assert_eq!(create.status, 501);
+
+        assert_eq!(respond(&routes, "DELETE", "/users/42").status, 404);
+        assert_eq!(respond(&routes, "OPTIONS", "/users/42").status, 204);
+    }
+
+    #[tokio::test]
+    async fn test_serve() {
+        let routes = routes(&[request(
+            HttpMethod::GET,
+            "Health",
+            "/health",
+            Some(r#"{"ok":true}"#),
+        )]);
+        let config = MockServerConfig {
+            port: 0,
+            latency_ms: 0,
+        };
+        let stop = Arc::new(AtomicBool::new(false));
+        let mut events = Box::pin(serve(routes, config, stop.clone()));
+
+        let Some(MockEvent::Started(port)) = events.next().await else {
+            panic!("server did not start");
+        };
+        // The server only runs while its stream is polled, so send from a task
+        let client = tokio::spawn(async move {
+            let response = reqwest::get(format!("http://127.0.0.1:{}/health", port)).await?;
+            Ok::<_, reqwest::Error>((response.status(), response.text().await?))
+        });
+
+        let Some(MockEvent::Hit(hit)) = events.next().await else {
+            panic!("hit was not logged");
+        };
+        let (status, body) = client.await.unwrap().unwrap();
+        assert_eq!(status, 200);
+        assert_eq!(body, r#"{"ok":true}"#);
+        assert_eq!(hit.path, "/health");
+        assert_eq!(hit.matched.as_deref(), Some("Health"));
+
+        stop.store(true, Ordering::Relaxed);
+        let remaining: Vec<MockEvent> = events.collect().await;
+        assert!(matches!(remaining.last(), Some(MockEvent::Stopped)));
+    }
+}
@@ -0,0 +1,213 @@
+use base64::Engine;
+
+use super::key_value::KeyValue;
+
+/// Auth settings recognised in a request's free-form auth fields
+///
+/// A `type` field names the scheme (as written by imports); without one the
+/// scheme is inferred from the field names.
+#[derive(Debug, Clone)]
+pub enum AuthScheme {
+    Basic {
+        username: String,
+        password: String,
+    },
+    Bearer {
+        token: String,
+    },
+    ApiKey {
+        key: String,
+        value: String,
+        in_query: bool,
+    },
+    /// A scheme other tools know but this app does not, with its raw fields
+    Other {
+        kind: String,
+        fields: Vec<KeyValue>,
+    },
+}
+
+/// Where credentials go when a request is sent
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Credential {
+    Header(String, String),
+    Query(String, String),
+}
+
+impl AuthScheme {
+    pub fn from_fields(fields: &[KeyValue]) -> Option<AuthScheme> {
+        let fields: Vec<&KeyValue> = fields
+            .iter()
+            .filter(|f| f.enabled && !f.key.is_empty())
+            .collect();
+        let get = |name: &str| {
+            fields
+                .iter()
+                .find(|f| f.key.eq_ignore_ascii_case(name))
+                .map(|f| f.value.clone())
+        };
+        let has = |name: &str| get(name).is_some();
+
+        let kind = match get("type") {
+            Some(kind) => kind.to_lowercase(),
+            None if has("username") || has("password") => "basic".to_string(),
+            None if has("token") => "bearer".to_string(),
+            None if has("key") && has("value") => "apikey".to_string(),
+            None => return None,
+        };
+
+        Some(match kind.as_str() {
+            "basic" => AuthScheme::Basic {
+                username: get("username").unwrap_or_default(),
+                password: get("password").unwrap_or_default(),
+            },
+            "bearer" => AuthScheme::Bearer {
+                token: get("token").unwrap_or_default(),
+            },
+            "apikey" => AuthScheme::ApiKey {
+                key: get("key").unwrap_or_default(),
+                value: get("value").unwrap_or_default(),
+                in_query: get("in").is_some_and(|place| place.eq_ignore_ascii_case("query")),
+            },
+            _ => AuthScheme::Other {
+                kind,
+                fields: fields
+                    .into_iter()
+                    .filter(|f| !f.key.eq_ignore_ascii_case("type"))
+                    .cloned()
+                    .collect(),
+            },
+        })
+    }
+
+    /// Fields describing this scheme, as read back by [`AuthScheme::from_fields`]
+    pub fn to_fields(&self) -> Vec<KeyValue> {
+        match self {
+            AuthScheme::Basic { username, password } => vec![
+                KeyValue::new("type", "basic"),
+                KeyValue::new("username", username),
+                KeyValue::new("password", password),
+            ],
+            AuthScheme::Bearer { token } => vec![
+                KeyValue::new("type", "bearer"),
+                KeyValue::new("token", token),
+            ],
+            AuthScheme::ApiKey {
+                key,
+                value,
+                in_query,
+            } => vec![
+                KeyValue::new("type", "apikey"),
+                KeyValue::new("key", key),
+                KeyValue::new("value", value),
+                KeyValue::new("in", if *in_query { "query" } else { "header" }),
+            ],
+            AuthScheme::Other { kind, fields } => {
+                let mut all = vec![KeyValue::new("type", kind)];
+                all.extend(fields.iter().cloned());
+                all
+            }
+        }
+    }
+
+    /// The header or query parameter that sends these credentials, or `None`
+    /// for schemes this app cannot send
+    ///
+    /// Call this on a request whose variables are resolved, since basic
+    /// credentials are encoded.
+    pub fn credential(&self) -> Option<Credential> {
+        match self {
+            AuthScheme::Basic { username, password } => {
+                let encoded = base64::engine::general_purpose::STANDARD
+                    .encode(format!("{}:{}", username, password));
+                Some(Credential::Header(
+                    "Authorization".to_string(),
+                    format!("Basic {}", encoded),
+                ))
+            }
+            AuthScheme::Bearer { token } => Some(Credential::Header(
+                "Authorization".to_string(),
+                format!("Bearer {}", token),
+            )),
+            AuthScheme::ApiKey {
+                key,
+                value,
+                in_query,
+            } if !key.is_empty() => Some(match in_query {
+                true => Credential::Query(key.clone(), value.clone()),
+                false => Credential::Header(key.clone(), value.clone()),
+            }),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_fields() {
+        let typed = [
+            KeyValue::new("type", "basic"),
+            KeyValue::new("username", "{{user}}"),
+        ];
+        assert!(matches!(
+            AuthScheme::from_fields(&typed),
+            Some(AuthScheme::Basic { username, password })
+                if username == "{{user}}" && password.is_empty()
+        ));
+
+        let inferred = [
+            KeyValue::new("key", "X-Api-Key"),
+            KeyValue::new("value", "abc"),
+            KeyValue::new("in", "query"),
+        ];
+        assert!(matches!(
+            AuthScheme::from_fields(&inferred),
+            Some(AuthScheme::ApiKey { in_query: true, .. })
+        ));
+
+        let mut disabled = KeyValue::new("token", "abc");
+        disabled.enabled = false;
+        assert!(AuthScheme::from_fields(&[disabled]).is_none());
+
+        let other = [KeyValue::new("type", "oauth2"), KeyValue::new("scope", "a")];
+        let Some(AuthScheme::Other { kind, fields }) = AuthScheme::from_fields(&other) else {
+            panic!("expected an unknown scheme");
+        };
+        assert_eq!(kind, "oauth2");
+        assert_eq!(fields.len(), 1);
+    }
+
+    #[test]
+    fn test_credential() {
+        let basic = AuthScheme::Basic {
+            username: "admin".to_string(),
+            password: "secret".to_string(),
+        };
+        let read_back = AuthScheme::from_fields(&basic.to_fields()).unwrap();
+        assert_eq!(
+            read_back.credential(),
+            Some(Credential::Header(
+                "Authorization".to_string(),
+                "Basic YWRtaW46c2VjcmV0".to_string()
+            ))
+        );
+
+        let api_key = AuthScheme::ApiKey {
+            key: "api_key".to_string(),
+            value: "abc".to_string(),
+            in_query: true,
+        };
+        assert_eq!(
+            AuthScheme::from_fields(&api_key.to_fields()).and_then(|a| a.credential()),
+            Some(Credential::Query("api_key".to_string(), "abc".to_string()))
+        );
+        let other = AuthScheme::Other {
+            kind: "oauth2".to_string(),
+            fields: vec![],
+        };
+        assert!(other.credential().is_none());
+    }
+}
@@ -0,0 +1,109 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use super::http_method::HttpMethod;
+
+/// When a blast run stops
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BlastLimit {
+    /// Stop after this many requests have been sent
+    Requests(u64),
+    /// Stop after this many seconds
+    Duration(u64),
+}
+
+/// Load test settings for a single request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlastConfig {
+    /// Number of concurrent workers
+    pub concurrency: u32,
+    pub limit: BlastLimit,
+    /// Seconds over which workers are started, 0 starts them all at once
+    pub ramp_up_secs: u64,
+    /// Upper bound on requests per second across all workers
+    pub rate_limit: Option<u32>,
+}
+
+impl Default for BlastConfig {
+    fn default() -> Self {
+        Self {
+            concurrency: 10,
+            limit: BlastLimit::Requests(1000),
+            ramp_up_secs: 0,
+            rate_limit: None,
+        }
+    }
+}
+
+/// One request sent during a blast run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlastSample {
+    /// Milliseconds since the start of the run when the request completed
+    pub offset_ms: u64,
+    pub latency_ms: u64,
+    pub status: Option<u16>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl BlastSample {
+    /// Transport errors and 5xx responses count as errors
+    pub fn is_error(&self) -> bool {
+        self.error.is_some() || self.status.is_none_or(|s| s >= 500)
+    }
+}
+
+/// Per-second bucket used for the live charts
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BlastSecond {
+    pub second: u64,
+    pub requests: u64,
+    pub errors: u64,
+    pub p50_ms: u64,
+    pub p99_ms: u64,
+}
+
+/// Aggregated statistics of a blast run, complete or in progress
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BlastSummary {
+    pub elapsed_ms: u64,
+    pub total: u64,
+    pub errors: u64,
+    /// Average requests per second over the elapsed time
+    pub rps: f64,
+    pub min_ms: u64,
+    pub mean_ms: u64,
+    pub p50_ms: u64,
+    pub p90_ms: u64,
+    pub p99_ms: u64,
+    pub max_ms: u64,
+    /// Count per status code, transport errors are keyed as "error"
+    pub status_counts: BTreeMap<String, u64>,
+    /// Count per transport error message
+    pub error_counts: BTreeMap<String, u64>,
+    pub timeline: Vec<BlastSecond>,
+}
+
+/// File format for exporting a blast report
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlastExportFormat {
+    /// Full report with summary and samples
+    Json,
+    /// One line per request
+    Csv,
+}
+
+/// Final result of a blast run, exportable as JSON or CSV
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlastReport {
+    pub request_name: String,
+    pub method: HttpMethod,
+    pub url: String,
+    pub started_at: String,
+    pub config: BlastConfig,
+    pub summary: BlastSummary,
+    /// True when the run was stopped by the user
+    pub stopped: bool,
+    /// Individual requests, up to `blast::MAX_SAMPLES`; the summary counts all
+    pub samples: Vec<BlastSample>,
+}
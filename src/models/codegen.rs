@@ -0,0 +1,55 @@
+use serde::{Deserialize, Serialize};
+
+/// Target of "Copy as...", a command line tool or an HTTP library
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum CodeLanguage {
+    #[default]
+    Curl,
+    Httpie,
+    Wget,
+    PythonRequests,
+    JavaScriptFetch,
+    JavaScriptAxios,
+    GoNetHttp,
+    RustReqwest,
+    JavaHttpClient,
+    PowerShell,
+}
+
+impl CodeLanguage {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CodeLanguage::Curl => "cURL",
+            CodeLanguage::Httpie => "HTTPie",
+            CodeLanguage::Wget => "wget",
+            CodeLanguage::PythonRequests => "Python requests",
+            CodeLanguage::JavaScriptFetch => "JavaScript fetch",
+            CodeLanguage::JavaScriptAxios => "JavaScript axios",
+            CodeLanguage::GoNetHttp => "Go net/http",
+            CodeLanguage::RustReqwest => "Rust reqwest",
+            CodeLanguage::JavaHttpClient => "Java HttpClient",
+            CodeLanguage::PowerShell => "PowerShell",
+        }
+    }
+
+    pub fn all() -> Vec<CodeLanguage> {
+        vec![
+            CodeLanguage::Curl,
+            CodeLanguage::Httpie,
+            CodeLanguage::Wget,
+            CodeLanguage::PythonRequests,
+            CodeLanguage::JavaScriptFetch,
+            CodeLanguage::JavaScriptAxios,
+            CodeLanguage::GoNetHttp,
+            CodeLanguage::RustReqwest,
+            CodeLanguage::JavaHttpClient,
+            CodeLanguage::PowerShell,
+        ]
+    }
+}
+
+impl std::fmt::Display for CodeLanguage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
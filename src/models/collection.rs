@@ -1,6 +1,9 @@
+use std::path::PathBuf;
+
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use super::flow::Flow;
 use super::request::Request;
 
 /// Folder item that can contain requests or other folders
@@ -12,11 +15,14 @@ pub struct Folder {
     pub expanded: bool,
 }
 
-/// Collection item - can be a request or a folder
+/// Collection item - can be a request, a folder or a flow
+// Requests dominate collections, so boxing them would only add indirection
+#[allow(clippy::large_enum_variant)]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum CollectionItem {
     Request(Request),
     Folder(Folder),
+    Flow(Flow),
 }
 
 /// Project/Collection structure
@@ -26,4 +32,19 @@ pub struct Collection {
     pub name: String,
     pub items: Vec<CollectionItem>,
     pub expanded: bool,
+    /// `.http` file the collection is kept in sync with
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub http_file: Option<HttpFileLink>,
+}
+
+/// Link between a collection and the `.http` file it was opened from
+///
+/// The file is read again when collections are loaded and rewritten
+/// whenever the collection is saved.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HttpFileLink {
+    pub path: PathBuf,
+    /// `@name = value` definitions of the file, written back on save
+    #[serde(default)]
+    pub variables: Vec<(String, String)>,
 }
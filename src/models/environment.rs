@@ -1,6 +1,8 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
 use std::fmt;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
 pub enum Environment {
     #[default]
     Development,
@@ -20,6 +22,32 @@ impl Environment {
     }
 }
 
+impl Environment {
+    /// Stable identifier used on the command line and in files
+    pub fn code(&self) -> &'static str {
+        match self {
+            Environment::Development => "development",
+            Environment::Testing => "testing",
+            Environment::Staging => "staging",
+            Environment::Production => "production",
+        }
+    }
+}
+
+impl std::str::FromStr for Environment {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "development" | "dev" => Ok(Environment::Development),
+            "testing" | "test" => Ok(Environment::Testing),
+            "staging" => Ok(Environment::Staging),
+            "production" | "prod" => Ok(Environment::Production),
+            _ => Err(format!("Invalid environment: {}", s)),
+        }
+    }
+}
+
 impl fmt::Display for Environment {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -57,3 +85,53 @@ impl fmt::Display for EnvironmentOption {
         }
     }
 }
+
+/// Variables defined per environment, referenced as `{{name}}` in requests
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EnvironmentVariables {
+    #[serde(default)]
+    pub variables: HashMap<Environment, HashMap<String, String>>,
+    /// Names of the variables kept in the local secret store
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub secrets: HashMap<Environment, BTreeSet<String>>,
+}
+
+impl EnvironmentVariables {
+    /// Get all variables of an environment
+    pub fn get(&self, environment: Environment) -> Option<&HashMap<String, String>> {
+        self.variables.get(&environment)
+    }
+
+    /// Set a single variable in an environment
+    pub fn set(
+        &mut self,
+        environment: Environment,
+        key: impl Into<String>,
+        value: impl Into<String>,
+    ) {
+        self.variables
+            .entry(environment)
+            .or_default()
+            .insert(key.into(), value.into());
+    }
+
+    /// Whether a variable is kept in the secret store
+    pub fn is_secret(&self, environment: Environment, key: &str) -> bool {
+        self.secrets
+            .get(&environment)
+            .is_some_and(|names| names.contains(key))
+    }
+
+    /// Mark a variable as secret or not
+    pub fn set_secret(&mut self, environment: Environment, key: &str, secret: bool) {
+        let names = self.secrets.entry(environment).or_default();
+        if secret {
+            names.insert(key.to_string());
+        } else {
+            names.remove(key);
+        }
+        if names.is_empty() {
+            self.secrets.remove(&environment);
+        }
+    }
+}
@@ -0,0 +1,38 @@
+use serde::{Deserialize, Serialize};
+
+use super::key_value::KeyValue;
+use super::response::Response;
+
+/// A response pinned on a request
+///
+/// Served by the mock server and used as the snapshot later responses are
+/// compared against.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResponseExample {
+    pub status: u16,
+    pub headers: Vec<KeyValue>,
+    pub body: String,
+    pub saved_at: String,
+    /// Volatile fields skipped by the snapshot diff, e.g. `updatedAt` or `/items/*/id`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ignore: Vec<String>,
+}
+
+impl ResponseExample {
+    pub fn from_response(response: &Response) -> Self {
+        let mut headers: Vec<KeyValue> = response
+            .headers
+            .iter()
+            .map(|(key, value)| KeyValue::new(key, value))
+            .collect();
+        headers.sort_by(|a, b| a.key.cmp(&b.key));
+
+        Self {
+            status: response.status,
+            headers,
+            body: response.body.clone(),
+            saved_at: chrono::Local::now().to_rfc3339(),
+            ignore: vec![],
+        }
+    }
+}
@@ -0,0 +1,71 @@
+/// File format for exporting a collection or folder
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CollectionExportFormat {
+    /// Postman collection v2.1
+    #[default]
+    Postman,
+    /// Insomnia export format 4
+    Insomnia,
+    /// OpenAPI 3 document
+    OpenApi,
+    /// `.http` file for VS Code REST Client and the JetBrains HTTP Client
+    Http,
+    /// Documentation as a single HTML page
+    Html,
+    /// Documentation as Markdown
+    Markdown,
+}
+
+impl CollectionExportFormat {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CollectionExportFormat::Postman => "Postman",
+            CollectionExportFormat::Insomnia => "Insomnia",
+            CollectionExportFormat::OpenApi => "OpenAPI 3",
+            CollectionExportFormat::Http => ".http",
+            CollectionExportFormat::Html => "HTML",
+            CollectionExportFormat::Markdown => "Markdown",
+        }
+    }
+
+    pub fn all() -> Vec<CollectionExportFormat> {
+        vec![
+            CollectionExportFormat::Postman,
+            CollectionExportFormat::Insomnia,
+            CollectionExportFormat::OpenApi,
+            CollectionExportFormat::Http,
+            CollectionExportFormat::Html,
+            CollectionExportFormat::Markdown,
+        ]
+    }
+
+    /// Appended to the collection name to suggest a file name
+    pub fn file_suffix(&self) -> &'static str {
+        match self {
+            CollectionExportFormat::Postman => ".postman_collection.json",
+            CollectionExportFormat::Insomnia => ".insomnia.json",
+            CollectionExportFormat::OpenApi => ".openapi.json",
+            CollectionExportFormat::Http => ".http",
+            CollectionExportFormat::Html => ".html",
+            CollectionExportFormat::Markdown => ".md",
+        }
+    }
+
+    /// Extension offered in the save dialog filter
+    pub fn extension(&self) -> &'static str {
+        match self {
+            CollectionExportFormat::Postman
+            | CollectionExportFormat::Insomnia
+            | CollectionExportFormat::OpenApi => "json",
+            CollectionExportFormat::Http => "http",
+            CollectionExportFormat::Html => "html",
+            CollectionExportFormat::Markdown => "md",
+        }
+    }
+}
+
+impl std::fmt::Display for CollectionExportFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
@@ -0,0 +1,84 @@
+use serde::{Deserialize, Serialize};
+
+/// Where an extraction rule reads its value from in the response
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum ExtractionSource {
+    #[default]
+    JsonPath,
+    Header,
+    Cookie,
+    Regex,
+}
+
+impl ExtractionSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ExtractionSource::JsonPath => "JSONPath",
+            ExtractionSource::Header => "Header",
+            ExtractionSource::Cookie => "Cookie",
+            ExtractionSource::Regex => "Regex",
+        }
+    }
+
+    pub fn all() -> Vec<Self> {
+        vec![
+            ExtractionSource::JsonPath,
+            ExtractionSource::Header,
+            ExtractionSource::Cookie,
+            ExtractionSource::Regex,
+        ]
+    }
+}
+
+/// Where an extracted value is written to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum VariableScope {
+    /// Kept in memory until the application exits
+    #[default]
+    Runtime,
+    /// Stored in the active environment and persisted to disk
+    Environment,
+}
+
+impl VariableScope {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            VariableScope::Runtime => "Runtime",
+            VariableScope::Environment => "Environment",
+        }
+    }
+
+    pub fn all() -> Vec<Self> {
+        vec![VariableScope::Runtime, VariableScope::Environment]
+    }
+}
+
+/// Rule that captures a value from a response into a variable
+///
+/// The expression depends on the source: a JSONPath such as `$.data.token`,
+/// a header or cookie name, or a regex whose first capture group is used.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExtractionRule {
+    pub variable: String,
+    pub source: ExtractionSource,
+    pub expression: String,
+    #[serde(default)]
+    pub scope: VariableScope,
+    pub enabled: bool,
+}
+
+impl ExtractionRule {
+    pub fn new(
+        variable: impl Into<String>,
+        source: ExtractionSource,
+        expression: impl Into<String>,
+    ) -> Self {
+        Self {
+            variable: variable.into(),
+            source,
+            expression: expression.into(),
+            scope: VariableScope::default(),
+            enabled: true,
+        }
+    }
+}
@@ -0,0 +1,279 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use super::run::RequestRunResult;
+
+/// What a flow condition looks at
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ConditionSource {
+    /// HTTP status code of the step's last response
+    #[default]
+    Status,
+    /// A variable, typically captured by an extraction rule
+    Variable,
+}
+
+impl ConditionSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ConditionSource::Status => "Status",
+            ConditionSource::Variable => "Variable",
+        }
+    }
+
+    pub fn all() -> Vec<ConditionSource> {
+        vec![ConditionSource::Status, ConditionSource::Variable]
+    }
+}
+
+/// Comparison used by a flow condition
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ConditionOperator {
+    #[default]
+    Equals,
+    NotEquals,
+    Contains,
+    GreaterThan,
+    LessThan,
+    Exists,
+}
+
+impl ConditionOperator {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ConditionOperator::Equals => "==",
+            ConditionOperator::NotEquals => "!=",
+            ConditionOperator::Contains => "contains",
+            ConditionOperator::GreaterThan => ">",
+            ConditionOperator::LessThan => "<",
+            ConditionOperator::Exists => "exists",
+        }
+    }
+
+    pub fn all() -> Vec<ConditionOperator> {
+        vec![
+            ConditionOperator::Equals,
+            ConditionOperator::NotEquals,
+            ConditionOperator::Contains,
+            ConditionOperator::GreaterThan,
+            ConditionOperator::LessThan,
+            ConditionOperator::Exists,
+        ]
+    }
+}
+
+/// A check on the status code or a variable, e.g. `status == 200` or `state == done`
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct FlowCondition {
+    pub source: ConditionSource,
+    /// Variable name, used when the source is `Variable`
+    #[serde(default)]
+    pub variable: String,
+    pub operator: ConditionOperator,
+    #[serde(default)]
+    pub value: String,
+}
+
+impl FlowCondition {
+    /// Evaluate against the last status code and the current variables
+    ///
+    /// Numeric comparisons fall back to string comparison when either side
+    /// is not a number.
+    pub fn evaluate(&self, status: Option<u16>, variables: &HashMap<String, String>) -> bool {
+        let actual = match self.source {
+            ConditionSource::Status => status.map(|s| s.to_string()),
+            ConditionSource::Variable => variables.get(self.variable.trim()).cloned(),
+        };
+        let expected = self.value.trim();
+
+        let Some(actual) = actual else {
+            return matches!(self.operator, ConditionOperator::NotEquals);
+        };
+
+        let numbers = actual
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .zip(expected.parse::<f64>().ok());
+
+        match self.operator {
+            ConditionOperator::Equals => actual == expected,
+            ConditionOperator::NotEquals => actual != expected,
+            ConditionOperator::Contains => actual.contains(expected),
+            ConditionOperator::GreaterThan => match numbers {
+                Some((a, e)) => a > e,
+                None => actual.as_str() > expected,
+            },
+            ConditionOperator::LessThan => match numbers {
+                Some((a, e)) => a < e,
+                None => actual.as_str() < expected,
+            },
+            ConditionOperator::Exists => !actual.is_empty(),
+        }
+    }
+
+    /// Human readable form for logs, e.g. `status == 200`
+    pub fn describe(&self) -> String {
+        let subject = match self.source {
+            ConditionSource::Status => "status",
+            ConditionSource::Variable => self.variable.trim(),
+        };
+        match self.operator {
+            ConditionOperator::Exists => format!("{} exists", subject),
+            operator => format!("{} {} {}", subject, operator.as_str(), self.value.trim()),
+        }
+    }
+}
+
+/// Where a flow continues after a step
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum FlowTarget {
+    /// The following step
+    #[default]
+    Next,
+    /// Jump to the step with this id
+    Step(Uuid),
+    /// Finish the flow successfully
+    End,
+}
+
+/// Re-run a step until its condition holds, e.g. to poll a job until it is done
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FlowLoop {
+    pub until: FlowCondition,
+    pub max_attempts: u32,
+    /// Pause between two attempts, in milliseconds
+    pub interval_ms: u64,
+}
+
+impl Default for FlowLoop {
+    fn default() -> Self {
+        Self {
+            until: FlowCondition::default(),
+            max_attempts: 10,
+            interval_ms: 1000,
+        }
+    }
+}
+
+/// Choose the next step based on a condition
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct FlowBranch {
+    pub condition: FlowCondition,
+    pub on_true: FlowTarget,
+    pub on_false: FlowTarget,
+}
+
+/// One step of a flow, running a saved request of the same collection
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FlowStep {
+    pub id: Uuid,
+    pub request_id: Uuid,
+    /// Pause before the step, in milliseconds
+    #[serde(default)]
+    pub wait_ms: u64,
+    #[serde(default)]
+    pub repeat: Option<FlowLoop>,
+    #[serde(default)]
+    pub branch: Option<FlowBranch>,
+}
+
+impl FlowStep {
+    pub fn new(request_id: Uuid) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            request_id,
+            wait_ms: 0,
+            repeat: None,
+            branch: None,
+        }
+    }
+}
+
+/// A sequence of requests with waits, loops and branches
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Flow {
+    pub id: Uuid,
+    pub name: String,
+    pub steps: Vec<FlowStep>,
+}
+
+impl Flow {
+    pub fn new(name: String) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            name,
+            steps: vec![],
+        }
+    }
+}
+
+/// One line of the step-by-step flow log
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FlowLogEntry {
+    /// Index of the step, starting at 0
+    pub step: usize,
+    pub message: String,
+    /// Set when the entry records a request execution
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<RequestRunResult>,
+}
+
+/// Outcome of a flow execution
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FlowReport {
+    pub name: String,
+    pub started_at: String,
+    pub duration_ms: u64,
+    pub log: Vec<FlowLogEntry>,
+    pub passed: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn condition(
+        source: ConditionSource,
+        operator: ConditionOperator,
+        value: &str,
+    ) -> FlowCondition {
+        FlowCondition {
+            source,
+            variable: "state".to_string(),
+            operator,
+            value: value.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_condition_evaluate() {
+        let mut variables = HashMap::new();
+        variables.insert("state".to_string(), "done".to_string());
+
+        let status_ok = condition(ConditionSource::Status, ConditionOperator::Equals, "200");
+        assert!(status_ok.evaluate(Some(200), &variables));
+        assert!(!status_ok.evaluate(Some(404), &variables));
+        assert!(!status_ok.evaluate(None, &variables));
+
+        let below_400 = condition(ConditionSource::Status, ConditionOperator::LessThan, "400");
+        assert!(below_400.evaluate(Some(302), &variables));
+
+        let done = condition(ConditionSource::Variable, ConditionOperator::Equals, "done");
+        assert!(done.evaluate(None, &variables));
+        assert!(
+            condition(ConditionSource::Variable, ConditionOperator::Exists, "")
+                .evaluate(None, &variables)
+        );
+
+        variables.clear();
+        assert!(!done.evaluate(None, &variables));
+        assert!(condition(
+            ConditionSource::Variable,
+            ConditionOperator::NotEquals,
+            "done"
+        )
+        .evaluate(None, &variables));
+    }
+}
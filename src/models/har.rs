@@ -0,0 +1,97 @@
+use std::collections::HashSet;
+
+use super::import::ImportIssue;
+use super::request::Request;
+
+/// Resource types of static assets, left out of imports by default
+const ASSET_TYPES: [&str; 6] = ["script", "stylesheet", "image", "font", "media", "manifest"];
+
+/// A request read from a HAR archive
+#[derive(Debug, Clone)]
+pub struct HarEntry {
+    pub request: Request,
+    /// Host the request was sent to
+    pub domain: String,
+    /// Browser resource type, e.g. `xhr`, `document` or `image`
+    pub resource_type: String,
+    /// Parts of the entry that could not be carried over
+    pub issues: Vec<String>,
+}
+
+/// Entries of a HAR archive, read before choosing which to import
+#[derive(Debug, Clone)]
+pub struct HarArchive {
+    pub name: String,
+    pub entries: Vec<HarEntry>,
+    /// Entries that could not be read at all
+    pub issues: Vec<ImportIssue>,
+}
+
+impl HarArchive {
+    /// Domains with their number of entries, most used first
+    pub fn domains(&self) -> Vec<(String, usize)> {
+        count(self.entries.iter().map(|e| e.domain.as_str()))
+    }
+
+    /// Resource types with their number of entries, most used first
+    pub fn resource_types(&self) -> Vec<(String, usize)> {
+        count(self.entries.iter().map(|e| e.resource_type.as_str()))
+    }
+}
+
+/// Domains and resource types left out of a HAR import
+#[derive(Debug, Clone, Default)]
+pub struct HarFilter {
+    pub excluded_domains: HashSet<String>,
+    pub excluded_types: HashSet<String>,
+}
+
+impl HarFilter {
+    /// Leave out static assets, unless the archive holds nothing else
+    pub fn for_archive(archive: &HarArchive) -> Self {
+        let has_requests = archive
+            .entries
+            .iter()
+            .any(|e| !ASSET_TYPES.contains(&e.resource_type.as_str()));
+        let mut filter = Self::default();
+        if has_requests {
+            for (resource_type, _) in archive.resource_types() {
+                if ASSET_TYPES.contains(&resource_type.as_str()) {
+                    filter.excluded_types.insert(resource_type);
+                }
+            }
+        }
+        filter
+    }
+
+    pub fn includes(&self, entry: &HarEntry) -> bool {
+        !self.excluded_domains.contains(&entry.domain)
+            && !self.excluded_types.contains(&entry.resource_type)
+    }
+
+    pub fn toggle_domain(&mut self, domain: &str) {
+        toggle(&mut self.excluded_domains, domain);
+    }
+
+    pub fn toggle_type(&mut self, resource_type: &str) {
+        toggle(&mut self.excluded_types, resource_type);
+    }
+}
+
+fn toggle(set: &mut HashSet<String>, value: &str) {
+    if !set.remove(value) {
+        set.insert(value.to_string());
+    }
+}
+
+fn count<'a>(values: impl Iterator<Item = &'a str>) -> Vec<(String, usize)> {
+    let mut counts: Vec<(String, usize)> = vec![];
+    for value in values {
+        match counts.iter_mut().find(|(v, _)| v == value) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((value.to_string(), 1)),
+        }
+    }
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    counts
+}
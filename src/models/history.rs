@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::request::Request;
+use super::response::Response;
+
+/// A request as it was sent, after variables were resolved, and its outcome
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub id: Uuid,
+    /// RFC 3339 time the request was sent
+    pub sent_at: String,
+    pub request: Request,
+    pub response: Option<Response>,
+    /// Transport error when no response arrived
+    pub error: Option<String>,
+    /// Whether the response body was left out of the stored entry for its size
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub body_omitted: bool,
+}
@@ -0,0 +1,71 @@
+use std::collections::HashMap;
+
+use super::collection::Collection;
+use super::environment::Environment;
+use super::har::HarArchive;
+
+/// Something in an imported file that could not be carried over
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportIssue {
+    /// Where it was found, e.g. `Users / Get user`
+    pub location: String,
+    pub message: String,
+}
+
+/// Summary of an import, shown to the user afterwards
+#[derive(Debug, Clone, Default)]
+pub struct ImportReport {
+    /// Kind of file that was imported, e.g. `Postman collection`
+    pub source: String,
+    /// Name of the imported collection or environment
+    pub name: String,
+    pub requests: usize,
+    /// Existing requests updated by a re-import
+    pub updated: usize,
+    pub folders: usize,
+    pub variables: usize,
+    pub issues: Vec<ImportIssue>,
+}
+
+impl ImportReport {
+    pub fn new(source: impl Into<String>) -> Self {
+        Self {
+            source: source.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Record something that was skipped or only partly imported
+    pub fn issue(&mut self, location: impl Into<String>, message: impl Into<String>) {
+        self.issues.push(ImportIssue {
+            location: location.into(),
+            message: message.into(),
+        });
+    }
+}
+
+/// Everything produced by importing one file
+#[derive(Debug, Clone)]
+pub struct ImportResult {
+    pub collection: Option<Collection>,
+    /// Variables to add to an environment
+    pub variables: HashMap<String, String>,
+    /// Environment named by the file; the current one is used otherwise
+    pub environment: Option<Environment>,
+    /// Replace existing values instead of only filling in missing ones
+    pub overwrite: bool,
+    /// Update a collection of the same name instead of adding another
+    pub merge: bool,
+    pub report: ImportReport,
+}
+
+/// A file read for importing
+// Created once per import, so boxing the result would only add indirection
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Clone)]
+pub enum ImportFile {
+    /// Imported as a whole
+    Ready(ImportResult),
+    /// Entries are chosen before importing
+    Har(HarArchive),
+}
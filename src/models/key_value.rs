@@ -15,6 +15,9 @@ pub struct KeyValue {
     pub enabled: bool,
     #[serde(default)]
     pub param_type: FormDataParamType,
+    /// Kept in the local secret store, with only a reference in saved files
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub secret: bool,
 }
 
 impl KeyValue {
@@ -24,6 +27,7 @@ impl KeyValue {
             value: value.into(),
             enabled: true,
             param_type: FormDataParamType::Text,
+            secret: false,
         }
     }
 }
@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+
+/// Settings of a running mock server
+#[derive(Debug, Clone, PartialEq)]
+pub struct MockServerConfig {
+    pub port: u16,
+    /// Delay added before every response, in milliseconds
+    pub latency_ms: u64,
+}
+
+impl Default for MockServerConfig {
+    fn default() -> Self {
+        Self {
+            port: 3030,
+            latency_ms: 0,
+        }
+    }
+}
+
+/// One request received by the mock server
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MockHit {
+    pub time: String,
+    pub method: String,
+    pub path: String,
+    pub status: u16,
+    /// Name of the saved request that answered, if any matched
+    pub matched: Option<String>,
+}
@@ -1,23 +1,58 @@
 mod ai_config;
+mod auth;
+mod blast;
 mod body;
+mod codegen;
 mod collection;
 mod environment;
+mod example;
+mod export;
+mod extraction;
+mod flow;
+mod har;
+mod history;
 mod http_method;
+mod import;
 mod key_value;
+mod mock;
+mod reload;
 mod request;
 mod request_tab;
 mod response;
 mod response_tab;
+mod run;
+mod schema;
 mod shortcuts;
+mod snapshot;
 
 pub use ai_config::{AiConfig, AiEngine};
+pub use auth::{AuthScheme, Credential};
+pub use blast::{
+    BlastConfig, BlastExportFormat, BlastLimit, BlastReport, BlastSample, BlastSecond, BlastSummary,
+};
 pub use body::{BodyFormat, BodyType};
-pub use collection::{Collection, CollectionItem, Folder};
-pub use environment::{Environment, EnvironmentOption};
+pub use codegen::CodeLanguage;
+pub use collection::{Collection, CollectionItem, Folder, HttpFileLink};
+pub use environment::{Environment, EnvironmentOption, EnvironmentVariables};
+pub use example::ResponseExample;
+pub use export::CollectionExportFormat;
+pub use extraction::{ExtractionRule, ExtractionSource, VariableScope};
+pub use flow::{
+    ConditionOperator, ConditionSource, Flow, FlowBranch, FlowCondition, FlowLogEntry, FlowLoop,
+    FlowReport, FlowStep, FlowTarget,
+};
+pub use har::{HarArchive, HarEntry, HarFilter};
+pub use history::HistoryEntry;
 pub use http_method::HttpMethod;
+pub use import::{ImportFile, ImportIssue, ImportReport, ImportResult};
 pub use key_value::{FormDataParamType, KeyValue};
-pub use request::Request;
+pub use mock::{MockHit, MockServerConfig};
+pub use reload::{DiffLine, ReloadConflict};
+pub use request::{ImportBaseline, Request};
 pub use request_tab::RequestTab;
 pub use response::Response;
 pub use response_tab::{BodyViewMode, ResponseTab};
+pub use run::{AssertionResult, IterationResult, RequestRunResult, RunOptions, RunReport};
+pub use schema::{ResponseSchema, SchemaSource, SchemaViolation};
 pub use shortcuts::{ShortcutAction, ShortcutRegistry};
+pub use snapshot::{DiffEntry, DiffKind, SnapshotDiff};
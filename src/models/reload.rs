@@ -0,0 +1,24 @@
+use uuid::Uuid;
+
+use super::request::Request;
+
+/// A request with unsaved edits that was also changed on disk
+#[derive(Debug, Clone)]
+pub struct ReloadConflict {
+    pub request_id: Uuid,
+    pub name: String,
+    /// The request as edited in the app
+    pub mine: Request,
+    /// The request as read from disk, `None` when its file was deleted
+    pub theirs: Option<Request>,
+}
+
+/// One line of a diff between two versions of a request
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffLine {
+    Both(String),
+    /// Only in the version edited in the app
+    Mine(String),
+    /// Only in the version read from disk
+    Theirs(String),
+}
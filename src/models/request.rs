@@ -1,9 +1,13 @@
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use super::auth::{AuthScheme, Credential};
 use super::body::BodyType;
+use super::example::ResponseExample;
+use super::extraction::ExtractionRule;
 use super::http_method::HttpMethod;
 use super::key_value::KeyValue;
+use super::schema::ResponseSchema;
 
 /// HTTP Request
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -17,6 +21,56 @@ pub struct Request {
     pub cookies: Vec<KeyValue>,
     pub auth: Vec<KeyValue>,
     pub body: BodyType,
+    /// Free-form notes, e.g. carried over from an imported collection
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub description: String,
+    /// Rules that capture response values into variables
+    #[serde(default)]
+    pub extractions: Vec<ExtractionRule>,
+    /// Pinned example response, served by the mock server
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub example: Option<ResponseExample>,
+    /// JSON Schema that responses are validated against
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema: Option<ResponseSchema>,
+    /// Skip TLS certificate verification, like `curl -k`
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub insecure: bool,
+    /// What the document this request was imported from last said about it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub imported: Option<ImportBaseline>,
+}
+
+/// Documentation of an imported request as of its last import
+///
+/// Importing the document again compares against it, so that only what the
+/// document changed is applied and the user's own edits are kept.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImportBaseline {
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub schema: Option<ResponseSchema>,
+    /// Names of the documented query parameters, headers and cookies
+    #[serde(default)]
+    pub query_params: Vec<String>,
+    #[serde(default)]
+    pub headers: Vec<String>,
+    #[serde(default)]
+    pub cookies: Vec<String>,
+}
+
+impl ImportBaseline {
+    pub fn of(request: &Request) -> Self {
+        let keys = |items: &[KeyValue]| items.iter().map(|kv| kv.key.clone()).collect();
+        Self {
+            description: request.description.clone(),
+            schema: request.schema.clone(),
+            query_params: keys(&request.query_params),
+            headers: keys(&request.headers),
+            cookies: keys(&request.cookies),
+        }
+    }
 }
 
 impl Default for Request {
@@ -31,6 +85,53 @@ impl Default for Request {
             cookies: vec![],
             auth: vec![],
             body: BodyType::None,
+            description: String::new(),
+            extractions: vec![],
+            example: None,
+            schema: None,
+            insecure: false,
+            imported: None,
         }
     }
 }
+
+impl Request {
+    /// Copy with the auth fields and cookies turned into the headers and
+    /// query parameters they are sent as
+    ///
+    /// Each is left out when the request already sets the same header or
+    /// parameter itself.
+    pub fn with_credentials(&self) -> Request {
+        let mut request = self.clone();
+        match AuthScheme::from_fields(&request.auth).and_then(|auth| auth.credential()) {
+            Some(Credential::Header(key, value)) if !is_set(&request.headers, &key) => {
+                request.headers.push(KeyValue::new(key, value));
+            }
+            Some(Credential::Query(key, value)) if !is_set(&request.query_params, &key) => {
+                request.query_params.push(KeyValue::new(key, value));
+            }
+            _ => {}
+        }
+
+        // Cookies go out as one header
+        let cookies: Vec<String> = request
+            .cookies
+            .iter()
+            .filter(|cookie| cookie.enabled && !cookie.key.is_empty())
+            .map(|cookie| format!("{}={}", cookie.key, cookie.value))
+            .collect();
+        if !cookies.is_empty() && !is_set(&request.headers, "cookie") {
+            request
+                .headers
+                .push(KeyValue::new("Cookie", cookies.join("; ")));
+        }
+        request
+    }
+}
+
+/// Whether an enabled row sets `key`
+fn is_set(items: &[KeyValue], key: &str) -> bool {
+    items
+        .iter()
+        .any(|kv| kv.enabled && kv.key.eq_ignore_ascii_case(key))
+}
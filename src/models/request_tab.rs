@@ -6,6 +6,8 @@ pub enum RequestTab {
     Headers,
     Cookies,
     Auth,
+    Extract,
+    Schema,
 }
 
 impl RequestTab {
@@ -16,6 +18,8 @@ impl RequestTab {
             RequestTab::Headers => "Headers",
             RequestTab::Cookies => "Cookies",
             RequestTab::Auth => "Auth",
+            RequestTab::Extract => "Extract",
+            RequestTab::Schema => "Schema",
         }
     }
 
@@ -26,6 +30,8 @@ impl RequestTab {
             RequestTab::Headers,
             RequestTab::Cookies,
             RequestTab::Auth,
+            RequestTab::Extract,
+            RequestTab::Schema,
         ]
     }
 }
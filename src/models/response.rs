@@ -1,8 +1,9 @@
 use super::KeyValue;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 /// HTTP Response
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Response {
     pub status: u16,
     pub status_text: String,
@@ -4,6 +4,8 @@ pub enum ResponseTab {
     Body,
     Cookies,
     Headers,
+    Schema,
+    Diff,
 }
 
 impl ResponseTab {
@@ -12,6 +14,8 @@ impl ResponseTab {
             ResponseTab::Body => "Body",
             ResponseTab::Cookies => "Cookies",
             ResponseTab::Headers => "Headers",
+            ResponseTab::Schema => "Schema",
+            ResponseTab::Diff => "Diff",
         }
     }
 
@@ -20,6 +24,8 @@ impl ResponseTab {
             ResponseTab::Body,
             ResponseTab::Cookies,
             ResponseTab::Headers,
+            ResponseTab::Schema,
+            ResponseTab::Diff,
         ]
     }
 }
@@ -0,0 +1,143 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use super::http_method::HttpMethod;
+
+/// Options for running every request of a folder or collection
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunOptions {
+    /// How many times the whole sequence is run
+    pub iterations: u32,
+    /// Pause between two requests, in milliseconds
+    pub delay_ms: u64,
+    /// Abort the run as soon as one request fails
+    pub stop_on_failure: bool,
+    /// Data file driving the iterations, one row per iteration
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data_file: Option<String>,
+}
+
+impl Default for RunOptions {
+    fn default() -> Self {
+        Self {
+            iterations: 1,
+            delay_ms: 0,
+            stop_on_failure: false,
+            data_file: None,
+        }
+    }
+}
+
+/// Outcome of a single check made on a response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssertionResult {
+    pub name: String,
+    pub passed: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+impl AssertionResult {
+    pub fn pass(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            passed: true,
+            message: None,
+        }
+    }
+
+    pub fn fail(name: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            passed: false,
+            message: Some(message.into()),
+        }
+    }
+}
+
+/// Result of one request execution within a run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RequestRunResult {
+    pub iteration: u32,
+    pub request_id: Uuid,
+    pub name: String,
+    pub method: HttpMethod,
+    /// URL after variable substitution
+    pub url: String,
+    pub status: Option<u16>,
+    pub time_ms: u64,
+    pub assertions: Vec<AssertionResult>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl RequestRunResult {
+    pub fn passed(&self) -> bool {
+        self.error.is_none() && self.assertions.iter().all(|a| a.passed)
+    }
+}
+
+/// Report of a folder or collection run, saveable as JSON
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunReport {
+    pub name: String,
+    pub started_at: String,
+    pub duration_ms: u64,
+    pub options: RunOptions,
+    pub results: Vec<RequestRunResult>,
+    /// True when the run stopped early because of stop-on-failure
+    pub aborted: bool,
+    /// Rows of the data file, indexed by iteration - 1
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub data_rows: Vec<HashMap<String, String>>,
+}
+
+impl RunReport {
+    pub fn passed_count(&self) -> usize {
+        self.results.iter().filter(|r| r.passed()).count()
+    }
+
+    pub fn failed_count(&self) -> usize {
+        self.results.len() - self.passed_count()
+    }
+
+    /// Results grouped by iteration, in execution order
+    pub fn iterations(&self) -> Vec<IterationResult<'_>> {
+        let mut groups: Vec<IterationResult<'_>> = Vec::new();
+        for result in &self.results {
+            match groups.last_mut() {
+                Some(group) if group.iteration == result.iteration => group.results.push(result),
+                _ => groups.push(IterationResult {
+                    iteration: result.iteration,
+                    data: self.data_rows.get(result.iteration as usize - 1),
+                    results: vec![result],
+                }),
+            }
+        }
+        groups
+    }
+
+    /// Iteration numbers with at least one failed request
+    pub fn failed_iterations(&self) -> Vec<u32> {
+        self.iterations()
+            .into_iter()
+            .filter(|group| !group.passed())
+            .map(|group| group.iteration)
+            .collect()
+    }
+}
+
+/// Results of one iteration, with the data row that drove it
+#[derive(Debug, Clone)]
+pub struct IterationResult<'a> {
+    pub iteration: u32,
+    pub data: Option<&'a HashMap<String, String>>,
+    pub results: Vec<&'a RequestRunResult>,
+}
+
+impl IterationResult<'_> {
+    pub fn passed(&self) -> bool {
+        self.results.iter().all(|r| r.passed())
+    }
+}
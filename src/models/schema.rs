@@ -0,0 +1,45 @@
+use serde::{Deserialize, Serialize};
+
+/// Where the JSON Schema of a request comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum SchemaSource {
+    /// Schema document stored on the request
+    #[default]
+    Inline,
+    /// Path to a schema file, read on every validation
+    File,
+}
+
+impl SchemaSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SchemaSource::Inline => "Inline",
+            SchemaSource::File => "File",
+        }
+    }
+
+    pub fn all() -> Vec<SchemaSource> {
+        vec![SchemaSource::Inline, SchemaSource::File]
+    }
+}
+
+/// JSON Schema that every response body of a request must satisfy
+///
+/// Both the inline document and the file path are kept so switching the
+/// source in the editor does not lose either.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResponseSchema {
+    pub source: SchemaSource,
+    #[serde(default)]
+    pub inline: String,
+    #[serde(default)]
+    pub file: String,
+}
+
+/// A schema violation, located by the JSON pointer of the offending value
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SchemaViolation {
+    /// JSON pointer into the response body, empty for the root
+    pub pointer: String,
+    pub message: String,
+}
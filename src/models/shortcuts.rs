@@ -18,6 +18,10 @@ pub enum ShortcutAction {
     NewTab,
     ReopenClosedTab,
 
+    // Edit operations
+    Undo,
+    Redo,
+
     // Collection operations
     NewCollection,
     NewFolder,
@@ -82,6 +86,13 @@ impl ShortcutAction {
                 Modifiers::CTRL | Modifiers::SHIFT,
             ),
 
+            // Edit operations
+            ShortcutAction::Undo => KeyShortcut::new(Key::Character("z".into()), Modifiers::CTRL),
+            ShortcutAction::Redo => KeyShortcut::new(
+                Key::Character("z".into()),
+                Modifiers::CTRL | Modifiers::SHIFT,
+            ),
+
             // Collection operations
             ShortcutAction::NewCollection => KeyShortcut::new(
                 Key::Character("n".into()),
@@ -153,6 +164,8 @@ impl ShortcutAction {
             ShortcutAction::PreviousTab => "Previous Tab",
             ShortcutAction::NewTab => "New Tab",
             ShortcutAction::ReopenClosedTab => "Reopen Closed Tab",
+            ShortcutAction::Undo => "Undo",
+            ShortcutAction::Redo => "Redo",
             ShortcutAction::NewCollection => "New Collection",
             ShortcutAction::NewFolder => "New Folder",
             ShortcutAction::DeleteItem => "Delete Item",
@@ -184,6 +197,8 @@ impl ShortcutAction {
             ShortcutAction::PreviousTab => "上一个标签",
             ShortcutAction::NewTab => "新建标签",
             ShortcutAction::ReopenClosedTab => "重新打开已关闭的标签",
+            ShortcutAction::Undo => "撤销",
+            ShortcutAction::Redo => "重做",
             ShortcutAction::NewCollection => "新建集合",
             ShortcutAction::NewFolder => "新建文件夹",
             ShortcutAction::DeleteItem => "删除项目",
@@ -217,6 +232,8 @@ impl ShortcutAction {
             | ShortcutAction::NewTab
             | ShortcutAction::ReopenClosedTab => ShortcutCategory::Tabs,
 
+            ShortcutAction::Undo | ShortcutAction::Redo => ShortcutCategory::Edit,
+
             ShortcutAction::NewCollection
             | ShortcutAction::NewFolder
             | ShortcutAction::DeleteItem
@@ -253,6 +270,9 @@ impl ShortcutAction {
             ShortcutAction::PreviousTab,
             ShortcutAction::NewTab,
             ShortcutAction::ReopenClosedTab,
+            // Edit operations
+            ShortcutAction::Undo,
+            ShortcutAction::Redo,
             // Collection operations
             ShortcutAction::NewCollection,
             ShortcutAction::NewFolder,
@@ -281,6 +301,7 @@ impl ShortcutAction {
 pub enum ShortcutCategory {
     Request,
     Tabs,
+    Edit,
     Collection,
     View,
     Environment,
@@ -292,6 +313,7 @@ impl ShortcutCategory {
         match self {
             ShortcutCategory::Request => "Request Operations",
             ShortcutCategory::Tabs => "Tab Management",
+            ShortcutCategory::Edit => "Edit Operations",
             ShortcutCategory::Collection => "Collection Management",
             ShortcutCategory::View => "View Operations",
             ShortcutCategory::Environment => "Environment Management",
@@ -303,6 +325,7 @@ impl ShortcutCategory {
         match self {
             ShortcutCategory::Request => "请求操作",
             ShortcutCategory::Tabs => "标签管理",
+            ShortcutCategory::Edit => "编辑操作",
             ShortcutCategory::Collection => "集合管理",
             ShortcutCategory::View => "视图操作",
             ShortcutCategory::Environment => "环境管理",
@@ -513,6 +536,7 @@ impl ShortcutRegistry {
         let categories = vec![
             ShortcutCategory::Request,
             ShortcutCategory::Tabs,
+            ShortcutCategory::Edit,
             ShortcutCategory::Collection,
             ShortcutCategory::View,
             ShortcutCategory::Environment,
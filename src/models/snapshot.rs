@@ -0,0 +1,46 @@
+use serde::{Deserialize, Serialize};
+
+/// How a value differs from the pinned example
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DiffKind {
+    /// Present in the response but not in the example
+    Added,
+    /// Present in the example but missing from the response
+    Removed,
+    Changed,
+}
+
+/// One difference between the example and a response
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DiffEntry {
+    /// JSON pointer of the value, empty for the whole body
+    pub pointer: String,
+    pub kind: DiffKind,
+    /// Compact JSON of the example value
+    pub expected: Option<String>,
+    /// Compact JSON of the response value
+    pub actual: Option<String>,
+}
+
+/// Result of comparing a response with the pinned example of its request
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotDiff {
+    /// `(expected, actual)` when the status code differs
+    pub status: Option<(u16, u16)>,
+    /// `(expected, actual)` when the Content-Type differs
+    pub content_type: Option<(String, String)>,
+    pub body: Vec<DiffEntry>,
+    /// Number of differences skipped by ignore rules
+    pub ignored: usize,
+}
+
+impl SnapshotDiff {
+    pub fn is_match(&self) -> bool {
+        self.status.is_none() && self.content_type.is_none() && self.body.is_empty()
+    }
+
+    /// Total number of differences, ignored ones excluded
+    pub fn count(&self) -> usize {
+        self.status.iter().count() + self.content_type.iter().count() + self.body.len()
+    }
+}
@@ -0,0 +1,541 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use tracing::{debug, info};
+
+use crate::http_client;
+use crate::models::{
+    AssertionResult, Flow, FlowLogEntry, FlowReport, FlowTarget, Request, RequestRunResult,
+    Response, RunOptions, RunReport,
+};
+use crate::utils::{extractor, json_schema, snapshot, variables};
+
+/// Run requests in order, honouring the iteration count, delay and stop-on-failure
+///
+/// Values captured by extraction rules are visible to the following requests of
+/// the same iteration, so login flows can be chained without touching the
+/// environment. When `data_rows` is not empty each row drives one iteration and
+/// its columns override the variables of that iteration; the iteration count is
+/// then ignored. Every iteration starts again from `base`. Schema files
+/// are read relative to `save_dir`.
+pub async fn run_requests(
+    name: String,
+    requests: Vec<Request>,
+    base: HashMap<String, String>,
+    options: RunOptions,
+    data_rows: Vec<HashMap<String, String>>,
+    save_dir: PathBuf,
+) -> RunReport {
+    let started_at = chrono::Local::now().to_rfc3339();
+    let start = Instant::now();
+    let mut results = Vec::new();
+    let mut aborted = false;
+
+    let iterations = if data_rows.is_empty() {
+        options.iterations.max(1)
+    } else {
+        data_rows.len() as u32
+    };
+
+    info!(
+        "Running '{}': {} requests x {} iterations",
+        name,
+        requests.len(),
+        iterations
+    );
+
+    'iterations: for iteration in 1..=iterations {
+        // A column missing from this row must not keep the previous row's value
+        let mut variables = base.clone();
+        if let Some(row) = data_rows.get(iteration as usize - 1) {
+            variables.extend(row.iter().map(|(k, v)| (k.clone(), v.clone())));
+        }
+
+        for (idx, request) in requests.iter().enumerate() {
+            if (iteration > 1 || idx > 0) && options.delay_ms > 0 {
+                tokio::time::sleep(Duration::from_millis(options.delay_ms)).await;
+            }
+
+            let result = run_single(iteration, request, &mut variables, &save_dir).await;
+            let passed = result.passed();
+            results.push(result);
+
+            if !passed && options.stop_on_failure {
+                info!("Stopping run '{}' after failed request", name);
+                aborted = true;
+                break 'iterations;
+            }
+        }
+    }
+
+    RunReport {
+        name,
+        started_at,
+        duration_ms: start.elapsed().as_millis() as u64,
+        options,
+        results,
+        aborted,
+        data_rows,
+    }
+}
+
+/// Upper bound on executed steps, so a branch cycle cannot run forever
+const MAX_FLOW_STEPS: usize = 1000;
+
+/// Execute a flow step by step, following its waits, loops and branches
+///
+/// `requests` are the saved requests the steps refer to by id. A step without
+/// a branch ends the flow as failed when its request fails; with a branch the
+/// branch decides where to go. Schema files are read relative to `save_dir`.
+pub async fn run_flow(
+    flow: Flow,
+    requests: Vec<Request>,
+    mut variables: HashMap<String, String>,
+    save_dir: PathBuf,
+) -> FlowReport {
+    let started_at = chrono::Local::now().to_rfc3339();
+    let start = Instant::now();
+    let requests: HashMap<_, _> = requests.into_iter().map(|r| (r.id, r)).collect();
+    let mut log = Vec::new();
+    let mut passed = true;
+    let mut index = 0;
+    let mut executed = 0;
+
+    info!(
+        "Running flow '{}' with {} steps",
+        flow.name,
+        flow.steps.len()
+    );
+
+    let note = |log: &mut Vec<FlowLogEntry>, step: usize, message: String| {
+        debug!("Flow step {}: {}", step + 1, message);
+        log.push(FlowLogEntry {
+            step,
+            message,
+            result: None,
+        });
+    };
+
+    while let Some(step) = flow.steps.get(index) {
+        executed += 1;
+        if executed > MAX_FLOW_STEPS {
+            note(
+                &mut log,
+                index,
+                format!("Stopped after {} steps", MAX_FLOW_STEPS),
+            );
+            passed = false;
+            break;
+        }
+
+        let Some(request) = requests.get(&step.request_id) else {
+            note(&mut log, index, "Request not found".to_string());
+            passed = false;
+            break;
+        };
+
+        if step.wait_ms > 0 {
+            note(&mut log, index, format!("Waiting {} ms", step.wait_ms));
+            tokio::time::sleep(Duration::from_millis(step.wait_ms)).await;
+        }
+
+        // Run once, or poll until the loop condition holds
+        let mut attempt = 1;
+        let result = loop {
+            let result = run_single(attempt, request, &mut variables, &save_dir).await;
+            log.push(FlowLogEntry {
+                step: index,
+                message: format!("{} {}", request.method.as_str(), request.name),
+                result: Some(result.clone()),
+            });
+
+            match &step.repeat {
+                Some(repeat) if !repeat.until.evaluate(result.status, &variables) => {
+                    if attempt >= repeat.max_attempts.max(1) {
+                        note(
+                            &mut log,
+                            index,
+                            format!(
+                                "Condition {} not met after {} attempts",
+                                repeat.until.describe(),
+                                attempt
+                            ),
+                        );
+                        break None;
+                    }
+                    attempt += 1;
+                    tokio::time::sleep(Duration::from_millis(repeat.interval_ms)).await;
+                }
+                _ => break Some(result),
+            }
+        };
+
+        let Some(result) = result else {
+            passed = false;
+            break;
+        };
+
+        let target = match &step.branch {
+            Some(branch) => {
+                let holds = branch.condition.evaluate(result.status, &variables);
+                note(
+                    &mut log,
+                    index,
+                    format!("Condition {} is {}", branch.condition.describe(), holds),
+                );
+                if holds {
+                    branch.on_true
+                } else {
+                    branch.on_false
+                }
+            }
+            None if !result.passed() => {
+                passed = false;
+                break;
+            }
+            None => FlowTarget::Next,
+        };
+
+        index = match target {
+            FlowTarget::Next => index + 1,
+            FlowTarget::End => break,
+            FlowTarget::Step(id) => match flow.steps.iter().position(|s| s.id == id) {
+                Some(position) => {
+                    note(&mut log, index, format!("Going to step {}", position + 1));
+                    position
+                }
+                None => {
+                    note(&mut log, index, "Target step not found".to_string());
+                    passed = false;
+                    break;
+                }
+            },
+        };
+    }
+
+    FlowReport {
+        name: flow.name,
+        started_at,
+        duration_ms: start.elapsed().as_millis() as u64,
+        log,
+        passed,
+    }
+}
+
+/// Execute one request of a run and evaluate its checks
+async fn run_single(
+    iteration: u32,
+    request: &Request,
+    variables: &mut HashMap<String, String>,
+    save_dir: &Path,
+) -> RequestRunResult {
+    let resolved = variables::resolve_request(request, variables);
+    debug!("Run request: {} {}", resolved.method.as_str(), resolved.url);
+
+    let mut result = RequestRunResult {
+        iteration,
+        request_id: request.id,
+        name: request.name.clone(),
+        method: request.method,
+        url: resolved.url.clone(),
+        status: None,
+        time_ms: 0,
+        assertions: vec![],
+        error: None,
+    };
+
+    match http_client::execute_request(&resolved).await {
+        Ok(response) => {
+            result.status = Some(response.status);
+            result.time_ms = response.time_ms as u64;
+            result.assertions = check_response(request, &response, variables, save_dir);
+        }
+        Err(e) => {
+            result.error = Some(e.to_string());
+        }
+    }
+
+    result
+}
+
+/// Built-in checks: a successful status and every extraction rule matching
+fn check_response(
+    request: &Request,
+    response: &Response,
+    variables: &mut HashMap<String, String>,
+    save_dir: &Path,
+) -> Vec<AssertionResult> {
+    let mut assertions = Vec::new();
+
+    let status_check = "Status code is successful";
+    if (200..400).contains(&response.status) {
+        assertions.push(AssertionResult::pass(status_check));
+    } else {
+        assertions.push(AssertionResult::fail(
+            status_check,
+            format!("Got {}", response.status_text),
+        ));
+    }
+
+    // Contract drift fails the request even when the status looks fine
+    if let Some(schema) = &request.schema {
+        let check = "Response matches schema";
+        match json_schema::check(schema, &response.body, save_dir) {
+            Ok(violations) if violations.is_empty() => {
+                assertions.push(AssertionResult::pass(check));
+            }
+            Ok(violations) => assertions.push(AssertionResult::fail(
+                check,
+                json_schema::summarize(&violations),
+            )),
+            Err(e) => assertions.push(AssertionResult::fail(check, e)),
+        }
+    }
+
+    if let Some(example) = &request.example {
+        let check = "Response matches example";
+        let diff = snapshot::compare(example, response);
+        if diff.is_match() {
+            assertions.push(AssertionResult::pass(check));
+        } else {
+            assertions.push(AssertionResult::fail(check, snapshot::summarize(&diff)));
+        }
+    }
+
+    for (rule, extracted) in extractor::extract_all(&request.extractions, response) {
+        let check = format!("Extract {}", rule.variable.trim());
+        match extracted {
+            Ok(value) => {
+                variables.insert(rule.variable.trim().to_string(), value);
+                assertions.push(AssertionResult::pass(check));
+            }
+            Err(e) => assertions.push(AssertionResult::fail(check, e)),
+        }
+    }
+
+    assertions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{
+        ConditionOperator, ConditionSource, ExtractionRule, ExtractionSource, FlowBranch,
+        FlowCondition, FlowLoop, FlowStep, HttpMethod, ResponseExample, ResponseSchema,
+    };
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    /// Serve `/job` as pending for the first two hits, then done; anything else is 404
+    async fn job_server() -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let hits = Arc::new(AtomicUsize::new(0));
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let hits = hits.clone();
+                tokio::spawn(async move {
+                    let mut buf = [0u8; 1024];
+                    let n = socket.read(&mut buf).await.unwrap_or(0);
+                    let head = String::from_utf8_lossy(&buf[..n]).to_string();
+                    let (status, body) = if head.starts_with("GET /job ") {
+                        let state = if hits.fetch_add(1, Ordering::SeqCst) < 2 {
+                            "pending"
+                        } else {
+                            "done"
+                        };
+                        ("200 OK", format!(r#"{{"state":"{}"}}"#, state))
+                    } else {
+                        ("404 Not Found", "{}".to_string())
+                    };
+                    let response = format!(
+                        "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        status,
+                        body.len(),
+                        body
+                    );
+                    let _ = socket.write_all(response.as_bytes()).await;
+                });
+            }
+        });
+        format!("http://{}", addr)
+    }
+
+    fn request(name: &str, url: String) -> Request {
+        Request {
+            name: name.to_string(),
+            method: HttpMethod::GET,
+            url,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_run_requests_isolates_data_rows() {
+        let base = job_server().await;
+        let search = request(
+            "Search",
+            format!("{}/job?user={{{{user}}}}&tag={{{{tag}}}}", base),
+        );
+        let rows = vec![
+            HashMap::from([
+                ("user".to_string(), "ann".to_string()),
+                ("tag".to_string(), "red".to_string()),
+            ]),
+            HashMap::from([("user".to_string(), "bob".to_string())]),
+        ];
+
+        let report = run_requests(
+            "Rows".to_string(),
+            vec![search],
+            HashMap::new(),
+            RunOptions::default(),
+            rows,
+            PathBuf::new(),
+        )
+        .await;
+        let urls: Vec<&str> = report.results.iter().map(|r| r.url.as_str()).collect();
+        assert_eq!(
+            urls,
+            [
+                format!("{}/job?user=ann&tag=red", base),
+                format!("{}/job?user=bob&tag={{{{tag}}}}", base),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_run_flow_polls_and_branches() {
+        let base = job_server().await;
+
+        let mut poll = request("Poll job", format!("{}/job", base));
+        poll.extractions = vec![ExtractionRule::new(
+            "state",
+            ExtractionSource::JsonPath,
+            "$.state",
+        )];
+        let missing = request("Missing", format!("{}/missing", base));
+
+        let mut first = FlowStep::new(poll.id);
+        first.repeat = Some(FlowLoop {
+            until: FlowCondition {
+                source: ConditionSource::Variable,
+                variable: "state".to_string(),
+                operator: ConditionOperator::Equals,
+                value: "done".to_string(),
+            },
+            max_attempts: 5,
+            interval_ms: 0,
+        });
+        first.branch = Some(FlowBranch {
+            condition: FlowCondition {
+                source: ConditionSource::Status,
+                operator: ConditionOperator::Equals,
+                value: "200".to_string(),
+                ..Default::default()
+            },
+            on_true: FlowTarget::End,
+            on_false: FlowTarget::Next,
+        });
+        let second = FlowStep::new(missing.id);
+
+        let mut flow = Flow::new("Job".to_string());
+        flow.steps = vec![first, second];
+
+        let report = run_flow(flow, vec![poll, missing], HashMap::new(), PathBuf::new()).await;
+
+        assert!(report.passed);
+        let executed: Vec<_> = report
+            .log
+            .iter()
+            .filter_map(|e| e.result.as_ref())
+            .collect();
+        assert_eq!(executed.len(), 3);
+        assert!(executed.iter().all(|r| r.name == "Poll job"));
+        assert_eq!(executed[2].iteration, 3);
+    }
+
+    #[test]
+    fn test_check_response_validates_schema() {
+        let mut request = request("Get user", "http://localhost/user".to_string());
+        request.schema = Some(ResponseSchema {
+            inline: r#"{"type":"object","properties":{"id":{"type":"integer"}}}"#.to_string(),
+            ..Default::default()
+        });
+        let response = |body: &str| {
+            Response::new(
+                200,
+                "200 OK".to_string(),
+                HashMap::new(),
+                vec![],
+                body.to_string(),
+                1,
+            )
+        };
+
+        let valid = check_response(
+            &request,
+            &response(r#"{"id":1}"#),
+            &mut HashMap::new(),
+            Path::new(""),
+        );
+        assert!(valid.iter().all(|a| a.passed));
+
+        let drifted = check_response(
+            &request,
+            &response(r#"{"id":"1"}"#),
+            &mut HashMap::new(),
+            Path::new(""),
+        );
+        let schema_check = drifted
+            .iter()
+            .find(|a| a.name == "Response matches schema")
+            .unwrap();
+        assert!(!schema_check.passed);
+        assert!(schema_check
+            .message
+            .as_deref()
+            .unwrap()
+            .starts_with("/id: "));
+    }
+
+    #[test]
+    fn test_check_response_compares_example() {
+        let mut request = request("Get user", "http://localhost/user".to_string());
+        let response = |body: &str| {
+            Response::new(
+                200,
+                "200 OK".to_string(),
+                HashMap::new(),
+                vec![],
+                body.to_string(),
+                1,
+            )
+        };
+        let mut example = ResponseExample::from_response(&response(r#"{"id":1,"at":"t1"}"#));
+        example.ignore = vec!["at".to_string()];
+        request.example = Some(example);
+
+        let same = check_response(
+            &request,
+            &response(r#"{"at":"t2","id":1}"#),
+            &mut HashMap::new(),
+            Path::new(""),
+        );
+        assert!(same.iter().all(|a| a.passed));
+
+        let drifted = check_response(
+            &request,
+            &response(r#"{"id":2}"#),
+            &mut HashMap::new(),
+            Path::new(""),
+        );
+        let example_check = drifted
+            .iter()
+            .find(|a| a.name == "Response matches example")
+            .unwrap();
+        assert!(!example_check.passed);
+        assert_eq!(example_check.message.as_deref(), Some("/id changed"));
+    }
+}
@@ -0,0 +1,662 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io::{self, Write};
+use std::sync::RwLock;
+
+use argon2::Argon2;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use serde::{Deserialize, Serialize};
+
+use crate::models::{
+    AiConfig, Collection, CollectionItem, Environment, EnvironmentVariables, HistoryEntry,
+    KeyValue, Request,
+};
+
+/// Prefix of a saved value that refers to an entry of the secret store
+pub const SECRET_PREFIX: &str = "secret:";
+
+/// Shown in place of secret values
+pub const MASK: &str = "••••••••";
+
+/// Key of the OpenAI API key in the store
+const API_KEY: &str = "config/openai_api_key";
+
+/// Shorter values are not masked in logs, where they would match too much
+const MIN_LOGGED_LEN: usize = 4;
+
+/// Values masked in log output, shared with the log writers
+static LOGGED_SECRETS: RwLock<Vec<String>> = RwLock::new(Vec::new());
+
+/// Contents of `secrets.json`
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "format", rename_all = "lowercase")]
+enum StoreFile {
+    Plain {
+        secrets: BTreeMap<String, String>,
+    },
+    /// Secrets as JSON, encrypted with ChaCha20-Poly1305 under a key derived
+    /// from the passphrase with Argon2
+    Encrypted {
+        salt: String,
+        nonce: String,
+        data: String,
+    },
+}
+
+/// Secret values by key, kept locally next to the collections
+///
+/// Saved files only hold `secret:<key>` references to them. A store may be
+/// encrypted with a passphrase; until it is unlocked, references stay
+/// unresolved and nothing may be stored.
+#[derive(Clone, Default)]
+pub struct SecretStore {
+    secrets: BTreeMap<String, String>,
+    passphrase: Option<String>,
+    locked: bool,
+    changed: bool,
+}
+
+impl fmt::Debug for SecretStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SecretStore")
+            .field("secrets", &self.secrets.len())
+            .field("encrypted", &self.is_encrypted())
+            .field("locked", &self.locked)
+            .finish()
+    }
+}
+
+impl SecretStore {
+    /// Read the store of a save directory
+    ///
+    /// An encrypted store read without a passphrase comes back locked.
+    pub fn load(base_dir: &str, passphrase: Option<&str>) -> Result<Self, String> {
+        let Some(file) = crate::storage::load_secrets::<StoreFile>(base_dir)? else {
+            return Ok(Self::default());
+        };
+        let store = match (file, passphrase) {
+            (StoreFile::Plain { secrets }, _) => Self {
+                secrets,
+                ..Self::default()
+            },
+            (StoreFile::Encrypted { .. }, None) => Self {
+                locked: true,
+                ..Self::default()
+            },
+            (StoreFile::Encrypted { salt, nonce, data }, Some(passphrase)) => Self {
+                secrets: decrypt(passphrase, &salt, &nonce, &data)?,
+                passphrase: Some(passphrase.to_string()),
+                ..Self::default()
+            },
+        };
+        for value in store.secrets.values() {
+            hide_in_logs(value);
+        }
+        Ok(store)
+    }
+
+    /// Stand-in for a store that could not be read, which keeps it from
+    /// being overwritten
+    pub fn unreadable() -> Self {
+        Self {
+            locked: true,
+            ..Self::default()
+        }
+    }
+
+    /// Write the store if anything changed since it was read
+    pub fn save(&mut self, base_dir: &str) -> Result<(), String> {
+        if !self.changed {
+            return Ok(());
+        }
+        let file = match &self.passphrase {
+            Some(passphrase) => encrypt(passphrase, &self.secrets)?,
+            None => StoreFile::Plain {
+                secrets: self.secrets.clone(),
+            },
+        };
+        crate::storage::save_secrets(base_dir, &file)?;
+        self.changed = false;
+        Ok(())
+    }
+
+    pub fn is_locked(&self) -> bool {
+        self.locked
+    }
+
+    pub fn is_encrypted(&self) -> bool {
+        self.locked || self.passphrase.is_some()
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.secrets.get(key).map(String::as_str)
+    }
+
+    pub fn set(&mut self, key: &str, value: String) -> Result<(), String> {
+        if self.locked {
+            return Err("Secrets are locked; unlock them with the passphrase first".to_string());
+        }
+        hide_in_logs(&value);
+        if self.secrets.get(key) != Some(&value) {
+            self.secrets.insert(key.to_string(), value);
+            self.changed = true;
+        }
+        Ok(())
+    }
+
+    /// Encrypt the store with a passphrase from now on, or stop encrypting
+    /// it when there is none
+    pub fn set_passphrase(&mut self, passphrase: Option<String>) -> Result<(), String> {
+        if self.locked {
+            return Err("Secrets are locked; unlock them with the passphrase first".to_string());
+        }
+        self.passphrase = passphrase.filter(|p| !p.is_empty());
+        self.changed = true;
+        Ok(())
+    }
+}
+
+fn derive_key(passphrase: &str, salt: &[u8]) -> Result<Key, String> {
+    let mut key = Key::default();
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|e| format!("Failed to derive key: {}", e))?;
+    Ok(key)
+}
+
+fn encrypt(passphrase: &str, secrets: &BTreeMap<String, String>) -> Result<StoreFile, String> {
+    let mut salt = [0u8; 16];
+    OsRng.fill_bytes(&mut salt);
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let plain = serde_json::to_vec(secrets).map_err(|e| format!("Failed to serialize: {}", e))?;
+    let data = ChaCha20Poly1305::new(&derive_key(passphrase, &salt)?)
+        .encrypt(&nonce, plain.as_slice())
+        .map_err(|_| "Failed to encrypt secrets".to_string())?;
+    Ok(StoreFile::Encrypted {
+        salt: BASE64.encode(salt),
+        nonce: BASE64.encode(nonce),
+        data: BASE64.encode(data),
+    })
+}
+
+fn decrypt(
+    passphrase: &str,
+    salt: &str,
+    nonce: &str,
+    data: &str,
+) -> Result<BTreeMap<String, String>, String> {
+    let decode = |value: &str| {
+        BASE64
+            .decode(value)
+            .map_err(|e| format!("Invalid secrets file: {}", e))
+    };
+    let (salt, nonce, data) = (decode(salt)?, decode(nonce)?, decode(data)?);
+    if nonce.len() != 12 {
+        return Err("Invalid secrets file: bad nonce".to_string());
+    }
+    let plain = ChaCha20Poly1305::new(&derive_key(passphrase, &salt)?)
+        .decrypt(Nonce::from_slice(&nonce), data.as_slice())
+        .map_err(|_| "Wrong passphrase".to_string())?;
+    serde_json::from_slice(&plain).map_err(|e| format!("Invalid secrets file: {}", e))
+}
+
+/// Move a value into the store, leaving a reference in its place
+///
+/// Values that are still references, because the store holds nothing for
+/// them here, are left as they are.
+fn extract_value(value: &mut String, key: &str, store: &mut SecretStore) -> Result<(), String> {
+    if value.starts_with(SECRET_PREFIX) {
+        return Ok(());
+    }
+    store.set(key, std::mem::take(value))?;
+    *value = format!("{}{}", SECRET_PREFIX, key);
+    Ok(())
+}
+
+/// Replace a reference with the value it refers to, if the store has it
+fn resolve_value(value: &mut String, store: &SecretStore) {
+    if let Some(secret) = value
+        .strip_prefix(SECRET_PREFIX)
+        .and_then(|key| store.get(key))
+    {
+        *value = secret.to_string();
+    }
+}
+
+fn requests_mut(
+    items: &mut [CollectionItem],
+    f: &mut impl FnMut(&mut Request) -> Result<(), String>,
+) -> Result<(), String> {
+    for item in items {
+        match item {
+            CollectionItem::Request(request) => f(request)?,
+            CollectionItem::Folder(folder) => requests_mut(&mut folder.items, f)?,
+            CollectionItem::Flow(_) => {}
+        }
+    }
+    Ok(())
+}
+
+/// Fields of a request marked secret, with their keys in the store
+///
+/// Auth fields keep the `<request id>/<name>` keys they had before headers,
+/// query parameters and cookies could be secret too.
+fn secret_fields(request: &mut Request) -> impl Iterator<Item = (String, &mut KeyValue)> {
+    let id = request.id;
+    [
+        ("", &mut request.auth),
+        ("header/", &mut request.headers),
+        ("query/", &mut request.query_params),
+        ("cookie/", &mut request.cookies),
+    ]
+    .into_iter()
+    .flat_map(move |(kind, fields)| {
+        fields
+            .iter_mut()
+            .filter(|field| field.secret)
+            .map(move |field| {
+                let key = format!("{}/{}{}", id, kind, field.key);
+                (key, field)
+            })
+    })
+}
+
+/// Copy of a request ready to be saved, with its secret fields stored
+pub fn extract_request(request: &Request, store: &mut SecretStore) -> Result<Request, String> {
+    let mut request = request.clone();
+    for (key, field) in secret_fields(&mut request) {
+        extract_value(&mut field.value, &key, store)?;
+    }
+    Ok(request)
+}
+
+/// Copy of a request with its secret fields replaced by references, for
+/// keeping a record of it without touching the store
+pub fn redact_request(request: &Request) -> Request {
+    let mut request = request.clone();
+    for (key, field) in secret_fields(&mut request) {
+        if !field.value.starts_with(SECRET_PREFIX) {
+            field.value = format!("{}{}", SECRET_PREFIX, key);
+        }
+    }
+    request
+}
+
+/// Copy of collection items with their secret fields replaced by references,
+/// for sharing them outside the save directory
+pub fn redact_items(items: &[CollectionItem]) -> Vec<CollectionItem> {
+    let mut items = items.to_vec();
+    let _ = requests_mut(&mut items, &mut |request| {
+        *request = redact_request(request);
+        Ok(())
+    });
+    items
+}
+
+/// Copy of a history entry with its secret fields replaced by references
+pub fn redact_history(entry: &HistoryEntry) -> HistoryEntry {
+    HistoryEntry {
+        request: redact_request(&entry.request),
+        ..entry.clone()
+    }
+}
+
+/// Fill in the secret fields of a request read from disk
+pub fn resolve_request(request: &mut Request, store: &SecretStore) {
+    for (_, field) in secret_fields(request) {
+        resolve_value(&mut field.value, store);
+    }
+}
+
+/// Whether a request refers to values that only the store can fill in
+pub fn has_references(request: &Request) -> bool {
+    [
+        &request.auth,
+        &request.headers,
+        &request.query_params,
+        &request.cookies,
+    ]
+    .into_iter()
+    .flatten()
+    .any(|field| field.secret && field.value.starts_with(SECRET_PREFIX))
+}
+
+/// Copy of a collection ready to be saved, with its secrets stored
+pub fn extract_collection(
+    collection: &Collection,
+    store: &mut SecretStore,
+) -> Result<Collection, String> {
+    let mut collection = collection.clone();
+    requests_mut(&mut collection.items, &mut |request| {
+        *request = extract_request(request, store)?;
+        Ok(())
+    })?;
+    Ok(collection)
+}
+
+/// Fill in the secrets of a collection read from disk
+pub fn resolve_collection(collection: &mut Collection, store: &SecretStore) {
+    let _ = requests_mut(&mut collection.items, &mut |request| {
+        resolve_request(request, store);
+        Ok(())
+    });
+}
+
+/// Copy of the environment variables ready to be saved, with secrets stored
+pub fn extract_environments(
+    variables: &EnvironmentVariables,
+    store: &mut SecretStore,
+) -> Result<EnvironmentVariables, String> {
+    let mut variables = variables.clone();
+    for (environment, names) in &variables.secrets {
+        let Some(values) = variables.variables.get_mut(environment) else {
+            continue;
+        };
+        for name in names {
+            if let Some(value) = values.get_mut(name) {
+                let key = format!("env/{}/{}", environment.code(), name);
+                extract_value(value, &key, store)?;
+            }
+        }
+    }
+    Ok(variables)
+}
+
+/// Fill in the secret environment variables read from disk
+pub fn resolve_environments(variables: &mut EnvironmentVariables, store: &SecretStore) {
+    for (environment, names) in &variables.secrets {
+        let Some(values) = variables.variables.get_mut(environment) else {
+            continue;
+        };
+        for name in names {
+            if let Some(value) = values.get_mut(name) {
+                resolve_value(value, store);
+            }
+        }
+    }
+}
+
+/// Whether the variables of an environment refer to values in the store
+pub fn environment_has_references(
+    variables: &EnvironmentVariables,
+    environment: Environment,
+) -> bool {
+    let (Some(names), Some(values)) = (
+        variables.secrets.get(&environment),
+        variables.variables.get(&environment),
+    ) else {
+        return false;
+    };
+    names.iter().any(|name| {
+        values
+            .get(name)
+            .is_some_and(|value| value.starts_with(SECRET_PREFIX))
+    })
+}
+
+/// Copy of the AI settings ready to be saved, with the API key stored
+pub fn extract_ai_config(config: &AiConfig, store: &mut SecretStore) -> Result<AiConfig, String> {
+    let mut config = config.clone();
+    if !config.openai_config.api_key.is_empty() {
+        extract_value(&mut config.openai_config.api_key, API_KEY, store)?;
+    }
+    Ok(config)
+}
+
+/// Fill in the API key of AI settings read from the config file
+pub fn resolve_ai_config(config: &mut AiConfig, store: &SecretStore) {
+    resolve_value(&mut config.openai_config.api_key, store);
+}
+
+/// Whether the AI settings still hold the API key in plain text
+pub fn has_plain_api_key(config: &AiConfig) -> bool {
+    let key = &config.openai_config.api_key;
+    !key.is_empty() && !key.starts_with(SECRET_PREFIX)
+}
+
+/// Mask a value in everything logged from now on
+pub fn hide_in_logs(value: &str) {
+    if value.chars().count() < MIN_LOGGED_LEN {
+        return;
+    }
+    if let Ok(mut secrets) = LOGGED_SECRETS.write() {
+        if !secrets.iter().any(|secret| secret == value) {
+            secrets.push(value.to_string());
+            // Longer values first, so one containing another is masked whole
+            secrets.sort_by_key(|secret| std::cmp::Reverse(secret.len()));
+        }
+    }
+}
+
+/// Replace every secret value in a text with the mask
+pub fn mask_text(text: &str) -> String {
+    let Ok(secrets) = LOGGED_SECRETS.read() else {
+        return text.to_string();
+    };
+    secrets
+        .iter()
+        .fold(text.to_string(), |text, secret| text.replace(secret, MASK))
+}
+
+/// Mask secret values in every string of a JSON value
+///
+/// Done before serializing, since escaping would hide a secret holding
+/// quotes, backslashes or control characters from `mask_text`.
+pub fn mask_json(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::String(text) => *text = mask_text(text),
+        serde_json::Value::Array(items) => items.iter_mut().for_each(mask_json),
+        serde_json::Value::Object(map) => map.values_mut().for_each(mask_json),
+        _ => {}
+    }
+}
+
+/// Log writer that masks secret values on their way out
+pub struct MaskedWriter<W>(pub W);
+
+impl<W: Write> Write for MaskedWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        // Log lines are written whole, so a secret is never split across calls
+        let text = String::from_utf8_lossy(buf);
+        self.0.write_all(mask_text(&text).as_bytes())?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn collection() -> Collection {
+        let mut password = KeyValue::new("password", "hunter2-password");
+        password.secret = true;
+        Collection {
+            id: uuid::Uuid::new_v4(),
+            name: "Secret API".to_string(),
+            items: vec![CollectionItem::Request(Request {
+                auth: vec![KeyValue::new("username", "ada"), password],
+                ..Request::default()
+            })],
+            expanded: true,
+            http_file: None,
+        }
+    }
+
+    fn auth(collection: &Collection) -> &[KeyValue] {
+        match &collection.items[0] {
+            CollectionItem::Request(request) => &request.auth,
+            _ => &[],
+        }
+    }
+
+    #[test]
+    fn test_secrets_are_stored_apart_from_collections() {
+        let temp_dir = std::env::temp_dir().join("requiem_test_secrets");
+        let temp_path = temp_dir.to_str().unwrap();
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        let mut store = SecretStore::load(temp_path, None).unwrap();
+        let saved = extract_collection(&collection(), &mut store).unwrap();
+        assert_eq!(auth(&saved)[0].value, "ada");
+        assert!(auth(&saved)[1].value.starts_with(SECRET_PREFIX));
+        store.save(temp_path).unwrap();
+
+        let gitignore = fs::read_to_string(temp_dir.join(".gitignore")).unwrap();
+        assert!(gitignore.lines().any(|line| line == "secrets.json"));
+
+        let mut loaded = saved.clone();
+        resolve_collection(&mut loaded, &SecretStore::load(temp_path, None).unwrap());
+        assert_eq!(auth(&loaded)[1].value, "hunter2-password");
+
+        // Exports get references, never the values
+        let exported = Collection {
+            items: redact_items(&loaded.items),
+            ..loaded.clone()
+        };
+        assert_eq!(auth(&exported)[0].value, "ada");
+        assert_eq!(auth(&exported)[1].value, auth(&saved)[1].value);
+
+        // Without the store the reference stays, and is saved as it is
+        let mut empty = SecretStore::default();
+        let mut unresolved = saved.clone();
+        resolve_collection(&mut unresolved, &empty);
+        let resaved = extract_collection(&unresolved, &mut empty).unwrap();
+        assert_eq!(auth(&resaved)[1].value, auth(&saved)[1].value);
+
+        let _ = fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    fn test_secret_headers_query_and_cookies() {
+        let secret = |key: &str, value: &str| KeyValue {
+            secret: true,
+            ..KeyValue::new(key, value)
+        };
+        let request = Request {
+            headers: vec![
+                secret("token", "header-value"),
+                KeyValue::new("Accept", "*/*"),
+            ],
+            query_params: vec![secret("token", "query-value")],
+            cookies: vec![secret("session", "cookie-value")],
+            auth: vec![secret("token", "auth-value")],
+            ..Request::default()
+        };
+
+        let mut store = SecretStore::default();
+        let saved = extract_request(&request, &mut store).unwrap();
+        assert_eq!(saved.headers[1].value, "*/*");
+        let references = [
+            &saved.headers[0].value,
+            &saved.query_params[0].value,
+            &saved.cookies[0].value,
+            &saved.auth[0].value,
+        ];
+        assert!(references.iter().all(|r| r.starts_with(SECRET_PREFIX)));
+        // Fields of the same name in different lists do not share a key
+        assert_ne!(references[0], references[1]);
+        assert_ne!(references[0], references[3]);
+        assert!(has_references(&saved));
+        assert_eq!(
+            redact_request(&request).headers[0].value,
+            saved.headers[0].value
+        );
+
+        let mut loaded = saved.clone();
+        resolve_request(&mut loaded, &store);
+        assert_eq!(loaded.headers[0].value, "header-value");
+        assert_eq!(loaded.query_params[0].value, "query-value");
+        assert_eq!(loaded.cookies[0].value, "cookie-value");
+        assert_eq!(loaded.auth[0].value, "auth-value");
+        assert!(!has_references(&loaded));
+    }
+
+    #[test]
+    fn test_encrypted_store_needs_passphrase() {
+        let temp_dir = std::env::temp_dir().join("requiem_test_secrets_encrypted");
+        let temp_path = temp_dir.to_str().unwrap();
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        let mut store = SecretStore::default();
+        store.set("token", "s3cr3t-token".to_string()).unwrap();
+        store
+            .set_passphrase(Some("correct horse".to_string()))
+            .unwrap();
+        store.save(temp_path).unwrap();
+        let contents = fs::read_to_string(temp_dir.join("secrets.json")).unwrap();
+        assert!(!contents.contains("s3cr3t-token"));
+
+        let mut locked = SecretStore::load(temp_path, None).unwrap();
+        assert!(locked.is_locked());
+        assert_eq!(locked.get("token"), None);
+        assert!(locked.set("token", "other".to_string()).is_err());
+        assert_eq!(
+            SecretStore::load(temp_path, Some("wrong")).unwrap_err(),
+            "Wrong passphrase"
+        );
+
+        let unlocked = SecretStore::load(temp_path, Some("correct horse")).unwrap();
+        assert_eq!(unlocked.get("token"), Some("s3cr3t-token"));
+
+        let _ = fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    fn test_secret_environment_variables() {
+        let mut variables = EnvironmentVariables::default();
+        variables.set(Environment::Staging, "host", "staging.example.com");
+        variables.set(Environment::Staging, "token", "staging-token");
+        variables.set_secret(Environment::Staging, "token", true);
+
+        let mut store = SecretStore::default();
+        let saved = extract_environments(&variables, &mut store).unwrap();
+        let values = saved.get(Environment::Staging).unwrap();
+        assert_eq!(values["host"], "staging.example.com");
+        assert_eq!(values["token"], "secret:env/staging/token");
+
+        let mut loaded = saved.clone();
+        resolve_environments(&mut loaded, &store);
+        assert_eq!(
+            loaded.get(Environment::Staging).unwrap()["token"],
+            "staging-token"
+        );
+    }
+
+    #[test]
+    fn test_masked_writer() {
+        hide_in_logs("mask-me-please");
+        hide_in_logs("abc");
+
+        let mut writer = MaskedWriter(Vec::new());
+        writer
+            .write_all(b"GET /users?token=mask-me-please&abc=1\n")
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(writer.0).unwrap(),
+            format!("GET /users?token={}&abc=1\n", MASK)
+        );
+    }
+
+    #[test]
+    fn test_mask_json() {
+        hide_in_logs("quote\"back\\slash\ttab");
+
+        let mut value = serde_json::json!({
+            "headers": [{"key": "X-Token", "value": "quote\"back\\slash\ttab"}],
+            "url": "https://h/?t=quote\"back\\slash\ttab",
+        });
+        mask_json(&mut value);
+        let line = value.to_string();
+        assert!(!line.contains("slash"));
+        assert_eq!(value["headers"][0]["value"], MASK);
+    }
+}
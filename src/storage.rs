@@ -1,12 +1,116 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashSet;
 use std::fs;
+use std::hash::{Hash, Hasher};
+use std::io::{Read, Seek, SeekFrom, Write};
 use std::path::{Path, PathBuf};
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use tracing::{debug, error, info};
+use uuid::Uuid;
+
+use crate::models::{
+    Collection, CollectionItem, EnvironmentVariables, Flow, Folder, HistoryEntry, HttpFileLink,
+    Request,
+};
+use crate::utils::http_file;
+
+/// File holding per-environment variables, stored next to the collections
+pub const ENVIRONMENTS_FILE: &str = "environments.json";
+
+/// File holding secret values, kept out of version control
+pub const SECRETS_FILE: &str = "secrets.json";
+
+/// Directory holding one directory per collection
+pub const COLLECTIONS_DIR: &str = "collections";
+
+/// Directory that single-file collections are moved to once migrated
+pub const LEGACY_DIR: &str = "legacy";
+
+/// Directory holding the request history, one JSON Lines file per day
+pub const HISTORY_DIR: &str = "history";
 
-use crate::models::Collection;
+/// Directory holding previous versions of saved files
+pub const BACKUPS_DIR: &str = "backups";
 
-/// Get the path to a collection file
-pub fn get_collection_path(base_dir: &str, collection_id: &uuid::Uuid) -> PathBuf {
-    Path::new(base_dir).join(format!("{}.json", collection_id))
+/// Number of previous versions kept of each file
+const BACKUP_COUNT: usize = 3;
+
+/// Version of the stored file format, written to every file as `schema_version`
+///
+/// Files without it are version 1. Whenever a stored model changes shape,
+/// bump this and add a step to `MIGRATIONS` that upgrades the previous
+/// version, so that older files keep loading.
+pub const SCHEMA_VERSION: u64 = 2;
+
+/// Steps of the migration chain; `MIGRATIONS[n]` upgrades version `n + 1`
+const MIGRATIONS: &[fn(&mut Value, FileKind)] = &[v1_to_v2];
+
+/// What a stored file holds, for migrations that only touch some files
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FileKind {
+    Collection,
+    Folder,
+    Request,
+    Flow,
+    Environments,
+    Secrets,
+    /// One line of a history file
+    HistoryEntry,
+    /// A whole collection in one `<uuid>.json` file, as saved before the
+    /// directory layout
+    LegacyCollection,
+}
+
+/// Collection settings and item order, in the collection's directory
+const COLLECTION_FILE: &str = "collection.json";
+
+/// Folder settings and item order, in the folder's directory
+const FOLDER_FILE: &str = "folder.json";
+
+/// Suffix of flow files; other `.json` files hold one request each
+const FLOW_SUFFIX: &str = ".flow.json";
+
+/// Contents of `collection.json`
+#[derive(Debug, Serialize, Deserialize)]
+struct CollectionMeta {
+    id: Uuid,
+    name: String,
+    expanded: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    http_file: Option<HttpFileLink>,
+    /// File names of the items, in display order
+    #[serde(default)]
+    order: Vec<String>,
+}
+
+/// Contents of `folder.json`
+#[derive(Debug, Serialize, Deserialize)]
+struct FolderMeta {
+    id: Uuid,
+    name: String,
+    expanded: bool,
+    #[serde(default)]
+    order: Vec<String>,
+}
+
+/// A collection file that could not be read
+#[derive(Debug, Clone)]
+pub struct LoadFailure {
+    pub path: PathBuf,
+    pub error: String,
+    /// Previous versions of the file, newest first
+    pub backups: Vec<Backup>,
+}
+
+/// A previous version of a file in the backups directory
+#[derive(Debug, Clone)]
+pub struct Backup {
+    pub path: PathBuf,
+    /// Local time the version was replaced, for display
+    pub replaced_at: String,
 }
 
 /// Ensure the storage directory exists
@@ -20,79 +124,942 @@ pub fn ensure_storage_dir(base_dir: &str) -> Result<(), String> {
     Ok(())
 }
 
+/// Directory of a saved collection, found by its id
+pub fn find_collection_dir(base_dir: &str, collection_id: &Uuid) -> Option<PathBuf> {
+    let entries = fs::read_dir(Path::new(base_dir).join(COLLECTIONS_DIR)).ok()?;
+    entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .find(|dir| {
+            read_stored::<CollectionMeta>(&dir.join(COLLECTION_FILE), FileKind::Collection)
+                .is_ok_and(|meta| meta.id == *collection_id)
+        })
+}
+
+/// Directory holding the item at `item_path` within a collection, or the
+/// folder's own directory when the item is a folder
+pub fn item_dir(base_dir: &str, collection: &Collection, item_path: &[usize]) -> Option<PathBuf> {
+    let mut dir = find_collection_dir(base_dir, &collection.id)?;
+    let mut items = &collection.items;
+    for &index in item_path {
+        let CollectionItem::Folder(folder) = items.get(index)? else {
+            break;
+        };
+        dir = dir.join(&file_names(items)[index]);
+        items = &folder.items;
+    }
+    Some(dir)
+}
+
 /// Save a collection to disk
+///
+/// Each collection is a directory named after it, with a directory per
+/// folder and a file per request, so that changes to different requests
+/// never touch the same file. Files of removed or renamed items are
+/// deleted, and unchanged files are not rewritten.
 pub fn save_collection(base_dir: &str, collection: &Collection) -> Result<(), String> {
     ensure_storage_dir(base_dir)?;
 
-    let path = get_collection_path(base_dir, &collection.id);
-    let json = serde_json::to_string_pretty(collection)
-        .map_err(|e| format!("Failed to serialize collection: {}", e))?;
+    let dir = collection_dir(base_dir, collection)?;
+    let mut files = vec![(
+        dir.join(COLLECTION_FILE),
+        to_json(&CollectionMeta {
+            id: collection.id,
+            name: collection.name.clone(),
+            expanded: collection.expanded,
+            http_file: collection.http_file.clone(),
+            order: file_names(&collection.items),
+        })?,
+    )];
+    layout_items(&collection.items, &dir, &mut files)?;
+
+    for (path, contents) in &files {
+        if fs::read_to_string(path).is_ok_and(|existing| existing == *contents) {
+            continue;
+        }
+        backup_file(base_dir, path);
+        write_atomic(path, contents)?;
+    }
+    let kept: HashSet<PathBuf> = files.into_iter().map(|(path, _)| path).collect();
+    remove_stale(base_dir, &dir, &kept)?;
+
+    debug!("Saved collection '{}' to {:?}", collection.name, dir);
+    save_http_file(collection)
+}
+
+/// Directory to save a collection to, following renames of the collection
+fn collection_dir(base_dir: &str, collection: &Collection) -> Result<PathBuf, String> {
+    let root = Path::new(base_dir).join(COLLECTIONS_DIR);
+    let wanted = slug(&collection.name, "collection");
+    let existing = find_collection_dir(base_dir, &collection.id);
+    if let Some(dir) = &existing {
+        if dir.file_name().and_then(|name| name.to_str()) == Some(wanted.as_str()) {
+            return Ok(dir.clone());
+        }
+    }
+
+    let mut name = wanted.clone();
+    let mut counter = 1;
+    while root.join(&name).exists() {
+        counter += 1;
+        name = format!("{}-{}", wanted, counter);
+    }
+    let dir = root.join(name);
+    match existing {
+        Some(old) => {
+            fs::rename(&old, &dir).map_err(|e| {
+                format!(
+                    "Failed to rename {} to {}: {}",
+                    old.display(),
+                    dir.display(),
+                    e
+                )
+            })?;
+        }
+        None => {
+            fs::create_dir_all(&dir)
+                .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+        }
+    }
+    Ok(dir)
+}
+
+/// Files of the items in `dir`, with their contents
+fn layout_items(
+    items: &[CollectionItem],
+    dir: &Path,
+    files: &mut Vec<(PathBuf, String)>,
+) -> Result<(), String> {
+    for (item, name) in items.iter().zip(file_names(items)) {
+        let path = dir.join(name);
+        match item {
+            CollectionItem::Request(request) => files.push((path, to_json(request)?)),
+            CollectionItem::Flow(flow) => files.push((path, to_json(flow)?)),
+            CollectionItem::Folder(folder) => {
+                files.push((
+                    path.join(FOLDER_FILE),
+                    to_json(&FolderMeta {
+                        id: folder.id,
+                        name: folder.name.clone(),
+                        expanded: folder.expanded,
+                        order: file_names(&folder.items),
+                    })?,
+                ));
+                layout_items(&folder.items, &path, files)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// File or directory names of items, derived from their names and unique
+/// within their directory
+fn file_names(items: &[CollectionItem]) -> Vec<String> {
+    let mut taken: HashSet<String> =
+        HashSet::from([COLLECTION_FILE.to_string(), FOLDER_FILE.to_string()]);
+    items
+        .iter()
+        .map(|item| {
+            let (name, suffix) = match item {
+                CollectionItem::Request(request) => (slug(&request.name, "request"), ".json"),
+                CollectionItem::Flow(flow) => (slug(&flow.name, "flow"), FLOW_SUFFIX),
+                CollectionItem::Folder(folder) => (slug(&folder.name, "folder"), ""),
+            };
+            let mut file_name = format!("{}{}", name, suffix);
+            let mut counter = 1;
+            while !taken.insert(file_name.clone()) {
+                counter += 1;
+                file_name = format!("{}-{}{}", name, counter, suffix);
+            }
+            file_name
+        })
+        .collect()
+}
+
+/// Lowercase file name made of the letters and digits of `name`
+fn slug(name: &str, fallback: &str) -> String {
+    let mut slug = String::new();
+    for c in name.trim().chars().flat_map(char::to_lowercase) {
+        if c.is_alphanumeric() {
+            slug.push(c);
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    let slug = slug.trim_end_matches('-');
+    if slug.is_empty() {
+        fallback.to_string()
+    } else {
+        slug.chars().take(64).collect()
+    }
+}
+
+/// Pretty JSON with keys in alphabetical order, so files diff cleanly,
+/// stamped with the current schema version
+fn to_json(value: &impl Serialize) -> Result<String, String> {
+    let value = stamped(value)?;
+    let mut json =
+        serde_json::to_string_pretty(&value).map_err(|e| format!("Failed to serialize: {}", e))?;
+    json.push('\n');
+    Ok(json)
+}
+
+/// A value as JSON, stamped with the current schema version
+fn stamped(value: &impl Serialize) -> Result<Value, String> {
+    let mut value =
+        serde_json::to_value(value).map_err(|e| format!("Failed to serialize: {}", e))?;
+    if let Value::Object(map) = &mut value {
+        map.insert("schema_version".to_string(), SCHEMA_VERSION.into());
+    }
+    Ok(value)
+}
+
+/// Read a stored file, upgrading it to the current schema version first
+fn read_stored<T: DeserializeOwned>(path: &Path, kind: FileKind) -> Result<T, String> {
+    let contents = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let mut value: Value = serde_json::from_str(&contents)
+        .map_err(|e| format!("Failed to parse {}: {}", path.display(), e))?;
+    migrate(&mut value, kind).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    serde_json::from_value(value).map_err(|e| format!("Failed to parse {}: {}", path.display(), e))
+}
+
+/// Run the migrations between a file's schema version and the current one
+///
+/// Files written by a newer version are refused rather than read with
+/// fields missing, since saving them would lose those fields.
+fn migrate(value: &mut Value, kind: FileKind) -> Result<(), String> {
+    let version = match value.get("schema_version") {
+        None => 1,
+        Some(version) => version
+            .as_u64()
+            .ok_or_else(|| format!("invalid schema version {}", version))?,
+    };
+    if version == 0 || version > SCHEMA_VERSION {
+        return Err(format!(
+            "schema version {} is not supported; this version of Requiem reads up to {}",
+            version, SCHEMA_VERSION
+        ));
+    }
+    for migration in &MIGRATIONS[version as usize - 1..] {
+        migration(value, kind);
+    }
+    if let Value::Object(map) = value {
+        map.remove("schema_version");
+    }
+    Ok(())
+}
+
+/// Version 2 writes every field of a request, which files from before
+/// request chaining and form file uploads lack: `extractions` on requests
+/// and `param_type` on their rows. Later migrations can rely on both.
+fn v1_to_v2(value: &mut Value, kind: FileKind) {
+    match kind {
+        FileKind::Request => stamp_request_fields(value),
+        FileKind::HistoryEntry => {
+            if let Some(request) = value.get_mut("request") {
+                stamp_request_fields(request);
+            }
+        }
+        FileKind::LegacyCollection => stamp_item_fields(value),
+        _ => {}
+    }
+}
+
+/// Add the fields of version 2 to the requests of a collection or folder
+fn stamp_item_fields(value: &mut Value) {
+    let Some(items) = value.get_mut("items").and_then(Value::as_array_mut) else {
+        return;
+    };
+    for item in items {
+        if let Some(request) = item.get_mut("Request") {
+            stamp_request_fields(request);
+        } else if let Some(folder) = item.get_mut("Folder") {
+            stamp_item_fields(folder);
+        }
+    }
+}
+
+fn stamp_request_fields(request: &mut Value) {
+    let Value::Object(map) = request else {
+        return;
+    };
+    map.entry("extractions")
+        .or_insert_with(|| Value::Array(vec![]));
+    for field in ["headers", "query_params", "cookies", "auth"] {
+        if let Some(rows) = map.get_mut(field) {
+            stamp_param_types(rows);
+        }
+    }
+    // Form bodies hold rows too, e.g. `{"FormData": [...]}`
+    if let Some(Value::Object(body)) = map.get_mut("body") {
+        body.values_mut().for_each(stamp_param_types);
+    }
+}
+
+fn stamp_param_types(rows: &mut Value) {
+    for row in rows.as_array_mut().into_iter().flatten() {
+        if let Value::Object(row) = row {
+            row.entry("param_type").or_insert_with(|| "Text".into());
+        }
+    }
+}
+
+/// Kind of a file inside a collection directory, going by its name
+fn file_kind(path: &Path) -> FileKind {
+    let name = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or_default();
+    if name == COLLECTION_FILE {
+        FileKind::Collection
+    } else if name == FOLDER_FILE {
+        FileKind::Folder
+    } else if name.ends_with(FLOW_SUFFIX) {
+        FileKind::Flow
+    } else {
+        FileKind::Request
+    }
+}
+
+/// Write a file so that a crash leaves either its old or its new contents
+///
+/// The contents go to a temporary file next to it, which is flushed to disk
+/// and then renamed over the original.
+fn write_atomic(path: &Path, contents: &str) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    let mut temp = path.as_os_str().to_owned();
+    temp.push(".tmp");
+    let temp = PathBuf::from(temp);
+
+    let written = fs::File::create(&temp).and_then(|mut file| {
+        file.write_all(contents.as_bytes())?;
+        file.sync_all()
+    });
+    if let Err(e) = written.and_then(|_| fs::rename(&temp, path)) {
+        let _ = fs::remove_file(&temp);
+        return Err(format!("Failed to write {}: {}", path.display(), e));
+    }
+    Ok(())
+}
+
+/// Where the `n`th previous version of a file in the save directory is kept
+fn backup_path(base_dir: &str, path: &Path, n: usize) -> Option<PathBuf> {
+    let relative = path.strip_prefix(base_dir).ok()?;
+    let mut backup = Path::new(base_dir)
+        .join(BACKUPS_DIR)
+        .join(relative)
+        .into_os_string();
+    backup.push(format!(".{}", n));
+    Some(PathBuf::from(backup))
+}
+
+/// Keep the current contents of a file before it is replaced or deleted
+///
+/// Older versions move up one place and the oldest is dropped. Git ignores
+/// the backups directory, since it may hold secrets from before they were
+/// moved to the secret store. A failed backup is logged but does not stop
+/// the save.
+fn backup_file(base_dir: &str, path: &Path) {
+    if !path.is_file() {
+        return;
+    }
+    if let Err(e) = ignore_in_git(base_dir, &format!("{}/", BACKUPS_DIR)) {
+        error!("Failed to ignore {} in git: {}", BACKUPS_DIR, e);
+    }
+    let Some(newest) = backup_path(base_dir, path, 1) else {
+        return;
+    };
+    let rotated = (1..BACKUP_COUNT).rev().try_for_each(|n| {
+        match (
+            backup_path(base_dir, path, n),
+            backup_path(base_dir, path, n + 1),
+        ) {
+            (Some(from), Some(to)) if from.is_file() => fs::rename(from, to),
+            _ => Ok(()),
+        }
+    });
+    let copied = rotated
+        .and_then(|_| match newest.parent() {
+            Some(parent) => fs::create_dir_all(parent),
+            None => Ok(()),
+        })
+        .and_then(|_| fs::copy(path, &newest));
+    if let Err(e) = copied {
+        error!("Failed to back up {}: {}", path.display(), e);
+    }
+}
+
+/// Previous versions of a file in the save directory, newest first
+pub fn backups(base_dir: &str, path: &Path) -> Vec<Backup> {
+    (1..=BACKUP_COUNT)
+        .filter_map(|n| backup_path(base_dir, path, n))
+        .filter_map(|backup| {
+            let modified = fs::metadata(&backup).and_then(|m| m.modified()).ok()?;
+            Some(Backup {
+                replaced_at: chrono::DateTime::<chrono::Local>::from(modified)
+                    .format("%Y-%m-%d %H:%M:%S")
+                    .to_string(),
+                path: backup,
+            })
+        })
+        .collect()
+}
 
-    fs::write(&path, json).map_err(|e| format!("Failed to write collection file: {}", e))?;
+/// Put a previous version of a file back in place
+///
+/// The contents being replaced are backed up in turn, so restoring the
+/// wrong version can be undone.
+pub fn restore_backup(base_dir: &str, backup: &Path, path: &Path) -> Result<(), String> {
+    let contents = fs::read_to_string(backup)
+        .map_err(|e| format!("Failed to read {}: {}", backup.display(), e))?;
+    backup_file(base_dir, path);
+    write_atomic(path, &contents)?;
+    info!("Restored {:?} from {:?}", path, backup);
+    Ok(())
+}
 
-    debug!("Saved collection '{}' to {:?}", collection.name, path);
+/// Delete `.json` files under `dir` that are not in `kept`, then any
+/// directories left empty
+///
+/// Files that cannot be read are left alone, along with folders whose
+/// `folder.json` cannot be read, so they can still be recovered.
+fn remove_stale(base_dir: &str, dir: &Path, kept: &HashSet<PathBuf>) -> Result<(), String> {
+    let entries =
+        fs::read_dir(dir).map_err(|e| format!("Failed to read {}: {}", dir.display(), e))?;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            // Only folders Requiem saved; other directories belong to the user
+            if !is_stored_item(&path.join(FOLDER_FILE)) {
+                continue;
+            }
+            remove_stale(base_dir, &path, kept)?;
+            if fs::read_dir(&path).is_ok_and(|mut entries| entries.next().is_none()) {
+                let _ = fs::remove_dir(&path);
+            }
+        } else if !kept.contains(&path) && is_stored_item(&path) {
+            backup_file(base_dir, &path);
+            fs::remove_file(&path)
+                .map_err(|e| format!("Failed to delete {}: {}", path.display(), e))?;
+            debug!("Deleted stale file {:?}", path);
+        }
+    }
+    Ok(())
+}
+
+/// Whether a file holds the request, flow or folder its name says it does
+///
+/// Unreadable files are kept for recovery, and other JSON files next to the
+/// collection, such as notes or tool settings, are not Requiem's to delete.
+fn is_stored_item(path: &Path) -> bool {
+    let name = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or_default();
+    if !name.ends_with(".json") || name == COLLECTION_FILE {
+        return false;
+    }
+    match file_kind(path) {
+        FileKind::Folder => read_stored::<FolderMeta>(path, FileKind::Folder).is_ok(),
+        FileKind::Flow => read_stored::<Flow>(path, FileKind::Flow).is_ok(),
+        _ => read_stored::<Request>(path, FileKind::Request).is_ok(),
+    }
+}
+
+/// Write a linked collection to its `.http` file
+///
+/// The file is left alone when it already holds the same requests, so its
+/// formatting and comments survive saves that did not change them.
+fn save_http_file(collection: &Collection) -> Result<(), String> {
+    let Some(link) = &collection.http_file else {
+        return Ok(());
+    };
+    if let Ok(existing) = fs::read_to_string(&link.path) {
+        if http_file::matches_file(collection, &existing) {
+            return Ok(());
+        }
+    }
+
+    let contents = http_file::export(&collection.items, &link.variables);
+    write_atomic(&link.path, &contents)?;
+    debug!("Saved collection '{}' to {:?}", collection.name, link.path);
     Ok(())
 }
 
+/// Read the `.http` file of a linked collection again
+///
+/// Returns whether the collection changed.
+pub fn sync_http_file(collection: &mut Collection) -> Result<bool, String> {
+    let Some(link) = &collection.http_file else {
+        return Ok(false);
+    };
+    let contents = fs::read_to_string(&link.path)
+        .map_err(|e| format!("Failed to read {}: {}", link.path.display(), e))?;
+    Ok(http_file::sync(collection, &contents))
+}
+
 /// Load all collections from disk
+///
+/// Collections saved as a single `<uuid>.json` file by earlier versions are
+/// converted to the directory layout first.
 pub fn load_collections(base_dir: &str) -> Result<Vec<Collection>, String> {
+    load_collections_with_failures(base_dir).map(|(collections, _)| collections)
+}
+
+/// Load all collections from disk without writing anything
+///
+/// Collections in the `<uuid>.json` format of earlier versions are converted
+/// in memory only, for readers such as the command line that must leave the
+/// directory as they found it.
+pub fn read_collections(base_dir: &str) -> Result<Vec<Collection>, String> {
+    load_all(base_dir, false).map(|(collections, _)| collections)
+}
+
+/// Load all collections from disk, along with the files that failed to parse
+///
+/// A request or flow that cannot be read is left out of its collection and a
+/// folder that cannot be read is left out with its items; a collection whose
+/// `collection.json` cannot be read is left out entirely.
+pub fn load_collections_with_failures(
+    base_dir: &str,
+) -> Result<(Vec<Collection>, Vec<LoadFailure>), String> {
+    load_all(base_dir, true)
+}
+
+/// Load all collections, converting legacy files on disk when `migrate` is set
+fn load_all(base_dir: &str, migrate: bool) -> Result<(Vec<Collection>, Vec<LoadFailure>), String> {
     let path = Path::new(base_dir);
 
     // If directory doesn't exist, return empty vector
     if !path.exists() {
         debug!("Storage directory does not exist: {}", base_dir);
-        return Ok(vec![]);
+        return Ok((vec![], vec![]));
     }
 
+    let mut failures = Vec::new();
+    let unmigrated = if migrate {
+        migrate_legacy_files(base_dir, &mut failures)?
+    } else {
+        read_legacy_files(base_dir, &mut failures)?
+            .into_iter()
+            .map(|(_, collection)| collection)
+            .collect()
+    };
+
+    let mut dirs: Vec<PathBuf> = match fs::read_dir(path.join(COLLECTIONS_DIR)) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|dir| dir.join(COLLECTION_FILE).is_file())
+            .collect(),
+        Err(_) => vec![],
+    };
+    dirs.sort();
+
     let mut collections = Vec::new();
+    for dir in dirs {
+        match load_collection_dir(&dir, &mut failures) {
+            Ok(mut collection) => {
+                debug!("Loaded collection '{}' from {:?}", collection.name, dir);
+                // A linked `.http` file may have been edited in the meantime
+                if let Err(e) = sync_http_file(&mut collection) {
+                    error!("Failed to sync collection '{}': {}", collection.name, e);
+                }
+                collections.push(collection);
+            }
+            Err(e) => {
+                error!("Failed to load collection from {:?}: {}", dir, e);
+                failures.push((dir.join(COLLECTION_FILE), e));
+            }
+        }
+    }
+    // A legacy file left behind after its collection was migrated is loaded already
+    for collection in unmigrated {
+        if !collections.iter().any(|c| c.id == collection.id) {
+            collections.push(collection);
+        }
+    }
+
+    info!("Loaded {} collections from {}", collections.len(), base_dir);
+    let failures = failures
+        .into_iter()
+        .map(|(path, error)| LoadFailure {
+            backups: backups(base_dir, &path),
+            path,
+            error,
+        })
+        .collect();
+    Ok((collections, failures))
+}
 
+/// Load a collection from its directory
+fn load_collection_dir(
+    dir: &Path,
+    failures: &mut Vec<(PathBuf, String)>,
+) -> Result<Collection, String> {
+    let meta: CollectionMeta = read_stored(&dir.join(COLLECTION_FILE), FileKind::Collection)?;
+    Ok(Collection {
+        id: meta.id,
+        name: meta.name,
+        items: load_items(dir, &meta.order, failures)?,
+        expanded: meta.expanded,
+        http_file: meta.http_file,
+    })
+}
+
+/// Load the items of a collection or folder directory
+///
+/// Items are listed in their saved order; files added by other means, such
+/// as a merge, follow in alphabetical order. Items that cannot be read are
+/// added to `failures` instead.
+fn load_items(
+    dir: &Path,
+    order: &[String],
+    failures: &mut Vec<(PathBuf, String)>,
+) -> Result<Vec<CollectionItem>, String> {
+    let mut names: Vec<String> = fs::read_dir(dir)
+        .map_err(|e| format!("Failed to read {}: {}", dir.display(), e))?
+        .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+        .filter(|name| name != COLLECTION_FILE && name != FOLDER_FILE)
+        .collect();
+    names.sort_by_key(|name| {
+        (
+            order.iter().position(|o| o == name).unwrap_or(usize::MAX),
+            name.clone(),
+        )
+    });
+
+    let mut items = Vec::new();
+    for name in names {
+        let path = dir.join(&name);
+        let item = if path.join(FOLDER_FILE).is_file() {
+            let folder_file = path.join(FOLDER_FILE);
+            read_stored::<FolderMeta>(&folder_file, FileKind::Folder)
+                .and_then(|meta| {
+                    Ok(CollectionItem::Folder(Folder {
+                        id: meta.id,
+                        name: meta.name,
+                        items: load_items(&path, &meta.order, failures)?,
+                        expanded: meta.expanded,
+                    }))
+                })
+                .map_err(|e| (folder_file, e))
+        } else if name.ends_with(FLOW_SUFFIX) {
+            read_stored(&path, FileKind::Flow)
+                .map(CollectionItem::Flow)
+                .map_err(|e| (path, e))
+        } else if name.ends_with(".json") {
+            read_stored(&path, FileKind::Request)
+                .map(CollectionItem::Request)
+                .map_err(|e| (path, e))
+        } else {
+            continue;
+        };
+        match item {
+            Ok(item) => items.push(item),
+            Err((path, e)) => {
+                error!("Failed to load {:?}: {}", path, e);
+                failures.push((path, e));
+            }
+        }
+    }
+    Ok(items)
+}
+
+/// Read the collections saved as `<uuid>.json` by earlier versions
+fn read_legacy_files(
+    base_dir: &str,
+    failures: &mut Vec<(PathBuf, String)>,
+) -> Result<Vec<(fs::DirEntry, Collection)>, String> {
     let entries =
-        fs::read_dir(path).map_err(|e| format!("Failed to read storage directory: {}", e))?;
+        fs::read_dir(base_dir).map_err(|e| format!("Failed to read storage directory: {}", e))?;
+    let mut legacy = Vec::new();
 
     for entry in entries {
         let entry = entry.map_err(|e| format!("Failed to read directory entry: {}", e))?;
         let path = entry.path();
 
-        // Only process .json files
-        if path.extension().and_then(|s| s.to_str()) == Some("json") {
-            match load_collection_from_file(&path) {
-                Ok(collection) => {
-                    debug!("Loaded collection '{}' from {:?}", collection.name, path);
-                    collections.push(collection);
-                }
-                Err(e) => {
-                    error!("Failed to load collection from {:?}: {}", path, e);
-                }
+        // Only process .json files, skipping the environments and secrets files
+        let name = path.file_name().and_then(|s| s.to_str());
+        if name == Some(ENVIRONMENTS_FILE) || name == Some(SECRETS_FILE) {
+            continue;
+        }
+        if path.extension().and_then(|s| s.to_str()) != Some("json") {
+            continue;
+        }
+        match read_stored(&path, FileKind::LegacyCollection) {
+            Ok(collection) => legacy.push((entry, collection)),
+            Err(e) => {
+                error!("Failed to load collection from {:?}: {}", path, e);
+                failures.push((path, e));
             }
         }
     }
+    Ok(legacy)
+}
 
-    info!("Loaded {} collections from {}", collections.len(), base_dir);
-    Ok(collections)
+/// Convert collections saved as `<uuid>.json` to the directory layout
+///
+/// Converted files are moved to the `legacy` directory rather than deleted.
+/// Collections that fail to convert are returned so they still load.
+fn migrate_legacy_files(
+    base_dir: &str,
+    failures: &mut Vec<(PathBuf, String)>,
+) -> Result<Vec<Collection>, String> {
+    let base = Path::new(base_dir);
+    let mut unmigrated = Vec::new();
+
+    for (entry, collection) in read_legacy_files(base_dir, failures)? {
+        let path = entry.path();
+        if let Err(e) = save_collection(base_dir, &collection) {
+            error!("Failed to migrate collection from {:?}: {}", path, e);
+            unmigrated.push(collection);
+            continue;
+        }
+        info!("Migrated collection '{}' from {:?}", collection.name, path);
+
+        // Saving again is harmless, so a file that cannot be moved is simply
+        // migrated again on the next start
+        let legacy = base.join(LEGACY_DIR);
+        if let Err(e) = ignore_in_git(base_dir, &format!("{}/", LEGACY_DIR)) {
+            error!("Failed to ignore {:?} in git: {}", legacy, e);
+        }
+        let moved = fs::create_dir_all(&legacy)
+            .and_then(|_| fs::rename(&path, legacy.join(entry.file_name())));
+        if let Err(e) = moved {
+            error!("Failed to move {:?} to {:?}: {}", path, legacy, e);
+        }
+    }
+    Ok(unmigrated)
 }
 
-/// Load a single collection from a file
-fn load_collection_from_file(path: &Path) -> Result<Collection, String> {
-    let contents = fs::read_to_string(path).map_err(|e| format!("Failed to read file: {}", e))?;
+/// Delete a collection's directory from disk
+pub fn delete_collection(base_dir: &str, collection_id: &Uuid) -> Result<(), String> {
+    if let Some(dir) = find_collection_dir(base_dir, collection_id) {
+        fs::remove_dir_all(&dir)
+            .map_err(|e| format!("Failed to delete collection directory: {}", e))?;
+        debug!("Deleted collection directory: {:?}", dir);
+    }
 
-    let collection: Collection = serde_json::from_str(&contents)
-        .map_err(|e| format!("Failed to deserialize collection: {}", e))?;
+    Ok(())
+}
 
-    Ok(collection)
+/// Fingerprint of the saved collections and of the `.http` files linked to them
+///
+/// Sizes and modification times are hashed rather than contents, so the
+/// directory can be polled for changes made by other programs cheaply.
+pub fn fingerprint(base_dir: &str, linked_files: &[PathBuf]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    hash_tree(&Path::new(base_dir).join(COLLECTIONS_DIR), &mut hasher);
+    for path in linked_files {
+        hash_file(path, &mut hasher);
+    }
+    hasher.finish()
 }
 
-/// Delete a collection file from disk
-pub fn delete_collection(base_dir: &str, collection_id: &uuid::Uuid) -> Result<(), String> {
-    let path = get_collection_path(base_dir, collection_id);
+fn hash_tree(dir: &Path, hasher: &mut DefaultHasher) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .collect();
+    paths.sort();
+    for path in paths {
+        if path.is_dir() {
+            hash_tree(&path, hasher);
+        } else {
+            hash_file(&path, hasher);
+        }
+    }
+}
 
-    if path.exists() {
-        fs::remove_file(&path).map_err(|e| format!("Failed to delete collection file: {}", e))?;
-        debug!("Deleted collection file: {:?}", path);
+fn hash_file(path: &Path, hasher: &mut DefaultHasher) {
+    path.hash(hasher);
+    if let Ok(metadata) = fs::metadata(path) {
+        metadata.len().hash(hasher);
+        metadata.modified().ok().hash(hasher);
     }
+}
 
+/// Save environment variables to disk
+pub fn save_environment_variables(
+    base_dir: &str,
+    variables: &EnvironmentVariables,
+) -> Result<(), String> {
+    ensure_storage_dir(base_dir)?;
+
+    let path = Path::new(base_dir).join(ENVIRONMENTS_FILE);
+    let json = to_json(variables)?;
+
+    backup_file(base_dir, &path);
+    write_atomic(&path, &json)?;
+
+    debug!("Saved environment variables to {:?}", path);
+    Ok(())
+}
+
+/// Load environment variables from disk, or an empty set if none are saved
+pub fn load_environment_variables(base_dir: &str) -> Result<EnvironmentVariables, String> {
+    let path = Path::new(base_dir).join(ENVIRONMENTS_FILE);
+
+    if !path.exists() {
+        return Ok(EnvironmentVariables::default());
+    }
+
+    read_stored(&path, FileKind::Environments)
+}
+
+/// Save the secret store, making sure git ignores it
+///
+/// No backups are kept, so that a value removed from the store, or a store
+/// that is now encrypted, leaves no plain copy behind.
+pub fn save_secrets(base_dir: &str, secrets: &impl Serialize) -> Result<(), String> {
+    ensure_storage_dir(base_dir)?;
+    ignore_in_git(base_dir, SECRETS_FILE)?;
+
+    let path = Path::new(base_dir).join(SECRETS_FILE);
+    write_atomic(&path, &to_json(secrets)?)?;
+
+    debug!("Saved secrets to {:?}", path);
     Ok(())
 }
 
+/// Load the secret store, or `None` if there is none yet
+pub fn load_secrets<T: DeserializeOwned>(base_dir: &str) -> Result<Option<T>, String> {
+    let path = Path::new(base_dir).join(SECRETS_FILE);
+
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    read_stored(&path, FileKind::Secrets).map(Some)
+}
+
+/// Append an entry to the history file of the day it was sent
+///
+/// Secret values known to the secret store are masked on the way to disk, and
+/// git ignores the history directory.
+pub fn append_history(base_dir: &str, entry: &HistoryEntry) -> Result<(), String> {
+    ensure_storage_dir(base_dir)?;
+    ignore_in_git(base_dir, &format!("{}/", HISTORY_DIR))?;
+
+    let mut value = stamped(entry)?;
+    crate::secrets::mask_json(&mut value);
+    let mut line = value.to_string();
+    line.push('\n');
+
+    let dir = Path::new(base_dir).join(HISTORY_DIR);
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    let day = entry.sent_at.get(..10).unwrap_or("unknown");
+    let path = dir.join(format!("{}.jsonl", day));
+    let appended = fs::OpenOptions::new()
+        .create(true)
+        .read(true)
+        .append(true)
+        .open(&path)
+        .and_then(|mut file| {
+            // Start on a line of its own after a line cut short by a crash
+            let mut last = [b'\n'];
+            if file.metadata()?.len() > 0 {
+                file.seek(SeekFrom::End(-1))?;
+                file.read_exact(&mut last)?;
+            }
+            if last[0] != b'\n' {
+                line.insert(0, '\n');
+            }
+            file.write_all(line.as_bytes())
+        });
+    appended.map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+/// Load the request history, oldest first
+///
+/// Lines that cannot be read, such as one cut short by a crash, are skipped.
+pub fn load_history(base_dir: &str) -> Result<Vec<HistoryEntry>, String> {
+    let mut entries = Vec::new();
+    for path in history_files(base_dir)? {
+        let contents = fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        for line in contents.lines().filter(|line| !line.trim().is_empty()) {
+            let entry = serde_json::from_str(line)
+                .map_err(|e| e.to_string())
+                .and_then(|mut value| {
+                    migrate(&mut value, FileKind::HistoryEntry)?;
+                    serde_json::from_value(value).map_err(|e| e.to_string())
+                });
+            match entry {
+                Ok(entry) => entries.push(entry),
+                Err(e) => error!("Skipping history entry in {}: {}", path.display(), e),
+            }
+        }
+    }
+    entries.sort_by(|a: &HistoryEntry, b| a.sent_at.cmp(&b.sent_at));
+    Ok(entries)
+}
+
+/// Delete the history of every day before `day` (`YYYY-MM-DD`), returning
+/// the number of days removed
+pub fn purge_history(base_dir: &str, day: &str) -> Result<usize, String> {
+    let mut removed = 0;
+    for path in history_files(base_dir)? {
+        let stem = path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or_default();
+        if stem < day {
+            fs::remove_file(&path)
+                .map_err(|e| format!("Failed to delete {}: {}", path.display(), e))?;
+            removed += 1;
+        }
+    }
+    if removed > 0 {
+        info!("Purged {} days of history before {}", removed, day);
+    }
+    Ok(removed)
+}
+
+/// Delete the whole request history
+pub fn clear_history(base_dir: &str) -> Result<(), String> {
+    let dir = Path::new(base_dir).join(HISTORY_DIR);
+    if !dir.exists() {
+        return Ok(());
+    }
+    fs::remove_dir_all(&dir).map_err(|e| format!("Failed to delete {}: {}", dir.display(), e))
+}
+
+/// History files in the save directory, oldest day first
+fn history_files(base_dir: &str) -> Result<Vec<PathBuf>, String> {
+    let dir = Path::new(base_dir).join(HISTORY_DIR);
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut files: Vec<PathBuf> = fs::read_dir(&dir)
+        .map_err(|e| format!("Failed to read {}: {}", dir.display(), e))?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "jsonl"))
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
+/// Add a file name to the `.gitignore` of the save directory
+fn ignore_in_git(base_dir: &str, name: &str) -> Result<(), String> {
+    let path = Path::new(base_dir).join(".gitignore");
+    let mut contents = fs::read_to_string(&path).unwrap_or_default();
+    if contents.lines().any(|line| line.trim() == name) {
+        return Ok(());
+    }
+    if !contents.is_empty() && !contents.ends_with('\n') {
+        contents.push('\n');
+    }
+    contents.push_str(name);
+    contents.push('\n');
+    write_atomic(&path, &contents)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -128,6 +1095,7 @@ mod tests {
             name: "Test Collection".to_string(),
             items: vec![],
             expanded: true,
+            http_file: None,
         };
 
         // Save collection
@@ -142,4 +1110,540 @@ mod tests {
         // Clean up
         let _ = fs::remove_dir_all(&temp_dir);
     }
+
+    fn request(name: &str) -> CollectionItem {
+        CollectionItem::Request(crate::models::Request {
+            name: name.to_string(),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_saves_one_file_per_request() {
+        let temp_dir = std::env::temp_dir().join("requiem_test_layout");
+        let temp_path = temp_dir.to_str().unwrap();
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        let mut collection = Collection {
+            id: Uuid::new_v4(),
+            name: "My API".to_string(),
+            items: vec![
+                request("List users"),
+                CollectionItem::Folder(Folder {
+                    id: Uuid::new_v4(),
+                    name: "Admin".to_string(),
+                    items: vec![request("Get user"), request("Get user")],
+                    expanded: false,
+                }),
+                request("Collection"),
+            ],
+            expanded: true,
+            http_file: None,
+        };
+        save_collection(temp_path, &collection).unwrap();
+
+        let dir = temp_dir.join(COLLECTIONS_DIR).join("my-api");
+        assert!(dir.join("collection.json").is_file());
+        assert!(dir.join("list-users.json").is_file());
+        assert!(dir.join("collection-2.json").is_file());
+        assert!(dir.join("admin/folder.json").is_file());
+        assert!(dir.join("admin/get-user.json").is_file());
+        assert!(dir.join("admin/get-user-2.json").is_file());
+
+        // Keys are sorted, so saving the same request twice gives the same file
+        let contents = fs::read_to_string(dir.join("list-users.json")).unwrap();
+        let body = contents.find("\"body\"").unwrap();
+        assert!(body < contents.find("\"url\"").unwrap());
+
+        // Renamed and deleted items leave no files behind
+        collection.name = "Renamed API".to_string();
+        collection.items.truncate(1);
+        if let CollectionItem::Request(request) = &mut collection.items[0] {
+            request.name = "All users".to_string();
+        }
+        save_collection(temp_path, &collection).unwrap();
+        let dir = temp_dir.join(COLLECTIONS_DIR).join("renamed-api");
+        assert!(!temp_dir.join(COLLECTIONS_DIR).join("my-api").exists());
+        assert!(dir.join("all-users.json").is_file());
+        assert!(!dir.join("list-users.json").exists());
+        assert!(!dir.join("admin").exists());
+
+        let loaded = load_collections(temp_path).unwrap();
+        assert_eq!(loaded[0].name, "Renamed API");
+        assert_eq!(loaded[0].items.len(), 1);
+
+        delete_collection(temp_path, &collection.id).unwrap();
+        assert!(!dir.exists());
+
+        let _ = fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    fn test_saves_leave_other_files_alone() {
+        let temp_dir = std::env::temp_dir().join("requiem_test_other_files");
+        let temp_path = temp_dir.to_str().unwrap();
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        let mut collection = Collection {
+            id: Uuid::new_v4(),
+            name: "My API".to_string(),
+            items: vec![request("List users"), request("Get user")],
+            expanded: true,
+            http_file: None,
+        };
+        save_collection(temp_path, &collection).unwrap();
+        let dir = temp_dir.join(COLLECTIONS_DIR).join("my-api");
+        fs::write(dir.join("settings.json"), "{\"theme\": \"dark\"}").unwrap();
+        fs::create_dir_all(dir.join("notes")).unwrap();
+        fs::write(dir.join("notes/todo.json"), "[]").unwrap();
+        // Even a request file is only removed from folders Requiem saved
+        let ping = crate::models::Request::default();
+        fs::write(dir.join("notes/ping.json"), to_json(&ping).unwrap()).unwrap();
+
+        collection.items.truncate(1);
+        save_collection(temp_path, &collection).unwrap();
+        assert!(!dir.join("get-user.json").exists());
+        assert!(dir.join("settings.json").is_file());
+        assert!(dir.join("notes/todo.json").is_file());
+        assert!(dir.join("notes/ping.json").is_file());
+
+        let _ = fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    fn test_migrates_single_file_collections() {
+        let temp_dir = std::env::temp_dir().join("requiem_test_migrate");
+        let temp_path = temp_dir.to_str().unwrap();
+        let _ = fs::remove_dir_all(&temp_dir);
+        ensure_storage_dir(temp_path).unwrap();
+
+        let collection = Collection {
+            id: Uuid::new_v4(),
+            name: "Old".to_string(),
+            items: vec![request("B"), request("A")],
+            expanded: true,
+            http_file: None,
+        };
+        let legacy_file = temp_dir.join(format!("{}.json", collection.id));
+        fs::write(&legacy_file, serde_json::to_string(&collection).unwrap()).unwrap();
+
+        // Reading converts in memory and leaves the files alone
+        let read = read_collections(temp_path).unwrap();
+        assert_eq!(read.len(), 1);
+        assert_eq!(read[0].id, collection.id);
+        assert!(legacy_file.is_file());
+        assert!(!temp_dir.join(COLLECTIONS_DIR).exists());
+
+        let loaded = load_collections(temp_path).unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].id, collection.id);
+        let names: Vec<&str> = loaded[0]
+            .items
+            .iter()
+            .filter_map(|item| match item {
+                CollectionItem::Request(request) => Some(request.name.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(names, ["B", "A"]);
+        assert!(!legacy_file.exists());
+        assert!(temp_dir
+            .join(LEGACY_DIR)
+            .join(legacy_file.file_name().unwrap())
+            .is_file());
+        let gitignore = fs::read_to_string(temp_dir.join(".gitignore")).unwrap();
+        assert!(gitignore.lines().any(|line| line == "legacy/"));
+
+        // Loading again does not duplicate the collection
+        assert_eq!(load_collections(temp_path).unwrap().len(), 1);
+
+        let _ = fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    fn test_linked_http_file_is_kept_in_sync() {
+        let temp_dir = std::env::temp_dir().join("requiem_test_http_file");
+        let temp_path = temp_dir.to_str().unwrap();
+        let _ = fs::remove_dir_all(&temp_dir);
+        ensure_storage_dir(temp_path).unwrap();
+
+        let http_path = temp_dir.join("api.http");
+        fs::write(&http_path, "### Ping\nGET https://example.com/ping\n").unwrap();
+        let mut collection = http_file::open(&http_path).unwrap().collection.unwrap();
+        collection.name = "Renamed".to_string();
+        assert!(save_collection(temp_path, &collection).is_ok());
+        // Unchanged requests leave the file as it was written
+        assert_eq!(
+            fs::read_to_string(&http_path).unwrap(),
+            "### Ping\nGET https://example.com/ping\n"
+        );
+
+        fs::write(&http_path, "### Ping\nGET https://example.com/health\n").unwrap();
+        let loaded = load_collections(temp_path).unwrap();
+        let Some(CollectionItem::Request(request)) = loaded[0].items.first() else {
+            panic!("expected a request");
+        };
+        assert_eq!(request.url, "https://example.com/health");
+
+        let _ = fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    fn test_saves_keep_backups() {
+        let temp_dir = std::env::temp_dir().join("requiem_test_backups");
+        let temp_path = temp_dir.to_str().unwrap();
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        let mut request = crate::models::Request {
+            name: "Ping".to_string(),
+            ..Default::default()
+        };
+        let mut collection = Collection {
+            id: Uuid::new_v4(),
+            name: "Backed up".to_string(),
+            items: vec![],
+            expanded: true,
+            http_file: None,
+        };
+        for url in ["/v1", "/v2", "/v3", "/v4", "/v5"] {
+            request.url = url.to_string();
+            collection.items = vec![CollectionItem::Request(request.clone())];
+            save_collection(temp_path, &collection).unwrap();
+        }
+
+        let file = temp_dir
+            .join(COLLECTIONS_DIR)
+            .join("backed-up")
+            .join("ping.json");
+        let backups = backups(temp_path, &file);
+        assert_eq!(backups.len(), BACKUP_COUNT);
+        assert!(fs::read_to_string(&backups[0].path)
+            .unwrap()
+            .contains("/v4"));
+        assert!(fs::read_to_string(&backups[2].path)
+            .unwrap()
+            .contains("/v2"));
+        assert!(!file.with_extension("json.tmp").exists());
+        let gitignore = fs::read_to_string(temp_dir.join(".gitignore")).unwrap();
+        assert!(gitignore.lines().any(|line| line == "backups/"));
+
+        restore_backup(temp_path, &backups[0].path, &file).unwrap();
+        assert!(fs::read_to_string(&file).unwrap().contains("/v4"));
+
+        let _ = fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    fn test_reports_unreadable_files() {
+        let temp_dir = std::env::temp_dir().join("requiem_test_unreadable");
+        let temp_path = temp_dir.to_str().unwrap();
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        let collection = Collection {
+            id: Uuid::new_v4(),
+            name: "Damaged".to_string(),
+            items: vec![
+                CollectionItem::Request(crate::models::Request {
+                    name: "Fine".to_string(),
+                    ..Default::default()
+                }),
+                CollectionItem::Request(crate::models::Request {
+                    name: "Broken".to_string(),
+                    ..Default::default()
+                }),
+            ],
+            expanded: true,
+            http_file: None,
+        };
+        save_collection(temp_path, &collection).unwrap();
+        let dir = temp_dir.join(COLLECTIONS_DIR).join("damaged");
+        fs::write(dir.join("broken.json"), "{\"id\": ").unwrap();
+
+        let (loaded, failures) = load_collections_with_failures(temp_path).unwrap();
+        assert_eq!(loaded[0].items.len(), 1);
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].path, dir.join("broken.json"));
+
+        // Saving what was loaded leaves the unreadable file for recovery
+        save_collection(temp_path, &loaded[0]).unwrap();
+        assert!(dir.join("broken.json").exists());
+
+        let _ = fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    fn test_fingerprint_changes_with_collection_files() {
+        let temp_dir = std::env::temp_dir().join("requiem_test_fingerprint");
+        let temp_path = temp_dir.to_str().unwrap();
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        let mut collection = Collection {
+            id: Uuid::new_v4(),
+            name: "Watched".to_string(),
+            items: vec![],
+            expanded: true,
+            http_file: None,
+        };
+        save_collection(temp_path, &collection).unwrap();
+        let before = fingerprint(temp_path, &[]);
+        assert_eq!(fingerprint(temp_path, &[]), before);
+
+        collection
+            .items
+            .push(CollectionItem::Request(crate::models::Request::default()));
+        save_collection(temp_path, &collection).unwrap();
+        assert_ne!(fingerprint(temp_path, &[]), before);
+
+        let _ = fs::remove_dir_all(&temp_dir);
+    }
+
+    /// Copy the stored files of a schema version to a fresh directory
+    fn fixture(version: u64, name: &str) -> PathBuf {
+        fn copy(from: &Path, to: &Path) {
+            fs::create_dir_all(to).unwrap();
+            for entry in fs::read_dir(from).unwrap().flatten() {
+                let path = entry.path();
+                if path.is_dir() {
+                    copy(&path, &to.join(entry.file_name()));
+                } else {
+                    fs::copy(&path, to.join(entry.file_name())).unwrap();
+                }
+            }
+        }
+
+        let temp_dir = std::env::temp_dir().join(name);
+        let _ = fs::remove_dir_all(&temp_dir);
+        let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/storage");
+        copy(&fixtures.join(format!("v{}", version)), &temp_dir);
+        temp_dir
+    }
+
+    /// Every stored file under `dir`, with its schema version
+    fn schema_versions(dir: &Path) -> Vec<(PathBuf, Option<u64>)> {
+        let mut versions = Vec::new();
+        for entry in fs::read_dir(dir).unwrap().flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                versions.extend(schema_versions(&path));
+            } else if path.extension().and_then(|e| e.to_str()) == Some("json") {
+                let value: Value =
+                    serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+                versions.push((path, value["schema_version"].as_u64()));
+            }
+        }
+        versions
+    }
+
+    #[test]
+    fn test_loads_files_of_every_schema_version() {
+        assert_eq!(MIGRATIONS.len() as u64, SCHEMA_VERSION - 1);
+
+        let mut loaded = Vec::new();
+        for version in 1..=SCHEMA_VERSION {
+            let temp_dir = fixture(version, &format!("requiem_test_schema_v{}", version));
+            let temp_path = temp_dir.to_str().unwrap();
+
+            let (collections, failures) = load_collections_with_failures(temp_path).unwrap();
+            assert!(failures.is_empty(), "v{}: {:?}", version, failures);
+            let sample = collections
+                .into_iter()
+                .find(|c| c.name == "Sample API")
+                .unwrap();
+            let variables = load_environment_variables(temp_path).unwrap();
+            assert_eq!(
+                variables
+                    .get(crate::models::Environment::Development)
+                    .and_then(|vars| vars.get("base_url"))
+                    .map(String::as_str),
+                Some("http://localhost:8080")
+            );
+            loaded.push(serde_json::to_value(&sample).unwrap());
+
+            let _ = fs::remove_dir_all(&temp_dir);
+        }
+
+        // Every version reads back as the same collection
+        let current = loaded.last().unwrap();
+        assert_eq!(current["items"].as_array().unwrap().len(), 3);
+        assert_eq!(
+            current["items"][1]["Folder"]["items"]
+                .as_array()
+                .unwrap()
+                .len(),
+            2
+        );
+        assert!(loaded.iter().all(|collection| collection == current));
+    }
+
+    #[test]
+    fn test_v1_requests_gain_the_fields_of_v2() {
+        let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/storage");
+        let read = |version: u64, file: &str| -> Value {
+            let path = fixtures.join(format!("v{}", version)).join(file);
+            let mut value = serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap();
+            migrate(&mut value, FileKind::Request).unwrap();
+            value
+        };
+        for file in [
+            "collections/sample-api/list-users.json",
+            "collections/sample-api/users/create-user.json",
+            "collections/sample-api/users/get-user.json",
+        ] {
+            let migrated = read(1, file);
+            assert!(migrated["extractions"].is_array());
+            assert_eq!(migrated, read(2, file), "{}", file);
+        }
+
+        let mut entry = serde_json::json!({
+            "request": {"headers": [{"key": "A", "value": "1", "enabled": true}]}
+        });
+        migrate(&mut entry, FileKind::HistoryEntry).unwrap();
+        assert_eq!(entry["request"]["headers"][0]["param_type"], "Text");
+        assert_eq!(entry["request"]["extractions"], serde_json::json!([]));
+    }
+
+    #[test]
+    fn test_saves_upgrade_files_to_the_current_schema_version() {
+        let temp_dir = fixture(1, "requiem_test_schema_upgrade");
+        let temp_path = temp_dir.to_str().unwrap();
+
+        for collection in load_collections(temp_path).unwrap() {
+            save_collection(temp_path, &collection).unwrap();
+        }
+        let variables = load_environment_variables(temp_path).unwrap();
+        save_environment_variables(temp_path, &variables).unwrap();
+
+        let versions = schema_versions(&temp_dir.join(COLLECTIONS_DIR));
+        assert_eq!(versions.len(), 8);
+        for (path, version) in versions {
+            assert_eq!(version, Some(SCHEMA_VERSION), "{}", path.display());
+        }
+        let environments: Value =
+            serde_json::from_str(&fs::read_to_string(temp_dir.join(ENVIRONMENTS_FILE)).unwrap())
+                .unwrap();
+        assert_eq!(
+            environments["schema_version"].as_u64(),
+            Some(SCHEMA_VERSION)
+        );
+
+        let _ = fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    fn test_refuses_files_from_newer_versions() {
+        let temp_dir = fixture(SCHEMA_VERSION, "requiem_test_schema_newer");
+        let temp_path = temp_dir.to_str().unwrap();
+        let file = temp_dir
+            .join(COLLECTIONS_DIR)
+            .join("sample-api")
+            .join("list-users.json");
+        let contents = fs::read_to_string(&file).unwrap().replace(
+            &format!("\"schema_version\": {}", SCHEMA_VERSION),
+            &format!("\"schema_version\": {}", SCHEMA_VERSION + 1),
+        );
+        fs::write(&file, &contents).unwrap();
+
+        let (loaded, failures) = load_collections_with_failures(temp_path).unwrap();
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].path, file);
+        assert!(failures[0].error.contains("not supported"));
+
+        // Saving does not overwrite or delete what a newer version wrote
+        save_collection(temp_path, &loaded[0]).unwrap();
+        assert_eq!(fs::read_to_string(&file).unwrap(), contents);
+
+        let _ = fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    fn test_environment_variables_are_not_loaded_as_collections() {
+        let temp_dir = std::env::temp_dir().join("requiem_test_env_vars");
+        let temp_path = temp_dir.to_str().unwrap();
+
+        // Clean up if exists
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        let mut variables = EnvironmentVariables::default();
+        variables.set(crate::models::Environment::Staging, "token", "abc");
+        assert!(save_environment_variables(temp_path, &variables).is_ok());
+
+        let loaded = load_environment_variables(temp_path).unwrap();
+        assert_eq!(
+            loaded
+                .get(crate::models::Environment::Staging)
+                .and_then(|vars| vars.get("token"))
+                .map(String::as_str),
+            Some("abc")
+        );
+        assert!(load_collections(temp_path).unwrap().is_empty());
+
+        // Clean up
+        let _ = fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    fn test_history_is_kept_per_day_and_purged() {
+        let temp_dir = std::env::temp_dir().join("requiem_test_history");
+        let temp_path = temp_dir.to_str().unwrap();
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        let entry = |sent_at: &str, url: &str| HistoryEntry {
+            id: Uuid::new_v4(),
+            sent_at: sent_at.to_string(),
+            request: crate::models::Request {
+                url: url.to_string(),
+                ..Default::default()
+            },
+            response: None,
+            error: Some("connection refused".to_string()),
+            body_omitted: false,
+        };
+        crate::secrets::hide_in_logs("history-token-123");
+        let entries = [
+            entry("2026-10-18T23:59:00+02:00", "https://example.com/a"),
+            entry("2026-10-19T08:00:00+02:00", "https://example.com/b"),
+            entry(
+                "2026-10-19T09:00:00+02:00",
+                "https://example.com/c?token=history-token-123",
+            ),
+        ];
+        for entry in &entries {
+            append_history(temp_path, entry).unwrap();
+        }
+        assert!(temp_dir.join("history").join("2026-10-18.jsonl").exists());
+        let gitignore = fs::read_to_string(temp_dir.join(".gitignore")).unwrap();
+        assert!(gitignore.lines().any(|line| line == "history/"));
+
+        // A line cut short does not lose the rest of the day
+        let mut file = fs::OpenOptions::new()
+            .append(true)
+            .open(temp_dir.join("history").join("2026-10-19.jsonl"))
+            .unwrap();
+        file.write_all(b"{\"id\":").unwrap();
+        append_history(
+            temp_path,
+            &entry("2026-10-19T10:00:00+02:00", "https://example.com/d"),
+        )
+        .unwrap();
+
+        let loaded = load_history(temp_path).unwrap();
+        let urls: Vec<&str> = loaded.iter().map(|e| e.request.url.as_str()).collect();
+        let masked = format!("https://example.com/c?token={}", crate::secrets::MASK);
+        assert_eq!(
+            urls,
+            [
+                "https://example.com/a",
+                "https://example.com/b",
+                &masked,
+                "https://example.com/d"
+            ]
+        );
+
+        assert_eq!(purge_history(temp_path, "2026-10-19").unwrap(), 1);
+        assert_eq!(load_history(temp_path).unwrap().len(), 3);
+        clear_history(temp_path).unwrap();
+        assert!(load_history(temp_path).unwrap().is_empty());
+
+        let _ = fs::remove_dir_all(&temp_dir);
+    }
 }
@@ -0,0 +1,58 @@
+use iced::widget::{column, container, text, Row};
+use iced::{Alignment, Color, Element, Length};
+
+/// A minimal column chart built from containers
+///
+/// Bars share the available width and are scaled to the largest value,
+/// so no canvas support is needed for the live blast charts.
+pub fn view<'a, Message: 'a>(
+    title: String,
+    values: &[f64],
+    height: f32,
+    color: Color,
+) -> Element<'a, Message> {
+    let max = values.iter().copied().fold(0.0_f64, f64::max);
+
+    let mut bars = Row::new()
+        .spacing(1)
+        .align_y(Alignment::End)
+        .height(Length::Fixed(height));
+    for value in values {
+        let bar_height = if max > 0.0 {
+            ((value / max) as f32 * height).max(1.0)
+        } else {
+            1.0
+        };
+        bars = bars.push(
+            container(text(""))
+                .width(Length::Fill)
+                .height(Length::Fixed(bar_height))
+                .style(move |_theme| container::Style {
+                    background: Some(iced::Background::Color(color)),
+                    ..Default::default()
+                }),
+        );
+    }
+
+    let header = Row::new()
+        .push(text(title).size(12).width(Length::Fill))
+        .push(
+            text(format!("max {:.0}", max))
+                .size(11)
+                .color(Color::from_rgb(0.5, 0.5, 0.5)),
+        );
+
+    container(column![header, bars].spacing(4))
+        .padding(8)
+        .width(Length::Fill)
+        .style(|_theme| container::Style {
+            background: Some(iced::Background::Color(Color::from_rgb(0.98, 0.98, 0.98))),
+            border: iced::Border {
+                color: Color::from_rgb(0.9, 0.9, 0.9),
+                width: 1.0,
+                radius: 4.0.into(),
+            },
+            ..Default::default()
+        })
+        .into()
+}
@@ -0,0 +1,267 @@
+use iced::widget::{button, container, row, scrollable, text, text_input, Column, Row};
+use iced::{Alignment, Color, Element, Length};
+
+use crate::app::state::BlastState;
+use crate::app::Message;
+use crate::i18n::Translations;
+use crate::models::{BlastExportFormat, BlastSummary};
+
+use super::{bar_chart, dialog, option_buttons};
+
+/// Number of most recent seconds shown in the live charts
+const CHART_SECONDS: usize = 60;
+
+/// Blast (load test) dialog: settings, live statistics and charts
+pub fn view<'a>(state: &'a BlastState, translations: &'a Translations) -> Element<'a, Message> {
+    let target = text(format!(
+        "{} {}",
+        state.request.method.as_str(),
+        state.request.url
+    ))
+    .size(13)
+    .color(Color::from_rgb(0.4, 0.4, 0.4));
+
+    let settings = Row::new()
+        .spacing(10)
+        .align_y(Alignment::Center)
+        .push(labeled_input(
+            translations.get("blast_concurrency"),
+            &state.concurrency,
+            "10",
+            Message::BlastConcurrencyChanged,
+        ))
+        .push(labeled_input(
+            translations.get("blast_ramp_up"),
+            &state.ramp_up_secs,
+            "0",
+            Message::BlastRampUpChanged,
+        ))
+        .push(labeled_input(
+            translations.get("blast_rate_limit"),
+            &state.rate_limit,
+            "∞",
+            Message::BlastRateLimitChanged,
+        ));
+
+    let limit_value = if state.use_duration {
+        labeled_input(
+            translations.get("blast_duration"),
+            &state.duration_secs,
+            "30",
+            Message::BlastDurationChanged,
+        )
+    } else {
+        labeled_input(
+            translations.get("blast_total_requests"),
+            &state.total_requests,
+            "1000",
+            Message::BlastTotalRequestsChanged,
+        )
+    };
+    let requests_label = translations.get("blast_limit_requests").to_string();
+    let duration_label = translations.get("blast_limit_duration").to_string();
+    let limit_row = Row::new()
+        .align_y(Alignment::Center)
+        .push(
+            container(option_buttons::view(
+                vec![false, true],
+                state.use_duration,
+                move |use_duration| {
+                    if *use_duration {
+                        duration_label.clone()
+                    } else {
+                        requests_label.clone()
+                    }
+                },
+                Message::BlastUseDuration,
+            ))
+            .width(Length::Fixed(260.0)),
+        )
+        .push(limit_value);
+
+    let mut content = Column::new()
+        .spacing(12)
+        .push(target)
+        .push(settings)
+        .push(limit_row);
+
+    if let Some(summary) = &state.summary {
+        content = content.push(summary_view(summary, translations));
+    } else if state.running {
+        content = content.push(
+            text(translations.get("blast_starting"))
+                .size(13)
+                .color(Color::from_rgb(0.4, 0.4, 0.4)),
+        );
+    }
+
+    let mut buttons = vec![(
+        translations.get("close").to_string(),
+        Message::CloseBlastDialog,
+        button::secondary as fn(&iced::Theme, button::Status) -> button::Style,
+    )];
+    if state.report.is_some() && !state.running {
+        buttons.push((
+            translations.get("blast_export_json").to_string(),
+            Message::ExportBlastReport(BlastExportFormat::Json),
+            button::secondary as fn(&iced::Theme, button::Status) -> button::Style,
+        ));
+        buttons.push((
+            translations.get("blast_export_csv").to_string(),
+            Message::ExportBlastReport(BlastExportFormat::Csv),
+            button::secondary as fn(&iced::Theme, button::Status) -> button::Style,
+        ));
+    }
+    if state.running {
+        buttons.push((
+            translations.get("blast_stop").to_string(),
+            Message::StopBlast,
+            button::danger as fn(&iced::Theme, button::Status) -> button::Style,
+        ));
+    } else {
+        buttons.push((
+            translations.get("blast_start").to_string(),
+            Message::StartBlast,
+            button::primary as fn(&iced::Theme, button::Status) -> button::Style,
+        ));
+    }
+
+    dialog::view_with_buttons(
+        translations.get("blast_dialog_title"),
+        scrollable(content).height(Length::Fill).into(),
+        buttons,
+        820.0,
+        640.0,
+    )
+}
+
+fn labeled_input<'a>(
+    label: &'a str,
+    value: &'a str,
+    placeholder: &'a str,
+    on_input: fn(String) -> Message,
+) -> Element<'a, Message> {
+    row![
+        text(label).size(12),
+        text_input(placeholder, value)
+            .on_input(on_input)
+            .padding([6, 8])
+            .size(12)
+            .width(Length::Fixed(80.0)),
+    ]
+    .spacing(6)
+    .align_y(Alignment::Center)
+    .into()
+}
+
+/// Headline numbers, charts, status distribution and errors
+fn summary_view<'a>(
+    summary: &'a BlastSummary,
+    translations: &'a Translations,
+) -> Element<'a, Message> {
+    let stats = Row::new()
+        .spacing(16)
+        .push(stat(
+            translations.get("blast_requests"),
+            summary.total.to_string(),
+        ))
+        .push(stat("RPS", format!("{:.1}", summary.rps)))
+        .push(stat(
+            translations.get("blast_errors"),
+            summary.errors.to_string(),
+        ))
+        .push(stat("p50", format!("{} ms", summary.p50_ms)))
+        .push(stat("p90", format!("{} ms", summary.p90_ms)))
+        .push(stat("p99", format!("{} ms", summary.p99_ms)))
+        .push(stat("max", format!("{} ms", summary.max_ms)))
+        .push(stat(
+            translations.get("blast_elapsed"),
+            format!("{:.1} s", summary.elapsed_ms as f64 / 1000.0),
+        ));
+
+    let recent = &summary.timeline[summary.timeline.len().saturating_sub(CHART_SECONDS)..];
+    let rps: Vec<f64> = recent.iter().map(|s| s.requests as f64).collect();
+    let p50: Vec<f64> = recent.iter().map(|s| s.p50_ms as f64).collect();
+    let p99: Vec<f64> = recent.iter().map(|s| s.p99_ms as f64).collect();
+    let charts = Row::new()
+        .spacing(10)
+        .push(bar_chart::view(
+            "RPS".to_string(),
+            &rps,
+            80.0,
+            Color::from_rgb(0.3, 0.5, 0.9),
+        ))
+        .push(bar_chart::view(
+            "p50 (ms)".to_string(),
+            &p50,
+            80.0,
+            Color::from_rgb(0.3, 0.7, 0.4),
+        ))
+        .push(bar_chart::view(
+            "p99 (ms)".to_string(),
+            &p99,
+            80.0,
+            Color::from_rgb(0.9, 0.6, 0.2),
+        ));
+
+    let mut statuses = Column::new()
+        .spacing(4)
+        .push(text(translations.get("blast_status_distribution")).size(13));
+    for (status, count) in &summary.status_counts {
+        let share = *count as f32 / summary.total.max(1) as f32;
+        let color = match status.chars().next() {
+            Some('2') | Some('3') => Color::from_rgb(0.3, 0.7, 0.4),
+            Some('4') => Color::from_rgb(0.9, 0.6, 0.2),
+            _ => Color::from_rgb(0.8, 0.2, 0.2),
+        };
+        statuses = statuses.push(
+            Row::new()
+                .spacing(8)
+                .align_y(Alignment::Center)
+                .push(text(status).size(12).width(Length::Fixed(50.0)))
+                .push(
+                    container(text(""))
+                        .width(Length::Fixed((share * 300.0).max(1.0)))
+                        .height(Length::Fixed(10.0))
+                        .style(move |_theme| container::Style {
+                            background: Some(iced::Background::Color(color)),
+                            ..Default::default()
+                        }),
+                )
+                .push(text(format!("{} ({:.1}%)", count, share * 100.0)).size(12)),
+        );
+    }
+
+    let mut content = Column::new()
+        .spacing(12)
+        .push(stats)
+        .push(charts)
+        .push(statuses);
+
+    if !summary.error_counts.is_empty() {
+        let mut errors: Vec<_> = summary.error_counts.iter().collect();
+        errors.sort_by(|a, b| b.1.cmp(a.1));
+
+        let mut list = Column::new()
+            .spacing(4)
+            .push(text(translations.get("blast_errors")).size(13));
+        for (message, count) in errors.into_iter().take(5) {
+            list = list.push(
+                text(format!("{} × {}", count, message))
+                    .size(11)
+                    .color(Color::from_rgb(0.8, 0.2, 0.2)),
+            );
+        }
+        content = content.push(list);
+    }
+
+    content.into()
+}
+
+fn stat<'a>(label: &'a str, value: String) -> Element<'a, Message> {
+    Column::new()
+        .spacing(2)
+        .push(text(label).size(11).color(Color::from_rgb(0.5, 0.5, 0.5)))
+        .push(text(value).size(15))
+        .into()
+}
@@ -0,0 +1,101 @@
+use iced::widget::{button, container, pick_list, scrollable, text, Column, Row};
+use iced::{Alignment, Color, Element, Font, Length};
+
+use crate::app::state::CodeDialogState;
+use crate::app::Message;
+use crate::i18n::Translations;
+use crate::models::CodeLanguage;
+
+use super::{dialog, option_buttons};
+
+/// "Copy as..." dialog: the request rendered as a snippet in the chosen language
+pub fn view<'a>(
+    state: &'a CodeDialogState,
+    translations: &'a Translations,
+) -> Element<'a, Message> {
+    let language_picker = pick_list(
+        Some(state.language),
+        CodeLanguage::all(),
+        |language: &CodeLanguage| language.to_string(),
+    )
+    .on_select(Message::CodeLanguageSelected)
+    .padding([6, 8])
+    .width(Length::Fixed(200.0));
+
+    let placeholders_label = translations.get("code_keep_placeholders").to_string();
+    let resolved_label = translations.get("code_resolve_variables").to_string();
+    let resolve_toggle = option_buttons::view(
+        vec![false, true],
+        state.resolve_variables,
+        move |resolve| {
+            if *resolve {
+                resolved_label.clone()
+            } else {
+                placeholders_label.clone()
+            }
+        },
+        |_| Message::ToggleCodeResolveVariables,
+    );
+
+    let options = Row::new()
+        .spacing(10)
+        .align_y(Alignment::Center)
+        .push(language_picker)
+        .push(resolve_toggle);
+
+    let code = container(
+        scrollable(
+            // Long lines scroll instead of wrapping, so the snippet copies as shown
+            container(text(state.code()).size(12).font(Font::MONOSPACE)).padding(12),
+        )
+        .direction(scrollable::Direction::Both {
+            vertical: scrollable::Scrollbar::new().width(8).scroller_width(8),
+            horizontal: scrollable::Scrollbar::new().width(8).scroller_width(8),
+        })
+        .height(Length::Fill),
+    )
+    .width(Length::Fill)
+    .height(Length::Fill)
+    .style(|_theme| container::Style {
+        background: Some(iced::Background::Color(Color::from_rgb(0.97, 0.97, 0.97))),
+        border: iced::Border {
+            color: Color::from_rgb(0.85, 0.85, 0.85),
+            width: 1.0,
+            radius: 4.0.into(),
+        },
+        ..Default::default()
+    });
+
+    let content = Column::new()
+        .spacing(12)
+        .push(
+            text(format!(
+                "{} {}",
+                state.request.method.as_str(),
+                state.request.name
+            ))
+            .size(13)
+            .color(Color::from_rgb(0.4, 0.4, 0.4)),
+        )
+        .push(options)
+        .push(code);
+
+    dialog::view_with_buttons(
+        translations.get("code_dialog_title"),
+        content.into(),
+        vec![
+            (
+                translations.get("close").to_string(),
+                Message::CloseCodeDialog,
+                button::secondary as fn(&iced::Theme, button::Status) -> button::Style,
+            ),
+            (
+                translations.get("code_copy").to_string(),
+                Message::CopyGeneratedCode,
+                button::primary as fn(&iced::Theme, button::Status) -> button::Style,
+            ),
+        ],
+        760.0,
+        600.0,
+    )
+}
@@ -10,6 +10,8 @@ pub fn view<'a>(
     x: f32,
     y: f32,
     target: &ContextMenuTarget,
+    mock_running: bool,
+    http_linked: bool,
     translations: &'a Translations,
 ) -> Element<'a, Message> {
     let menu_item_style = |_theme: &iced::Theme, status: button::Status| {
@@ -39,7 +41,21 @@ pub fn view<'a>(
     // Add menu items based on target type
     match target {
         ContextMenuTarget::Request => {
-            // For requests, show Open Folder, Rename, and Delete
+            // For requests, show Blast, Copy as, Open Folder, Rename, and Delete
+            menu_items = menu_items.push(
+                button(text(translations.get("ctx_blast")).size(12))
+                    .on_press(Message::ShowBlastDialog(path.clone()))
+                    .width(Length::Fixed(150.0))
+                    .padding([6, 12])
+                    .style(menu_item_style),
+            );
+            menu_items = menu_items.push(
+                button(text(translations.get("ctx_copy_as")).size(12))
+                    .on_press(Message::ShowCodeDialog(Some(path.clone())))
+                    .width(Length::Fixed(150.0))
+                    .padding([6, 12])
+                    .style(menu_item_style),
+            );
             menu_items = menu_items.push(
                 button(text(translations.get("ctx_open_folder")).size(12))
                     .on_press(Message::OpenFolder(path.clone()))
@@ -62,6 +78,30 @@ pub fn view<'a>(
                     .style(menu_item_style),
             );
         }
+        ContextMenuTarget::Flow => {
+            // For flows, show Open, Rename, and Delete
+            menu_items = menu_items.push(
+                button(text(translations.get("ctx_open_flow")).size(12))
+                    .on_press(Message::OpenFlow(path.clone()))
+                    .width(Length::Fixed(150.0))
+                    .padding([6, 12])
+                    .style(menu_item_style),
+            );
+            menu_items = menu_items.push(
+                button(text(translations.get("ctx_rename")).size(12))
+                    .on_press(Message::StartRename(path.clone()))
+                    .width(Length::Fixed(150.0))
+                    .padding([6, 12])
+                    .style(menu_item_style),
+            );
+            menu_items = menu_items.push(
+                button(text(translations.get("ctx_delete")).size(12))
+                    .on_press(Message::DeleteItem(path.clone()))
+                    .width(Length::Fixed(150.0))
+                    .padding([6, 12])
+                    .style(menu_item_style),
+            );
+        }
         ContextMenuTarget::Folder | ContextMenuTarget::Collection => {
             // For folders and collections, show all options
             menu_items = menu_items.push(
@@ -78,6 +118,55 @@ pub fn view<'a>(
                     .padding([6, 12])
                     .style(menu_item_style),
             );
+            menu_items = menu_items.push(
+                button(text(translations.get("ctx_new_flow")).size(12))
+                    .on_press(Message::AddNewFlow(path.clone()))
+                    .width(Length::Fixed(150.0))
+                    .padding([6, 12])
+                    .style(menu_item_style),
+            );
+            menu_items = menu_items.push(
+                button(text(translations.get("ctx_run")).size(12))
+                    .on_press(Message::ShowRunnerDialog(path.clone()))
+                    .width(Length::Fixed(150.0))
+                    .padding([6, 12])
+                    .style(menu_item_style),
+            );
+            if matches!(target, ContextMenuTarget::Collection) {
+                // Collections can be served as a mock server
+                let (label, message) = if mock_running {
+                    ("ctx_stop_mock", Message::StopMockServer)
+                } else {
+                    (
+                        "ctx_mock_server",
+                        Message::ShowMockServerDialog(path.clone()),
+                    )
+                };
+                menu_items = menu_items.push(
+                    button(text(translations.get(label)).size(12))
+                        .on_press(message)
+                        .width(Length::Fixed(150.0))
+                        .padding([6, 12])
+                        .style(menu_item_style),
+                );
+                if http_linked {
+                    // Collections opened from a `.http` file can re-read it
+                    menu_items = menu_items.push(
+                        button(text(translations.get("ctx_reload_http")).size(12))
+                            .on_press(Message::ReloadHttpFile(path[0]))
+                            .width(Length::Fixed(150.0))
+                            .padding([6, 12])
+                            .style(menu_item_style),
+                    );
+                }
+            }
+            menu_items = menu_items.push(
+                button(text(translations.get("ctx_export")).size(12))
+                    .on_press(Message::ShowExportDialog(path.clone()))
+                    .width(Length::Fixed(150.0))
+                    .padding([6, 12])
+                    .style(menu_item_style),
+            );
             menu_items = menu_items.push(
                 button(text(translations.get("ctx_open_folder")).size(12))
                     .on_press(Message::OpenFolder(path.clone()))
@@ -101,7 +190,7 @@ pub fn view<'a>(
             );
         }
         ContextMenuTarget::EmptyArea => {
-            // For empty area, only show New Collection
+            // For empty area, show New Collection, Import, Open .http and history export
             menu_items = menu_items.push(
                 button(text(translations.get("ctx_new_collection")).size(12))
                     .on_press(Message::AddNewCollection)
@@ -109,6 +198,27 @@ pub fn view<'a>(
                     .padding([6, 12])
                     .style(menu_item_style),
             );
+            menu_items = menu_items.push(
+                button(text(translations.get("ctx_import")).size(12))
+                    .on_press(Message::ImportFile)
+                    .width(Length::Fixed(150.0))
+                    .padding([6, 12])
+                    .style(menu_item_style),
+            );
+            menu_items = menu_items.push(
+                button(text(translations.get("ctx_open_http")).size(12))
+                    .on_press(Message::OpenHttpFile)
+                    .width(Length::Fixed(150.0))
+                    .padding([6, 12])
+                    .style(menu_item_style),
+            );
+            menu_items = menu_items.push(
+                button(text(translations.get("ctx_export_har")).size(12))
+                    .on_press(Message::ExportHistoryHar)
+                    .width(Length::Fixed(150.0))
+                    .padding([6, 12])
+                    .style(menu_item_style),
+            );
         }
     }
 
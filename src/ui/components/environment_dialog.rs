@@ -1,22 +1,84 @@
-use iced::widget::{text, Column};
-use iced::Element;
+use iced::widget::{button, container, scrollable, text, Column, Row};
+use iced::{Alignment, Color, Element, Length};
+use std::collections::{BTreeSet, HashMap};
 
 use crate::app::Message;
+use crate::models::{Environment, EnvironmentVariables};
+use crate::secrets::MASK;
 
 use super::dialog;
 
-pub fn view<'a>() -> Element<'a, Message> {
+/// List variables as sorted name/value rows
+///
+/// With `secrets`, each variable can be marked secret, which masks its value.
+fn variables_list<'a>(
+    variables: Option<&HashMap<String, String>>,
+    secrets: Option<&BTreeSet<String>>,
+) -> Column<'a, Message> {
+    let mut entries: Vec<(String, String)> = variables
+        .map(|vars| vars.iter().map(|(k, v)| (k.clone(), v.clone())).collect())
+        .unwrap_or_default();
+    entries.sort();
+
+    if entries.is_empty() {
+        return Column::new().push(text("(空)").size(12).color(Color::from_rgb(0.5, 0.5, 0.5)));
+    }
+
+    entries
+        .into_iter()
+        .fold(Column::new().spacing(6), |col, (key, value)| {
+            let secret = secrets.is_some_and(|names| names.contains(&key));
+            let value = if secret { MASK.to_string() } else { value };
+            let mut row = Row::new()
+                .spacing(8)
+                .align_y(Alignment::Center)
+                .push(container(text(key.clone()).size(12)).width(Length::Fixed(160.0)))
+                .push(
+                    container(text(value).size(12).color(Color::from_rgb(0.4, 0.4, 0.4)))
+                        .width(Length::Fill),
+                );
+            if secrets.is_some() {
+                row = row.push(
+                    button(text("机密").size(11))
+                        .on_press(Message::ToggleSecretVariable(key))
+                        .padding([4, 8])
+                        .style(if secret {
+                            button::primary
+                        } else {
+                            button::secondary
+                        }),
+                );
+            }
+            col.push(row)
+        })
+}
+
+pub fn view<'a>(
+    current_env: Environment,
+    environment_variables: &EnvironmentVariables,
+    runtime_variables: &HashMap<String, String>,
+) -> Element<'a, Message> {
     let content = Column::new()
-        .spacing(20)
-        .push(text("这里可以管理自定义环境").size(14))
-        .push(text("功能开发中...").size(12));
+        .spacing(12)
+        .push(text(format!("{} 变量", current_env)).size(14))
+        .push(variables_list(
+            environment_variables.get(current_env),
+            Some(
+                environment_variables
+                    .secrets
+                    .get(&current_env)
+                    .unwrap_or(&BTreeSet::new()),
+            ),
+        ))
+        .push(text("运行时变量").size(14))
+        .push(variables_list(Some(runtime_variables), None));
 
     dialog::view(
         "环境管理",
-        content.into(),
+        scrollable(content).height(Length::Fill).into(),
         "关闭",
         Message::CloseEnvironmentDialog,
         500.0,
-        300.0,
+        400.0,
     )
 }
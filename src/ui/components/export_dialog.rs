@@ -0,0 +1,64 @@
+use iced::widget::{button, text, Column};
+use iced::{Color, Element};
+
+use crate::app::state::ExportDialogState;
+use crate::app::Message;
+use crate::i18n::Translations;
+use crate::models::CollectionExportFormat;
+
+use super::{dialog, option_buttons};
+
+/// Export a collection or folder for other tools or as documentation
+pub fn view<'a>(
+    state: &'a ExportDialogState,
+    translations: &'a Translations,
+) -> Element<'a, Message> {
+    let formats = option_buttons::view(
+        CollectionExportFormat::all(),
+        state.format,
+        |format| format.to_string(),
+        Message::ExportFormatSelected,
+    );
+
+    let hint = match state.format {
+        CollectionExportFormat::Postman => "export_hint_postman",
+        CollectionExportFormat::Insomnia => "export_hint_insomnia",
+        CollectionExportFormat::OpenApi => "export_hint_openapi",
+        CollectionExportFormat::Http => "export_hint_http",
+        CollectionExportFormat::Html => "export_hint_html",
+        CollectionExportFormat::Markdown => "export_hint_markdown",
+    };
+
+    let content = Column::new()
+        .spacing(8)
+        .push(
+            text(&state.name)
+                .size(13)
+                .color(Color::from_rgb(0.4, 0.4, 0.4)),
+        )
+        .push(formats)
+        .push(
+            text(translations.get(hint))
+                .size(12)
+                .color(Color::from_rgb(0.5, 0.5, 0.5)),
+        );
+
+    dialog::view_with_buttons(
+        translations.get("export_dialog_title"),
+        content.into(),
+        vec![
+            (
+                translations.get("cancel").to_string(),
+                Message::CloseExportDialog,
+                button::secondary as fn(&iced::Theme, button::Status) -> button::Style,
+            ),
+            (
+                translations.get("export_button").to_string(),
+                Message::ExportCollection(state.path.clone(), state.format),
+                button::primary as fn(&iced::Theme, button::Status) -> button::Style,
+            ),
+        ],
+        560.0,
+        240.0,
+    )
+}
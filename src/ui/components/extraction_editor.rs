@@ -0,0 +1,128 @@
+use iced::widget::{button, container, pick_list, scrollable, text, text_input, Column, Row};
+use iced::{Alignment, Element, Length};
+
+use crate::app::Message;
+use crate::i18n::Translations;
+use crate::models::{ExtractionRule, ExtractionSource, VariableScope};
+
+/// Editor for the response extraction rules of a request
+///
+/// Each row maps a JSONPath, header, cookie or regex to a variable name
+pub fn view<'a>(
+    rules: &'a [ExtractionRule],
+    translations: &'a Translations,
+) -> Element<'a, Message> {
+    let mut rows = Column::new().spacing(8);
+
+    let header_labels = Row::new()
+        .spacing(10)
+        .padding([6, 0])
+        .push(
+            container(text(translations.get("extract_variable_label")).size(12))
+                .width(Length::FillPortion(2)),
+        )
+        .push(
+            container(text(translations.get("extract_source_label")).size(12))
+                .width(Length::Fixed(110.0)),
+        )
+        .push(
+            container(text(translations.get("extract_expression_label")).size(12))
+                .width(Length::FillPortion(3)),
+        )
+        .push(
+            container(text(translations.get("extract_scope_label")).size(12))
+                .width(Length::Fixed(120.0)),
+        )
+        .push(container(text("").size(12)).width(Length::Fixed(32.0)));
+
+    for (idx, rule) in rules.iter().enumerate() {
+        let variable_input = text_input(
+            translations.get("extract_variable_placeholder"),
+            &rule.variable,
+        )
+        .on_input(move |v| Message::ExtractionVariableChanged(idx, v))
+        .padding([8, 10])
+        .size(12);
+
+        let source_picker = pick_list(Some(rule.source), ExtractionSource::all(), |source| {
+            source.as_str().to_string()
+        })
+        .on_select(move |source| Message::ExtractionSourceChanged(idx, source))
+        .padding([6, 8])
+        .width(Length::Fixed(110.0));
+
+        let expression_placeholder = match rule.source {
+            ExtractionSource::JsonPath => "$.data.token",
+            ExtractionSource::Header => "X-Auth-Token",
+            ExtractionSource::Cookie => "session_id",
+            ExtractionSource::Regex => "token=(\\w+)",
+        };
+        let expression_input = text_input(expression_placeholder, &rule.expression)
+            .on_input(move |v| Message::ExtractionExpressionChanged(idx, v))
+            .padding([8, 10])
+            .size(12);
+
+        let scope_picker = pick_list(Some(rule.scope), VariableScope::all(), |scope| {
+            scope.as_str().to_string()
+        })
+        .on_select(move |scope| Message::ExtractionScopeChanged(idx, scope))
+        .padding([6, 8])
+        .width(Length::Fixed(120.0));
+
+        let remove_button = button(text("×").size(14))
+            .on_press(Message::RemoveExtraction(idx))
+            .padding([6, 8])
+            .style(button::text);
+
+        let row = Row::new()
+            .spacing(10)
+            .align_y(Alignment::Center)
+            .push(container(variable_input).width(Length::FillPortion(2)))
+            .push(source_picker)
+            .push(container(expression_input).width(Length::FillPortion(3)))
+            .push(scope_picker)
+            .push(remove_button);
+
+        rows = rows.push(row);
+    }
+
+    let rows_scrollable =
+        scrollable(rows)
+            .height(Length::Fill)
+            .direction(scrollable::Direction::Vertical(
+                scrollable::Scrollbar::new().width(8).scroller_width(8),
+            ));
+
+    let add_button = button(text(translations.get("add_extraction")).size(12))
+        .on_press(Message::AddExtraction)
+        .padding([8, 12])
+        .style(button::secondary);
+
+    let hint = text(translations.get("extract_hint"))
+        .size(11)
+        .color(iced::Color::from_rgb(0.5, 0.5, 0.5));
+
+    let content = Column::new()
+        .spacing(8)
+        .height(Length::Fill)
+        .push(header_labels)
+        .push(container(rows_scrollable).height(Length::Fill))
+        .push(
+            Row::new()
+                .spacing(12)
+                .align_y(Alignment::Center)
+                .push(add_button)
+                .push(hint),
+        );
+
+    container(content)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .padding(iced::Padding {
+            top: 10.0,
+            right: 16.0,
+            bottom: 10.0,
+            left: 16.0,
+        })
+        .into()
+}
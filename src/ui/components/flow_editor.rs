@@ -0,0 +1,379 @@
+use iced::widget::{button, container, pick_list, scrollable, text, text_input, Column, Row};
+use iced::{Alignment, Color, Element, Length};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::app::message::{ConditionEdit, FlowStepEdit};
+use crate::app::state::FlowEditorState;
+use crate::app::Message;
+use crate::i18n::Translations;
+use crate::models::{
+    ConditionOperator, ConditionSource, FlowCondition, FlowReport, FlowStep, FlowTarget,
+};
+
+use super::dialog;
+
+/// Flow editor dialog: the chain of steps on top, the run log below
+pub fn view<'a>(
+    editor: &'a FlowEditorState,
+    translations: &'a Translations,
+) -> Element<'a, Message> {
+    let request_names: HashMap<Uuid, String> = editor
+        .requests
+        .iter()
+        .map(|r| (r.id, format!("{} {}", r.method.as_str(), r.name)))
+        .collect();
+    let request_ids: Vec<Uuid> = editor.requests.iter().map(|r| r.id).collect();
+
+    let mut targets = vec![FlowTarget::Next, FlowTarget::End];
+    targets.extend(editor.flow.steps.iter().map(|s| FlowTarget::Step(s.id)));
+    let step_numbers: HashMap<Uuid, usize> = editor
+        .flow
+        .steps
+        .iter()
+        .enumerate()
+        .map(|(idx, s)| (s.id, idx + 1))
+        .collect();
+
+    let mut steps = Column::new().spacing(10);
+    for (idx, step) in editor.flow.steps.iter().enumerate() {
+        steps = steps.push(step_view(
+            idx,
+            step,
+            &request_ids,
+            &request_names,
+            &targets,
+            &step_numbers,
+            translations,
+        ));
+
+        // Arrow between steps to show the default chain
+        if idx + 1 < editor.flow.steps.len() {
+            steps = steps.push(
+                container(text("↓").size(14).color(Color::from_rgb(0.5, 0.5, 0.5)))
+                    .width(Length::Fill)
+                    .center_x(Length::Fill),
+            );
+        }
+    }
+
+    if editor.flow.steps.is_empty() {
+        steps = steps.push(
+            text(translations.get("flow_empty"))
+                .size(13)
+                .color(Color::from_rgb(0.5, 0.5, 0.5)),
+        );
+    }
+
+    let add_step = button(text(translations.get("flow_add_step")).size(12))
+        .on_press(Message::AddFlowStep)
+        .padding([6, 12])
+        .style(button::secondary);
+
+    let mut content = Column::new()
+        .spacing(12)
+        .push(
+            text(&editor.flow.name)
+                .size(14)
+                .color(Color::from_rgb(0.4, 0.4, 0.4)),
+        )
+        .push(steps)
+        .push(add_step);
+
+    if editor.running {
+        content = content.push(
+            text(translations.get("flow_running"))
+                .size(13)
+                .color(Color::from_rgb(0.4, 0.4, 0.4)),
+        );
+    } else if let Some(report) = &editor.report {
+        content = content.push(log_view(report, translations));
+    }
+
+    let buttons = vec![
+        (
+            translations.get("close").to_string(),
+            Message::CloseFlowEditor,
+            button::secondary as fn(&iced::Theme, button::Status) -> button::Style,
+        ),
+        (
+            translations.get("save").to_string(),
+            Message::SaveFlow,
+            button::secondary as fn(&iced::Theme, button::Status) -> button::Style,
+        ),
+        (
+            translations.get("flow_run").to_string(),
+            Message::RunFlow,
+            button::primary as fn(&iced::Theme, button::Status) -> button::Style,
+        ),
+    ];
+
+    dialog::view_with_buttons(
+        translations.get("flow_dialog_title"),
+        scrollable(content).height(Length::Fill).into(),
+        buttons,
+        860.0,
+        640.0,
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+fn step_view<'a>(
+    idx: usize,
+    step: &'a FlowStep,
+    request_ids: &[Uuid],
+    request_names: &HashMap<Uuid, String>,
+    targets: &[FlowTarget],
+    step_numbers: &HashMap<Uuid, usize>,
+    translations: &'a Translations,
+) -> Element<'a, Message> {
+    let names = request_names.clone();
+    let request_picker = pick_list(Some(step.request_id), request_ids.to_vec(), move |id| {
+        names
+            .get(id)
+            .cloned()
+            .unwrap_or_else(|| "Missing request".to_string())
+    })
+    .on_select(move |id| Message::FlowStepEdited(idx, FlowStepEdit::Request(id)))
+    .padding([6, 8])
+    .width(Length::Fill);
+
+    let wait_input = text_input("0", &step.wait_ms.to_string())
+        .on_input(move |v| Message::FlowStepEdited(idx, FlowStepEdit::Wait(v)))
+        .padding([6, 8])
+        .size(12)
+        .width(Length::Fixed(70.0));
+
+    let toggle = |label: &'a str, active: bool, edit: FlowStepEdit| {
+        button(text(label).size(12))
+            .on_press(Message::FlowStepEdited(idx, edit))
+            .padding([6, 10])
+            .style(if active {
+                button::primary
+            } else {
+                button::secondary
+            })
+    };
+
+    let header = Row::new()
+        .spacing(8)
+        .align_y(Alignment::Center)
+        .push(
+            text(format!("{}", idx + 1))
+                .size(14)
+                .width(Length::Fixed(20.0)),
+        )
+        .push(request_picker)
+        .push(text(translations.get("flow_wait")).size(12))
+        .push(wait_input)
+        .push(toggle(
+            translations.get("flow_loop"),
+            step.repeat.is_some(),
+            FlowStepEdit::ToggleLoop,
+        ))
+        .push(toggle(
+            translations.get("flow_branch"),
+            step.branch.is_some(),
+            FlowStepEdit::ToggleBranch,
+        ))
+        .push(
+            button(text("↑").size(14))
+                .on_press(Message::MoveFlowStepUp(idx))
+                .padding([4, 8])
+                .style(button::text),
+        )
+        .push(
+            button(text("×").size(14))
+                .on_press(Message::RemoveFlowStep(idx))
+                .padding([4, 8])
+                .style(button::text),
+        );
+
+    let mut card = Column::new().spacing(8).push(header);
+
+    if let Some(repeat) = &step.repeat {
+        let row = Row::new()
+            .spacing(8)
+            .align_y(Alignment::Center)
+            .push(text(translations.get("flow_until")).size(12))
+            .push(condition_view(&repeat.until, move |edit| {
+                Message::FlowStepEdited(idx, FlowStepEdit::LoopCondition(edit))
+            }))
+            .push(text(translations.get("flow_max_attempts")).size(12))
+            .push(
+                text_input("10", &repeat.max_attempts.to_string())
+                    .on_input(move |v| Message::FlowStepEdited(idx, FlowStepEdit::MaxAttempts(v)))
+                    .padding([6, 8])
+                    .size(12)
+                    .width(Length::Fixed(50.0)),
+            )
+            .push(text(translations.get("flow_interval")).size(12))
+            .push(
+                text_input("1000", &repeat.interval_ms.to_string())
+                    .on_input(move |v| Message::FlowStepEdited(idx, FlowStepEdit::Interval(v)))
+                    .padding([6, 8])
+                    .size(12)
+                    .width(Length::Fixed(70.0)),
+            );
+        card = card.push(container(row).padding([0, 28]));
+    }
+
+    if let Some(branch) = &step.branch {
+        let target_label = {
+            let next = translations.get("flow_target_next").to_string();
+            let end = translations.get("flow_target_end").to_string();
+            let step_label = translations.get("flow_target_step").to_string();
+            let numbers = step_numbers.clone();
+            move |target: &FlowTarget| match target {
+                FlowTarget::Next => next.clone(),
+                FlowTarget::End => end.clone(),
+                FlowTarget::Step(id) => match numbers.get(id) {
+                    Some(number) => format!("{} {}", step_label, number),
+                    None => "?".to_string(),
+                },
+            }
+        };
+        let on_true_label = target_label.clone();
+
+        let row = Row::new()
+            .spacing(8)
+            .align_y(Alignment::Center)
+            .push(text(translations.get("flow_if")).size(12))
+            .push(condition_view(&branch.condition, move |edit| {
+                Message::FlowStepEdited(idx, FlowStepEdit::BranchCondition(edit))
+            }))
+            .push(text(translations.get("flow_then")).size(12))
+            .push(
+                pick_list(Some(branch.on_true), targets.to_vec(), on_true_label)
+                    .on_select(move |t| Message::FlowStepEdited(idx, FlowStepEdit::OnTrue(t)))
+                    .padding([6, 8]),
+            )
+            .push(text(translations.get("flow_else")).size(12))
+            .push(
+                pick_list(Some(branch.on_false), targets.to_vec(), target_label)
+                    .on_select(move |t| Message::FlowStepEdited(idx, FlowStepEdit::OnFalse(t)))
+                    .padding([6, 8]),
+            );
+        card = card.push(container(row).padding([0, 28]));
+    }
+
+    container(card)
+        .padding(10)
+        .width(Length::Fill)
+        .style(|_theme| container::Style {
+            background: Some(iced::Background::Color(Color::from_rgb(0.98, 0.98, 0.98))),
+            border: iced::Border {
+                color: Color::from_rgb(0.85, 0.85, 0.85),
+                width: 1.0,
+                radius: 6.0.into(),
+            },
+            ..Default::default()
+        })
+        .into()
+}
+
+/// Source, variable, operator and value inputs of a condition
+fn condition_view<'a>(
+    condition: &'a FlowCondition,
+    on_edit: impl Fn(ConditionEdit) -> Message + Clone + 'a,
+) -> Element<'a, Message> {
+    let on_source = on_edit.clone();
+    let on_variable = on_edit.clone();
+    let on_operator = on_edit.clone();
+
+    let mut row = Row::new().spacing(6).align_y(Alignment::Center).push(
+        pick_list(Some(condition.source), ConditionSource::all(), |source| {
+            source.as_str().to_string()
+        })
+        .on_select(move |source| on_source(ConditionEdit::Source(source)))
+        .padding([6, 8]),
+    );
+
+    if condition.source == ConditionSource::Variable {
+        row = row.push(
+            text_input("state", &condition.variable)
+                .on_input(move |v| on_variable(ConditionEdit::Variable(v)))
+                .padding([6, 8])
+                .size(12)
+                .width(Length::Fixed(90.0)),
+        );
+    }
+
+    row = row.push(
+        pick_list(
+            Some(condition.operator),
+            ConditionOperator::all(),
+            |operator| operator.as_str().to_string(),
+        )
+        .on_select(move |operator| on_operator(ConditionEdit::Operator(operator)))
+        .padding([6, 8]),
+    );
+
+    if condition.operator != ConditionOperator::Exists {
+        row = row.push(
+            text_input("200", &condition.value)
+                .on_input(move |v| on_edit(ConditionEdit::Value(v)))
+                .padding([6, 8])
+                .size(12)
+                .width(Length::Fixed(90.0)),
+        );
+    }
+
+    row.into()
+}
+
+/// Step-by-step log of the last execution
+fn log_view<'a>(report: &'a FlowReport, translations: &'a Translations) -> Element<'a, Message> {
+    let (summary, color) = if report.passed {
+        (
+            translations.get("flow_passed"),
+            Color::from_rgb(0.2, 0.6, 0.3),
+        )
+    } else {
+        (
+            translations.get("flow_failed"),
+            Color::from_rgb(0.8, 0.2, 0.2),
+        )
+    };
+
+    let mut log = Column::new().spacing(4).push(
+        text(format!("{} · {} ms", summary, report.duration_ms))
+            .size(13)
+            .color(color),
+    );
+
+    for entry in &report.log {
+        let line = match &entry.result {
+            Some(result) => {
+                let (mark, color) = if result.passed() {
+                    ("✓", Color::from_rgb(0.2, 0.6, 0.3))
+                } else {
+                    ("✗", Color::from_rgb(0.8, 0.2, 0.2))
+                };
+                let status = result
+                    .status
+                    .map(|s| s.to_string())
+                    .or_else(|| result.error.clone())
+                    .unwrap_or_default();
+                Row::new()
+                    .spacing(8)
+                    .push(text(format!("#{}", entry.step + 1)).size(12))
+                    .push(text(mark).size(12).color(color))
+                    .push(text(&entry.message).size(12).width(Length::Fill))
+                    .push(text(status).size(12))
+                    .push(text(format!("{} ms", result.time_ms)).size(12))
+            }
+            None => Row::new()
+                .spacing(8)
+                .push(text(format!("#{}", entry.step + 1)).size(12))
+                .push(
+                    text(&entry.message)
+                        .size(12)
+                        .color(Color::from_rgb(0.45, 0.45, 0.45)),
+                ),
+        };
+        log = log.push(line);
+    }
+
+    log.into()
+}
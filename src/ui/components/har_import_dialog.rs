@@ -0,0 +1,92 @@
+use iced::widget::{button, container, scrollable, text, Column, Row};
+use iced::{Color, Element, Length};
+
+use crate::app::state::HarImportState;
+use crate::app::Message;
+use crate::i18n::Translations;
+
+use super::dialog;
+
+/// Choose which domains and resource types of a HAR archive to import
+pub fn view<'a>(state: &'a HarImportState, translations: &'a Translations) -> Element<'a, Message> {
+    let archive = &state.archive;
+    let filter = &state.filter;
+    let included = archive
+        .entries
+        .iter()
+        .filter(|e| filter.includes(e))
+        .count();
+
+    let mut types = Row::new().spacing(4);
+    for (resource_type, count) in archive.resource_types() {
+        let on = !filter.excluded_types.contains(&resource_type);
+        types = types.push(toggle(
+            format!("{} ({})", resource_type, count),
+            on,
+            Message::ToggleHarResourceType(resource_type),
+        ));
+    }
+
+    let mut domains = Column::new().spacing(4);
+    for (domain, count) in archive.domains() {
+        let on = !filter.excluded_domains.contains(&domain);
+        domains = domains.push(toggle(
+            format!("{} ({})", domain, count),
+            on,
+            Message::ToggleHarDomain(domain),
+        ));
+    }
+
+    let content = Column::new()
+        .spacing(10)
+        .push(label(format!(
+            "{} \"{}\": {} / {}",
+            translations.get("har_import_entries"),
+            archive.name,
+            included,
+            archive.entries.len()
+        )))
+        .push(label(translations.get("har_import_types").to_string()))
+        .push(
+            scrollable(types).direction(scrollable::Direction::Horizontal(
+                scrollable::Scrollbar::default(),
+            )),
+        )
+        .push(label(translations.get("har_import_domains").to_string()))
+        .push(scrollable(domains).height(Length::Fill));
+
+    dialog::view_with_buttons(
+        translations.get("har_import_title"),
+        container(content).height(Length::Fill).into(),
+        vec![
+            (
+                translations.get("cancel").to_string(),
+                Message::CloseHarImport,
+                button::secondary as fn(&iced::Theme, button::Status) -> button::Style,
+            ),
+            (
+                translations.get("har_import_button").to_string(),
+                Message::ConfirmHarImport,
+                button::primary as fn(&iced::Theme, button::Status) -> button::Style,
+            ),
+        ],
+        640.0,
+        480.0,
+    )
+}
+
+/// Included values are highlighted; pressing one leaves it out again
+fn toggle<'a>(label: String, on: bool, message: Message) -> Element<'a, Message> {
+    button(text(label).size(12))
+        .on_press(message)
+        .padding([6, 12])
+        .style(if on { button::primary } else { button::text })
+        .into()
+}
+
+fn label<'a>(value: String) -> Element<'a, Message> {
+    text(value)
+        .size(13)
+        .color(Color::from_rgb(0.4, 0.4, 0.4))
+        .into()
+}
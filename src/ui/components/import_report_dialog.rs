@@ -0,0 +1,79 @@
+use iced::widget::{button, container, scrollable, text, Column, Row};
+use iced::{Color, Element, Length};
+
+use crate::app::Message;
+use crate::i18n::Translations;
+use crate::models::ImportReport;
+
+use super::dialog;
+
+/// Summary of an import, listing everything that was not carried over
+pub fn view<'a>(report: &'a ImportReport, translations: &'a Translations) -> Element<'a, Message> {
+    let mut summary = Row::new()
+        .spacing(16)
+        .push(text(format!("{} \"{}\"", report.source, report.name)).size(14))
+        .push(count(translations.get("import_requests"), report.requests));
+    if report.updated > 0 {
+        summary = summary.push(count(translations.get("import_updated"), report.updated));
+    }
+    let summary = summary
+        .push(count(translations.get("import_folders"), report.folders))
+        .push(count(
+            translations.get("import_variables"),
+            report.variables,
+        ));
+
+    let mut content = Column::new().spacing(12).push(summary);
+
+    if report.issues.is_empty() {
+        content = content.push(
+            text(translations.get("import_no_issues"))
+                .size(13)
+                .color(Color::from_rgb(0.2, 0.6, 0.3)),
+        );
+    } else {
+        content = content.push(
+            text(format!(
+                "{} ({})",
+                translations.get("import_issues"),
+                report.issues.len()
+            ))
+            .size(13)
+            .color(Color::from_rgb(0.8, 0.5, 0.1)),
+        );
+
+        let mut issues = Column::new().spacing(6);
+        for issue in &report.issues {
+            issues = issues.push(
+                Column::new()
+                    .spacing(2)
+                    .push(
+                        text(&issue.location)
+                            .size(12)
+                            .color(Color::from_rgb(0.4, 0.4, 0.4)),
+                    )
+                    .push(text(&issue.message).size(13)),
+            );
+        }
+        content = content.push(scrollable(issues).height(Length::Fill));
+    }
+
+    dialog::view_with_buttons(
+        translations.get("import_report_title"),
+        container(content).height(Length::Fill).into(),
+        vec![(
+            translations.get("close").to_string(),
+            Message::CloseImportReport,
+            button::primary as fn(&iced::Theme, button::Status) -> button::Style,
+        )],
+        640.0,
+        480.0,
+    )
+}
+
+fn count<'a>(label: &'a str, value: usize) -> Element<'a, Message> {
+    text(format!("{}: {}", label, value))
+        .size(13)
+        .color(Color::from_rgb(0.4, 0.4, 0.4))
+        .into()
+}
@@ -27,46 +27,54 @@ pub struct FormBodyEditorConfig<'a> {
 
 /// A reusable key-value pair editor component with add/remove functionality
 ///
-/// Used for editing params, headers, cookies, auth fields, and form data
+/// Values can be marked secret, which masks them. Used for editing params,
+/// headers, cookies and auth fields
 #[allow(clippy::too_many_arguments)]
-pub fn view<'a, Message: Clone + 'a, T, F1, F2, F3, F4, F5>(
-    items: &'a [T],
+pub fn view<'a, Message: Clone + 'a, F1, F2, F3, F4>(
+    items: &'a [KeyValue],
     config: KeyValueEditorConfig<'a>,
-    get_key: F1,
-    get_value: F2,
-    on_key_changed: F3,
-    on_value_changed: F4,
-    on_remove: F5,
+    secret_label: &'a str,
+    on_key_changed: F1,
+    on_value_changed: F2,
+    on_toggle_secret: F3,
+    on_remove: F4,
     on_add: Message,
 ) -> Element<'a, Message>
 where
-    F1: Fn(&T) -> &str + 'a,
-    F2: Fn(&T) -> &str + 'a,
-    F3: Fn(usize, String) -> Message + 'a + Copy,
-    F4: Fn(usize, String) -> Message + 'a + Copy,
-    F5: Fn(usize) -> Message + 'a + Copy,
+    F1: Fn(usize, String) -> Message + 'a + Copy,
+    F2: Fn(usize, String) -> Message + 'a + Copy,
+    F3: Fn(usize) -> Message + 'a + Copy,
+    F4: Fn(usize) -> Message + 'a + Copy,
 {
     let mut rows = Column::new().spacing(10);
 
-    // Header row with column labels
     let header_labels = Row::new()
         .spacing(16)
         .padding([8, 0])
         .push(container(text(config.key_label).size(12)).width(Length::FillPortion(1)))
         .push(container(text(config.value_label).size(12)).width(Length::FillPortion(2)))
-        .push(container(text("").size(12)).width(Length::Fixed(40.0)));
+        .push(container(text("").size(12)).width(Length::Fixed(110.0)));
 
-    // Data rows
     for (idx, item) in items.iter().enumerate() {
-        let key_input = text_input(config.key_placeholder, get_key(item))
+        let key_input = text_input(config.key_placeholder, &item.key)
             .on_input(move |v| on_key_changed(idx, v))
             .padding(10)
             .size(13);
 
-        let value_input = text_input(config.value_placeholder, get_value(item))
+        let value_input = text_input(config.value_placeholder, &item.value)
             .on_input(move |v| on_value_changed(idx, v))
             .padding(10)
-            .size(13);
+            .size(13)
+            .secure(item.secret);
+
+        let secret_button = button(text(secret_label).size(12))
+            .on_press(on_toggle_secret(idx))
+            .padding([8, 10])
+            .style(if item.secret {
+                button::primary
+            } else {
+                button::secondary
+            });
 
         let remove_button = button(text("×").size(16))
             .on_press(on_remove(idx))
@@ -78,6 +86,7 @@ where
             .align_y(Alignment::Center)
             .push(container(key_input).width(Length::FillPortion(1)))
             .push(container(value_input).width(Length::FillPortion(2)))
+            .push(secret_button)
             .push(remove_button);
 
         rows = rows.push(row);
@@ -89,7 +98,6 @@ where
             scrollable::Scrollbar::new().width(8).scroller_width(8),
         ));
 
-    // Add button
     let add_button = button(text(config.add_button_text).size(13))
         .on_press(on_add)
         .padding([10, 16])
@@ -0,0 +1,146 @@
+use iced::widget::{button, container, scrollable, text, text_input, Column, Row};
+use iced::{Alignment, Color, Element, Length};
+
+use crate::app::state::MockServerState;
+use crate::app::Message;
+use crate::i18n::Translations;
+use crate::models::MockHit;
+
+use super::dialog;
+
+/// Mock server dialog: port and latency, server status and the hit log
+pub fn view<'a>(
+    state: &'a MockServerState,
+    translations: &'a Translations,
+) -> Element<'a, Message> {
+    // Settings only apply when the server starts
+    let port_input = if state.running {
+        text_input("3030", &state.port)
+    } else {
+        text_input("3030", &state.port).on_input(Message::MockPortChanged)
+    }
+    .padding([8, 10])
+    .size(13)
+    .width(Length::Fixed(80.0));
+
+    let latency_input = if state.running {
+        text_input("0", &state.latency_ms)
+    } else {
+        text_input("0", &state.latency_ms).on_input(Message::MockLatencyChanged)
+    }
+    .padding([8, 10])
+    .size(13)
+    .width(Length::Fixed(80.0));
+
+    let options_row = Row::new()
+        .spacing(10)
+        .align_y(Alignment::Center)
+        .push(text(translations.get("mock_port")).size(13))
+        .push(port_input)
+        .push(text(translations.get("mock_latency")).size(13))
+        .push(latency_input);
+
+    let status = if state.running {
+        text(format!(
+            "● {} http://127.0.0.1:{}",
+            translations.get("mock_listening"),
+            state.port
+        ))
+        .size(13)
+        .color(Color::from_rgb(0.2, 0.6, 0.3))
+    } else {
+        text(format!("○ {}", translations.get("mock_stopped")))
+            .size(13)
+            .color(Color::from_rgb(0.5, 0.5, 0.5))
+    };
+
+    let hint = text(translations.get("mock_hint"))
+        .size(12)
+        .color(Color::from_rgb(0.5, 0.5, 0.5));
+
+    let mut hits = Column::new().spacing(4);
+    if state.hits.is_empty() {
+        hits = hits.push(
+            text(translations.get("mock_no_hits"))
+                .size(12)
+                .color(Color::from_rgb(0.5, 0.5, 0.5)),
+        );
+    }
+    for hit in state.hits.iter().rev() {
+        hits = hits.push(hit_row(hit));
+    }
+
+    let content = Column::new()
+        .spacing(12)
+        .push(
+            text(&state.name)
+                .size(14)
+                .color(Color::from_rgb(0.4, 0.4, 0.4)),
+        )
+        .push(options_row)
+        .push(status)
+        .push(hint)
+        .push(text(translations.get("mock_hits")).size(13))
+        .push(scrollable(hits).height(Length::Fill));
+
+    let mut buttons = vec![
+        (
+            translations.get("close").to_string(),
+            Message::CloseMockServerDialog,
+            button::secondary as fn(&iced::Theme, button::Status) -> button::Style,
+        ),
+        (
+            translations.get("mock_clear_log").to_string(),
+            Message::ClearMockHits,
+            button::secondary as fn(&iced::Theme, button::Status) -> button::Style,
+        ),
+    ];
+    if state.running {
+        buttons.push((
+            translations.get("mock_stop").to_string(),
+            Message::StopMockServer,
+            button::danger as fn(&iced::Theme, button::Status) -> button::Style,
+        ));
+    } else {
+        buttons.push((
+            translations.get("mock_start").to_string(),
+            Message::StartMockServer,
+            button::primary as fn(&iced::Theme, button::Status) -> button::Style,
+        ));
+    }
+
+    dialog::view_with_buttons(
+        translations.get("mock_dialog_title"),
+        content.into(),
+        buttons,
+        760.0,
+        560.0,
+    )
+}
+
+fn hit_row<'a>(hit: &'a MockHit) -> Element<'a, Message> {
+    let status_color = match hit.status {
+        200..=399 => Color::from_rgb(0.2, 0.6, 0.3),
+        400..=499 => Color::from_rgb(0.9, 0.6, 0.2),
+        _ => Color::from_rgb(0.8, 0.2, 0.2),
+    };
+
+    let row = Row::new()
+        .spacing(10)
+        .align_y(Alignment::Center)
+        .push(
+            text(&hit.time)
+                .size(12)
+                .color(Color::from_rgb(0.5, 0.5, 0.5)),
+        )
+        .push(text(&hit.method).size(12).width(Length::Fixed(60.0)))
+        .push(text(&hit.path).size(12).width(Length::Fill))
+        .push(
+            text(hit.matched.as_deref().unwrap_or("—"))
+                .size(12)
+                .color(Color::from_rgb(0.4, 0.4, 0.4)),
+        )
+        .push(text(hit.status.to_string()).size(12).color(status_color));
+
+    container(row).padding([2, 4]).into()
+}
@@ -1,15 +1,29 @@
 pub mod ai_engine_picker;
 pub mod ai_fill_dialog;
+pub mod bar_chart;
+pub mod blast_dialog;
+pub mod code_dialog;
 pub mod code_editor;
 pub mod context_menu;
 pub mod dialog;
 pub mod environment_dialog;
 pub mod environment_picker;
+pub mod export_dialog;
+pub mod extraction_editor;
+pub mod flow_editor;
+pub mod har_import_dialog;
+pub mod import_report_dialog;
 pub mod key_value_editor;
 pub mod language_picker;
 pub mod method_picker;
+pub mod mock_server_dialog;
 pub mod option_buttons;
+pub mod recovery_dialog;
+pub mod reload_conflict_dialog;
+pub mod runner_dialog;
+pub mod schema_editor;
 pub mod settings_dialog;
 pub mod shortcuts_dialog;
 pub mod tabs_bar;
 pub mod textarea;
+pub mod unlock_secrets_dialog;
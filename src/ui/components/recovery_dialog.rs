@@ -0,0 +1,87 @@
+use std::path::Path;
+
+use iced::widget::{button, container, scrollable, text, Column, Row};
+use iced::{Color, Element, Length};
+
+use crate::app::Message;
+use crate::i18n::Translations;
+use crate::storage::LoadFailure;
+
+use super::dialog;
+
+/// Collection files that could not be read, with ways to recover them
+pub fn view<'a>(
+    failures: &'a [LoadFailure],
+    save_directory: &'a str,
+    translations: &'a Translations,
+) -> Element<'a, Message> {
+    let mut files = Column::new().spacing(12);
+    for failure in failures {
+        let path = failure
+            .path
+            .strip_prefix(Path::new(save_directory))
+            .unwrap_or(&failure.path);
+
+        let mut actions = Row::new().spacing(6).push(
+            button(text(translations.get("recovery_open_file")).size(12))
+                .on_press(Message::OpenRawFile(failure.path.clone()))
+                .padding([4, 10])
+                .style(button::secondary),
+        );
+        for backup in &failure.backups {
+            actions = actions.push(
+                button(
+                    text(format!(
+                        "{} {}",
+                        translations.get("recovery_restore"),
+                        backup.replaced_at
+                    ))
+                    .size(12),
+                )
+                .on_press(Message::RestoreBackup(
+                    failure.path.clone(),
+                    backup.path.clone(),
+                ))
+                .padding([4, 10])
+                .style(button::primary),
+            );
+        }
+        if failure.backups.is_empty() {
+            actions = actions.push(
+                text(translations.get("recovery_no_backups"))
+                    .size(12)
+                    .color(Color::from_rgb(0.4, 0.4, 0.4)),
+            );
+        }
+
+        files = files.push(
+            Column::new()
+                .spacing(4)
+                .push(text(path.display().to_string()).size(13))
+                .push(
+                    text(&failure.error)
+                        .size(12)
+                        .color(Color::from_rgb(0.75, 0.2, 0.2)),
+                )
+                .push(
+                    scrollable(actions).direction(scrollable::Direction::Horizontal(
+                        scrollable::Scrollbar::default(),
+                    )),
+                ),
+        );
+    }
+
+    let content = Column::new()
+        .spacing(12)
+        .push(text(translations.get("recovery_message")).size(13))
+        .push(scrollable(files).height(Length::Fill));
+
+    dialog::view(
+        translations.get("recovery_title"),
+        container(content).height(Length::Fill).into(),
+        translations.get("close"),
+        Message::CloseRecoveryDialog,
+        640.0,
+        480.0,
+    )
+}
@@ -0,0 +1,90 @@
+use iced::widget::{button, container, scrollable, text, Column};
+use iced::{Color, Element, Font, Length};
+
+use crate::app::state::ReloadConflictState;
+use crate::app::Message;
+use crate::i18n::Translations;
+use crate::models::DiffLine;
+use crate::utils::reload;
+
+use super::dialog;
+
+/// Ask whether to keep the edits made here or the requests changed on disk
+pub fn view<'a>(
+    state: &'a ReloadConflictState,
+    translations: &'a Translations,
+) -> Element<'a, Message> {
+    let mut content = Column::new().spacing(8).push(
+        text(format!(
+            "{} \"{}\"",
+            translations.get("reload_conflict_message"),
+            state.collection.name
+        ))
+        .size(13),
+    );
+
+    for conflict in &state.conflicts {
+        let name = if conflict.theirs.is_some() {
+            conflict.name.clone()
+        } else {
+            format!("{} ({})", conflict.name, translations.get("reload_deleted"))
+        };
+        content = content.push(text(format!("• {}", name)).size(13));
+
+        if state.show_diff {
+            let mut lines = Column::new();
+            for line in reload::diff(conflict) {
+                let (prefix, value, color) = match line {
+                    DiffLine::Both(value) => ("  ", value, Color::from_rgb(0.4, 0.4, 0.4)),
+                    DiffLine::Mine(value) => ("- ", value, Color::from_rgb(0.75, 0.2, 0.2)),
+                    DiffLine::Theirs(value) => ("+ ", value, Color::from_rgb(0.15, 0.55, 0.25)),
+                };
+                lines = lines.push(
+                    text(format!("{}{}", prefix, value))
+                        .size(12)
+                        .font(Font::MONOSPACE)
+                        .color(color),
+                );
+            }
+            content = content.push(container(lines).padding([4, 12]));
+        }
+    }
+
+    if state.show_diff {
+        content = content.push(
+            text(translations.get("reload_diff_legend"))
+                .size(12)
+                .color(Color::from_rgb(0.4, 0.4, 0.4)),
+        );
+    }
+
+    let diff_label = if state.show_diff {
+        translations.get("reload_hide_diff")
+    } else {
+        translations.get("reload_view_diff")
+    };
+
+    dialog::view_with_buttons(
+        translations.get("reload_conflict_title"),
+        scrollable(content).height(Length::Fill).into(),
+        vec![
+            (
+                diff_label.to_string(),
+                Message::ToggleReloadDiff,
+                button::text as fn(&iced::Theme, button::Status) -> button::Style,
+            ),
+            (
+                translations.get("reload_take_theirs").to_string(),
+                Message::TakeTheirChanges,
+                button::secondary as fn(&iced::Theme, button::Status) -> button::Style,
+            ),
+            (
+                translations.get("reload_keep_mine").to_string(),
+                Message::KeepMyChanges,
+                button::primary as fn(&iced::Theme, button::Status) -> button::Style,
+            ),
+        ],
+        640.0,
+        480.0,
+    )
+}
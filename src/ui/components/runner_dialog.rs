@@ -0,0 +1,253 @@
+use iced::widget::{button, container, scrollable, text, text_input, Column, Row};
+use iced::{Alignment, Color, Element, Length};
+
+use crate::app::state::RunnerState;
+use crate::app::Message;
+use crate::i18n::Translations;
+use crate::models::{RequestRunResult, RunReport};
+
+use super::dialog;
+
+/// Collection runner dialog: run options on top, the last report below
+pub fn view<'a>(runner: &'a RunnerState, translations: &'a Translations) -> Element<'a, Message> {
+    // A data file decides the iteration count, so the input is read-only then
+    let iterations_input = if runner.data_rows.is_empty() {
+        text_input("1", &runner.iterations).on_input(Message::RunnerIterationsChanged)
+    } else {
+        text_input("", &runner.iterations)
+    }
+    .padding([8, 10])
+    .size(13)
+    .width(Length::Fixed(80.0));
+
+    let delay_input = text_input("0", &runner.delay_ms)
+        .on_input(Message::RunnerDelayChanged)
+        .padding([8, 10])
+        .size(13)
+        .width(Length::Fixed(80.0));
+
+    let stop_toggle = button(text(translations.get("runner_stop_on_failure")).size(12))
+        .on_press(Message::ToggleRunnerStopOnFailure)
+        .padding([6, 10])
+        .style(if runner.stop_on_failure {
+            button::primary
+        } else {
+            button::secondary
+        });
+
+    let options_row = Row::new()
+        .spacing(10)
+        .align_y(Alignment::Center)
+        .push(text(translations.get("runner_iterations")).size(13))
+        .push(iterations_input)
+        .push(text(translations.get("runner_delay")).size(13))
+        .push(delay_input)
+        .push(stop_toggle);
+
+    let mut data_row = Row::new().spacing(10).align_y(Alignment::Center).push(
+        button(text(translations.get("runner_data_file")).size(12))
+            .on_press(Message::SelectRunnerDataFile)
+            .padding([6, 10])
+            .style(button::secondary),
+    );
+    match &runner.data_file {
+        Some(path) => {
+            let file_name = std::path::Path::new(path)
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_else(|| path.clone());
+            data_row = data_row
+                .push(
+                    text(format!(
+                        "{} ({} {})",
+                        file_name,
+                        runner.data_rows.len(),
+                        translations.get("runner_data_rows")
+                    ))
+                    .size(12),
+                )
+                .push(
+                    button(text("×").size(14))
+                        .on_press(Message::ClearRunnerDataFile)
+                        .padding([4, 8])
+                        .style(button::text),
+                );
+        }
+        None => {
+            data_row = data_row.push(
+                text(translations.get("runner_data_hint"))
+                    .size(12)
+                    .color(Color::from_rgb(0.5, 0.5, 0.5)),
+            );
+        }
+    }
+
+    let mut content = Column::new()
+        .spacing(12)
+        .push(
+            text(&runner.name)
+                .size(14)
+                .color(Color::from_rgb(0.4, 0.4, 0.4)),
+        )
+        .push(options_row)
+        .push(data_row);
+
+    if runner.running {
+        content = content.push(
+            text(translations.get("runner_running"))
+                .size(13)
+                .color(Color::from_rgb(0.4, 0.4, 0.4)),
+        );
+    } else if let Some(report) = &runner.report {
+        content = content.push(report_view(report, translations));
+    }
+
+    let mut buttons = vec![(
+        translations.get("close").to_string(),
+        Message::CloseRunnerDialog,
+        button::secondary as fn(&iced::Theme, button::Status) -> button::Style,
+    )];
+    if runner.report.is_some() && !runner.running {
+        buttons.push((
+            translations.get("runner_save_report").to_string(),
+            Message::SaveRunReport,
+            button::secondary as fn(&iced::Theme, button::Status) -> button::Style,
+        ));
+    }
+    buttons.push((
+        translations.get("runner_start").to_string(),
+        Message::StartCollectionRun,
+        button::primary as fn(&iced::Theme, button::Status) -> button::Style,
+    ));
+
+    dialog::view_with_buttons(
+        translations.get("runner_dialog_title"),
+        content.into(),
+        buttons,
+        700.0,
+        560.0,
+    )
+}
+
+/// Summary line followed by one group per iteration
+fn report_view<'a>(report: &'a RunReport, translations: &'a Translations) -> Element<'a, Message> {
+    let mut summary = format!(
+        "{} {} · {} {} · {} ms",
+        report.passed_count(),
+        translations.get("runner_passed"),
+        report.failed_count(),
+        translations.get("runner_failed"),
+        report.duration_ms
+    );
+    if report.aborted {
+        summary.push_str(" · ");
+        summary.push_str(translations.get("runner_aborted"));
+    }
+
+    let failed_iterations = report.failed_iterations();
+    if !report.data_rows.is_empty() && !failed_iterations.is_empty() {
+        let numbers = failed_iterations
+            .iter()
+            .map(|n| n.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        summary.push_str(&format!(
+            " · {}: {}",
+            translations.get("runner_failed_rows"),
+            numbers
+        ));
+    }
+
+    let mut rows = Column::new().spacing(10);
+    for group in report.iterations() {
+        let passed = group.passed();
+        let mut header = Row::new().spacing(8).push(
+            text(format!(
+                "{} #{}",
+                translations.get("runner_iteration"),
+                group.iteration
+            ))
+            .size(13),
+        );
+        if let Some(data) = group.data {
+            let mut columns: Vec<_> = data.iter().collect();
+            columns.sort();
+            let summary = columns
+                .iter()
+                .map(|(k, v)| format!("{}={}", k, v))
+                .collect::<Vec<_>>()
+                .join(", ");
+            header = header.push(text(summary).size(12).color(Color::from_rgb(0.4, 0.4, 0.4)));
+        }
+
+        let mut entries = Column::new().spacing(6).push(header);
+        for result in group.results {
+            entries = entries.push(result_view(result));
+        }
+
+        // Failed rows get a tinted background so they stand out in long runs
+        rows = rows.push(
+            container(entries)
+                .padding(8)
+                .width(Length::Fill)
+                .style(move |_theme| container::Style {
+                    background: (!passed)
+                        .then_some(iced::Background::Color(Color::from_rgb(1.0, 0.95, 0.95))),
+                    border: iced::Border {
+                        color: Color::from_rgb(0.9, 0.9, 0.9),
+                        width: 1.0,
+                        radius: 4.0.into(),
+                    },
+                    ..Default::default()
+                }),
+        );
+    }
+
+    Column::new()
+        .spacing(8)
+        .push(text(summary).size(13))
+        .push(scrollable(rows).height(Length::Fill))
+        .into()
+}
+
+/// One request of an iteration, followed by its failed checks
+fn result_view(result: &RequestRunResult) -> Element<'_, Message> {
+    let (mark, color) = if result.passed() {
+        ("✓", Color::from_rgb(0.2, 0.6, 0.3))
+    } else {
+        ("✗", Color::from_rgb(0.8, 0.2, 0.2))
+    };
+
+    let status = result
+        .status
+        .map(|s| s.to_string())
+        .unwrap_or_else(|| "-".to_string());
+
+    let mut entry = Column::new().spacing(2).push(
+        Row::new()
+            .spacing(8)
+            .push(text(mark).size(13).color(color))
+            .push(text(result.method.as_str()).size(12))
+            .push(text(&result.name).size(12).width(Length::Fill))
+            .push(text(status).size(12))
+            .push(text(format!("{} ms", result.time_ms)).size(12)),
+    );
+
+    let failures = result
+        .assertions
+        .iter()
+        .filter(|a| !a.passed)
+        .map(|a| match &a.message {
+            Some(message) => format!("{}: {}", a.name, message),
+            None => a.name.clone(),
+        })
+        .chain(result.error.clone());
+    for failure in failures {
+        entry = entry.push(
+            container(text(failure).size(11).color(Color::from_rgb(0.8, 0.2, 0.2)))
+                .padding([0, 24]),
+        );
+    }
+
+    entry.into()
+}
@@ -0,0 +1,81 @@
+use iced::widget::{button, container, text, text_editor, text_input, Column, Row};
+use iced::{Alignment, Color, Element, Length};
+
+use crate::app::Message;
+use crate::i18n::Translations;
+use crate::models::{ResponseSchema, SchemaSource};
+use crate::ui::body_highlighter::BodyLanguage;
+
+use super::{code_editor, option_buttons};
+
+/// Editor for the JSON Schema that responses of a request must satisfy
+pub fn view<'a>(
+    schema: Option<&'a ResponseSchema>,
+    content: &'a text_editor::Content,
+    translations: &'a Translations,
+) -> Element<'a, Message> {
+    let toggle = button(text(translations.get("schema_validate")).size(12))
+        .on_press(Message::ToggleResponseSchema)
+        .padding([6, 10])
+        .style(if schema.is_some() {
+            button::primary
+        } else {
+            button::secondary
+        });
+
+    let Some(schema) = schema else {
+        return Column::new()
+            .spacing(10)
+            .padding([12, 16])
+            .push(toggle)
+            .push(
+                text(translations.get("schema_hint"))
+                    .size(12)
+                    .color(Color::from_rgb(0.5, 0.5, 0.5)),
+            )
+            .into();
+    };
+
+    let header = Row::new()
+        .align_y(Alignment::Center)
+        .push(container(toggle).padding([0, 16]))
+        .push(option_buttons::view(
+            SchemaSource::all(),
+            schema.source,
+            |source| source.as_str().to_string(),
+            Message::SchemaSourceSelected,
+        ));
+
+    let body: Element<'a, Message> = match schema.source {
+        SchemaSource::Inline => code_editor::view(
+            content,
+            BodyLanguage::Json,
+            Message::SchemaEditorAction,
+            false,
+        ),
+        SchemaSource::File => Row::new()
+            .spacing(10)
+            .padding([0, 16])
+            .align_y(Alignment::Center)
+            .push(
+                text_input(translations.get("schema_file_placeholder"), &schema.file)
+                    .on_input(Message::SchemaFileChanged)
+                    .padding([8, 10])
+                    .size(12),
+            )
+            .push(
+                button(text(translations.get("browse")).size(12))
+                    .on_press(Message::BrowseSchemaFile)
+                    .padding([8, 12])
+                    .style(button::secondary),
+            )
+            .into(),
+    };
+
+    Column::new()
+        .spacing(0)
+        .height(Length::Fill)
+        .push(header)
+        .push(body)
+        .into()
+}
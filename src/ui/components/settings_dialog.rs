@@ -2,8 +2,10 @@ use iced::widget::{button, scrollable, text, text_input, Column, Row};
 use iced::{Element, Length};
 
 use crate::app::Message;
+use crate::config::HistoryConfig;
 use crate::i18n::{Language, Translations};
 use crate::models::{AiConfig, AiEngine};
+use crate::secrets::SecretStore;
 
 use super::{ai_engine_picker, dialog, language_picker};
 
@@ -11,6 +13,9 @@ pub fn view<'a>(
     current_language: Language,
     save_directory: &str,
     ai_config: &AiConfig,
+    secrets: &SecretStore,
+    secrets_passphrase: &'a str,
+    history_config: &HistoryConfig,
     translations: &'a Translations,
 ) -> Element<'a, Message> {
     let language_section = Column::new()
@@ -86,11 +91,80 @@ pub fn view<'a>(
         ai_section
     };
 
+    let secrets_status = if secrets.is_locked() {
+        translations.get("secrets_locked")
+    } else if secrets.is_encrypted() {
+        translations.get("secrets_encrypted_status")
+    } else {
+        translations.get("secrets_plain_status")
+    };
+    // A locked store has to be unlocked before its passphrase can change
+    let (secrets_action, secrets_label) = if secrets.is_locked() {
+        (Message::UnlockSecrets, translations.get("secrets_unlock"))
+    } else {
+        (
+            Message::SetSecretsPassphrase,
+            translations.get("secrets_set_passphrase"),
+        )
+    };
+    let secrets_section = Column::new()
+        .spacing(8)
+        .push(text(translations.get("secrets")).size(14))
+        .push(text(secrets_status).size(12))
+        .push(
+            Row::new()
+                .spacing(10)
+                .push(
+                    text_input(translations.get("secrets_passphrase"), secrets_passphrase)
+                        .padding(10)
+                        .size(14)
+                        .width(Length::Fill)
+                        .on_input(Message::SecretsPassphraseChanged)
+                        .on_submit(secrets_action.clone())
+                        .secure(true),
+                )
+                .push(
+                    button(text(secrets_label).size(14))
+                        .on_press(secrets_action)
+                        .padding([10, 15]),
+                ),
+        );
+
+    let history_section = Column::new()
+        .spacing(8)
+        .push(text(translations.get("history")).size(14))
+        .push(text(translations.get("history_retention_days")).size(12))
+        .push(
+            Row::new()
+                .spacing(10)
+                .push(
+                    text_input("", &history_config.retention_days.to_string())
+                        .padding(10)
+                        .size(14)
+                        .width(Length::Fill)
+                        .on_input(Message::HistoryRetentionChanged)
+                        .on_submit(Message::PurgeHistory),
+                )
+                .push(
+                    button(text(translations.get("history_purge")).size(14))
+                        .on_press(Message::PurgeHistory)
+                        .padding([10, 15]),
+                )
+                .push(
+                    button(text(translations.get("history_clear")).size(14))
+                        .on_press(Message::ClearHistory)
+                        .style(button::danger)
+                        .padding([10, 15]),
+                ),
+        );
+
     let content = Column::new()
         .spacing(20)
         .push(language_section)
         .push(save_directory_section)
-        .push(ai_section);
+        .push(ai_section)
+        .push(secrets_section)
+        .push(history_section);
 
     let scrollable_content = scrollable(content).height(Length::Fill);
 
@@ -0,0 +1,41 @@
+use iced::widget::{button, text, text_input, Column};
+use iced::Element;
+
+use crate::app::Message;
+use crate::i18n::Translations;
+
+use super::dialog;
+
+/// Ask for the passphrase of the encrypted secret store
+pub fn view<'a>(passphrase: &'a str, translations: &'a Translations) -> Element<'a, Message> {
+    let content = Column::new()
+        .spacing(12)
+        .push(text(translations.get("secrets_unlock_message")).size(13))
+        .push(
+            text_input(translations.get("secrets_passphrase"), passphrase)
+                .padding(10)
+                .size(14)
+                .on_input(Message::SecretsPassphraseChanged)
+                .on_submit(Message::UnlockSecrets)
+                .secure(true),
+        );
+
+    dialog::view_with_buttons(
+        translations.get("secrets_unlock_title"),
+        content.into(),
+        vec![
+            (
+                translations.get("secrets_skip").to_string(),
+                Message::CloseUnlockSecrets,
+                button::secondary as fn(&iced::Theme, button::Status) -> button::Style,
+            ),
+            (
+                translations.get("secrets_unlock").to_string(),
+                Message::UnlockSecrets,
+                button::primary as fn(&iced::Theme, button::Status) -> button::Style,
+            ),
+        ],
+        440.0,
+        240.0,
+    )
+}
@@ -0,0 +1,110 @@
+use iced::widget::{button, container, scrollable, text, text_input, Column, Row, Space};
+use iced::{Alignment, Element, Length, Padding};
+
+use crate::app::Message;
+use crate::i18n::Translations;
+use crate::models::HistoryEntry;
+use crate::ui::request_list;
+use crate::utils::history::HistoryQuery;
+
+/// Most entries listed at once; narrow the search to see older ones
+const MAX_LISTED: usize = 200;
+
+pub fn view<'a>(
+    history: &'a [HistoryEntry],
+    query: &'a str,
+    translations: &'a Translations,
+) -> Element<'a, Message> {
+    let search_input = text_input(translations.get("history_search_placeholder"), query)
+        .padding([8, 12])
+        .size(13)
+        .on_input(Message::HistoryQueryChanged);
+
+    let search_row = Row::new()
+        .spacing(8)
+        .padding(Padding::new(16.0).top(16.0).bottom(12.0))
+        .push(container(search_input).width(Length::Fill));
+
+    let header = Row::new()
+        .spacing(8)
+        .padding([8, 16])
+        .align_y(Alignment::Center)
+        .push(text(translations.get("history")).size(16))
+        .push(Space::new().width(Length::Fill))
+        .push(
+            button(text(translations.get("api_information")).size(13))
+                .padding([4, 8])
+                .style(button::text)
+                .on_press(Message::ShowHistory(false)),
+        );
+
+    // Newest first
+    let query = HistoryQuery::parse(query);
+    let mut entries = Column::new().spacing(4).padding([0, 8]);
+    let mut listed = 0;
+    for entry in history
+        .iter()
+        .rev()
+        .filter(|entry| query.matches(entry))
+        .take(MAX_LISTED)
+    {
+        entries = entries.push(entry_row(entry, translations));
+        listed += 1;
+    }
+    if listed == 0 {
+        entries = entries
+            .push(container(text(translations.get("history_empty")).size(12)).padding([8, 16]));
+    }
+
+    Column::new()
+        .push(search_row)
+        .push(header)
+        .push(scrollable(entries).height(Length::Fill))
+        .push(request_list::settings_button(translations))
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .into()
+}
+
+fn entry_row<'a>(entry: &'a HistoryEntry, translations: &'a Translations) -> Element<'a, Message> {
+    let status = match &entry.response {
+        Some(response) => response.status.to_string(),
+        None => translations.get("history_failed").to_string(),
+    };
+    // `sent_at` is RFC 3339; show the date and time to the minute
+    let sent_at = entry
+        .sent_at
+        .get(..16)
+        .unwrap_or(&entry.sent_at)
+        .replace('T', " ");
+
+    let details = Column::new()
+        .spacing(2)
+        .push(
+            Row::new()
+                .spacing(8)
+                .push(text(entry.request.method.as_str()).size(11))
+                .push(text(status).size(11))
+                .push(Space::new().width(Length::Fill))
+                .push(text(sent_at).size(10)),
+        )
+        .push(text(&entry.request.url).size(12));
+
+    let open_button = button(details)
+        .padding([6, 8])
+        .width(Length::Fill)
+        .style(button::text)
+        .on_press(Message::OpenHistoryEntry(entry.id));
+
+    Row::new()
+        .spacing(4)
+        .align_y(Alignment::Center)
+        .push(open_button)
+        .push(
+            button(text(translations.get("save")).size(11))
+                .padding([4, 6])
+                .style(button::text)
+                .on_press(Message::SaveHistoryEntry(entry.id)),
+        )
+        .into()
+}
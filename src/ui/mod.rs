@@ -1,5 +1,6 @@
 pub mod body_highlighter;
 pub mod components;
+pub mod history_list;
 pub mod icons;
 pub mod request_editor;
 pub mod request_list;
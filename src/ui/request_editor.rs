@@ -6,8 +6,8 @@ use crate::i18n::Translations;
 use crate::models::{BodyFormat, BodyType, Environment, Request, RequestTab};
 use crate::ui::body_highlighter::BodyLanguage;
 use crate::ui::components::{
-    code_editor, /* environment_picker, */ key_value_editor, method_picker, option_buttons,
-    tabs_bar,
+    code_editor, /* environment_picker, */ extraction_editor, key_value_editor, method_picker,
+    option_buttons, schema_editor, tabs_bar,
 };
 
 pub fn view<'a>(
@@ -15,6 +15,7 @@ pub fn view<'a>(
     active_tab: RequestTab,
     _current_env: Environment,
     body_content: &'a text_editor::Content,
+    schema_content: &'a text_editor::Content,
     translations: &'a Translations,
     request_body_word_wrap: bool,
 ) -> Element<'a, Message> {
@@ -35,21 +36,38 @@ pub fn view<'a>(
         .padding([10, 20])
         .style(button::secondary);
 
+    let code_button = button(text(translations.get("code")).size(14))
+        .on_press(Message::ShowCodeDialog(None))
+        .padding([10, 20])
+        .style(button::secondary);
+
     let send_text = translations.get("send");
     let send_button = button(text(send_text).size(14))
         .on_press(Message::SendRequest)
         .padding([10, 24])
         .style(button::primary);
 
+    let mut top_row = Row::new()
+        .spacing(12)
+        .padding([12, 16])
+        .align_y(Alignment::Center)
+        .push(method_selector)
+        .push(url_input);
+    // Only shown while certificate checks are off, so it can be turned back on
+    if request.insecure {
+        top_row = top_row.push(
+            button(text(translations.get("insecure_tls")).size(12))
+                .on_press(Message::ClearInsecure)
+                .padding([6, 10])
+                .style(button::danger),
+        );
+    }
+
     let top_bar = container(
-        Row::new()
-            .spacing(12)
-            .padding([12, 16])
-            .align_y(Alignment::Center)
-            .push(method_selector)
-            .push(url_input)
+        top_row
             // .push(env_selector)
             .push(ai_fill_button)
+            .push(code_button)
             .push(send_button),
     )
     .height(Length::Shrink);
@@ -65,6 +83,10 @@ pub fn view<'a>(
         RequestTab::Headers => view_headers_tab(request, translations),
         RequestTab::Cookies => view_cookies_tab(request, translations),
         RequestTab::Auth => view_auth_tab(request, translations),
+        RequestTab::Extract => extraction_editor::view(&request.extractions, translations),
+        RequestTab::Schema => {
+            schema_editor::view(request.schema.as_ref(), schema_content, translations)
+        }
     };
 
     // Wrap tab content in container with Fill height to ensure it takes all remaining space
@@ -104,10 +126,10 @@ fn view_params_tab<'a>(
     key_value_editor::view(
         &request.query_params,
         config,
-        |param| &param.key,
-        |param| &param.value,
+        translations.get("secret"),
         Message::ParamKeyChanged,
         Message::ParamValueChanged,
+        Message::ToggleParamSecret,
         Message::RemoveParam,
         Message::AddParam,
     )
@@ -326,10 +348,10 @@ fn view_headers_tab<'a>(
     key_value_editor::view(
         &request.headers,
         config,
-        |header| &header.key,
-        |header| &header.value,
+        translations.get("secret"),
         Message::HeaderKeyChanged,
         Message::HeaderValueChanged,
+        Message::ToggleHeaderSecret,
         Message::RemoveHeader,
         Message::AddHeader,
     )
@@ -350,10 +372,10 @@ fn view_cookies_tab<'a>(
     key_value_editor::view(
         &request.cookies,
         config,
-        |cookie| &cookie.key,
-        |cookie| &cookie.value,
+        translations.get("secret"),
         Message::CookieKeyChanged,
         Message::CookieValueChanged,
+        Message::ToggleCookieSecret,
         Message::RemoveCookie,
         Message::AddCookie,
     )
@@ -371,10 +393,10 @@ fn view_auth_tab<'a>(request: &'a Request, translations: &'a Translations) -> El
     key_value_editor::view(
         &request.auth,
         config,
-        |auth| &auth.key,
-        |auth| &auth.value,
+        translations.get("secret"),
         Message::AuthKeyChanged,
         Message::AuthValueChanged,
+        Message::ToggleAuthSecret,
         Message::RemoveAuthField,
         Message::AddAuthField,
     )
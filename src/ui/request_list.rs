@@ -1,4 +1,6 @@
-use iced::widget::{button, container, mouse_area, scrollable, text, text_input, Column, Id, Row};
+use iced::widget::{
+    button, container, mouse_area, scrollable, text, text_input, Column, Id, Row, Space,
+};
 use iced::{Alignment, Element, Length, Padding};
 
 use crate::app::state::ContextMenuTarget;
@@ -28,6 +30,12 @@ pub fn view<'a>(
         .spacing(8)
         .padding(Padding::new(16.0).top(16.0).bottom(12.0))
         .push(container(search_input).width(Length::Fill))
+        .push(
+            button(text(translations.get("import")).size(13))
+                .padding([6, 8])
+                .style(button::text)
+                .on_press(Message::ImportFile),
+        )
         .push(
             button(text("+").size(18))
                 .padding([4, 10])
@@ -41,7 +49,15 @@ pub fn view<'a>(
     let interface_header = Row::new()
         .spacing(8)
         .padding([8, 16])
-        .push(text(translations.get("api_information")).size(16));
+        .align_y(Alignment::Center)
+        .push(text(translations.get("api_information")).size(16))
+        .push(Space::new().width(Length::Fill))
+        .push(
+            button(text(translations.get("history")).size(13))
+                .padding([4, 8])
+                .style(button::text)
+                .on_press(Message::ShowHistory(true)),
+        );
 
     sidebar = sidebar.push(interface_header);
 
@@ -135,24 +151,11 @@ pub fn view<'a>(
         Message::ShowContextMenu(vec![], 0.0, 0.0, ContextMenuTarget::EmptyArea),
     );
 
-    // Settings button at the bottom
-    let settings_button = button(
-        Row::new()
-            .spacing(8)
-            .padding([8, 16])
-            .align_y(Alignment::Center)
-            .push(icons::settings_icon(16))
-            .push(text(translations.get("settings")).size(13)),
-    )
-    .on_press(Message::ShowSettingsDialog)
-    .width(Length::Fill)
-    .style(button::text);
-
     // Build final layout: header + scrollable content + settings button
     let final_column = Column::new()
         .push(sidebar)
         .push(scrollable_with_context)
-        .push(settings_button);
+        .push(settings_button(translations));
 
     container(final_column)
         .width(Length::Fill)
@@ -160,6 +163,22 @@ pub fn view<'a>(
         .into()
 }
 
+/// Settings button at the bottom of the sidebar
+pub fn settings_button(translations: &Translations) -> Element<'_, Message> {
+    button(
+        Row::new()
+            .spacing(8)
+            .padding([8, 16])
+            .align_y(Alignment::Center)
+            .push(icons::settings_icon(16))
+            .push(text(translations.get("settings")).size(13)),
+    )
+    .on_press(Message::ShowSettingsDialog)
+    .width(Length::Fill)
+    .style(button::text)
+    .into()
+}
+
 #[allow(clippy::too_many_arguments)]
 fn render_items<'a>(
     items: &'a [CollectionItem],
@@ -171,19 +190,21 @@ fn render_items<'a>(
     rename_input_id: &Id,
     translations: &'a Translations,
 ) -> Column<'a, Message> {
-    // Separate folders and requests
+    // Separate folders, requests and flows
     let mut folders: Vec<(usize, &CollectionItem)> = Vec::new();
     let mut requests: Vec<(usize, &CollectionItem)> = Vec::new();
+    let mut flows: Vec<(usize, &CollectionItem)> = Vec::new();
 
     for (item_idx, item) in items.iter().enumerate() {
         match item {
             CollectionItem::Folder(_) => folders.push((item_idx, item)),
             CollectionItem::Request(_) => requests.push((item_idx, item)),
+            CollectionItem::Flow(_) => flows.push((item_idx, item)),
         }
     }
 
-    // Render folders first, then requests
-    let all_items = folders.into_iter().chain(requests);
+    // Render folders first, then requests, then flows
+    let all_items = folders.into_iter().chain(requests).chain(flows);
 
     for (item_idx, item) in all_items {
         let mut item_path = parent_path.to_vec();
@@ -249,6 +270,53 @@ fn render_items<'a>(
 
                 column = column.push(request_content);
             }
+            CollectionItem::Flow(flow) => {
+                let is_renaming =
+                    renaming_item.as_ref().map(|(path, _, _)| path) == Some(&item_path);
+
+                let flow_content: Element<'a, Message> = if is_renaming {
+                    let current_name = renaming_item
+                        .as_ref()
+                        .map(|(_, _, name)| name.as_str())
+                        .unwrap_or(&flow.name);
+                    Row::new()
+                        .spacing(8)
+                        .padding(Padding::new(8.0).top(6.0).bottom(6.0).left(indent))
+                        .push(text("FLOW").size(11))
+                        .push(underline_input::underline_input_sized(
+                            rename_input_id.clone(),
+                            translations.get("name_placeholder"),
+                            current_name,
+                            12,
+                            Message::UpdateRenamingText,
+                            Some(Message::ConfirmRename),
+                        ))
+                        .into()
+                } else {
+                    let flow_button = button(
+                        Row::new()
+                            .spacing(8)
+                            .padding(Padding::new(8.0).top(6.0).bottom(6.0).left(indent))
+                            .push(text("FLOW").size(11))
+                            .push(text(&flow.name).size(12))
+                            .push(text(format!("({})", flow.steps.len())).size(10)),
+                    )
+                    .on_press(Message::OpenFlow(item_path.clone()))
+                    .width(Length::Fill)
+                    .style(button::text);
+
+                    mouse_area(flow_button)
+                        .on_right_press(Message::ShowContextMenu(
+                            item_path.clone(),
+                            0.0,
+                            0.0,
+                            ContextMenuTarget::Flow,
+                        ))
+                        .into()
+                };
+
+                column = column.push(flow_content);
+            }
             CollectionItem::Folder(folder) => {
                 let expand_icon = if folder.expanded { "v" } else { ">" };
                 let is_renaming =
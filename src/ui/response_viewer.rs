@@ -1,10 +1,14 @@
-use iced::widget::{button, column, container, row, scrollable, text, text_editor, Space};
+use iced::widget::{
+    button, column, container, row, scrollable, text, text_editor, text_input, Space,
+};
 use iced::{Border, Color, Element, Length};
 use std::collections::HashMap;
 
 use crate::app::Message;
 use crate::i18n::Translations;
-use crate::models::{BodyViewMode, Response, ResponseTab};
+use crate::models::{
+    BodyViewMode, DiffEntry, DiffKind, Response, ResponseTab, SchemaViolation, SnapshotDiff,
+};
 use crate::ui::body_highlighter::BodyLanguage;
 use crate::ui::components::code_editor;
 use crate::ui::icons;
@@ -32,6 +36,9 @@ pub fn view<'a>(
     active_tab: ResponseTab,
     active_body_view_mode: BodyViewMode,
     response_body_content: &'a text_editor::Content,
+    schema_result: &'a Option<Result<Vec<SchemaViolation>, String>>,
+    snapshot_diff: &'a Option<SnapshotDiff>,
+    ignore_rules: Option<&'a [String]>,
     loading: bool,
     error_message: &'a Option<String>,
     translations: &'a Translations,
@@ -158,6 +165,13 @@ pub fn view<'a>(
             text("Size:").size(14),
             Space::new().width(8),
             text(format!("{} bytes", resp.size_bytes)).size(14),
+            Space::new().width(20),
+            schema_badge(schema_result),
+            diff_badge(snapshot_diff),
+            button(text(translations.get("save_as_example")).size(12))
+                .on_press(Message::SaveResponseExample)
+                .padding([4, 10])
+                .style(button::secondary),
         ]
         .spacing(0)
         .padding([8, 16]);
@@ -328,6 +342,8 @@ pub fn view<'a>(
                         .into()
                 }
             }
+            ResponseTab::Schema => schema_view(schema_result, translations),
+            ResponseTab::Diff => diff_view(snapshot_diff, ignore_rules, translations),
         };
 
         container(
@@ -390,3 +406,238 @@ pub fn view<'a>(
         .into()
     }
 }
+
+/// Compact schema status shown next to the response size
+fn schema_badge<'a>(
+    schema_result: &'a Option<Result<Vec<SchemaViolation>, String>>,
+) -> Element<'a, Message> {
+    let (label, color) = match schema_result {
+        None => return Space::new().width(0).into(),
+        Some(Ok(violations)) if violations.is_empty() => {
+            ("Schema ✓".to_string(), Color::from_rgb(0.0, 0.7, 0.0))
+        }
+        Some(Ok(violations)) => (
+            format!("Schema ✗ {}", violations.len()),
+            Color::from_rgb(0.9, 0.0, 0.0),
+        ),
+        Some(Err(_)) => ("Schema ?".to_string(), Color::from_rgb(0.9, 0.6, 0.2)),
+    };
+    text(label).size(14).color(color).into()
+}
+
+/// Schema validation result of the current response, one row per violation
+fn schema_view<'a>(
+    schema_result: &'a Option<Result<Vec<SchemaViolation>, String>>,
+    translations: &'a Translations,
+) -> Element<'a, Message> {
+    let message = |label: &'a str, color: Color| -> Element<'a, Message> {
+        container(text(label).size(14).color(color))
+            .padding(16)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .into()
+    };
+
+    match schema_result {
+        None => message(
+            translations.get("schema_none"),
+            Color::from_rgb(0.5, 0.5, 0.5),
+        ),
+        Some(Err(error)) => message(error.as_str(), Color::from_rgb(0.9, 0.6, 0.2)),
+        Some(Ok(violations)) if violations.is_empty() => message(
+            translations.get("schema_valid"),
+            Color::from_rgb(0.0, 0.7, 0.0),
+        ),
+        Some(Ok(violations)) => {
+            let list =
+                violations
+                    .iter()
+                    .fold(column![].spacing(8).padding(16), |col, violation| {
+                        let pointer = if violation.pointer.is_empty() {
+                            "(root)"
+                        } else {
+                            &violation.pointer
+                        };
+                        col.push(
+                            row![
+                                container(
+                                    text(pointer).size(12).color(Color::from_rgb(0.9, 0.0, 0.0))
+                                )
+                                .width(Length::Fixed(200.0))
+                                .padding([4, 8]),
+                                text(&violation.message)
+                                    .size(12)
+                                    .color(Color::from_rgb(0.4, 0.4, 0.4)),
+                            ]
+                            .spacing(8),
+                        )
+                    });
+            container(scrollable(list))
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .into()
+        }
+    }
+}
+
+/// Compact example comparison shown next to the schema status
+fn diff_badge<'a>(snapshot_diff: &'a Option<SnapshotDiff>) -> Element<'a, Message> {
+    let Some(diff) = snapshot_diff else {
+        return Space::new().width(0).into();
+    };
+    let (label, color) = if diff.is_match() {
+        ("Example ✓".to_string(), Color::from_rgb(0.0, 0.7, 0.0))
+    } else {
+        (
+            format!("Example ✗ {}", diff.count()),
+            Color::from_rgb(0.9, 0.0, 0.0),
+        )
+    };
+    row![text(label).size(14).color(color), Space::new().width(20)].into()
+}
+
+/// Longest value shown in a diff row; the full body is in the Body tab
+const MAX_DIFF_VALUE: usize = 120;
+
+fn shorten(value: &str) -> String {
+    if value.chars().count() > MAX_DIFF_VALUE {
+        let head: String = value.chars().take(MAX_DIFF_VALUE).collect();
+        format!("{}…", head)
+    } else {
+        value.to_string()
+    }
+}
+
+/// One difference: `+` added, `-` removed, `~` changed with expected → actual
+fn diff_row<'a>(entry: &'a DiffEntry) -> Element<'a, Message> {
+    let (sign, color) = match entry.kind {
+        DiffKind::Added => ("+", Color::from_rgb(0.0, 0.6, 0.0)),
+        DiffKind::Removed => ("-", Color::from_rgb(0.9, 0.0, 0.0)),
+        DiffKind::Changed => ("~", Color::from_rgb(0.9, 0.6, 0.2)),
+    };
+    let pointer = if entry.pointer.is_empty() {
+        "(body)"
+    } else {
+        &entry.pointer
+    };
+    let value = match entry.kind {
+        DiffKind::Added => shorten(entry.actual.as_deref().unwrap_or_default()),
+        DiffKind::Removed => shorten(entry.expected.as_deref().unwrap_or_default()),
+        DiffKind::Changed => format!(
+            "{} → {}",
+            shorten(entry.expected.as_deref().unwrap_or_default()),
+            shorten(entry.actual.as_deref().unwrap_or_default())
+        ),
+    };
+
+    row![
+        text(sign).size(12).color(color).width(Length::Fixed(16.0)),
+        container(text(pointer).size(12).color(color))
+            .width(Length::Fixed(200.0))
+            .padding([0, 8]),
+        text(value).size(12).color(Color::from_rgb(0.4, 0.4, 0.4)),
+    ]
+    .spacing(4)
+    .into()
+}
+
+/// Current response compared with the pinned example, plus its ignore rules
+fn diff_view<'a>(
+    snapshot_diff: &'a Option<SnapshotDiff>,
+    ignore_rules: Option<&'a [String]>,
+    translations: &'a Translations,
+) -> Element<'a, Message> {
+    let (Some(diff), Some(ignore_rules)) = (snapshot_diff, ignore_rules) else {
+        return container(
+            text(translations.get("diff_no_example"))
+                .size(14)
+                .color(Color::from_rgb(0.5, 0.5, 0.5)),
+        )
+        .padding(16)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .into();
+    };
+
+    let mut list = column![].spacing(6).padding(16);
+
+    if diff.is_match() {
+        list = list.push(
+            text(translations.get("diff_match"))
+                .size(14)
+                .color(Color::from_rgb(0.0, 0.7, 0.0)),
+        );
+    }
+    if let Some((expected, actual)) = diff.status {
+        list = list.push(
+            text(format!("~ Status {} → {}", expected, actual))
+                .size(12)
+                .color(Color::from_rgb(0.9, 0.6, 0.2)),
+        );
+    }
+    if let Some((expected, actual)) = &diff.content_type {
+        list = list.push(
+            text(format!("~ Content-Type {} → {}", expected, actual))
+                .size(12)
+                .color(Color::from_rgb(0.9, 0.6, 0.2)),
+        );
+    }
+    for entry in &diff.body {
+        list = list.push(diff_row(entry));
+    }
+    if diff.ignored > 0 {
+        list = list.push(
+            text(format!(
+                "{} {}",
+                diff.ignored,
+                translations.get("diff_ignored")
+            ))
+            .size(12)
+            .color(Color::from_rgb(0.5, 0.5, 0.5)),
+        );
+    }
+
+    list = list
+        .push(Space::new().height(8))
+        .push(text(translations.get("diff_ignore_rules")).size(13));
+    list = list.push(
+        text(translations.get("diff_ignore_hint"))
+            .size(12)
+            .color(Color::from_rgb(0.5, 0.5, 0.5)),
+    );
+    for (idx, rule) in ignore_rules.iter().enumerate() {
+        list = list.push(
+            row![
+                text_input("updatedAt  /items/*/id", rule)
+                    .on_input(move |value| Message::SnapshotIgnoreChanged(idx, value))
+                    .padding([6, 10])
+                    .size(12)
+                    .width(Length::Fixed(320.0)),
+                button(text("×").size(14))
+                    .on_press(Message::RemoveSnapshotIgnore(idx))
+                    .padding([4, 10])
+                    .style(button::text),
+            ]
+            .spacing(8)
+            .align_y(iced::Alignment::Center),
+        );
+    }
+    list = list.push(
+        row![
+            button(text(translations.get("diff_add_rule")).size(12))
+                .on_press(Message::AddSnapshotIgnore)
+                .padding([6, 10])
+                .style(button::secondary),
+            button(text(translations.get("diff_update_example")).size(12))
+                .on_press(Message::SaveResponseExample)
+                .padding([6, 10])
+                .style(button::secondary),
+        ]
+        .spacing(8),
+    );
+
+    container(scrollable(list))
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .into()
+}
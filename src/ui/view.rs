@@ -5,20 +5,27 @@ use crate::app::{Message, Requiem};
 use crate::i18n::I18n;
 
 use super::components::{
-    ai_fill_dialog, context_menu, environment_dialog, settings_dialog, shortcuts_dialog,
+    ai_fill_dialog, blast_dialog, code_dialog, context_menu, environment_dialog, export_dialog,
+    flow_editor, har_import_dialog, import_report_dialog, mock_server_dialog, recovery_dialog,
+    reload_conflict_dialog, runner_dialog, settings_dialog, shortcuts_dialog,
+    unlock_secrets_dialog,
 };
-use super::{request_editor, request_list, request_tabs, response_viewer, toast};
+use super::{history_list, request_editor, request_list, request_tabs, response_viewer, toast};
 
 pub fn view(state: &Requiem) -> Element<'_, Message> {
-    let request_list_panel = request_list::view(
-        &state.collections,
-        state.selected_request.as_ref(),
-        None, // Don't pass context menu to request_list anymore
-        state.renaming_item.as_ref(),
-        &state.search_query,
-        &state.rename_input_id,
-        &state.translations,
-    );
+    let request_list_panel = if state.show_history {
+        history_list::view(&state.history, &state.history_query, &state.translations)
+    } else {
+        request_list::view(
+            &state.collections,
+            state.selected_request.as_ref(),
+            None, // Don't pass context menu to request_list anymore
+            state.renaming_item.as_ref(),
+            &state.search_query,
+            &state.rename_input_id,
+            &state.translations,
+        )
+    };
 
     // Build tab bar
     let tab_bar_row = container(request_tabs::view(
@@ -34,6 +41,7 @@ pub fn view(state: &Requiem) -> Element<'_, Message> {
             state.active_tab,
             state.current_environment,
             &state.request_body_content,
+            &state.schema_content,
             &state.translations,
             state.request_body_word_wrap,
         );
@@ -42,6 +50,12 @@ pub fn view(state: &Requiem) -> Element<'_, Message> {
             state.active_response_tab,
             state.active_body_view_mode,
             &state.response_body_content,
+            &state.schema_result,
+            &state.snapshot_diff,
+            request
+                .example
+                .as_ref()
+                .map(|example| example.ignore.as_slice()),
             state.loading,
             &state.error_message,
             &state.translations,
@@ -166,11 +180,27 @@ pub fn view(state: &Requiem) -> Element<'_, Message> {
 
     // Display context menu as a floating overlay if present
     if let Some(ctx_menu) = &state.context_menu {
+        // Whether the collection under the cursor is being served by the mock server
+        let mock_running = state.mock_server.as_ref().is_some_and(|mock| {
+            mock.running
+                && ctx_menu
+                    .path
+                    .first()
+                    .and_then(|idx| state.collections.get(*idx))
+                    .is_some_and(|collection| collection.id == mock.collection_id)
+        });
+        let http_linked = ctx_menu
+            .path
+            .first()
+            .and_then(|idx| state.collections.get(*idx))
+            .is_some_and(|collection| collection.http_file.is_some());
         let menu_overlay = context_menu::view(
             &ctx_menu.path,
             ctx_menu.x,
             ctx_menu.y,
             &ctx_menu.target,
+            mock_running,
+            http_linked,
             &state.translations,
         );
         layers.push(menu_overlay);
@@ -195,7 +225,11 @@ pub fn view(state: &Requiem) -> Element<'_, Message> {
         layers.push(backdrop.into());
 
         // Dialog centered on screen
-        let dialog = container(environment_dialog::view())
+        let dialog = container(environment_dialog::view(
+            state.current_environment,
+            &state.environment_variables,
+            &state.runtime_variables,
+        ))
             .width(Length::Fill)
             .height(Length::Fill)
             .align_x(Alignment::Center)
@@ -224,6 +258,9 @@ pub fn view(state: &Requiem) -> Element<'_, Message> {
             state.language(),
             &state.save_directory,
             &state.ai_config,
+            &state.secrets,
+            &state.secrets_passphrase,
+            &state.history_config,
             &state.translations,
         ))
         .width(Length::Fill)
@@ -288,6 +325,287 @@ pub fn view(state: &Requiem) -> Element<'_, Message> {
         layers.push(dialog.into());
     }
 
+    // Collection runner dialog overlay
+    if let Some(runner) = &state.runner {
+        let backdrop = mouse_area(
+            container(text(""))
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .style(|_theme| container::Style {
+                    background: Some(iced::Background::Color(iced::Color::from_rgba(
+                        0.0, 0.0, 0.0, 0.5,
+                    ))),
+                    ..Default::default()
+                }),
+        )
+        .on_press(Message::CloseRunnerDialog);
+
+        layers.push(backdrop.into());
+
+        let dialog = container(runner_dialog::view(runner, &state.translations))
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .align_x(Alignment::Center)
+            .align_y(Alignment::Center);
+
+        layers.push(dialog.into());
+    }
+
+    // Blast (load test) dialog overlay
+    if let Some(blast) = &state.blast {
+        let backdrop = container(text(""))
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .style(|_theme| container::Style {
+                background: Some(iced::Background::Color(iced::Color::from_rgba(
+                    0.0, 0.0, 0.0, 0.5,
+                ))),
+                ..Default::default()
+            });
+
+        layers.push(backdrop.into());
+
+        let dialog = container(blast_dialog::view(blast, &state.translations))
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .align_x(Alignment::Center)
+            .align_y(Alignment::Center);
+
+        layers.push(dialog.into());
+    }
+
+    // Code generation dialog overlay
+    if let Some(code) = &state.code_dialog {
+        let backdrop = mouse_area(
+            container(text(""))
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .style(|_theme| container::Style {
+                    background: Some(iced::Background::Color(iced::Color::from_rgba(
+                        0.0, 0.0, 0.0, 0.5,
+                    ))),
+                    ..Default::default()
+                }),
+        )
+        .on_press(Message::CloseCodeDialog);
+
+        layers.push(backdrop.into());
+
+        let dialog = container(code_dialog::view(code, &state.translations))
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .align_x(Alignment::Center)
+            .align_y(Alignment::Center);
+
+        layers.push(dialog.into());
+    }
+
+    // HAR import filter overlay
+    if let Some(har_import) = &state.har_import {
+        let backdrop = mouse_area(
+            container(text(""))
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .style(|_theme| container::Style {
+                    background: Some(iced::Background::Color(iced::Color::from_rgba(
+                        0.0, 0.0, 0.0, 0.5,
+                    ))),
+                    ..Default::default()
+                }),
+        )
+        .on_press(Message::CloseHarImport);
+
+        layers.push(backdrop.into());
+
+        let dialog = container(har_import_dialog::view(har_import, &state.translations))
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .align_x(Alignment::Center)
+            .align_y(Alignment::Center);
+
+        layers.push(dialog.into());
+    }
+
+    // Import report overlay
+    if let Some(report) = &state.import_report {
+        let backdrop = mouse_area(
+            container(text(""))
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .style(|_theme| container::Style {
+                    background: Some(iced::Background::Color(iced::Color::from_rgba(
+                        0.0, 0.0, 0.0, 0.5,
+                    ))),
+                    ..Default::default()
+                }),
+        )
+        .on_press(Message::CloseImportReport);
+
+        layers.push(backdrop.into());
+
+        let dialog = container(import_report_dialog::view(report, &state.translations))
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .align_x(Alignment::Center)
+            .align_y(Alignment::Center);
+
+        layers.push(dialog.into());
+    }
+
+    // Export dialog overlay
+    if let Some(export) = &state.export_dialog {
+        let backdrop = mouse_area(
+            container(text(""))
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .style(|_theme| container::Style {
+                    background: Some(iced::Background::Color(iced::Color::from_rgba(
+                        0.0, 0.0, 0.0, 0.5,
+                    ))),
+                    ..Default::default()
+                }),
+        )
+        .on_press(Message::CloseExportDialog);
+
+        layers.push(backdrop.into());
+
+        let dialog = container(export_dialog::view(export, &state.translations))
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .align_x(Alignment::Center)
+            .align_y(Alignment::Center);
+
+        layers.push(dialog.into());
+    }
+
+    // Flow editor overlay
+    if let Some(editor) = &state.flow_editor {
+        let backdrop = container(text(""))
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .style(|_theme| container::Style {
+                background: Some(iced::Background::Color(iced::Color::from_rgba(
+                    0.0, 0.0, 0.0, 0.5,
+                ))),
+                ..Default::default()
+            });
+
+        layers.push(backdrop.into());
+
+        let dialog = container(flow_editor::view(editor, &state.translations))
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .align_x(Alignment::Center)
+            .align_y(Alignment::Center);
+
+        layers.push(dialog.into());
+    }
+
+    // Mock server dialog overlay; the server may keep running while it is hidden
+    if let Some(mock) = state.mock_server.as_ref().filter(|m| m.show_dialog) {
+        let backdrop = mouse_area(
+            container(text(""))
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .style(|_theme| container::Style {
+                    background: Some(iced::Background::Color(iced::Color::from_rgba(
+                        0.0, 0.0, 0.0, 0.5,
+                    ))),
+                    ..Default::default()
+                }),
+        )
+        .on_press(Message::CloseMockServerDialog);
+
+        layers.push(backdrop.into());
+
+        let dialog = container(mock_server_dialog::view(mock, &state.translations))
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .align_x(Alignment::Center)
+            .align_y(Alignment::Center);
+
+        layers.push(dialog.into());
+    }
+
+    // Conflicts between unsaved edits and changes on disk need a decision
+    if let Some(conflict) = state.reload_conflicts.first() {
+        let backdrop = container(text(""))
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .style(|_theme| container::Style {
+                background: Some(iced::Background::Color(iced::Color::from_rgba(
+                    0.0, 0.0, 0.0, 0.5,
+                ))),
+                ..Default::default()
+            });
+
+        layers.push(backdrop.into());
+
+        let dialog = container(reload_conflict_dialog::view(conflict, &state.translations))
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .align_x(Alignment::Center)
+            .align_y(Alignment::Center);
+
+        layers.push(dialog.into());
+    }
+
+    // Files that could not be read when collections were loaded
+    if state.show_recovery_dialog {
+        let backdrop = mouse_area(
+            container(text(""))
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .style(|_theme| container::Style {
+                    background: Some(iced::Background::Color(iced::Color::from_rgba(
+                        0.0, 0.0, 0.0, 0.5,
+                    ))),
+                    ..Default::default()
+                }),
+        )
+        .on_press(Message::CloseRecoveryDialog);
+
+        layers.push(backdrop.into());
+
+        let dialog = container(recovery_dialog::view(
+            &state.load_failures,
+            &state.save_directory,
+            &state.translations,
+        ))
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .align_x(Alignment::Center)
+        .align_y(Alignment::Center);
+
+        layers.push(dialog.into());
+    }
+
+    // The encrypted secret store waits for its passphrase
+    if state.show_unlock_secrets {
+        let backdrop = container(text(""))
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .style(|_theme| container::Style {
+                background: Some(iced::Background::Color(iced::Color::from_rgba(
+                    0.0, 0.0, 0.0, 0.5,
+                ))),
+                ..Default::default()
+            });
+
+        layers.push(backdrop.into());
+
+        let dialog = container(unlock_secrets_dialog::view(
+            &state.secrets_passphrase,
+            &state.translations,
+        ))
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .align_x(Alignment::Center)
+        .align_y(Alignment::Center);
+
+        layers.push(dialog.into());
+    }
+
     // Use stack to layer all overlays
     stack(layers).into()
 }
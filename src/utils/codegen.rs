@@ -0,0 +1,1074 @@
+use std::collections::BTreeSet;
+
+use crate::models::{BodyType, CodeLanguage, FormDataParamType, HttpMethod, Request};
+
+/// Boundary used where a snippet has to assemble multipart bodies itself
+const BOUNDARY: &str = "RequiemFormBoundary";
+/// File that binary bodies are read from, since the bytes cannot be inlined
+const BINARY_FILE: &str = "body.bin";
+
+/// Render `request` as a snippet in `language`
+///
+/// `{{name}}` placeholders are kept as written; resolve the request first
+/// to inline variable values. Auth fields and cookies are written as the
+/// headers and query parameters they are sent as.
+pub fn generate(request: &Request, language: CodeLanguage) -> String {
+    let request = request.with_credentials();
+    let spec = Spec::new(&request);
+    match language {
+        CodeLanguage::Curl => curl(&spec),
+        CodeLanguage::Httpie => httpie(&spec),
+        CodeLanguage::Wget => wget(&spec),
+        CodeLanguage::PythonRequests => python(&spec),
+        CodeLanguage::JavaScriptFetch => fetch(&spec),
+        CodeLanguage::JavaScriptAxios => axios(&spec),
+        CodeLanguage::GoNetHttp => go(&spec),
+        CodeLanguage::RustReqwest => rust(&spec),
+        CodeLanguage::JavaHttpClient => java(&spec),
+        CodeLanguage::PowerShell => powershell(&spec),
+    }
+}
+
+/// The parts of a request every generator needs, with disabled rows dropped
+struct Spec<'a> {
+    method: HttpMethod,
+    url: String,
+    headers: Vec<(&'a str, &'a str)>,
+    body: Body<'a>,
+    insecure: bool,
+}
+
+enum Body<'a> {
+    None,
+    Raw(&'a str),
+    Form(Vec<(&'a str, &'a str)>),
+    Multipart(Vec<Part<'a>>),
+    /// Read from [`BINARY_FILE`]
+    Binary,
+}
+
+struct Part<'a> {
+    name: &'a str,
+    /// Text value, or the file path for file parts
+    value: &'a str,
+    file: bool,
+}
+
+impl Part<'_> {
+    fn file_name(&self) -> &str {
+        std::path::Path::new(self.value)
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or("upload.bin")
+    }
+}
+
+impl<'a> Spec<'a> {
+    fn new(request: &'a Request) -> Self {
+        let mut url = request.url.clone();
+        let query: Vec<String> = request
+            .query_params
+            .iter()
+            .filter(|p| p.enabled && !p.key.is_empty())
+            .map(|p| format!("{}={}", encode(&p.key), encode(&p.value)))
+            .collect();
+        if !query.is_empty() {
+            url.push(if url.contains('?') { '&' } else { '?' });
+            url.push_str(&query.join("&"));
+        }
+
+        let body = match &request.body {
+            BodyType::None => Body::None,
+            BodyType::Json(raw) | BodyType::Xml(raw) | BodyType::Text(raw) => Body::Raw(raw),
+            BodyType::FormUrlEncoded(fields) => Body::Form(
+                fields
+                    .iter()
+                    .filter(|f| f.enabled && !f.key.is_empty())
+                    .map(|f| (f.key.as_str(), f.value.as_str()))
+                    .collect(),
+            ),
+            BodyType::FormData(fields) => Body::Multipart(
+                fields
+                    .iter()
+                    .filter(|f| f.enabled && !f.key.is_empty())
+                    .map(|f| Part {
+                        name: &f.key,
+                        value: &f.value,
+                        file: f.param_type == FormDataParamType::File,
+                    })
+                    .collect(),
+            ),
+            BodyType::Binary(_) => Body::Binary,
+        };
+
+        let headers = request
+            .headers
+            .iter()
+            .filter(|h| h.enabled && !h.key.is_empty())
+            // Multipart tools pick their own boundary
+            .filter(|h| {
+                !(matches!(body, Body::Multipart(_)) && h.key.eq_ignore_ascii_case("content-type"))
+            })
+            .map(|h| (h.key.as_str(), h.value.as_str()))
+            .collect();
+
+        Self {
+            method: request.method,
+            url,
+            headers,
+            body,
+            insecure: request.insecure,
+        }
+    }
+
+    fn content_type(&self) -> Option<&'a str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case("content-type"))
+            .map(|(_, value)| *value)
+    }
+
+    /// Form bodies need an explicit content type in libraries that send raw strings
+    fn headers_with_form_type(&self) -> Vec<(&'a str, &'a str)> {
+        let mut headers = self.headers.clone();
+        if matches!(self.body, Body::Form(_)) && self.content_type().is_none() {
+            headers.push(("Content-Type", "application/x-www-form-urlencoded"));
+        }
+        headers
+    }
+}
+
+/// Percent-encode a query or form component, leaving placeholders intact
+fn encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    let mut rest = value;
+    while !rest.is_empty() {
+        if rest.starts_with("{{") {
+            if let Some(end) = rest.find("}}") {
+                encoded.push_str(&rest[..end + 2]);
+                rest = &rest[end + 2..];
+                continue;
+            }
+        }
+        let c = rest.chars().next().unwrap_or_default();
+        if c.is_ascii_alphanumeric() || matches!(c, '-' | '.' | '_' | '~') {
+            encoded.push(c);
+        } else {
+            let mut buf = [0; 4];
+            for byte in c.encode_utf8(&mut buf).bytes() {
+                encoded.push_str(&format!("%{:02X}", byte));
+            }
+        }
+        rest = &rest[c.len_utf8()..];
+    }
+    encoded
+}
+
+fn form_string(fields: &[(&str, &str)]) -> String {
+    fields
+        .iter()
+        .map(|(key, value)| format!("{}={}", encode(key), encode(value)))
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+/// POSIX shell single-quoted string
+fn sh(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+/// Double-quoted string literal, valid in Python, JavaScript, Go and Java
+fn quoted(value: &str) -> String {
+    serde_json::to_string(value).unwrap_or_default()
+}
+
+/// Rust string literal, raw when that avoids escaping
+fn rust_str(value: &str) -> String {
+    if (value.contains('"') || value.contains('\n')) && !value.contains("\"#") {
+        format!("r#\"{}\"#", value)
+    } else {
+        format!("{:?}", value)
+    }
+}
+
+/// PowerShell single-quoted string
+fn ps(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+fn multipart_prefix(part: &Part) -> String {
+    if part.file {
+        format!(
+            "--{}\r\nContent-Disposition: form-data; name=\"{}\"; filename=\"{}\"\r\n\r\n",
+            BOUNDARY,
+            part.name,
+            part.file_name()
+        )
+    } else {
+        format!(
+            "--{}\r\nContent-Disposition: form-data; name=\"{}\"\r\n\r\n{}\r\n",
+            BOUNDARY, part.name, part.value
+        )
+    }
+}
+
+fn shell_lines(lines: Vec<String>) -> String {
+    lines.join(" \\\n  ")
+}
+
+fn curl(spec: &Spec) -> String {
+    let mut lines = vec![format!("curl {}", sh(&spec.url))];
+    match spec.method {
+        HttpMethod::GET => {}
+        HttpMethod::HEAD => lines.push("--head".to_string()),
+        method => lines.push(format!("-X {}", method.as_str())),
+    }
+    for (key, value) in &spec.headers {
+        lines.push(format!("-H {}", sh(&format!("{}: {}", key, value))));
+    }
+    match &spec.body {
+        Body::None => {}
+        Body::Raw(raw) => lines.push(format!("--data-raw {}", sh(raw))),
+        Body::Form(fields) => {
+            for (key, value) in fields {
+                lines.push(format!(
+                    "--data-urlencode {}",
+                    sh(&format!("{}={}", key, value))
+                ));
+            }
+        }
+        Body::Multipart(parts) => {
+            for part in parts {
+                if part.file {
+                    lines.push(format!(
+                        "-F {}",
+                        sh(&format!("{}=@{}", part.name, part.value))
+                    ));
+                } else {
+                    // `-F` would treat a leading `@` or `<` as a file
+                    lines.push(format!(
+                        "--form-string {}",
+                        sh(&format!("{}={}", part.name, part.value))
+                    ));
+                }
+            }
+        }
+        Body::Binary => lines.push(format!(
+            "--data-binary {}",
+            sh(&format!("@{}", BINARY_FILE))
+        )),
+    }
+    if spec.insecure {
+        lines.push("-k".to_string());
+    }
+    shell_lines(lines)
+}
+
+fn httpie(spec: &Spec) -> String {
+    let mut lines = vec![];
+    let mut first = "http".to_string();
+    match &spec.body {
+        Body::Form(_) => first.push_str(" --form"),
+        Body::Multipart(_) => first.push_str(" --multipart"),
+        _ => {}
+    }
+    if spec.insecure {
+        first.push_str(" --verify=no");
+    }
+    if let Body::Raw(raw) = &spec.body {
+        first.push_str(&format!(" --raw {}", sh(raw)));
+    }
+    lines.push(format!(
+        "{} {} {}",
+        first,
+        spec.method.as_str(),
+        sh(&spec.url)
+    ));
+
+    for (key, value) in &spec.headers {
+        lines.push(sh(&format!("{}:{}", key, value)));
+    }
+    match &spec.body {
+        Body::Form(fields) => {
+            for (key, value) in fields {
+                lines.push(sh(&format!("{}={}", key, value)));
+            }
+        }
+        Body::Multipart(parts) => {
+            for part in parts {
+                let separator = if part.file { "@" } else { "=" };
+                lines.push(sh(&format!("{}{}{}", part.name, separator, part.value)));
+            }
+        }
+        _ => {}
+    }
+    let mut command = shell_lines(lines);
+    if matches!(spec.body, Body::Binary) {
+        command.push_str(&format!(" < {}", BINARY_FILE));
+    }
+    command
+}
+
+fn wget(spec: &Spec) -> String {
+    let mut script = String::new();
+    let mut lines = vec!["wget -q -O -".to_string()];
+    if spec.method != HttpMethod::GET {
+        lines.push(format!("--method={}", spec.method.as_str()));
+    }
+    for (key, value) in &spec.headers {
+        lines.push(format!("--header={}", sh(&format!("{}: {}", key, value))));
+    }
+    match &spec.body {
+        Body::None => {}
+        Body::Raw(raw) => lines.push(format!("--body-data={}", sh(raw))),
+        Body::Form(fields) => lines.push(format!("--body-data={}", sh(&form_string(fields)))),
+        Body::Multipart(parts) => {
+            // wget cannot build multipart bodies, so the script writes one first
+            script.push_str("{\n");
+            for part in parts {
+                // `%s` keeps backslashes in values literal, unlike `%b`
+                let prefix = multipart_prefix(part);
+                let rows: Vec<String> = prefix
+                    .strip_suffix("\r\n")
+                    .unwrap_or(&prefix)
+                    .split("\r\n")
+                    .map(sh)
+                    .collect();
+                script.push_str(&format!("  printf '%s\\r\\n' {}\n", rows.join(" ")));
+                if part.file {
+                    script.push_str(&format!("  cat {}\n", sh(part.value)));
+                    script.push_str("  printf '\\r\\n'\n");
+                }
+            }
+            script.push_str(&format!(
+                "  printf '%s\\r\\n' {}\n",
+                sh(&format!("--{}--", BOUNDARY))
+            ));
+            script.push_str("} > multipart.body\n");
+            lines.push(format!(
+                "--header={}",
+                sh(&format!(
+                    "Content-Type: multipart/form-data; boundary={}",
+                    BOUNDARY
+                ))
+            ));
+            lines.push("--body-file=multipart.body".to_string());
+        }
+        Body::Binary => lines.push(format!("--body-file={}", BINARY_FILE)),
+    }
+    if spec.insecure {
+        lines.push("--no-check-certificate".to_string());
+    }
+    lines.push(sh(&spec.url));
+    script.push_str(&shell_lines(lines));
+    script
+}
+
+fn python(spec: &Spec) -> String {
+    let mut code = String::from("import requests\n\n");
+    code.push_str(&format!("url = {}\n", quoted(&spec.url)));
+
+    let mut args = vec!["url".to_string()];
+    if !spec.headers.is_empty() {
+        code.push_str("headers = {\n");
+        for (key, value) in &spec.headers {
+            code.push_str(&format!("    {}: {},\n", quoted(key), quoted(value)));
+        }
+        code.push_str("}\n");
+        args.push("headers=headers".to_string());
+    }
+    match &spec.body {
+        Body::None => {}
+        Body::Raw(raw) => {
+            code.push_str(&format!("data = {}.encode(\"utf-8\")\n", quoted(raw)));
+            args.push("data=data".to_string());
+        }
+        Body::Form(fields) => {
+            code.push_str("data = [\n");
+            for (key, value) in fields {
+                code.push_str(&format!("    ({}, {}),\n", quoted(key), quoted(value)));
+            }
+            code.push_str("]\n");
+            args.push("data=data".to_string());
+        }
+        Body::Multipart(parts) => {
+            code.push_str("files = [\n");
+            for part in parts {
+                if part.file {
+                    code.push_str(&format!(
+                        "    ({}, ({}, open({}, \"rb\"))),\n",
+                        quoted(part.name),
+                        quoted(part.file_name()),
+                        quoted(part.value)
+                    ));
+                } else {
+                    code.push_str(&format!(
+                        "    ({}, (None, {})),\n",
+                        quoted(part.name),
+                        quoted(part.value)
+                    ));
+                }
+            }
+            code.push_str("]\n");
+            args.push("files=files".to_string());
+        }
+        Body::Binary => {
+            code.push_str(&format!("data = open({}, \"rb\")\n", quoted(BINARY_FILE)));
+            args.push("data=data".to_string());
+        }
+    }
+    if spec.insecure {
+        args.push("verify=False".to_string());
+    }
+
+    code.push_str(&format!(
+        "\nresponse = requests.{}({})\n",
+        spec.method.as_str().to_lowercase(),
+        args.join(", ")
+    ));
+    code.push_str("print(response.status_code)\nprint(response.text)\n");
+    code
+}
+
+/// Statements that build a `FormData` for fetch and axios
+fn js_form_data(parts: &[Part]) -> String {
+    let mut code = String::from("const form = new FormData();\n");
+    for part in parts {
+        if part.file {
+            code.push_str(&format!(
+                "form.append({}, await fs.openAsBlob({}), {});\n",
+                quoted(part.name),
+                quoted(part.value),
+                quoted(part.file_name())
+            ));
+        } else {
+            code.push_str(&format!(
+                "form.append({}, {});\n",
+                quoted(part.name),
+                quoted(part.value)
+            ));
+        }
+    }
+    code
+}
+
+fn js_headers(headers: &[(&str, &str)], indent: &str) -> String {
+    let mut code = format!("{}headers: {{\n", indent);
+    for (key, value) in headers {
+        code.push_str(&format!(
+            "{}  {}: {},\n",
+            indent,
+            quoted(key),
+            quoted(value)
+        ));
+    }
+    code.push_str(&format!("{}}},\n", indent));
+    code
+}
+
+/// The body expression for fetch and axios
+fn js_body(body: &Body) -> Option<String> {
+    match body {
+        Body::None => None,
+        Body::Raw(raw) => Some(quoted(raw)),
+        Body::Form(fields) => {
+            let pairs: Vec<String> = fields
+                .iter()
+                .map(|(key, value)| format!("[{}, {}]", quoted(key), quoted(value)))
+                .collect();
+            Some(format!("new URLSearchParams([{}])", pairs.join(", ")))
+        }
+        Body::Multipart(_) => Some("form".to_string()),
+        Body::Binary => Some(format!("fs.readFileSync({})", quoted(BINARY_FILE))),
+    }
+}
+
+fn uses_fs(body: &Body) -> bool {
+    match body {
+        Body::Binary => true,
+        Body::Multipart(parts) => parts.iter().any(|part| part.file),
+        _ => false,
+    }
+}
+
+fn fetch(spec: &Spec) -> String {
+    let mut code = String::new();
+    if uses_fs(&spec.body) {
+        code.push_str("import fs from \"node:fs\";\n\n");
+    }
+    if spec.insecure {
+        code.push_str(
+            "// fetch cannot skip certificate checks; in Node run with NODE_TLS_REJECT_UNAUTHORIZED=0\n",
+        );
+    }
+    if let Body::Multipart(parts) = &spec.body {
+        code.push_str(&js_form_data(parts));
+        code.push('\n');
+    }
+
+    code.push_str(&format!(
+        "const response = await fetch({}, {{\n",
+        quoted(&spec.url)
+    ));
+    code.push_str(&format!("  method: {},\n", quoted(spec.method.as_str())));
+    if !spec.headers.is_empty() {
+        code.push_str(&js_headers(&spec.headers, "  "));
+    }
+    if let Some(body) = js_body(&spec.body) {
+        code.push_str(&format!("  body: {},\n", body));
+    }
+    code.push_str("});\n\n");
+    code.push_str("console.log(response.status);\nconsole.log(await response.text());\n");
+    code
+}
+
+fn axios(spec: &Spec) -> String {
+    let mut code = String::from("import axios from \"axios\";\n");
+    if uses_fs(&spec.body) {
+        code.push_str("import fs from \"node:fs\";\n");
+    }
+    if spec.insecure {
+        code.push_str("import https from \"node:https\";\n");
+    }
+    code.push('\n');
+    if let Body::Multipart(parts) = &spec.body {
+        code.push_str(&js_form_data(parts));
+        code.push('\n');
+    }
+
+    code.push_str("const response = await axios({\n");
+    code.push_str(&format!(
+        "  method: {},\n",
+        quoted(&spec.method.as_str().to_lowercase())
+    ));
+    code.push_str(&format!("  url: {},\n", quoted(&spec.url)));
+    if !spec.headers.is_empty() {
+        code.push_str(&js_headers(&spec.headers, "  "));
+    }
+    if let Some(body) = js_body(&spec.body) {
+        code.push_str(&format!("  data: {},\n", body));
+    }
+    if spec.insecure {
+        code.push_str("  httpsAgent: new https.Agent({ rejectUnauthorized: false }),\n");
+    }
+    code.push_str("});\n\n");
+    code.push_str("console.log(response.status);\nconsole.log(response.data);\n");
+    code
+}
+
+fn go(spec: &Spec) -> String {
+    let mut imports: BTreeSet<&str> = ["fmt", "io", "net/http"].into();
+    let mut body_code = String::new();
+    let mut add_file = false;
+
+    let body_arg = match &spec.body {
+        Body::None => "nil",
+        Body::Raw(raw) => {
+            imports.insert("strings");
+            body_code.push_str(&format!("\tbody := strings.NewReader({})\n", quoted(raw)));
+            "body"
+        }
+        Body::Form(fields) => {
+            imports.insert("net/url");
+            imports.insert("strings");
+            body_code.push_str("\tform := url.Values{}\n");
+            for (key, value) in fields {
+                body_code.push_str(&format!("\tform.Add({}, {})\n", quoted(key), quoted(value)));
+            }
+            body_code.push_str("\tbody := strings.NewReader(form.Encode())\n");
+            "body"
+        }
+        Body::Multipart(parts) => {
+            imports.insert("bytes");
+            imports.insert("mime/multipart");
+            body_code
+                .push_str("\tbody := &bytes.Buffer{}\n\twriter := multipart.NewWriter(body)\n");
+            for part in parts {
+                if part.file {
+                    add_file = true;
+                    body_code.push_str(&format!(
+                        "\tif err := addFile(writer, {}, {}); err != nil {{\n\t\tpanic(err)\n\t}}\n",
+                        quoted(part.name),
+                        quoted(part.value)
+                    ));
+                } else {
+                    body_code.push_str(&format!(
+                        "\tif err := writer.WriteField({}, {}); err != nil {{\n\t\tpanic(err)\n\t}}\n",
+                        quoted(part.name),
+                        quoted(part.value)
+                    ));
+                }
+            }
+            body_code.push_str("\tif err := writer.Close(); err != nil {\n\t\tpanic(err)\n\t}\n");
+            "body"
+        }
+        Body::Binary => {
+            imports.insert("os");
+            body_code.push_str(&format!(
+                "\tbody, err := os.Open({})\n\tif err != nil {{\n\t\tpanic(err)\n\t}}\n\tdefer body.Close()\n",
+                quoted(BINARY_FILE)
+            ));
+            "body"
+        }
+    };
+    if add_file {
+        imports.insert("os");
+        imports.insert("path/filepath");
+    }
+    if spec.insecure {
+        imports.insert("crypto/tls");
+    }
+
+    let mut code = String::from("package main\n\nimport (\n");
+    for import in &imports {
+        code.push_str(&format!("\t\"{}\"\n", import));
+    }
+    code.push_str(")\n\nfunc main() {\n");
+    code.push_str(&body_code);
+    code.push_str(&format!(
+        "\treq, err := http.NewRequest({}, {}, {})\n\tif err != nil {{\n\t\tpanic(err)\n\t}}\n",
+        quoted(spec.method.as_str()),
+        quoted(&spec.url),
+        body_arg
+    ));
+    for (key, value) in spec.headers_with_form_type() {
+        code.push_str(&format!(
+            "\treq.Header.Add({}, {})\n",
+            quoted(key),
+            quoted(value)
+        ));
+    }
+    if matches!(spec.body, Body::Multipart(_)) {
+        code.push_str("\treq.Header.Set(\"Content-Type\", writer.FormDataContentType())\n");
+    }
+    code.push('\n');
+
+    if spec.insecure {
+        code.push_str(
+            "\tclient := &http.Client{Transport: &http.Transport{\n\t\tTLSClientConfig: &tls.Config{InsecureSkipVerify: true},\n\t}}\n",
+        );
+    } else {
+        code.push_str("\tclient := http.DefaultClient\n");
+    }
+    code.push_str(
+        "\tresp, err := client.Do(req)\n\tif err != nil {\n\t\tpanic(err)\n\t}\n\tdefer resp.Body.Close()\n\n",
+    );
+    code.push_str(
+        "\trespBody, err := io.ReadAll(resp.Body)\n\tif err != nil {\n\t\tpanic(err)\n\t}\n\tfmt.Println(resp.Status)\n\tfmt.Println(string(respBody))\n}\n",
+    );
+
+    if add_file {
+        code.push_str(
+            "\nfunc addFile(writer *multipart.Writer, field, path string) error {\n\tfile, err := os.Open(path)\n\tif err != nil {\n\t\treturn err\n\t}\n\tdefer file.Close()\n\tpart, err := writer.CreateFormFile(field, filepath.Base(path))\n\tif err != nil {\n\t\treturn err\n\t}\n\t_, err = io.Copy(part, file)\n\treturn err\n}\n",
+        );
+    }
+    code
+}
+
+fn rust(spec: &Spec) -> String {
+    let mut code = String::from(
+        "// Cargo.toml: reqwest = { version = \"0.12\", features = [\"blocking\", \"multipart\"] }\n",
+    );
+    code.push_str("fn main() -> Result<(), Box<dyn std::error::Error>> {\n");
+    if spec.insecure {
+        code.push_str(
+            "    let client = reqwest::blocking::Client::builder()\n        .danger_accept_invalid_certs(true)\n        .build()?;\n",
+        );
+    } else {
+        code.push_str("    let client = reqwest::blocking::Client::new();\n");
+    }
+    if let Body::Multipart(parts) = &spec.body {
+        code.push_str("    let form = reqwest::blocking::multipart::Form::new()");
+        for part in parts {
+            if part.file {
+                code.push_str(&format!(
+                    "\n        .file({}, {})?",
+                    rust_str(part.name),
+                    rust_str(part.value)
+                ));
+            } else {
+                code.push_str(&format!(
+                    "\n        .text({}, {})",
+                    rust_str(part.name),
+                    rust_str(part.value)
+                ));
+            }
+        }
+        code.push_str(";\n");
+    }
+
+    code.push_str(&format!(
+        "    let response = client\n        .request(reqwest::Method::{}, {})\n",
+        spec.method.as_str(),
+        rust_str(&spec.url)
+    ));
+    for (key, value) in &spec.headers {
+        code.push_str(&format!(
+            "        .header({}, {})\n",
+            rust_str(key),
+            rust_str(value)
+        ));
+    }
+    match &spec.body {
+        Body::None => {}
+        Body::Raw(raw) => code.push_str(&format!("        .body({})\n", rust_str(raw))),
+        Body::Form(fields) => {
+            let pairs: Vec<String> = fields
+                .iter()
+                .map(|(key, value)| format!("({}, {})", rust_str(key), rust_str(value)))
+                .collect();
+            code.push_str(&format!("        .form(&[{}])\n", pairs.join(", ")));
+        }
+        Body::Multipart(_) => code.push_str("        .multipart(form)\n"),
+        Body::Binary => code.push_str(&format!(
+            "        .body(std::fs::read({})?)\n",
+            rust_str(BINARY_FILE)
+        )),
+    }
+    code.push_str("        .send()?;\n\n");
+    code.push_str(
+        "    println!(\"{}\", response.status());\n    println!(\"{}\", response.text()?);\n    Ok(())\n}\n",
+    );
+    code
+}
+
+/// Headers `java.net.http` refuses to set
+const JAVA_RESTRICTED_HEADERS: [&str; 5] =
+    ["connection", "content-length", "expect", "host", "upgrade"];
+
+fn java(spec: &Spec) -> String {
+    let mut imports: BTreeSet<&str> = [
+        "java.net.URI",
+        "java.net.http.HttpClient",
+        "java.net.http.HttpRequest",
+        "java.net.http.HttpResponse",
+    ]
+    .into();
+    let mut setup = String::new();
+
+    let publisher = match &spec.body {
+        Body::None => "HttpRequest.BodyPublishers.noBody()".to_string(),
+        Body::Raw(raw) => format!("HttpRequest.BodyPublishers.ofString({})", quoted(raw)),
+        Body::Form(fields) => format!(
+            "HttpRequest.BodyPublishers.ofString({})",
+            quoted(&form_string(fields))
+        ),
+        Body::Multipart(parts) => {
+            imports.insert("java.util.ArrayList");
+            imports.insert("java.util.List");
+            imports.insert("java.nio.charset.StandardCharsets");
+            setup.push_str("        List<byte[]> parts = new ArrayList<>();\n");
+            for part in parts {
+                setup.push_str(&format!(
+                    "        parts.add({}.getBytes(StandardCharsets.UTF_8));\n",
+                    quoted(&multipart_prefix(part))
+                ));
+                if part.file {
+                    imports.insert("java.nio.file.Files");
+                    imports.insert("java.nio.file.Path");
+                    setup.push_str(&format!(
+                        "        parts.add(Files.readAllBytes(Path.of({})));\n        parts.add(\"\\r\\n\".getBytes(StandardCharsets.UTF_8));\n",
+                        quoted(part.value)
+                    ));
+                }
+            }
+            setup.push_str(&format!(
+                "        parts.add({}.getBytes(StandardCharsets.UTF_8));\n\n",
+                quoted(&format!("--{}--\r\n", BOUNDARY))
+            ));
+            "HttpRequest.BodyPublishers.ofByteArrays(parts)".to_string()
+        }
+        Body::Binary => {
+            imports.insert("java.nio.file.Path");
+            format!(
+                "HttpRequest.BodyPublishers.ofFile(Path.of({}))",
+                quoted(BINARY_FILE)
+            )
+        }
+    };
+
+    let client = if spec.insecure {
+        imports.insert("java.security.SecureRandom");
+        imports.insert("java.security.cert.X509Certificate");
+        imports.insert("javax.net.ssl.SSLContext");
+        imports.insert("javax.net.ssl.TrustManager");
+        imports.insert("javax.net.ssl.X509TrustManager");
+        setup.push_str(concat!(
+            "        TrustManager[] trustAll = { new X509TrustManager() {\n",
+            "            public void checkClientTrusted(X509Certificate[] chain, String authType) {}\n",
+            "            public void checkServerTrusted(X509Certificate[] chain, String authType) {}\n",
+            "            public X509Certificate[] getAcceptedIssuers() { return new X509Certificate[0]; }\n",
+            "        } };\n",
+            "        SSLContext sslContext = SSLContext.getInstance(\"TLS\");\n",
+            "        sslContext.init(null, trustAll, new SecureRandom());\n\n",
+        ));
+        "HttpClient.newBuilder().sslContext(sslContext).build()"
+    } else {
+        "HttpClient.newHttpClient()"
+    };
+
+    let mut code = String::new();
+    for import in &imports {
+        code.push_str(&format!("import {};\n", import));
+    }
+    code.push_str(
+        "\npublic class Main {\n    public static void main(String[] args) throws Exception {\n",
+    );
+    code.push_str(&setup);
+    code.push_str(&format!("        HttpClient client = {};\n", client));
+    code.push_str(&format!(
+        "        HttpRequest request = HttpRequest.newBuilder()\n                .uri(URI.create({}))\n",
+        quoted(&spec.url)
+    ));
+    for (key, value) in spec.headers_with_form_type() {
+        if JAVA_RESTRICTED_HEADERS.contains(&key.to_lowercase().as_str()) {
+            continue;
+        }
+        code.push_str(&format!(
+            "                .header({}, {})\n",
+            quoted(key),
+            quoted(value)
+        ));
+    }
+    if matches!(spec.body, Body::Multipart(_)) {
+        code.push_str(&format!(
+            "                .header(\"Content-Type\", {})\n",
+            quoted(&format!("multipart/form-data; boundary={}", BOUNDARY))
+        ));
+    }
+    code.push_str(&format!(
+        "                .method({}, {})\n                .build();\n\n",
+        quoted(spec.method.as_str()),
+        publisher
+    ));
+    code.push_str(concat!(
+        "        HttpResponse<String> response = client.send(request, HttpResponse.BodyHandlers.ofString());\n",
+        "        System.out.println(response.statusCode());\n",
+        "        System.out.println(response.body());\n",
+        "    }\n}\n",
+    ));
+    code
+}
+
+fn powershell(spec: &Spec) -> String {
+    let method = spec.method.as_str();
+    let method = format!("{}{}", &method[..1], method[1..].to_lowercase());
+
+    let mut code = String::from("$params = @{\n");
+    code.push_str(&format!("    Uri = {}\n", ps(&spec.url)));
+    code.push_str(&format!("    Method = {}\n", ps(&method)));
+
+    // Invoke-WebRequest wants the content type as its own parameter
+    let headers: Vec<_> = spec
+        .headers
+        .iter()
+        .filter(|(key, _)| !key.eq_ignore_ascii_case("content-type"))
+        .collect();
+    if !headers.is_empty() {
+        code.push_str("    Headers = @{\n");
+        for (key, value) in headers {
+            code.push_str(&format!("        {} = {}\n", ps(key), ps(value)));
+        }
+        code.push_str("    }\n");
+    }
+    if let Some(content_type) = spec.content_type() {
+        code.push_str(&format!("    ContentType = {}\n", ps(content_type)));
+    }
+    match &spec.body {
+        Body::None => {}
+        Body::Raw(raw) => code.push_str(&format!("    Body = {}\n", ps(raw))),
+        Body::Form(fields) => {
+            code.push_str("    Body = @{\n");
+            for (key, value) in fields {
+                code.push_str(&format!("        {} = {}\n", ps(key), ps(value)));
+            }
+            code.push_str("    }\n");
+        }
+        Body::Multipart(parts) => {
+            code.push_str("    Form = @{\n");
+            for part in parts {
+                let value = if part.file {
+                    format!("Get-Item -Path {}", ps(part.value))
+                } else {
+                    ps(part.value)
+                };
+                code.push_str(&format!("        {} = {}\n", ps(part.name), value));
+            }
+            code.push_str("    }\n");
+        }
+        Body::Binary => code.push_str(&format!("    InFile = {}\n", ps(BINARY_FILE))),
+    }
+    if spec.insecure {
+        code.push_str("    SkipCertificateCheck = $true\n");
+    }
+    code.push_str("}\n$response = Invoke-WebRequest @params\n");
+    code.push_str("$response.StatusCode\n$response.Content\n");
+    code
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{AuthScheme, KeyValue};
+    use crate::utils::curl;
+
+    fn request(body: BodyType) -> Request {
+        Request {
+            method: HttpMethod::POST,
+            url: "https://{{host}}/users".to_string(),
+            headers: vec![
+                KeyValue::new("Content-Type", "application/json"),
+                KeyValue::new("Authorization", "Bearer {{token}}"),
+            ],
+            query_params: vec![KeyValue::new("q", "a b&c")],
+            body,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_curl_round_trip() {
+        let original = request(BodyType::Json("{\"name\":\"it's\"}".to_string()));
+        let code = generate(&original, CodeLanguage::Curl);
+        assert!(code.starts_with("curl 'https://{{host}}/users?q=a%20b%26c' \\\n  -X POST"));
+
+        let parsed = curl::parse(&code).unwrap();
+        assert_eq!(parsed.method, HttpMethod::POST);
+        assert_eq!(parsed.url, "https://{{host}}/users?q=a%20b%26c");
+        assert_eq!(parsed.headers[1].value, "Bearer {{token}}");
+        assert!(matches!(parsed.body, BodyType::Json(ref body) if body == "{\"name\":\"it's\"}"));
+
+        let mut file = KeyValue::new("avatar", "/tmp/me.png");
+        file.param_type = FormDataParamType::File;
+        let multipart = request(BodyType::FormData(vec![KeyValue::new("name", "@me"), file]));
+        let parsed = curl::parse(&generate(&multipart, CodeLanguage::Curl)).unwrap();
+        let BodyType::FormData(fields) = parsed.body else {
+            panic!("expected multipart body");
+        };
+        assert_eq!(fields[0].value, "@me");
+        assert_eq!(fields[0].param_type, FormDataParamType::Text);
+        assert_eq!(fields[1].value, "/tmp/me.png");
+        assert_eq!(fields[1].param_type, FormDataParamType::File);
+        // The stored JSON content type would clash with the multipart boundary
+        assert_eq!(parsed.headers.len(), 1);
+    }
+
+    #[test]
+    fn test_python_multipart() {
+        let mut file = KeyValue::new("avatar", "/tmp/me.png");
+        file.param_type = FormDataParamType::File;
+        let mut disabled = KeyValue::new("skip", "1");
+        disabled.enabled = false;
+        let mut original = request(BodyType::FormData(vec![
+            KeyValue::new("name", "me"),
+            file,
+            disabled,
+        ]));
+        original.insecure = true;
+
+        let code = generate(&original, CodeLanguage::PythonRequests);
+        assert!(code.contains("    (\"name\", (None, \"me\")),\n"));
+        assert!(code.contains("    (\"avatar\", (\"me.png\", open(\"/tmp/me.png\", \"rb\"))),\n"));
+        assert!(!code.contains("skip"));
+        assert!(!code.contains("application/json"));
+        assert!(code
+            .contains("response = requests.post(url, headers=headers, files=files, verify=False)"));
+    }
+
+    #[test]
+    fn test_go_form_imports() {
+        let mut original = request(BodyType::FormUrlEncoded(vec![KeyValue::new("a", "1")]));
+        original.headers.clear();
+        original.method = HttpMethod::PUT;
+
+        let code = generate(&original, CodeLanguage::GoNetHttp);
+        assert!(code.contains(
+            "import (\n\t\"fmt\"\n\t\"io\"\n\t\"net/http\"\n\t\"net/url\"\n\t\"strings\"\n)"
+        ));
+        assert!(code.contains("\tform.Add(\"a\", \"1\")\n"));
+        assert!(code.contains(
+            "\treq.Header.Add(\"Content-Type\", \"application/x-www-form-urlencoded\")\n"
+        ));
+        assert!(
+            code.contains("http.NewRequest(\"PUT\", \"https://{{host}}/users?q=a%20b%26c\", body)")
+        );
+    }
+
+    #[test]
+    fn test_every_language_handles_every_body() {
+        let mut file = KeyValue::new("f", "a.txt");
+        file.param_type = FormDataParamType::File;
+        let bodies = [
+            BodyType::None,
+            BodyType::Text("x".to_string()),
+            BodyType::FormUrlEncoded(vec![KeyValue::new("a", "1")]),
+            BodyType::FormData(vec![file]),
+            BodyType::Binary(vec![0, 1]),
+        ];
+        for body in bodies {
+            let original = request(body);
+            for language in CodeLanguage::all() {
+                let code = generate(&original, language);
+                assert!(code.contains("{{host}}/users"), "{}", language);
+            }
+        }
+        let ps = generate(&request(BodyType::Binary(vec![])), CodeLanguage::PowerShell);
+        assert!(ps.contains("    Method = 'Post'\n    Headers = @{\n"));
+        assert!(ps.contains("    ContentType = 'application/json'\n    InFile = 'body.bin'\n"));
+    }
+
+    #[test]
+    fn test_every_language_sends_auth_and_cookies() {
+        let schemes = [
+            (
+                AuthScheme::Basic {
+                    username: "admin".to_string(),
+                    password: "secret".to_string(),
+                },
+                "Basic YWRtaW46c2VjcmV0",
+            ),
+            (
+                AuthScheme::Bearer {
+                    token: "{{token}}".to_string(),
+                },
+                "Bearer {{token}}",
+            ),
+            (
+                AuthScheme::ApiKey {
+                    key: "X-Api-Key".to_string(),
+                    value: "k3y".to_string(),
+                    in_query: false,
+                },
+                "X-Api-Key",
+            ),
+            (
+                AuthScheme::ApiKey {
+                    key: "api_key".to_string(),
+                    value: "k3y".to_string(),
+                    in_query: true,
+                },
+                "users?q=a%20b%26c&api_key=k3y",
+            ),
+        ];
+        for (scheme, expected) in schemes {
+            let mut original = request(BodyType::None);
+            original.headers.retain(|h| h.key != "Authorization");
+            original.auth = scheme.to_fields();
+            original.cookies = vec![KeyValue::new("session", "abc")];
+            for language in CodeLanguage::all() {
+                let code = generate(&original, language);
+                assert!(code.contains(expected), "{}: {}", language, expected);
+                assert!(code.contains("session=abc"), "{}", language);
+            }
+        }
+
+        // Headers written by hand win over the auth fields
+        let mut original = request(BodyType::None);
+        original.auth = AuthScheme::Bearer {
+            token: "other".to_string(),
+        }
+        .to_fields();
+        let code = generate(&original, CodeLanguage::Curl);
+        assert!(code.contains("Bearer {{token}}") && !code.contains("Bearer other"));
+    }
+}
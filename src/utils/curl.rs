@@ -0,0 +1,602 @@
+use base64::Engine;
+
+use crate::models::{BodyType, FormDataParamType, HttpMethod, KeyValue, Request};
+
+/// Whether pasted text is a curl command rather than a URL
+pub fn is_curl_command(input: &str) -> bool {
+    let input = input.trim_start();
+    input
+        .strip_prefix("curl")
+        .is_some_and(|rest| rest.starts_with(char::is_whitespace))
+}
+
+/// Parse a curl command, as copied from browser devtools or documentation
+///
+/// `-u` becomes an `Authorization` header, which is what curl sends, and
+/// `-b` fills the request's cookies. Data given as `@file` is read from the
+/// file. `--compressed` is accepted and ignored since the client negotiates
+/// encodings itself; output and transfer options are skipped.
+pub fn parse(command: &str) -> Result<Request, String> {
+    let tokens = tokenize(command)?;
+    let mut tokens = tokens.into_iter();
+    match tokens.next() {
+        Some(program) if program == "curl" || program == "curl.exe" => {}
+        _ => return Err("Not a curl command".to_string()),
+    }
+
+    let mut parsed = Parsed::default();
+    let mut positional_only = false;
+
+    while let Some(token) = tokens.next() {
+        if positional_only || !token.starts_with('-') || token == "-" {
+            parsed.positional(token);
+            continue;
+        }
+        if token == "--" {
+            positional_only = true;
+            continue;
+        }
+
+        if let Some(long) = token.strip_prefix("--") {
+            let (name, inline) = match long.split_once('=') {
+                Some((name, value)) if takes_value(name) => (name, Some(value.to_string())),
+                _ => (long, None),
+            };
+            let value = if takes_value(name) {
+                Some(
+                    inline
+                        .or_else(|| tokens.next())
+                        .ok_or_else(|| format!("--{} needs a value", name))?,
+                )
+            } else {
+                None
+            };
+            parsed.apply(name, value)?;
+            continue;
+        }
+
+        // Short options may be bundled (`-sSL`) or carry their value (`-XPOST`)
+        let flags: Vec<char> = token.chars().skip(1).collect();
+        for (idx, flag) in flags.iter().enumerate() {
+            let Some(name) = long_name(*flag) else {
+                continue;
+            };
+            if takes_value(name) {
+                let rest: String = flags[idx + 1..].iter().collect();
+                let value = if rest.is_empty() {
+                    tokens
+                        .next()
+                        .ok_or_else(|| format!("-{} needs a value", flag))?
+                } else {
+                    rest
+                };
+                parsed.apply(name, Some(value))?;
+                break;
+            }
+            parsed.apply(name, None)?;
+        }
+    }
+
+    parsed.into_request()
+}
+
+/// Long name of the short options this parser understands
+fn long_name(flag: char) -> Option<&'static str> {
+    Some(match flag {
+        'X' => "request",
+        'H' => "header",
+        'd' => "data",
+        'F' => "form",
+        'u' => "user",
+        'b' => "cookie",
+        'A' => "user-agent",
+        'e' => "referer",
+        'G' => "get",
+        'I' => "head",
+        'k' => "insecure",
+        'o' => "output",
+        'm' => "max-time",
+        'x' => "proxy",
+        'T' => "upload-file",
+        'c' => "cookie-jar",
+        'w' => "write-out",
+        'E' => "cert",
+        'K' => "config",
+        'r' => "range",
+        _ => return None,
+    })
+}
+
+/// Options followed by a value; every other option is a switch
+fn takes_value(name: &str) -> bool {
+    matches!(
+        name,
+        "request"
+            | "header"
+            | "data"
+            | "data-raw"
+            | "data-binary"
+            | "data-ascii"
+            | "data-urlencode"
+            | "json"
+            | "form"
+            | "form-string"
+            | "user"
+            | "cookie"
+            | "user-agent"
+            | "referer"
+            | "url"
+            | "output"
+            | "max-time"
+            | "connect-timeout"
+            | "proxy"
+            | "upload-file"
+            | "cookie-jar"
+            | "write-out"
+            | "cert"
+            | "key"
+            | "cacert"
+            | "config"
+            | "range"
+            | "resolve"
+            | "retry"
+            | "max-redirs"
+    )
+}
+
+enum DataPart {
+    /// Sent as is, like `-d` and `--data-raw`
+    Raw(String),
+    /// `--data-urlencode`, encoded by curl before sending
+    UrlEncode(String),
+}
+
+#[derive(Default)]
+struct Parsed {
+    method: Option<HttpMethod>,
+    url: Option<String>,
+    headers: Vec<KeyValue>,
+    cookies: Vec<KeyValue>,
+    data: Vec<DataPart>,
+    /// `--data-binary @file` contents that are not text
+    binary: Option<Vec<u8>>,
+    form: Vec<KeyValue>,
+    json: bool,
+    get: bool,
+    head: bool,
+    insecure: bool,
+}
+
+impl Parsed {
+    fn positional(&mut self, token: String) {
+        // curl accepts several URLs; only the first one is imported
+        if self.url.is_none() {
+            self.url = Some(token);
+        }
+    }
+
+    fn apply(&mut self, name: &str, value: Option<String>) -> Result<(), String> {
+        let value = value.unwrap_or_default();
+        match name {
+            "request" => self.method = Some(value.parse()?),
+            "url" => self.positional(value),
+            "header" => {
+                if let Some((key, value)) = value.split_once(':') {
+                    self.headers.push(KeyValue::new(key.trim(), value.trim()));
+                }
+            }
+            "data-raw" => self.data.push(DataPart::Raw(value)),
+            "data" | "data-binary" | "data-ascii" | "json" => {
+                self.json |= name == "json";
+                let value = match value.strip_prefix('@') {
+                    // `-d` drops the line breaks of the file, like curl does
+                    Some(path) => match read_data_file(path)? {
+                        Ok(text) if name == "data-binary" || name == "json" => text,
+                        Ok(text) => text.replace(['\r', '\n'], ""),
+                        Err(bytes) => {
+                            self.binary = Some(bytes);
+                            return Ok(());
+                        }
+                    },
+                    None => value,
+                };
+                self.data.push(DataPart::Raw(value));
+            }
+            "data-urlencode" => self.data.push(DataPart::UrlEncode(value)),
+            "form" | "form-string" => {
+                let (key, value) = value.split_once('=').unwrap_or((&value, ""));
+                let mut field = KeyValue::new(key, value);
+                if name == "form" {
+                    if let Some(path) = value.strip_prefix('@') {
+                        // Drop `;type=` and `;filename=` modifiers
+                        let path = path.split(';').next().unwrap_or(path);
+                        field = KeyValue::new(key, path);
+                        field.param_type = FormDataParamType::File;
+                    }
+                }
+                self.form.push(field);
+            }
+            "user" => {
+                let credentials = if value.contains(':') {
+                    value
+                } else {
+                    format!("{}:", value)
+                };
+                let encoded = base64::engine::general_purpose::STANDARD.encode(credentials);
+                self.headers
+                    .push(KeyValue::new("Authorization", format!("Basic {}", encoded)));
+            }
+            // Without `=` the value names a cookie file, which cannot be imported
+            "cookie" if value.contains('=') => {
+                for pair in value.split(';').filter(|pair| !pair.trim().is_empty()) {
+                    let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+                    self.cookies.push(KeyValue::new(key.trim(), value.trim()));
+                }
+            }
+            "user-agent" => self.headers.push(KeyValue::new("User-Agent", value)),
+            "referer" => self.headers.push(KeyValue::new("Referer", value)),
+            "get" => self.get = true,
+            "head" => self.head = true,
+            "insecure" => self.insecure = true,
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn into_request(mut self) -> Result<Request, String> {
+        let url = self
+            .url
+            .take()
+            .ok_or_else(|| "The curl command has no URL".to_string())?;
+        let has_body = !self.data.is_empty() || !self.form.is_empty() || self.binary.is_some();
+
+        let method = match self.method {
+            Some(method) => method,
+            None if self.head => HttpMethod::HEAD,
+            None if has_body && !self.get => HttpMethod::POST,
+            None => HttpMethod::GET,
+        };
+
+        let content_type = self
+            .headers
+            .iter()
+            .find(|h| h.key.eq_ignore_ascii_case("content-type"))
+            .map(|h| h.value.to_lowercase());
+
+        let mut query_params = vec![];
+        let body = if !self.form.is_empty() {
+            // The boundary in a copied header would not match the one sent
+            self.headers
+                .retain(|h| !h.key.eq_ignore_ascii_case("content-type"));
+            BodyType::FormData(std::mem::take(&mut self.form))
+        } else if let Some(bytes) = self.binary.take() {
+            BodyType::Binary(bytes)
+        } else if self.data.is_empty() {
+            BodyType::None
+        } else if self.get {
+            query_params = form_fields(&self.data);
+            BodyType::None
+        } else {
+            let raw = self
+                .data
+                .iter()
+                .map(|part| match part {
+                    DataPart::Raw(value) | DataPart::UrlEncode(value) => value.as_str(),
+                })
+                .collect::<Vec<_>>()
+                .join("&");
+            let looks_like_json = serde_json::from_str::<serde_json::Value>(&raw)
+                .is_ok_and(|value| value.is_object() || value.is_array());
+
+            match content_type.as_deref() {
+                Some(ct) if ct.contains("json") => BodyType::Json(raw),
+                Some(ct) if ct.contains("xml") => BodyType::Xml(raw),
+                Some(ct) if ct.contains("x-www-form-urlencoded") => {
+                    BodyType::FormUrlEncoded(form_fields(&self.data))
+                }
+                Some(_) => BodyType::Text(raw),
+                None if self.json || looks_like_json => {
+                    self.headers
+                        .push(KeyValue::new("Content-Type", "application/json"));
+                    BodyType::Json(raw)
+                }
+                None => BodyType::FormUrlEncoded(form_fields(&self.data)),
+            }
+        };
+
+        // Recomputed by the client for the body it actually sends
+        self.headers
+            .retain(|h| !h.key.eq_ignore_ascii_case("content-length"));
+
+        Ok(Request {
+            name: request_name(method, &url),
+            method,
+            url,
+            headers: self.headers,
+            query_params,
+            cookies: self.cookies,
+            body,
+            insecure: self.insecure,
+            ..Default::default()
+        })
+    }
+}
+
+/// Contents of a `@file` data argument, or its bytes when it is not text
+fn read_data_file(path: &str) -> Result<Result<String, Vec<u8>>, String> {
+    if path == "-" {
+        return Err("Data read from standard input cannot be imported".to_string());
+    }
+    let bytes = std::fs::read(path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    Ok(String::from_utf8(bytes).map_err(|e| e.into_bytes()))
+}
+
+/// `METHOD /path`, falling back to the whole URL
+fn request_name(method: HttpMethod, url: &str) -> String {
+    let path = reqwest::Url::parse(url)
+        .map(|url| url.path().to_string())
+        .unwrap_or_else(|_| url.to_string());
+    format!("{} {}", method.as_str(), path)
+}
+
+/// Split `a=1&b=2` data into decoded fields
+fn form_fields(data: &[DataPart]) -> Vec<KeyValue> {
+    let mut fields = vec![];
+    for part in data {
+        match part {
+            DataPart::Raw(raw) => {
+                for pair in raw.split('&').filter(|pair| !pair.is_empty()) {
+                    let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+                    fields.push(KeyValue::new(percent_decode(key), percent_decode(value)));
+                }
+            }
+            // `name=content` is sent encoded, so the content is already plain
+            DataPart::UrlEncode(raw) => {
+                let (key, value) = raw.split_once('=').unwrap_or(("", raw));
+                fields.push(KeyValue::new(key, value));
+            }
+        }
+    }
+    fields
+}
+
+/// Decode `%XX` escapes and `+` as used in query strings and form bodies
+pub fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => decoded.push(b' '),
+            b'%' if i + 2 < bytes.len() => {
+                let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).unwrap_or_default();
+                match u8::from_str_radix(hex, 16) {
+                    Ok(byte) => {
+                        decoded.push(byte);
+                        i += 2;
+                    }
+                    Err(_) => decoded.push(b'%'),
+                }
+            }
+            byte => decoded.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Split a command line like a POSIX shell would
+///
+/// Handles single, double and `$'...'` quotes and backslash line
+/// continuations, including the `^` continuations of Windows copies.
+fn tokenize(input: &str) -> Result<Vec<String>, String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut in_token = false;
+    let mut chars = input.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' | '^' if matches!(chars.peek(), Some('\n' | '\r')) => {
+                while matches!(chars.peek(), Some('\n' | '\r')) {
+                    chars.next();
+                }
+            }
+            c if c.is_whitespace() => {
+                if in_token {
+                    tokens.push(std::mem::take(&mut current));
+                    in_token = false;
+                }
+            }
+            '\\' => {
+                in_token = true;
+                if let Some(next) = chars.next() {
+                    current.push(next);
+                }
+            }
+            '\'' => {
+                in_token = true;
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => current.push(c),
+                        None => return Err("Unterminated ' quote".to_string()),
+                    }
+                }
+            }
+            '"' => {
+                in_token = true;
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(c @ ('"' | '\\' | '$' | '`')) => current.push(c),
+                            Some('\n') => {}
+                            Some(c) => {
+                                current.push('\\');
+                                current.push(c);
+                            }
+                            None => return Err("Unterminated \" quote".to_string()),
+                        },
+                        Some(c) => current.push(c),
+                        None => return Err("Unterminated \" quote".to_string()),
+                    }
+                }
+            }
+            '$' if chars.peek() == Some(&'\'') => {
+                chars.next();
+                in_token = true;
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some('\\') => current.push(ansi_c_escape(&mut chars)?),
+                        Some(c) => current.push(c),
+                        None => return Err("Unterminated $' quote".to_string()),
+                    }
+                }
+            }
+            c => {
+                in_token = true;
+                current.push(c);
+            }
+        }
+    }
+    if in_token {
+        tokens.push(current);
+    }
+    Ok(tokens)
+}
+
+/// Escape sequence inside `$'...'`, as emitted by Chrome's "Copy as cURL"
+fn ansi_c_escape(chars: &mut std::iter::Peekable<std::str::Chars<'_>>) -> Result<char, String> {
+    let len = match chars.next() {
+        Some('n') => return Ok('\n'),
+        Some('t') => return Ok('\t'),
+        Some('r') => return Ok('\r'),
+        Some('0') => return Ok('\0'),
+        Some('x') => 2,
+        Some('u') => 4,
+        Some(c) => return Ok(c),
+        None => return Err("Unterminated $' quote".to_string()),
+    };
+    let digits: String = (0..len).filter_map(|_| chars.next()).collect();
+    u32::from_str_radix(&digits, 16)
+        .ok()
+        .and_then(char::from_u32)
+        .ok_or_else(|| format!("Invalid escape \\{}", digits))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokenize() {
+        assert_eq!(
+            tokenize("curl 'a b' \"c \\\"d\\\"\" e\\ f \\\n  $'x\\ny\\u00e9'").unwrap(),
+            vec!["curl", "a b", "c \"d\"", "e f", "x\nyé"]
+        );
+        assert!(tokenize("curl 'open").is_err());
+    }
+
+    #[test]
+    fn test_parse_devtools_copy() {
+        let request = parse(
+            "curl 'https://api.example.com/users?page=2' \\\n  -H 'accept: application/json' \\\n  -H 'content-type: application/json' \\\n  -H 'content-length: 13' \\\n  -b 'session=abc; theme=dark' \\\n  --data-raw '{\"name\":\"a\"}' \\\n  --compressed -k",
+        )
+        .unwrap();
+
+        assert_eq!(request.method, HttpMethod::POST);
+        assert_eq!(request.url, "https://api.example.com/users?page=2");
+        assert_eq!(request.name, "POST /users");
+        assert!(request.insecure);
+        let headers: Vec<(&str, &str)> = request
+            .headers
+            .iter()
+            .map(|h| (h.key.as_str(), h.value.as_str()))
+            .collect();
+        assert_eq!(
+            headers,
+            vec![
+                ("accept", "application/json"),
+                ("content-type", "application/json"),
+            ]
+        );
+        let cookies: Vec<(&str, &str)> = request
+            .cookies
+            .iter()
+            .map(|c| (c.key.as_str(), c.value.as_str()))
+            .collect();
+        assert_eq!(cookies, vec![("session", "abc"), ("theme", "dark")]);
+        assert!(matches!(request.body, BodyType::Json(ref body) if body == "{\"name\":\"a\"}"));
+    }
+
+    #[test]
+    fn test_parse_bodies_and_auth() {
+        let form = parse("curl -XPUT -d 'a=1%202&b=x+y' -d c=3 http://h/f").unwrap();
+        assert_eq!(form.method, HttpMethod::PUT);
+        let BodyType::FormUrlEncoded(fields) = form.body else {
+            panic!("expected urlencoded body");
+        };
+        let fields: Vec<(&str, &str)> = fields
+            .iter()
+            .map(|f| (f.key.as_str(), f.value.as_str()))
+            .collect();
+        assert_eq!(fields, vec![("a", "1 2"), ("b", "x y"), ("c", "3")]);
+
+        let multipart = parse(
+            "curl -F name=doc -F 'file=@/tmp/a.png;type=image/png' -H 'Content-Type: multipart/form-data; boundary=x' -u admin:secret http://h/upload",
+        )
+        .unwrap();
+        assert_eq!(multipart.method, HttpMethod::POST);
+        let BodyType::FormData(fields) = &multipart.body else {
+            panic!("expected multipart body");
+        };
+        assert_eq!(fields[1].value, "/tmp/a.png");
+        assert_eq!(fields[1].param_type, FormDataParamType::File);
+        assert_eq!(multipart.headers.len(), 1);
+        assert_eq!(multipart.headers[0].value, "Basic YWRtaW46c2VjcmV0");
+
+        let query = parse("curl -sSLG --data-urlencode 'q=a b' https://h/search").unwrap();
+        assert_eq!(query.method, HttpMethod::GET);
+        assert!(matches!(query.body, BodyType::None));
+        assert_eq!(query.query_params[0].value, "a b");
+
+        let head = parse("curl -I https://h").unwrap();
+        assert_eq!(head.method, HttpMethod::HEAD);
+
+        assert!(parse("curl -H 'a: b'").is_err());
+        assert!(is_curl_command("  curl https://h"));
+        assert!(!is_curl_command("curly.example.com"));
+    }
+
+    #[test]
+    fn test_parse_data_files() {
+        let dir = std::env::temp_dir().join(format!("requiem-curl-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let json_file = dir.join("body.json");
+        std::fs::write(&json_file, "{\"name\":\n\"a\"}\n").unwrap();
+        let data = parse(&format!("curl -d @{} http://h/j", json_file.display())).unwrap();
+        assert!(matches!(data.body, BodyType::Json(ref body) if body == "{\"name\":\"a\"}"));
+        let kept = format!("curl --data-binary @{} http://h/j", json_file.display());
+        let kept = parse(&kept).unwrap();
+        assert!(matches!(kept.body, BodyType::Json(ref body) if body == "{\"name\":\n\"a\"}\n"));
+        let image = dir.join("image.png");
+        std::fs::write(&image, [0x89, 0x50, 0xff, 0x00]).unwrap();
+        let binary = parse(&format!(
+            "curl --data-binary @{} http://h/i",
+            image.display()
+        ))
+        .unwrap();
+        assert_eq!(binary.method, HttpMethod::POST);
+        assert!(
+            matches!(binary.body, BodyType::Binary(ref bytes) if bytes == &[0x89, 0x50, 0xff, 0x00])
+        );
+        assert!(parse(&format!(
+            "curl -d @{} http://h",
+            dir.join("missing").display()
+        ))
+        .is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
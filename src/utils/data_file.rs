@@ -0,0 +1,97 @@
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::Path;
+
+use super::json_path;
+
+/// One iteration worth of variables, keyed by column name
+pub type DataRow = HashMap<String, String>;
+
+/// Load the rows of a CSV or JSON data file, chosen by extension
+pub fn load(path: &Path) -> Result<Vec<DataRow>, String> {
+    let content =
+        std::fs::read_to_string(path).map_err(|e| format!("Failed to read data file: {}", e))?;
+
+    let extension = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.to_ascii_lowercase());
+
+    match extension.as_deref() {
+        Some("json") => parse_json(&content),
+        Some("csv") => parse_csv(&content),
+        _ => Err("Unsupported data file, expected .csv or .json".to_string()),
+    }
+}
+
+/// Parse CSV with a header row; every record becomes one row
+pub fn parse_csv(content: &str) -> Result<Vec<DataRow>, String> {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_reader(content.as_bytes());
+
+    let headers = reader
+        .headers()
+        .map_err(|e| format!("Invalid CSV header: {}", e))?
+        .clone();
+
+    let mut rows = Vec::new();
+    for (idx, record) in reader.records().enumerate() {
+        let record = record.map_err(|e| format!("Invalid CSV row {}: {}", idx + 1, e))?;
+        rows.push(
+            headers
+                .iter()
+                .zip(record.iter())
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+        );
+    }
+    Ok(rows)
+}
+
+/// Parse a JSON array of flat objects; nested values are kept as JSON text
+pub fn parse_json(content: &str) -> Result<Vec<DataRow>, String> {
+    let value: Value =
+        serde_json::from_str(content).map_err(|e| format!("Invalid JSON data file: {}", e))?;
+
+    let Value::Array(items) = value else {
+        return Err("JSON data file must contain an array of objects".to_string());
+    };
+
+    items
+        .iter()
+        .enumerate()
+        .map(|(idx, item)| match item {
+            Value::Object(map) => Ok(map
+                .iter()
+                .map(|(k, v)| (k.clone(), json_path::value_to_string(v)))
+                .collect()),
+            _ => Err(format!("Row {} is not an object", idx + 1)),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_csv() {
+        let rows = parse_csv("user, password\nalice, secret\nbob,\"a,b\"\n").unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0]["user"], "alice");
+        assert_eq!(rows[0]["password"], "secret");
+        assert_eq!(rows[1]["password"], "a,b");
+    }
+
+    #[test]
+    fn test_parse_json() {
+        let rows = parse_json(r#"[{"id": 1, "name": "a"}, {"id": 2, "tags": ["x"]}]"#).unwrap();
+        assert_eq!(rows[0]["id"], "1");
+        assert_eq!(rows[0]["name"], "a");
+        assert_eq!(rows[1]["tags"], r#"["x"]"#);
+
+        assert!(parse_json(r#"{"id": 1}"#).is_err());
+        assert!(parse_json("[1, 2]").is_err());
+    }
+}
@@ -0,0 +1,435 @@
+use uuid::Uuid;
+
+use crate::models::{
+    AuthScheme, BodyType, CodeLanguage, CollectionItem, FormDataParamType, KeyValue, Request,
+};
+use crate::utils::codegen;
+
+/// Render a collection or folder as a Markdown document
+///
+/// Variables are left as `{{name}}` placeholders, so the document can be
+/// shared without leaking environment values.
+pub fn markdown(name: &str, items: &[CollectionItem]) -> String {
+    let entries = entries(items);
+    let mut md = format!("# {}\n\n", name);
+
+    if !entries.is_empty() {
+        md.push_str("## Contents\n\n");
+        for entry in &entries {
+            let indent = "  ".repeat(entry.depth);
+            match entry.item {
+                Item::Folder(name, _) => md.push_str(&format!("{}- **{}**\n", indent, name)),
+                Item::Request(request) => md.push_str(&format!(
+                    "{}- [{} {}](#{})\n",
+                    indent,
+                    request.method,
+                    request.name,
+                    anchor(request)
+                )),
+            }
+        }
+        md.push('\n');
+    }
+
+    for entry in &entries {
+        // The collection title is the only level-one heading
+        let level = "#".repeat((entry.depth + 2).min(6));
+        let request = match entry.item {
+            Item::Folder(name, _) => {
+                md.push_str(&format!("{} {}\n\n", level, name));
+                continue;
+            }
+            Item::Request(request) => request,
+        };
+
+        md.push_str(&format!("<a id=\"{}\"></a>\n\n", anchor(request)));
+        md.push_str(&format!(
+            "{} {} {}\n\n",
+            level, request.method, request.name
+        ));
+        if !request.description.trim().is_empty() {
+            md.push_str(&format!("{}\n\n", request.description.trim()));
+        }
+        md.push_str(&format!("`{} {}`\n\n", request.method, request.url));
+        if let Some(auth) = auth_summary(request) {
+            md.push_str(&format!("**Authentication:** {}\n\n", auth));
+        }
+
+        for (title, rows) in tables(request) {
+            md.push_str(&format!("**{}**\n\n", title));
+            md.push_str("| Name | Value | Enabled |\n| --- | --- | --- |\n");
+            for row in rows {
+                md.push_str(&format!(
+                    "| {} | {} | {} |\n",
+                    table_cell(&row.name),
+                    table_cell(&row.value),
+                    if row.enabled { "Yes" } else { "No" }
+                ));
+            }
+            md.push('\n');
+        }
+
+        if let Some((language, body)) = body_text(&request.body) {
+            md.push_str(&format!("**Body**\n\n{}\n", fence(language, &body)));
+        }
+        md.push_str(&format!(
+            "**Example request**\n\n{}\n",
+            fence("bash", &codegen::generate(request, CodeLanguage::Curl))
+        ));
+        if let Some(example) = &request.example {
+            md.push_str(&format!("**Example response** `{}`\n\n", example.status));
+            if !example.body.is_empty() {
+                let (language, body) = pretty(&example.body);
+                md.push_str(&format!("{}\n", fence(language, &body)));
+            }
+        }
+    }
+    md
+}
+
+/// Render a collection or folder as a single self-contained HTML page
+///
+/// The page has no external assets, so it can be opened offline.
+pub fn html(name: &str, items: &[CollectionItem]) -> String {
+    let entries = entries(items);
+    let mut nav = String::new();
+    let mut main = format!("<h1>{}</h1>\n", escape(name));
+
+    for entry in &entries {
+        let indent = format!("padding-left:{}px", entry.depth * 16 + 8);
+        let level = (entry.depth + 2).min(6);
+        let request = match entry.item {
+            Item::Folder(name, id) => {
+                nav.push_str(&format!(
+                    "<a class=\"folder\" style=\"{}\" href=\"#folder-{}\">{}</a>\n",
+                    indent,
+                    id,
+                    escape(name)
+                ));
+                main.push_str(&format!(
+                    "<h{level} id=\"folder-{}\">{}</h{level}>\n",
+                    id,
+                    escape(name),
+                    level = level
+                ));
+                continue;
+            }
+            Item::Request(request) => request,
+        };
+
+        let method = request.method.as_str();
+        nav.push_str(&format!(
+            "<a style=\"{}\" href=\"#{}\"><span class=\"method {}\">{}</span> {}</a>\n",
+            indent,
+            anchor(request),
+            method.to_lowercase(),
+            method,
+            escape(&request.name)
+        ));
+
+        main.push_str(&format!("<section id=\"{}\">\n", anchor(request)));
+        main.push_str(&format!(
+            "<h{level}><span class=\"method {}\">{}</span> {}</h{level}>\n",
+            method.to_lowercase(),
+            method,
+            escape(&request.name),
+            level = level
+        ));
+        if !request.description.trim().is_empty() {
+            for paragraph in request.description.trim().split("\n\n") {
+                main.push_str(&format!("<p>{}</p>\n", html_text(paragraph)));
+            }
+        }
+        main.push_str(&format!(
+            "<p><code>{} {}</code></p>\n",
+            method,
+            html_text(&request.url)
+        ));
+        if let Some(auth) = auth_summary(request) {
+            main.push_str(&format!(
+                "<p><strong>Authentication:</strong> {}</p>\n",
+                html_text(&auth)
+            ));
+        }
+
+        let subheading = |title: &str| format!("<h{l}>{}</h{l}>\n", title, l = (level + 1).min(6));
+        for (title, rows) in tables(request) {
+            main.push_str(&subheading(title));
+            main.push_str("<table>\n<tr><th>Name</th><th>Value</th><th>Enabled</th></tr>\n");
+            for row in rows {
+                main.push_str(&format!(
+                    "<tr{}><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+                    if row.enabled {
+                        ""
+                    } else {
+                        " class=\"disabled\""
+                    },
+                    html_text(&row.name),
+                    html_text(&row.value),
+                    if row.enabled { "Yes" } else { "No" }
+                ));
+            }
+            main.push_str("</table>\n");
+        }
+
+        if let Some((_, body)) = body_text(&request.body) {
+            main.push_str(&subheading("Body"));
+            main.push_str(&format!("<pre><code>{}</code></pre>\n", html_text(&body)));
+        }
+        main.push_str(&subheading("Example request"));
+        main.push_str(&format!(
+            "<pre><code>{}</code></pre>\n",
+            html_text(&codegen::generate(request, CodeLanguage::Curl))
+        ));
+        if let Some(example) = &request.example {
+            main.push_str(&subheading(&format!(
+                "Example response ({})",
+                example.status
+            )));
+            if !example.body.is_empty() {
+                let (_, body) = pretty(&example.body);
+                main.push_str(&format!("<pre><code>{}</code></pre>\n", html_text(&body)));
+            }
+        }
+        main.push_str("</section>\n");
+    }
+
+    let mut page = String::from("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+    page.push_str(&format!(
+        "<title>{} API Documentation</title>\n<style>{}</style>\n</head>\n",
+        escape(name),
+        STYLE
+    ));
+    page.push_str(&format!(
+        "<body>\n<nav>\n{}</nav>\n<main>\n{}</main>\n</body>\n</html>\n",
+        nav, main
+    ));
+    page
+}
+
+const STYLE: &str = "
+body { margin: 0; font-family: -apple-system, 'Segoe UI', Helvetica, sans-serif; color: #222; }
+nav {
+  position: fixed; top: 0; bottom: 0; width: 280px; overflow-y: auto; box-sizing: border-box;
+  padding: 16px 8px; background: #f6f7f9; border-right: 1px solid #ddd;
+}
+nav a { display: block; padding: 4px 8px; color: #333; text-decoration: none; font-size: 13px; }
+nav a:hover { background: #e8eefc; }
+nav a.folder { font-weight: 600; margin-top: 8px; }
+main { margin-left: 280px; padding: 24px 40px; max-width: 960px; }
+section { padding-bottom: 24px; border-bottom: 1px solid #eee; }
+table { border-collapse: collapse; margin: 8px 0; }
+th, td { border: 1px solid #ddd; padding: 4px 10px; text-align: left; font-size: 13px; }
+tr.disabled { color: #999; }
+pre { background: #f6f7f9; padding: 12px; overflow-x: auto; font-size: 12px; }
+.var { color: #b35c00; }
+.method { font-family: monospace; font-size: 0.85em; font-weight: 700; }
+.get { color: #2e7d32; } .post { color: #ef6c00; } .put { color: #1565c0; }
+.patch { color: #6a1b9a; } .delete { color: #c62828; } .head, .options { color: #555; }
+";
+
+enum Item<'a> {
+    /// Folder name and id, used as its anchor
+    Folder(&'a str, Uuid),
+    Request(&'a Request),
+}
+
+struct Entry<'a> {
+    depth: usize,
+    item: Item<'a>,
+}
+
+/// Folders and requests in document order; flows are not documented
+fn entries(items: &[CollectionItem]) -> Vec<Entry<'_>> {
+    fn walk<'a>(items: &'a [CollectionItem], depth: usize, entries: &mut Vec<Entry<'a>>) {
+        for item in items {
+            match item {
+                CollectionItem::Folder(folder) => {
+                    entries.push(Entry {
+                        depth,
+                        item: Item::Folder(&folder.name, folder.id),
+                    });
+                    walk(&folder.items, depth + 1, entries);
+                }
+                CollectionItem::Request(request) => entries.push(Entry {
+                    depth,
+                    item: Item::Request(request),
+                }),
+                CollectionItem::Flow(_) => {}
+            }
+        }
+    }
+
+    let mut entries = vec![];
+    walk(items, 0, &mut entries);
+    entries
+}
+
+struct Row {
+    name: String,
+    value: String,
+    enabled: bool,
+}
+
+/// Parameter tables of a request, skipping the empty ones
+fn tables(request: &Request) -> Vec<(&'static str, Vec<Row>)> {
+    let rows = |items: &[KeyValue]| -> Vec<Row> {
+        items
+            .iter()
+            .filter(|item| !item.key.is_empty())
+            .map(|item| Row {
+                name: item.key.clone(),
+                value: match item.param_type {
+                    FormDataParamType::File => format!("(file) {}", item.value),
+                    FormDataParamType::Text => item.value.clone(),
+                },
+                enabled: item.enabled,
+            })
+            .collect()
+    };
+
+    let mut tables = vec![
+        ("Query parameters", rows(&request.query_params)),
+        ("Headers", rows(&request.headers)),
+        ("Cookies", rows(&request.cookies)),
+    ];
+    if let BodyType::FormUrlEncoded(fields) | BodyType::FormData(fields) = &request.body {
+        tables.push(("Form fields", rows(fields)));
+    }
+    tables.retain(|(_, rows)| !rows.is_empty());
+    tables
+}
+
+/// Raw bodies with the language used to highlight them
+fn body_text(body: &BodyType) -> Option<(&'static str, String)> {
+    match body {
+        BodyType::Json(text) if !text.trim().is_empty() => Some(pretty(text)),
+        BodyType::Xml(text) if !text.trim().is_empty() => Some(("xml", text.clone())),
+        BodyType::Text(text) if !text.trim().is_empty() => Some(("text", text.clone())),
+        _ => None,
+    }
+}
+
+/// Pretty-print JSON, leaving other text as it is
+fn pretty(text: &str) -> (&'static str, String) {
+    match serde_json::from_str::<serde_json::Value>(text) {
+        Ok(value) => (
+            "json",
+            serde_json::to_string_pretty(&value).unwrap_or_else(|_| text.to_string()),
+        ),
+        Err(_) => ("text", text.to_string()),
+    }
+}
+
+/// Auth settings kept on the request, without their secret values
+fn auth_summary(request: &Request) -> Option<String> {
+    Some(match AuthScheme::from_fields(&request.auth)? {
+        AuthScheme::Basic { username, .. } => format!("Basic, username {}", username),
+        AuthScheme::Bearer { .. } => "Bearer token".to_string(),
+        AuthScheme::ApiKey { key, in_query, .. } => format!(
+            "API key {} in the {}",
+            key,
+            if in_query { "query" } else { "headers" }
+        ),
+        AuthScheme::Other { kind, .. } => kind,
+    })
+}
+
+fn anchor(request: &Request) -> String {
+    format!("req-{}", request.id)
+}
+
+/// A fenced code block long enough not to be closed by the content
+fn fence(language: &str, code: &str) -> String {
+    let mut fence = "```".to_string();
+    while code.contains(&fence) {
+        fence.push('`');
+    }
+    format!("{}{}\n{}\n{}\n", fence, language, code.trim_end(), fence)
+}
+
+fn table_cell(value: &str) -> String {
+    value.replace('|', "\\|").replace('\n', "<br>")
+}
+
+/// Escape text and mark `{{name}}` placeholders
+fn html_text(value: &str) -> String {
+    let escaped = escape(value);
+    let mut marked = String::with_capacity(escaped.len());
+    let mut rest = escaped.as_str();
+    while let Some(start) = rest.find("{{") {
+        let Some(end) = rest[start..].find("}}") else {
+            break;
+        };
+        marked.push_str(&rest[..start]);
+        marked.push_str(&format!(
+            "<span class=\"var\">{}</span>",
+            &rest[start..start + end + 2]
+        ));
+        rest = &rest[start + end + 2..];
+    }
+    marked.push_str(rest);
+    marked
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Folder, HttpMethod, ResponseExample};
+
+    fn items() -> Vec<CollectionItem> {
+        let mut request = Request {
+            name: "Get user".to_string(),
+            method: HttpMethod::GET,
+            url: "{{baseUrl}}/users/{{id}}".to_string(),
+            headers: vec![KeyValue::new("Accept", "application/json")],
+            query_params: vec![KeyValue::new("fields", "a|b")],
+            description: "Fetch one <user>".to_string(),
+            ..Default::default()
+        };
+        request.query_params[0].enabled = false;
+        request.example = Some(ResponseExample {
+            status: 200,
+            headers: vec![],
+            body: r#"{"id":1}"#.to_string(),
+            saved_at: String::new(),
+            ignore: vec![],
+        });
+        vec![CollectionItem::Folder(Folder {
+            id: Uuid::new_v4(),
+            name: "Users".to_string(),
+            items: vec![CollectionItem::Request(request)],
+            expanded: true,
+        })]
+    }
+
+    #[test]
+    fn test_markdown() {
+        let md = markdown("Shop", &items());
+        assert!(md.starts_with("# Shop\n"));
+        assert!(md.contains("- **Users**\n  - [GET Get user](#req-"));
+        assert!(md.contains("## Users\n"));
+        assert!(md.contains("### GET Get user\n"));
+        assert!(md.contains("`GET {{baseUrl}}/users/{{id}}`"));
+        assert!(md.contains("| fields | a\\|b | No |"));
+        assert!(md.contains("**Example response** `200`\n\n```json\n{\n  \"id\": 1\n}\n```"));
+        assert!(md.contains("```bash\ncurl"));
+    }
+
+    #[test]
+    fn test_html() {
+        let html = html("Shop", &items());
+        assert!(html.contains("<title>Shop API Documentation</title>"));
+        assert!(html.contains("<p>Fetch one &lt;user&gt;</p>"));
+        assert!(html.contains("<span class=\"var\">{{baseUrl}}</span>/users/"));
+        assert!(html.contains("<tr class=\"disabled\"><td>fields</td>"));
+    }
+}
@@ -0,0 +1,106 @@
+use crate::models::{ExtractionRule, ExtractionSource, Response};
+
+use super::json_path;
+
+/// Evaluate a single extraction rule against a response
+pub fn extract(rule: &ExtractionRule, response: &Response) -> Result<String, String> {
+    let expression = rule.expression.trim();
+
+    match rule.source {
+        ExtractionSource::JsonPath => {
+            let body: serde_json::Value = serde_json::from_str(&response.body)
+                .map_err(|e| format!("Response body is not JSON: {}", e))?;
+            json_path::query(&body, expression)?
+                .map(json_path::value_to_string)
+                .ok_or_else(|| format!("No value at {}", expression))
+        }
+        ExtractionSource::Header => response
+            .headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(expression))
+            .map(|(_, value)| value.clone())
+            .ok_or_else(|| format!("Header '{}' not found", expression)),
+        ExtractionSource::Cookie => response
+            .cookies
+            .iter()
+            .find(|cookie| cookie.key == expression)
+            .map(|cookie| cookie.value.clone())
+            .ok_or_else(|| format!("Cookie '{}' not found", expression)),
+        ExtractionSource::Regex => {
+            let re = regex::Regex::new(expression)
+                .map_err(|e| format!("Invalid regex '{}': {}", expression, e))?;
+            let captures = re
+                .captures(&response.body)
+                .ok_or_else(|| format!("Regex '{}' did not match", expression))?;
+            // Prefer the first capture group, fall back to the whole match
+            captures
+                .get(1)
+                .or_else(|| captures.get(0))
+                .map(|m| m.as_str().to_string())
+                .ok_or_else(|| format!("Regex '{}' did not match", expression))
+        }
+    }
+}
+
+/// Evaluate all enabled rules, returning the captured value or error for each
+pub fn extract_all<'a>(
+    rules: &'a [ExtractionRule],
+    response: &Response,
+) -> Vec<(&'a ExtractionRule, Result<String, String>)> {
+    rules
+        .iter()
+        .filter(|rule| rule.enabled && !rule.variable.trim().is_empty())
+        .map(|rule| (rule, extract(rule, response)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::KeyValue;
+    use std::collections::HashMap;
+
+    fn response() -> Response {
+        let mut headers = HashMap::new();
+        headers.insert("x-request-id".to_string(), "req-42".to_string());
+        Response::new(
+            200,
+            "200 OK".to_string(),
+            headers,
+            vec![KeyValue::new("session", "s-1")],
+            r#"{"data":{"token":"abc123"}}"#.to_string(),
+            10,
+        )
+    }
+
+    #[test]
+    fn test_extract_sources() {
+        let response = response();
+
+        let rule = ExtractionRule::new("token", ExtractionSource::JsonPath, "$.data.token");
+        assert_eq!(extract(&rule, &response), Ok("abc123".to_string()));
+
+        let rule = ExtractionRule::new("rid", ExtractionSource::Header, "X-Request-Id");
+        assert_eq!(extract(&rule, &response), Ok("req-42".to_string()));
+
+        let rule = ExtractionRule::new("sid", ExtractionSource::Cookie, "session");
+        assert_eq!(extract(&rule, &response), Ok("s-1".to_string()));
+
+        let rule = ExtractionRule::new("tok", ExtractionSource::Regex, r#""token":"(\w+)""#);
+        assert_eq!(extract(&rule, &response), Ok("abc123".to_string()));
+    }
+
+    #[test]
+    fn test_extract_all_skips_disabled() {
+        let mut disabled = ExtractionRule::new("a", ExtractionSource::Header, "x-request-id");
+        disabled.enabled = false;
+        let rules = vec![
+            disabled,
+            ExtractionRule::new("b", ExtractionSource::Cookie, "missing"),
+        ];
+
+        let results = extract_all(&rules, &response());
+        assert_eq!(results.len(), 1);
+        assert!(results[0].1.is_err());
+    }
+}
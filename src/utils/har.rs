@@ -0,0 +1,618 @@
+use base64::Engine;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::models::{
+    BodyType, Collection, CollectionItem, Folder, FormDataParamType, HarArchive, HarEntry,
+    HarFilter, HistoryEntry, HttpMethod, ImportIssue, ImportReport, ImportResult, KeyValue,
+    Request, ResponseExample,
+};
+
+/// Headers the client sets itself, or that would break a replay
+const SKIPPED_HEADERS: [&str; 5] = [
+    "host",
+    "content-length",
+    "connection",
+    "cookie",
+    "accept-encoding",
+];
+
+/// Whether `value` looks like a HAR archive
+pub fn is_archive(value: &Value) -> bool {
+    value.pointer("/log/entries").is_some_and(Value::is_array)
+}
+
+/// Read the entries of a HAR archive, to be filtered before importing
+pub fn read_archive(value: &Value) -> Result<HarArchive, String> {
+    if !is_archive(value) {
+        return Err("Not a HAR archive".to_string());
+    }
+    let log = &value["log"];
+    let name = log
+        .pointer("/pages/0/title")
+        .and_then(Value::as_str)
+        .filter(|title| !title.trim().is_empty())
+        .unwrap_or("HAR import")
+        .to_string();
+
+    let mut archive = HarArchive {
+        name,
+        entries: vec![],
+        issues: vec![],
+    };
+    for (index, entry) in array(log, "entries").iter().enumerate() {
+        match read_entry(entry) {
+            Ok(entry) => archive.entries.push(entry),
+            Err(message) => archive.issues.push(ImportIssue {
+                location: format!("Entry {}", index + 1),
+                message,
+            }),
+        }
+    }
+    Ok(archive)
+}
+
+/// Import the entries of an archive that pass the filter
+///
+/// Requests are grouped into a folder per domain when there is more than
+/// one domain, and keep their recorded response as the example.
+pub fn import_archive(archive: &HarArchive, filter: &HarFilter) -> ImportResult {
+    let mut report = ImportReport::new("HAR archive");
+    report.name = archive.name.clone();
+    report.issues = archive.issues.clone();
+
+    let mut folders: Vec<(String, Vec<CollectionItem>)> = vec![];
+    for entry in archive.entries.iter().filter(|e| filter.includes(e)) {
+        for issue in &entry.issues {
+            report.issue(&entry.request.name, issue);
+        }
+        report.requests += 1;
+        let request = CollectionItem::Request(Request {
+            id: Uuid::new_v4(),
+            ..entry.request.clone()
+        });
+        match folders
+            .iter_mut()
+            .find(|(domain, _)| *domain == entry.domain)
+        {
+            Some((_, items)) => items.push(request),
+            None => folders.push((entry.domain.clone(), vec![request])),
+        }
+    }
+
+    let items = if folders.len() == 1 {
+        folders.remove(0).1
+    } else {
+        report.folders = folders.len();
+        folders
+            .into_iter()
+            .map(|(domain, items)| {
+                CollectionItem::Folder(Folder {
+                    id: Uuid::new_v4(),
+                    name: domain,
+                    items,
+                    expanded: false,
+                })
+            })
+            .collect()
+    };
+
+    ImportResult {
+        collection: Some(Collection {
+            id: Uuid::new_v4(),
+            name: archive.name.clone(),
+            items,
+            expanded: true,
+            http_file: None,
+        }),
+        variables: Default::default(),
+        environment: None,
+        overwrite: false,
+        merge: false,
+        report,
+    }
+}
+
+fn read_entry(entry: &Value) -> Result<HarEntry, String> {
+    let har_request = entry.get("request").ok_or("Entry has no request")?;
+    let method = str_field(har_request, "method").unwrap_or("GET");
+    let method: HttpMethod = method
+        .parse()
+        .map_err(|_| format!("{} requests are not supported and were skipped", method))?;
+    let raw_url = str_field(har_request, "url").ok_or("Entry has no URL")?;
+    let parsed = reqwest::Url::parse(raw_url).map_err(|e| format!("Invalid URL: {}", e))?;
+
+    let mut issues = vec![];
+    let base = raw_url.split(['?', '#']).next().unwrap_or(raw_url);
+    let mut request = Request {
+        name: format!("{} {}", method, parsed.path()),
+        method,
+        url: base.to_string(),
+        headers: key_values(array(har_request, "headers"))
+            .into_iter()
+            .filter(|h| {
+                let key = h.key.to_lowercase();
+                !key.starts_with(':') && !SKIPPED_HEADERS.contains(&key.as_str())
+            })
+            .collect(),
+        query_params: match har_request.get("queryString") {
+            Some(Value::Array(query)) => key_values(query),
+            _ => parsed
+                .query_pairs()
+                .map(|(key, value)| KeyValue::new(key, value))
+                .collect(),
+        },
+        cookies: key_values(array(har_request, "cookies")),
+        ..Default::default()
+    };
+    if let Some(post_data) = har_request.get("postData") {
+        request.body = body(post_data, &mut issues);
+    }
+
+    let response = entry.get("response");
+    let status = response
+        .and_then(|r| r.get("status"))
+        .and_then(Value::as_u64)
+        .and_then(|status| u16::try_from(status).ok())
+        .filter(|status| *status > 0);
+    if let (Some(response), Some(status)) = (response, status) {
+        request.example = Some(ResponseExample {
+            status,
+            headers: key_values(array(response, "headers"))
+                .into_iter()
+                .filter(|h| !h.key.starts_with(':'))
+                .collect(),
+            body: response_body(response, &mut issues),
+            saved_at: str_field(entry, "startedDateTime")
+                .map(str::to_string)
+                .unwrap_or_else(|| chrono::Local::now().to_rfc3339()),
+            ignore: vec![],
+        });
+    }
+
+    let mime_type = response
+        .and_then(|r| r.pointer("/content/mimeType"))
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    let resource_type = str_field(entry, "_resourceType")
+        .filter(|t| !t.is_empty())
+        .map(str::to_lowercase)
+        .unwrap_or_else(|| resource_type(mime_type).to_string());
+
+    Ok(HarEntry {
+        request,
+        domain: parsed.host_str().unwrap_or_default().to_string(),
+        resource_type,
+        issues,
+    })
+}
+
+fn body(post_data: &Value, issues: &mut Vec<String>) -> BodyType {
+    let mime_type = str_field(post_data, "mimeType")
+        .unwrap_or_default()
+        .to_lowercase();
+    let text = str_field(post_data, "text").unwrap_or_default().to_string();
+    let params = array(post_data, "params");
+
+    if mime_type.starts_with("multipart/form-data") {
+        let fields = params
+            .iter()
+            .map(|param| {
+                let mut field = KeyValue::new(
+                    str_field(param, "name").unwrap_or_default(),
+                    str_field(param, "value").unwrap_or_default(),
+                );
+                if let Some(file_name) = str_field(param, "fileName") {
+                    issues.push(format!(
+                        "File '{}' was not recorded, choose it again before sending",
+                        file_name
+                    ));
+                    field.value = file_name.to_string();
+                    field.param_type = FormDataParamType::File;
+                }
+                field
+            })
+            .collect();
+        BodyType::FormData(fields)
+    } else if mime_type.starts_with("application/x-www-form-urlencoded") {
+        if params.is_empty() {
+            let mut url = form_url();
+            url.set_query(Some(&text));
+            BodyType::FormUrlEncoded(
+                url.query_pairs()
+                    .map(|(key, value)| KeyValue::new(key, value))
+                    .collect(),
+            )
+        } else {
+            BodyType::FormUrlEncoded(key_values(params))
+        }
+    } else if mime_type.contains("json") {
+        BodyType::Json(text)
+    } else if mime_type.contains("xml") {
+        BodyType::Xml(text)
+    } else if text.is_empty() {
+        BodyType::None
+    } else {
+        BodyType::Text(text)
+    }
+}
+
+/// Response text, decoded when the archive stored it as base64
+fn response_body(response: &Value, issues: &mut Vec<String>) -> String {
+    let text = response
+        .pointer("/content/text")
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    if response
+        .pointer("/content/encoding")
+        .and_then(Value::as_str)
+        != Some("base64")
+    {
+        return text.to_string();
+    }
+    let decoded = base64::engine::general_purpose::STANDARD
+        .decode(text)
+        .ok()
+        .and_then(|bytes| String::from_utf8(bytes).ok());
+    decoded.unwrap_or_else(|| {
+        issues.push("Binary response body was not kept in the example".to_string());
+        String::new()
+    })
+}
+
+/// Resource type for archives that do not record one, from the response type
+fn resource_type(mime_type: &str) -> &'static str {
+    let mime_type = mime_type.to_lowercase();
+    if mime_type.contains("html") {
+        "document"
+    } else if mime_type.contains("javascript") || mime_type.contains("ecmascript") {
+        "script"
+    } else if mime_type.contains("css") {
+        "stylesheet"
+    } else if mime_type.starts_with("image/") {
+        "image"
+    } else if mime_type.starts_with("font/") || mime_type.contains("woff") {
+        "font"
+    } else if mime_type.starts_with("audio/") || mime_type.starts_with("video/") {
+        "media"
+    } else {
+        "xhr"
+    }
+}
+
+/// Export sent requests and their responses as a HAR 1.2 archive
+///
+/// Only the total time of each request is measured, so it is reported as
+/// waiting time and the other phases as unknown.
+pub fn export_history(entries: &[HistoryEntry]) -> Value {
+    let entries: Vec<Value> = entries.iter().map(export_entry).collect();
+    json!({
+        "log": {
+            "version": "1.2",
+            "creator": { "name": "Requiem", "version": env!("CARGO_PKG_VERSION") },
+            "entries": entries,
+        }
+    })
+}
+
+fn export_entry(entry: &HistoryEntry) -> Value {
+    let request = &entry.request;
+    let mut url = request.url.clone();
+    if let Ok(mut parsed) = reqwest::Url::parse(&request.url) {
+        for param in request.query_params.iter().filter(|p| p.enabled) {
+            parsed
+                .query_pairs_mut()
+                .append_pair(&param.key, &param.value);
+        }
+        url = parsed.to_string();
+    }
+
+    let mut har_request = json!({
+        "method": request.method.as_str(),
+        "url": url,
+        "httpVersion": "HTTP/1.1",
+        "cookies": export_pairs(&request.cookies),
+        "headers": export_pairs(&request.headers),
+        "queryString": export_pairs(&request.query_params),
+        "headersSize": -1,
+        "bodySize": -1,
+    });
+    if let Some(post_data) = export_post_data(request) {
+        har_request["bodySize"] = json!(post_data["text"].as_str().map_or(0, str::len));
+        har_request["postData"] = post_data;
+    }
+
+    let time = entry.response.as_ref().map_or(0, |r| r.time_ms as u64);
+    let response = match &entry.response {
+        Some(response) => {
+            let mut headers: Vec<(&String, &String)> = response.headers.iter().collect();
+            headers.sort();
+            let mime_type = headers
+                .iter()
+                .find(|(key, _)| key.eq_ignore_ascii_case("content-type"))
+                .map(|(_, value)| value.as_str())
+                .unwrap_or_default();
+            json!({
+                "status": response.status,
+                "statusText": status_text(&response.status_text),
+                "httpVersion": "HTTP/1.1",
+                "cookies": export_pairs(&response.cookies),
+                "headers": headers
+                    .iter()
+                    .map(|(name, value)| json!({ "name": name, "value": value }))
+                    .collect::<Vec<_>>(),
+                "content": {
+                    "size": response.size_bytes,
+                    "mimeType": mime_type,
+                    "text": response.body,
+                },
+                "redirectURL": "",
+                "headersSize": -1,
+                "bodySize": response.size_bytes,
+            })
+        }
+        // HAR has no place for failures, so they are recorded as status 0
+        None => json!({
+            "status": 0,
+            "statusText": "",
+            "httpVersion": "",
+            "cookies": [],
+            "headers": [],
+            "content": { "size": 0, "mimeType": "" },
+            "redirectURL": "",
+            "headersSize": -1,
+            "bodySize": -1,
+            "_error": entry.error.clone().unwrap_or_default(),
+        }),
+    };
+
+    json!({
+        "startedDateTime": entry.sent_at,
+        "time": time,
+        "request": har_request,
+        "response": response,
+        "cache": {},
+        "timings": {
+            "blocked": -1,
+            "dns": -1,
+            "connect": -1,
+            "ssl": -1,
+            "send": 0,
+            "wait": time,
+            "receive": 0,
+        },
+    })
+}
+
+fn export_post_data(request: &Request) -> Option<Value> {
+    let content_type = request
+        .headers
+        .iter()
+        .find(|h| h.enabled && h.key.eq_ignore_ascii_case("content-type"))
+        .map(|h| h.value.clone());
+    let mime_type = |default: &str| content_type.clone().unwrap_or_else(|| default.to_string());
+    let text = |mime_type: String, text: &str| json!({ "mimeType": mime_type, "text": text });
+
+    Some(match &request.body {
+        BodyType::None => return None,
+        BodyType::Json(body) => text(mime_type("application/json"), body),
+        BodyType::Xml(body) => text(mime_type("application/xml"), body),
+        BodyType::Text(body) => text(mime_type("text/plain"), body),
+        BodyType::Binary(bytes) => json!({
+            "mimeType": mime_type("application/octet-stream"),
+            "text": base64::engine::general_purpose::STANDARD.encode(bytes),
+            "encoding": "base64",
+        }),
+        BodyType::FormUrlEncoded(fields) => {
+            let enabled: Vec<&KeyValue> = fields.iter().filter(|f| f.enabled).collect();
+            let mut url = form_url();
+            url.query_pairs_mut()
+                .extend_pairs(enabled.iter().map(|f| (&f.key, &f.value)));
+            json!({
+                "mimeType": "application/x-www-form-urlencoded",
+                "params": enabled
+                    .iter()
+                    .map(|f| json!({ "name": f.key, "value": f.value }))
+                    .collect::<Vec<_>>(),
+                "text": url.query().unwrap_or_default(),
+            })
+        }
+        BodyType::FormData(fields) => {
+            let params: Vec<Value> = fields
+                .iter()
+                .filter(|f| f.enabled)
+                .map(|f| match f.param_type {
+                    FormDataParamType::Text => json!({ "name": f.key, "value": f.value }),
+                    FormDataParamType::File => json!({ "name": f.key, "fileName": f.value }),
+                })
+                .collect();
+            json!({ "mimeType": "multipart/form-data", "params": params, "text": "" })
+        }
+    })
+}
+
+/// `200 OK` as formatted by the HTTP client becomes `OK`
+fn status_text(status_text: &str) -> &str {
+    match status_text.split_once(' ') {
+        Some((code, reason)) if code.chars().all(|c| c.is_ascii_digit()) => reason,
+        _ => status_text,
+    }
+}
+
+/// Placeholder URL whose query does the form encoding
+fn form_url() -> reqwest::Url {
+    reqwest::Url::parse("http://localhost/").expect("valid URL")
+}
+
+fn export_pairs(items: &[KeyValue]) -> Vec<Value> {
+    items
+        .iter()
+        .filter(|item| item.enabled && !item.key.is_empty())
+        .map(|item| json!({ "name": item.key, "value": item.value }))
+        .collect()
+}
+
+fn key_values(items: &[Value]) -> Vec<KeyValue> {
+    items
+        .iter()
+        .filter_map(|item| {
+            let name = str_field(item, "name")?;
+            Some(KeyValue::new(
+                name,
+                str_field(item, "value").unwrap_or_default(),
+            ))
+        })
+        .collect()
+}
+
+fn str_field<'a>(value: &'a Value, key: &str) -> Option<&'a str> {
+    value.get(key).and_then(Value::as_str)
+}
+
+fn array<'a>(value: &'a Value, key: &str) -> &'a [Value] {
+    value
+        .get(key)
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Response;
+    use std::collections::HashMap;
+
+    fn archive() -> Value {
+        json!({"log": {
+            "version": "1.2",
+            "pages": [{"title": "Shop checkout"}],
+            "entries": [
+                {
+                    "startedDateTime": "2024-05-01T10:00:00.000Z",
+                    "_resourceType": "fetch",
+                    "request": {
+                        "method": "POST",
+                        "url": "https://api.shop.example.com/cart?session=1",
+                        "headers": [
+                            {"name": ":authority", "value": "api.shop.example.com"},
+                            {"name": "Content-Type", "value": "application/json"},
+                            {"name": "Content-Length", "value": "12"}
+                        ],
+                        "queryString": [{"name": "session", "value": "1"}],
+                        "cookies": [{"name": "sid", "value": "abc"}],
+                        "postData": {"mimeType": "application/json", "text": "{\"qty\": 1}"}
+                    },
+                    "response": {
+                        "status": 201,
+                        "headers": [{"name": "Content-Type", "value": "application/json"}],
+                        "content": {"mimeType": "application/json", "encoding": "base64",
+                                    "text": "eyJvayI6dHJ1ZX0="}
+                    }
+                },
+                {
+                    "request": {"method": "GET", "url": "https://cdn.example.com/app.js"},
+                    "response": {"status": 200, "content": {"mimeType": "text/javascript"}}
+                },
+                {
+                    "request": {"method": "CONNECT", "url": "https://proxy.example.com"},
+                    "response": {"status": 200, "content": {}}
+                }
+            ]
+        }})
+    }
+
+    #[test]
+    fn test_read_and_import_archive() {
+        let value = archive();
+        assert!(is_archive(&value));
+        let archive = read_archive(&value).unwrap();
+        assert_eq!(archive.name, "Shop checkout");
+        assert_eq!(archive.entries.len(), 2);
+        assert_eq!(archive.issues.len(), 1);
+        assert_eq!(archive.entries[1].resource_type, "script");
+
+        let filter = HarFilter::for_archive(&archive);
+        assert!(filter.excluded_types.contains("script"));
+        let result = import_archive(&archive, &filter);
+        assert_eq!(result.report.requests, 1);
+        let collection = result.collection.unwrap();
+        let CollectionItem::Request(request) = &collection.items[0] else {
+            panic!("expected a request");
+        };
+        assert_eq!(request.name, "POST /cart");
+        assert_eq!(request.url, "https://api.shop.example.com/cart");
+        assert_eq!(request.query_params.len(), 1);
+        assert_eq!(request.headers.len(), 1);
+        assert_eq!(request.cookies[0].key, "sid");
+        assert!(matches!(&request.body, BodyType::Json(body) if body == "{\"qty\": 1}"));
+        let example = request.example.as_ref().unwrap();
+        assert_eq!(example.status, 201);
+        assert_eq!(example.body, "{\"ok\":true}");
+
+        // With assets included, each domain gets a folder
+        let result = import_archive(&archive, &HarFilter::default());
+        assert_eq!(result.report.folders, 2);
+    }
+
+    #[test]
+    fn test_export_history() {
+        let request = Request {
+            method: HttpMethod::POST,
+            url: "https://api.example.com/login".to_string(),
+            query_params: vec![KeyValue::new("next", "/home page")],
+            body: BodyType::FormUrlEncoded(vec![KeyValue::new("user", "me")]),
+            headers: vec![],
+            ..Default::default()
+        };
+        let response = Response::new(
+            302,
+            "302 Found".to_string(),
+            HashMap::from([("location".to_string(), "/home".to_string())]),
+            vec![],
+            String::new(),
+            42,
+        );
+        let entries = vec![
+            HistoryEntry {
+                id: Uuid::new_v4(),
+                sent_at: "2024-05-01T10:00:00+00:00".to_string(),
+                request: request.clone(),
+                response: Some(response),
+                error: None,
+                body_omitted: false,
+            },
+            HistoryEntry {
+                id: Uuid::new_v4(),
+                sent_at: "2024-05-01T10:01:00+00:00".to_string(),
+                request,
+                response: None,
+                error: Some("connection refused".to_string()),
+                body_omitted: false,
+            },
+        ];
+
+        let har = export_history(&entries);
+        assert!(is_archive(&har));
+        let first = &har["log"]["entries"][0];
+        assert_eq!(
+            first["request"]["url"],
+            "https://api.example.com/login?next=%2Fhome+page"
+        );
+        assert_eq!(first["request"]["postData"]["text"], "user=me");
+        assert_eq!(first["response"]["statusText"], "Found");
+        assert_eq!(first["timings"]["wait"], 42);
+        assert_eq!(
+            har["log"]["entries"][1]["response"]["_error"],
+            "connection refused"
+        );
+
+        // Exported archives can be imported again
+        let archive = read_archive(&har).unwrap();
+        assert_eq!(archive.entries.len(), 2);
+        assert_eq!(
+            archive.entries[0].request.query_params[0].value,
+            "/home page"
+        );
+    }
+}
@@ -0,0 +1,150 @@
+use chrono::{Days, NaiveDate};
+
+use crate::models::HistoryEntry;
+
+/// A parsed history search, such as `method:post status:4xx users`
+///
+/// `method:` matches the HTTP method, `status:` a status code, a class like
+/// `4xx`, or `error` for requests that got no response, and `date:` the start
+/// of the send date, so `date:2026-10` matches a whole month. Other words
+/// must all appear in the URL.
+#[derive(Debug, Default)]
+pub struct HistoryQuery {
+    method: Option<String>,
+    status: Option<String>,
+    date: Option<String>,
+    words: Vec<String>,
+}
+
+impl HistoryQuery {
+    pub fn parse(query: &str) -> Self {
+        let mut parsed = Self::default();
+        for token in query.split_whitespace() {
+            let lower = token.to_lowercase();
+            if let Some(method) = lower.strip_prefix("method:") {
+                parsed.method = Some(method.to_string());
+            } else if let Some(status) = lower.strip_prefix("status:") {
+                parsed.status = Some(status.to_string());
+            } else if let Some(date) = lower.strip_prefix("date:") {
+                parsed.date = Some(date.to_string());
+            } else {
+                parsed.words.push(lower);
+            }
+        }
+        parsed
+    }
+
+    pub fn matches(&self, entry: &HistoryEntry) -> bool {
+        if let Some(method) = &self.method {
+            if !entry.request.method.as_str().eq_ignore_ascii_case(method) {
+                return false;
+            }
+        }
+        if let Some(status) = &self.status {
+            if !status_matches(status, entry) {
+                return false;
+            }
+        }
+        if let Some(date) = &self.date {
+            if !entry.sent_at.starts_with(date.as_str()) {
+                return false;
+            }
+        }
+        let url = entry.request.url.to_lowercase();
+        self.words.iter().all(|word| url.contains(word.as_str()))
+    }
+}
+
+fn status_matches(status: &str, entry: &HistoryEntry) -> bool {
+    let Some(response) = &entry.response else {
+        return status == "error";
+    };
+    let code = response.status.to_string();
+    match status.strip_suffix("xx") {
+        Some(class) => code.starts_with(class),
+        None => code == status,
+    }
+}
+
+/// First day (`YYYY-MM-DD`) whose history is kept when keeping `days` days
+/// up to `today`, or `None` when everything is kept
+pub fn retention_start(today: NaiveDate, days: u32) -> Option<String> {
+    if days == 0 {
+        return None;
+    }
+    let start = today
+        .checked_sub_days(Days::new(u64::from(days) - 1))
+        .unwrap_or(NaiveDate::MIN);
+    Some(start.format("%Y-%m-%d").to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{HttpMethod, Request, Response};
+    use std::collections::HashMap;
+    use uuid::Uuid;
+
+    fn entry(method: HttpMethod, url: &str, status: Option<u16>, sent_at: &str) -> HistoryEntry {
+        HistoryEntry {
+            id: Uuid::new_v4(),
+            sent_at: sent_at.to_string(),
+            request: Request {
+                method,
+                url: url.to_string(),
+                ..Request::default()
+            },
+            response: status.map(|status| {
+                Response::new(
+                    status,
+                    String::new(),
+                    HashMap::new(),
+                    vec![],
+                    String::new(),
+                    5,
+                )
+            }),
+            error: status.is_none().then(|| "connection refused".to_string()),
+            body_omitted: false,
+        }
+    }
+
+    #[test]
+    fn test_history_query() {
+        let get = entry(
+            HttpMethod::GET,
+            "https://api.example.com/users/1",
+            Some(404),
+            "2026-10-19T09:30:00+02:00",
+        );
+        let post = entry(
+            HttpMethod::POST,
+            "https://api.example.com/login",
+            None,
+            "2026-09-30T18:00:00+02:00",
+        );
+
+        let matching = |query: &str| -> Vec<bool> {
+            let query = HistoryQuery::parse(query);
+            vec![query.matches(&get), query.matches(&post)]
+        };
+        assert_eq!(matching(""), [true, true]);
+        assert_eq!(matching("USERS"), [true, false]);
+        assert_eq!(matching("api.example.com login"), [false, true]);
+        assert_eq!(matching("method:post"), [false, true]);
+        assert_eq!(matching("status:404"), [true, false]);
+        assert_eq!(matching("status:4xx"), [true, false]);
+        assert_eq!(matching("status:2xx"), [false, false]);
+        assert_eq!(matching("status:error"), [false, true]);
+        assert_eq!(matching("date:2026-10"), [true, false]);
+        assert_eq!(matching("date:2026-09-30 method:POST"), [false, true]);
+    }
+
+    #[test]
+    fn test_retention_start() {
+        let today = NaiveDate::from_ymd_opt(2026, 10, 19).unwrap();
+        assert_eq!(retention_start(today, 0), None);
+        assert_eq!(retention_start(today, 1).as_deref(), Some("2026-10-19"));
+        assert_eq!(retention_start(today, 30).as_deref(), Some("2026-09-20"));
+    }
+}
@@ -0,0 +1,960 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use base64::Engine;
+use uuid::Uuid;
+
+use crate::models::{
+    AuthScheme, BodyType, Collection, CollectionItem, Folder, FormDataParamType, HttpFileLink,
+    HttpMethod, ImportReport, ImportResult, KeyValue, Request,
+};
+use crate::utils::curl::percent_decode;
+use crate::utils::variables;
+
+/// Boundary of multipart bodies written to `.http` files
+const BOUNDARY: &str = "RequiemFormBoundary";
+
+/// Requests and variables read from a `.http` file
+#[derive(Debug, Default)]
+struct HttpFile {
+    items: Vec<CollectionItem>,
+    variables: Vec<(String, String)>,
+}
+
+/// Import a `.http` file in the format of VS Code REST Client and the
+/// JetBrains HTTP Client
+///
+/// Requests are separated by `###` lines, whose text names the request;
+/// `Folder / Request` names are put into folders. `@name = value` lines
+/// become environment variables. Relative file paths are resolved against
+/// `dir`, the directory of the file.
+pub fn import(name: &str, contents: &str, dir: &Path) -> ImportResult {
+    let mut report = ImportReport::new(".http file");
+    report.name = name.to_string();
+    let file = read(contents, dir, &mut report);
+    import_result(name, file, report)
+}
+
+fn import_result(name: &str, file: HttpFile, mut report: ImportReport) -> ImportResult {
+    report.variables = file.variables.len();
+    ImportResult {
+        collection: Some(Collection {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            items: file.items,
+            expanded: true,
+            http_file: None,
+        }),
+        variables: file.variables.into_iter().collect(),
+        environment: None,
+        overwrite: false,
+        merge: false,
+        report,
+    }
+}
+
+/// Open a `.http` file as a collection that stays in sync with it
+pub fn open(path: &Path) -> Result<ImportResult, String> {
+    let contents = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let name = path
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or("HTTP requests");
+    let mut report = ImportReport::new(".http file");
+    report.name = name.to_string();
+    let file = read(
+        &contents,
+        path.parent().unwrap_or(Path::new(".")),
+        &mut report,
+    );
+    let link = HttpFileLink {
+        path: path.to_path_buf(),
+        variables: file.variables.clone(),
+    };
+    let mut result = import_result(name, file, report);
+    if let Some(collection) = &mut result.collection {
+        collection.http_file = Some(link);
+    }
+    Ok(result)
+}
+
+/// Replace the items of a linked collection with the contents of its file
+///
+/// Requests are matched by folder and name, so they keep their id and the
+/// settings a `.http` file cannot hold, such as extraction rules, schemas,
+/// examples and flows. Returns whether anything changed.
+pub fn sync(collection: &mut Collection, contents: &str) -> bool {
+    let Some(file) = read_linked(collection, contents) else {
+        return false;
+    };
+    if is_current(collection, &file) {
+        return false;
+    }
+
+    let mut previous = std::mem::take(&mut collection.items);
+    collection.items = file.items;
+    restore(&mut collection.items, &mut previous);
+    if let Some(link) = &mut collection.http_file {
+        link.variables = file.variables;
+    }
+    true
+}
+
+/// Whether the `.http` file of a linked collection already holds what it
+/// would be saved as, ignoring formatting and comments
+pub fn matches_file(collection: &Collection, contents: &str) -> bool {
+    read_linked(collection, contents).is_some_and(|file| is_current(collection, &file))
+}
+
+fn read_linked(collection: &Collection, contents: &str) -> Option<HttpFile> {
+    let link = collection.http_file.as_ref()?;
+    let dir = link.path.parent().unwrap_or(Path::new("."));
+    Some(read(contents, dir, &mut ImportReport::default()))
+}
+
+fn is_current(collection: &Collection, file: &HttpFile) -> bool {
+    let variables = collection
+        .http_file
+        .as_ref()
+        .map_or(&[][..], |link| &link.variables);
+    export(&collection.items, variables) == export(&file.items, &file.variables)
+}
+
+/// Header, or query parameter when the flag is set, that auth fields are
+/// written as
+fn auth_pair(auth: &[KeyValue]) -> Option<(bool, String, String)> {
+    match AuthScheme::from_fields(auth)? {
+        // REST Client encodes `user:password` itself
+        AuthScheme::Basic { username, password } => Some((
+            false,
+            "Authorization".to_string(),
+            format!("Basic {}:{}", username, password),
+        )),
+        AuthScheme::Bearer { token } => Some((
+            false,
+            "Authorization".to_string(),
+            format!("Bearer {}", token),
+        )),
+        AuthScheme::ApiKey {
+            key,
+            value,
+            in_query,
+        } => Some((in_query, key, value)),
+        _ => None,
+    }
+}
+
+/// Enabled cookies as the value of one `Cookie` header
+fn cookie_header(cookies: &[KeyValue]) -> Option<String> {
+    let cookies: Vec<String> = enabled(cookies)
+        .iter()
+        .map(|(k, v)| format!("{}={}", k, v))
+        .collect();
+    (!cookies.is_empty()).then(|| cookies.join("; "))
+}
+
+/// `{{name}}` written in place of a secret value, for whoever runs the file
+/// to define in their own environment
+fn placeholder(field: &KeyValue) -> String {
+    format!("{{{{{}}}}}", field.key)
+}
+
+/// Fields with their secret values replaced by placeholders
+fn shareable(fields: &[KeyValue]) -> Vec<KeyValue> {
+    fields
+        .iter()
+        .map(|field| match field.secret {
+            true => KeyValue {
+                value: placeholder(field),
+                ..field.clone()
+            },
+            false => field.clone(),
+        })
+        .collect()
+}
+
+/// Put back the secret fields of a request that the file only holds
+/// placeholders for
+fn keep_secrets(request: &mut Request, old: &Request) {
+    for (fields, old_fields) in [
+        (&mut request.headers, &old.headers),
+        (&mut request.query_params, &old.query_params),
+    ] {
+        for field in fields.iter_mut() {
+            let secret = old_fields
+                .iter()
+                .find(|old| old.secret && old.key == field.key && placeholder(old) == field.value);
+            if let Some(secret) = secret {
+                *field = secret.clone();
+            }
+        }
+    }
+
+    // Cookies and auth fields come back as the header or parameter they
+    // were written as
+    if old.cookies.iter().any(|cookie| cookie.secret) {
+        if let Some(value) = cookie_header(&shareable(&old.cookies)) {
+            if take_field(&mut request.headers, "cookie", &value) {
+                request.cookies = old.cookies.clone();
+            }
+        }
+    }
+    if old.auth.iter().any(|field| field.secret) {
+        if let Some((in_query, key, value)) = auth_pair(&shareable(&old.auth)) {
+            let fields = match in_query {
+                true => &mut request.query_params,
+                false => &mut request.headers,
+            };
+            if take_field(fields, &key, &value) {
+                request.auth = old.auth.clone();
+            }
+        }
+    }
+}
+
+/// Remove the field with this key and value, returning whether there was one
+fn take_field(fields: &mut Vec<KeyValue>, key: &str, value: &str) -> bool {
+    let found = fields
+        .iter()
+        .position(|field| field.key.eq_ignore_ascii_case(key) && field.value == value);
+    if let Some(index) = found {
+        fields.remove(index);
+    }
+    found.is_some()
+}
+
+/// Keep ids, expanded folders and app-only settings of matching items,
+/// and the flows that a `.http` file cannot hold
+fn restore(items: &mut Vec<CollectionItem>, previous: &mut Vec<CollectionItem>) {
+    for item in items.iter_mut() {
+        match item {
+            CollectionItem::Request(request) => {
+                let found = previous.iter().position(
+                    |p| matches!(p, CollectionItem::Request(r) if r.name == request.name),
+                );
+                if let Some(CollectionItem::Request(old)) = found.map(|i| previous.remove(i)) {
+                    keep_secrets(request, &old);
+                    request.id = old.id;
+                    request.extractions = old.extractions;
+                    request.example = old.example;
+                    request.schema = old.schema;
+                    request.insecure = old.insecure;
+                }
+            }
+            CollectionItem::Folder(folder) => {
+                let found = previous
+                    .iter()
+                    .position(|p| matches!(p, CollectionItem::Folder(f) if f.name == folder.name));
+                if let Some(CollectionItem::Folder(mut old)) = found.map(|i| previous.remove(i)) {
+                    folder.id = old.id;
+                    folder.expanded = old.expanded;
+                    restore(&mut folder.items, &mut old.items);
+                }
+            }
+            CollectionItem::Flow(_) => {}
+        }
+    }
+    items.extend(
+        previous
+            .drain(..)
+            .filter(|item| matches!(item, CollectionItem::Flow(_))),
+    );
+}
+
+/// Variables referenced by the requests that have a value in `values`,
+/// in order of first use
+pub fn referenced_variables(
+    items: &[CollectionItem],
+    values: &HashMap<String, String>,
+) -> Vec<(String, String)> {
+    variables::referenced_variables(&export(items, &[]))
+        .into_iter()
+        .filter_map(|name| Some((name.clone(), values.get(&name)?.clone())))
+        .collect()
+}
+
+/// Write requests as a `.http` file, with `variables` defined at the top
+///
+/// Folders become part of the request names, so importing the file again
+/// restores them. Disabled headers and parameters are left out.
+pub fn export(items: &[CollectionItem], variables: &[(String, String)]) -> String {
+    let mut output = String::new();
+    for (name, value) in variables {
+        output.push_str(&format!("@{} = {}\n", name, value));
+    }
+    export_items(items, &mut vec![], &mut output);
+    output
+}
+
+fn export_items<'a>(items: &'a [CollectionItem], folders: &mut Vec<&'a str>, output: &mut String) {
+    for item in items {
+        match item {
+            CollectionItem::Request(request) => {
+                if !output.is_empty() {
+                    output.push('\n');
+                }
+                let mut name = folders.join(" / ");
+                if !name.is_empty() {
+                    name.push_str(" / ");
+                }
+                name.push_str(&request.name);
+                output.push_str(&format!("### {}\n", name));
+                export_request(request, output);
+            }
+            CollectionItem::Folder(folder) => {
+                folders.push(&folder.name);
+                export_items(&folder.items, folders, output);
+                folders.pop();
+            }
+            CollectionItem::Flow(_) => {}
+        }
+    }
+}
+
+fn export_request(request: &Request, output: &mut String) {
+    // Secret values never reach the file, which is likely committed
+    let request = &Request {
+        headers: shareable(&request.headers),
+        query_params: shareable(&request.query_params),
+        cookies: shareable(&request.cookies),
+        auth: shareable(&request.auth),
+        ..request.clone()
+    };
+    for line in request.description.lines() {
+        output.push_str(format!("# {}", line).trim_end());
+        output.push('\n');
+    }
+
+    let mut url = request.url.clone();
+    let mut headers: Vec<(String, String)> = enabled(&request.headers);
+    let mut query = enabled(&request.query_params);
+    if !has_header(&headers, "authorization") {
+        match auth_pair(&request.auth) {
+            Some((true, key, value)) => query.push((key, value)),
+            Some((false, key, value)) => headers.push((key, value)),
+            None => {}
+        }
+    }
+    if let Some(cookies) = cookie_header(&request.cookies) {
+        if !has_header(&headers, "cookie") {
+            headers.push(("Cookie".to_string(), cookies));
+        }
+    }
+    if !query.is_empty() {
+        let pairs: Vec<String> = query
+            .iter()
+            .map(|(k, v)| format!("{}={}", encode(k), encode(v)))
+            .collect();
+        url.push(if url.contains('?') { '&' } else { '?' });
+        url.push_str(&pairs.join("&"));
+    }
+
+    let body = match &request.body {
+        BodyType::None => None,
+        BodyType::Json(body) | BodyType::Xml(body) | BodyType::Text(body) => {
+            if !has_header(&headers, "content-type") {
+                let content_type = match request.body {
+                    BodyType::Json(_) => "application/json",
+                    BodyType::Xml(_) => "application/xml",
+                    _ => "text/plain",
+                };
+                headers.push(("Content-Type".to_string(), content_type.to_string()));
+            }
+            Some(body.clone())
+        }
+        BodyType::FormUrlEncoded(fields) => {
+            set_header(
+                &mut headers,
+                "Content-Type",
+                "application/x-www-form-urlencoded",
+            );
+            let pairs: Vec<String> = enabled(fields)
+                .iter()
+                .map(|(k, v)| format!("{}={}", encode(k), encode(v)))
+                .collect();
+            Some(pairs.join("\n&"))
+        }
+        BodyType::FormData(fields) => {
+            set_header(
+                &mut headers,
+                "Content-Type",
+                &format!("multipart/form-data; boundary={}", BOUNDARY),
+            );
+            let mut body = String::new();
+            for field in fields.iter().filter(|f| f.enabled) {
+                body.push_str(&format!("--{}\n", BOUNDARY));
+                match field.param_type {
+                    FormDataParamType::Text => body.push_str(&format!(
+                        "Content-Disposition: form-data; name=\"{}\"\n\n{}\n",
+                        field.key, field.value
+                    )),
+                    FormDataParamType::File => {
+                        let file_name = Path::new(&field.value)
+                            .file_name()
+                            .and_then(|name| name.to_str())
+                            .unwrap_or_default();
+                        body.push_str(&format!(
+                            "Content-Disposition: form-data; name=\"{}\"; filename=\"{}\"\n\n< {}\n",
+                            field.key, file_name, field.value
+                        ));
+                    }
+                }
+            }
+            body.push_str(&format!("--{}--", BOUNDARY));
+            Some(body)
+        }
+        BodyType::Binary(_) => {
+            output.push_str("# The binary body is not included\n");
+            None
+        }
+    };
+
+    output.push_str(&format!("{} {}\n", request.method, url));
+    for (key, value) in &headers {
+        output.push_str(&format!("{}: {}\n", key, value));
+    }
+    if let Some(body) = body.filter(|body| !body.is_empty()) {
+        output.push('\n');
+        output.push_str(body.trim_end());
+        output.push('\n');
+    }
+}
+
+fn read(contents: &str, dir: &Path, report: &mut ImportReport) -> HttpFile {
+    let mut file = HttpFile::default();
+    let mut block: Vec<&str> = vec![];
+    let mut title = None;
+    for line in contents.lines().chain(std::iter::once("###")) {
+        if let Some(rest) = line.strip_prefix("###") {
+            if let Some(request) = read_request(title.take(), &block, &mut file, dir, report) {
+                report.requests += 1;
+                add_request(&mut file.items, request, report);
+            }
+            block.clear();
+            title = Some(rest.trim().to_string()).filter(|t| !t.is_empty());
+        } else {
+            block.push(line);
+        }
+    }
+    file
+}
+
+/// Put a request named `Folder / Request` into its folder
+fn add_request(items: &mut Vec<CollectionItem>, mut request: Request, report: &mut ImportReport) {
+    let mut parts: Vec<&str> = request.name.split(" / ").collect();
+    let name = parts.pop().unwrap_or_default().to_string();
+    let mut items = items;
+    for folder_name in parts {
+        let index = items
+            .iter()
+            .position(|item| matches!(item, CollectionItem::Folder(f) if f.name == folder_name));
+        let index = index.unwrap_or_else(|| {
+            report.folders += 1;
+            items.push(CollectionItem::Folder(Folder {
+                id: Uuid::new_v4(),
+                name: folder_name.to_string(),
+                items: vec![],
+                expanded: false,
+            }));
+            items.len() - 1
+        });
+        let CollectionItem::Folder(folder) = &mut items[index] else {
+            unreachable!()
+        };
+        items = &mut folder.items;
+    }
+    request.name = name;
+    items.push(CollectionItem::Request(request));
+}
+
+fn read_request(
+    title: Option<String>,
+    lines: &[&str],
+    file: &mut HttpFile,
+    dir: &Path,
+    report: &mut ImportReport,
+) -> Option<Request> {
+    let mut name = title;
+    let mut description = vec![];
+    let mut lines = lines.iter().map(|line| line.trim_end()).peekable();
+
+    // Comments, variables and directives before the request line
+    let request_line = loop {
+        let line = lines.next()?;
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        if let Some(comment) = comment(trimmed) {
+            match comment.strip_prefix('@') {
+                Some(directive) => {
+                    if let Some(value) = directive.strip_prefix("name") {
+                        name = name.or(Some(value.trim().to_string()));
+                    }
+                }
+                None => description.push(comment),
+            }
+            continue;
+        }
+        if let Some(definition) = trimmed.strip_prefix('@') {
+            if let Some((key, value)) = definition.split_once('=') {
+                let key = key.trim().to_string();
+                file.variables.retain(|(existing, _)| *existing != key);
+                file.variables.push((key, value.trim().to_string()));
+            }
+            continue;
+        }
+        break trimmed;
+    };
+
+    let (method, mut url) = match request_line.split_once(char::is_whitespace) {
+        Some((method, rest)) if method.chars().all(|c| c.is_ascii_uppercase()) => {
+            (method, rest.trim())
+        }
+        _ => ("GET", request_line),
+    };
+    let location = name.clone().unwrap_or_else(|| request_line.to_string());
+    let Ok(method) = method.parse::<HttpMethod>() else {
+        report.issue(
+            location,
+            format!("{} requests are not supported and were skipped", method),
+        );
+        return None;
+    };
+    if let Some((rest, version)) = url.rsplit_once(' ') {
+        if version.starts_with("HTTP/") {
+            url = rest.trim_end();
+        }
+    }
+    let mut url = url.to_string();
+    // Query parameters may continue on the following lines
+    while let Some(line) = lines.next_if(|l| l.trim_start().starts_with(['?', '&'])) {
+        url.push_str(line.trim());
+    }
+
+    let mut request = Request {
+        method,
+        headers: vec![],
+        description: description.join("\n"),
+        ..Default::default()
+    };
+    match url.split_once('?') {
+        Some((base, query)) => {
+            request.url = base.to_string();
+            request.query_params = form_pairs(query);
+        }
+        None => request.url = url,
+    }
+    request.name = name.unwrap_or_else(|| {
+        let path = request.url.split("://").nth(1).unwrap_or(&request.url);
+        let path = path.find('/').map_or("/", |i| &path[i..]);
+        format!("{} {}", request.method, path)
+    });
+
+    for line in lines.by_ref() {
+        let line = line.trim();
+        if line.is_empty() {
+            break;
+        }
+        if comment(line).is_some() {
+            continue;
+        }
+        match line.split_once(':') {
+            Some((key, value)) => request
+                .headers
+                .push(KeyValue::new(key.trim(), value.trim())),
+            None => report.issue(&location, format!("Invalid header line: {}", line)),
+        }
+    }
+
+    let mut body = vec![];
+    for line in lines {
+        if line.starts_with("> ") || line.starts_with("<> ") {
+            report.issue(
+                &location,
+                "Response handlers and references are not supported",
+            );
+            break;
+        }
+        body.push(line);
+    }
+    while body.last().is_some_and(|line| line.trim().is_empty()) {
+        body.pop();
+    }
+    read_body(&mut request, &body.join("\n"), dir, &location, report);
+    read_auth(&mut request, &location, report);
+    Some(request)
+}
+
+fn read_body(
+    request: &mut Request,
+    body: &str,
+    dir: &Path,
+    location: &str,
+    report: &mut ImportReport,
+) {
+    let content_type = request
+        .headers
+        .iter()
+        .find(|h| h.key.eq_ignore_ascii_case("content-type"))
+        .map(|h| h.value.to_lowercase())
+        .unwrap_or_default();
+
+    if let Some(path) = body
+        .trim()
+        .strip_prefix("< ")
+        .filter(|_| !body.contains('\n'))
+    {
+        let path = dir.join(path.trim());
+        match std::fs::read(&path) {
+            Ok(bytes) => request.body = BodyType::Binary(bytes),
+            Err(e) => report.issue(
+                location,
+                format!("Body file {} could not be read: {}", path.display(), e),
+            ),
+        }
+        return;
+    }
+    if body.is_empty() {
+        return;
+    }
+
+    request.body = if content_type.starts_with("multipart/form-data") {
+        // Boundaries are case-sensitive, so they are taken from the header as written
+        let boundary = request
+            .headers
+            .iter()
+            .find(|h| h.key.eq_ignore_ascii_case("content-type"))
+            .and_then(|h| {
+                h.value
+                    .split(';')
+                    .find_map(|part| part.trim().strip_prefix("boundary="))
+            })
+            .map(|boundary| boundary.trim_matches('"').to_string());
+        let Some(boundary) = boundary else {
+            report.issue(location, "Multipart body has no boundary");
+            request.body = BodyType::Text(body.to_string());
+            return;
+        };
+        // The client sets its own boundary
+        request
+            .headers
+            .retain(|h| !h.key.eq_ignore_ascii_case("content-type"));
+        BodyType::FormData(multipart_fields(body, &boundary, dir))
+    } else if content_type.starts_with("application/x-www-form-urlencoded") {
+        let joined: String = body.lines().map(str::trim).collect();
+        BodyType::FormUrlEncoded(form_pairs(&joined))
+    } else if content_type.contains("json") {
+        BodyType::Json(body.to_string())
+    } else if content_type.contains("xml") {
+        BodyType::Xml(body.to_string())
+    } else {
+        BodyType::Text(body.to_string())
+    };
+}
+
+/// Fields of a multipart body; `< path` parts are files
+fn multipart_fields(body: &str, boundary: &str, dir: &Path) -> Vec<KeyValue> {
+    let delimiter = format!("--{}", boundary);
+    let mut fields = vec![];
+    for part in body.split(&delimiter).skip(1) {
+        if part.starts_with("--") {
+            break;
+        }
+        let part = part.trim_start_matches(['\r', '\n']);
+        let (headers, content) = part.split_once("\n\n").unwrap_or((part, ""));
+        let Some(disposition) = headers
+            .lines()
+            .find(|h| h.to_lowercase().starts_with("content-disposition"))
+        else {
+            continue;
+        };
+        let attribute = |name: &str| {
+            disposition
+                .split(';')
+                .find_map(|a| a.trim().strip_prefix(name)?.strip_prefix('='))
+                .map(|v| v.trim_matches('"').to_string())
+        };
+        let content = content.trim_end_matches(['\r', '\n']);
+        let mut field = KeyValue::new(attribute("name").unwrap_or_default(), content);
+        if let Some(path) = content.strip_prefix("< ") {
+            field.value = dir.join(path.trim()).to_string_lossy().to_string();
+            field.param_type = FormDataParamType::File;
+        }
+        fields.push(field);
+    }
+    fields
+}
+
+/// REST Client encodes `Authorization: Basic user:password` before sending,
+/// which this app only does for headers without variables
+fn read_auth(request: &mut Request, location: &str, report: &mut ImportReport) {
+    let Some(header) = request.headers.iter_mut().find(|h| {
+        h.key.eq_ignore_ascii_case("authorization")
+            && h.value.len() > 6
+            && h.value[..6].eq_ignore_ascii_case("basic ")
+    }) else {
+        return;
+    };
+    let credentials = header.value[6..].trim();
+    let Some((username, password)) = credentials
+        .split_once(':')
+        .or_else(|| credentials.split_once(' '))
+    else {
+        // Already encoded
+        return;
+    };
+    if credentials.contains("{{") {
+        report.issue(
+            location,
+            "Basic credentials with variables are sent without encoding them",
+        );
+        return;
+    }
+    let encoded = base64::engine::general_purpose::STANDARD.encode(format!(
+        "{}:{}",
+        username.trim(),
+        password.trim()
+    ));
+    header.value = format!("Basic {}", encoded);
+}
+
+/// Text of a `#` or `//` comment line
+fn comment(line: &str) -> Option<String> {
+    line.strip_prefix('#')
+        .or_else(|| line.strip_prefix("//"))
+        .map(|text| text.trim().to_string())
+}
+
+fn form_pairs(query: &str) -> Vec<KeyValue> {
+    query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            KeyValue::new(percent_decode(key), percent_decode(value))
+        })
+        .collect()
+}
+
+/// Escape what would end a query parameter, leaving `{{variables}}` readable
+fn encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            ' ' => encoded.push('+'),
+            '&' | '=' | '#' | '%' | '+' | '?' | '\n' => {
+                let mut buffer = [0; 4];
+                for byte in c.encode_utf8(&mut buffer).bytes() {
+                    encoded.push_str(&format!("%{:02X}", byte));
+                }
+            }
+            c => encoded.push(c),
+        }
+    }
+    encoded
+}
+
+fn enabled(items: &[KeyValue]) -> Vec<(String, String)> {
+    items
+        .iter()
+        .filter(|item| item.enabled && !item.key.is_empty())
+        .map(|item| (item.key.clone(), item.value.clone()))
+        .collect()
+}
+
+fn has_header(headers: &[(String, String)], name: &str) -> bool {
+    headers
+        .iter()
+        .any(|(key, _)| key.eq_ignore_ascii_case(name))
+}
+
+fn set_header(headers: &mut Vec<(String, String)>, name: &str, value: &str) {
+    headers.retain(|(key, _)| !key.eq_ignore_ascii_case(name));
+    headers.push((name.to_string(), value.to_string()));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FILE: &str = "\
+@baseUrl = https://api.example.com
+@token = secret
+
+### Users / List users
+# Lists every user
+GET {{baseUrl}}/users?page=2 HTTP/1.1
+    &sort=name+asc
+Authorization: Bearer {{token}}
+
+###
+# @name login
+POST {{baseUrl}}/login
+Content-Type: application/x-www-form-urlencoded
+
+user=me
+&password={{password}}
+
+> {% client.global.set(\"token\", response.body.token); %}
+
+### Upload
+POST {{baseUrl}}/files
+Content-Type: multipart/form-data; boundary=XYZ
+
+--XYZ
+Content-Disposition: form-data; name=\"title\"
+
+Report
+--XYZ
+Content-Disposition: form-data; name=\"file\"; filename=\"a.pdf\"
+
+< ./a.pdf
+--XYZ--
+
+### Ping
+https://api.example.com/ping
+
+### Trace
+TRACE https://api.example.com/
+";
+
+    #[test]
+    fn test_import() {
+        let result = import("api", FILE, Path::new("/repo"));
+        let report = &result.report;
+        assert_eq!(report.requests, 4);
+        assert_eq!(report.folders, 1);
+        assert_eq!(report.issues.len(), 2);
+        assert_eq!(result.variables["baseUrl"], "https://api.example.com");
+
+        let items = result.collection.unwrap().items;
+        let CollectionItem::Folder(users) = &items[0] else {
+            panic!("expected a folder");
+        };
+        let CollectionItem::Request(list) = &users.items[0] else {
+            panic!("expected a request");
+        };
+        assert_eq!(list.name, "List users");
+        assert_eq!(list.description, "Lists every user");
+        assert_eq!(list.url, "{{baseUrl}}/users");
+        assert_eq!(list.query_params.len(), 2);
+        assert_eq!(list.query_params[1].value, "name asc");
+        assert_eq!(list.headers[0].value, "Bearer {{token}}");
+
+        let CollectionItem::Request(login) = &items[1] else {
+            panic!("expected a request");
+        };
+        assert_eq!(login.name, "login");
+        assert_eq!(login.method, HttpMethod::POST);
+        let BodyType::FormUrlEncoded(fields) = &login.body else {
+            panic!("expected a form body");
+        };
+        assert_eq!(fields[1].value, "{{password}}");
+
+        let CollectionItem::Request(upload) = &items[2] else {
+            panic!("expected a request");
+        };
+        let BodyType::FormData(fields) = &upload.body else {
+            panic!("expected a multipart body");
+        };
+        assert_eq!(fields[0].value, "Report");
+        assert_eq!(fields[1].value, "/repo/./a.pdf");
+        assert!(matches!(fields[1].param_type, FormDataParamType::File));
+        assert!(upload.headers.is_empty());
+
+        let CollectionItem::Request(ping) = &items[3] else {
+            panic!("expected a request");
+        };
+        assert_eq!(ping.name, "Ping");
+        assert_eq!(ping.method, HttpMethod::GET);
+    }
+
+    #[test]
+    fn test_export_round_trip() {
+        let result = import("api", FILE, Path::new("/repo"));
+        let items = result.collection.unwrap().items;
+        let variables = vec![("baseUrl".to_string(), "https://api.example.com".to_string())];
+        let exported = export(&items, &variables);
+        assert!(
+            exported.starts_with("@baseUrl = https://api.example.com\n\n### Users / List users\n")
+        );
+        assert!(exported.contains("GET {{baseUrl}}/users?page=2&sort=name+asc\n"));
+
+        let again = import("api", &exported, Path::new("/"));
+        let again_items = again.collection.unwrap().items;
+        assert_eq!(export(&again_items, &variables), exported);
+    }
+
+    #[test]
+    fn test_sync_keeps_ids() {
+        let mut collection = open_collection(FILE);
+        let CollectionItem::Request(ping) = &mut collection.items[3] else {
+            panic!("expected a request");
+        };
+        ping.insecure = true;
+        let id = ping.id;
+
+        assert!(!sync(&mut collection, FILE));
+        let changed = FILE.replace("/ping", "/health");
+        assert!(sync(&mut collection, &changed));
+        let CollectionItem::Request(ping) = &collection.items[3] else {
+            panic!("expected a request");
+        };
+        assert_eq!(ping.id, id);
+        assert!(ping.insecure);
+        assert_eq!(ping.url, "https://api.example.com/health");
+    }
+
+    #[test]
+    fn test_secrets_round_trip_as_placeholders() {
+        let mut collection = open_collection("### Me\nGET https://api.example.com/me\n");
+        let CollectionItem::Request(me) = &mut collection.items[0] else {
+            panic!("expected a request");
+        };
+        let secret = |key: &str, value: &str| KeyValue {
+            secret: true,
+            ..KeyValue::new(key, value)
+        };
+        me.headers = vec![secret("X-Api-Key", "secret:me/header/X-Api-Key")];
+        me.cookies = vec![KeyValue::new("theme", "dark"), secret("session", "abc123")];
+        me.auth = vec![KeyValue::new("type", "bearer"), secret("token", "s3cr3t")];
+        let expected = me.clone();
+
+        let exported = export(&collection.items, &[]);
+        assert!(exported.contains("X-Api-Key: {{X-Api-Key}}\n"));
+        assert!(exported.contains("Authorization: Bearer {{token}}\n"));
+        assert!(exported.contains("Cookie: theme=dark; session={{session}}\n"));
+        assert!(!exported.contains("secret:") && !exported.contains("abc123"));
+        assert!(!exported.contains("s3cr3t"));
+
+        // The file holds placeholders, so it is what the collection saves as
+        assert!(!sync(&mut collection, &exported));
+        let changed = exported.replace("/me", "/users/me");
+        assert!(sync(&mut collection, &changed));
+        let CollectionItem::Request(me) = &collection.items[0] else {
+            panic!("expected a request");
+        };
+        assert_eq!(me.url, "https://api.example.com/users/me");
+        let fields = |items: &[KeyValue]| -> Vec<(String, String, bool)> {
+            items
+                .iter()
+                .map(|f| (f.key.clone(), f.value.clone(), f.secret))
+                .collect()
+        };
+        assert_eq!(fields(&me.headers), fields(&expected.headers));
+        assert_eq!(fields(&me.cookies), fields(&expected.cookies));
+        assert_eq!(fields(&me.auth), fields(&expected.auth));
+    }
+
+    fn open_collection(contents: &str) -> Collection {
+        let dir = std::env::temp_dir().join(format!("requiem-http-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("api.http");
+        std::fs::write(&path, contents).unwrap();
+        let collection = open(&path).unwrap().collection.unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+        collection
+    }
+}
@@ -0,0 +1,356 @@
+use std::path::Path;
+
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::models::{
+    BodyType, Collection, CollectionItem, Folder, ImportBaseline, ImportFile, ImportReport,
+    ImportResult, KeyValue, Request,
+};
+use crate::utils::{har, http_file, openapi, postman};
+
+/// Read a file to import, detecting its format
+///
+/// `.http` files are recognised by their extension. HAR archives are
+/// returned unimported, so the user can choose which entries to keep.
+pub fn read_file(path: &Path, contents: &str) -> Result<ImportFile, String> {
+    let extension = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or_default();
+    if extension.eq_ignore_ascii_case("http") || extension.eq_ignore_ascii_case("rest") {
+        let name = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .unwrap_or("HTTP requests");
+        let dir = path.parent().unwrap_or(Path::new("."));
+        return Ok(ImportFile::Ready(http_file::import(name, contents, dir)));
+    }
+
+    let value = parse(contents)?;
+    if har::is_archive(&value) {
+        har::read_archive(&value).map(ImportFile::Har)
+    } else {
+        import_value(&value).map(ImportFile::Ready)
+    }
+}
+
+/// Import a file exported from another tool, detecting its format
+pub fn import_contents(contents: &str) -> Result<ImportResult, String> {
+    import_value(&parse(contents)?)
+}
+
+/// Files are read as JSON, or as YAML when they are not valid JSON
+fn parse(contents: &str) -> Result<Value, String> {
+    match serde_json::from_str(contents) {
+        Ok(value) => Ok(value),
+        Err(_) => serde_yaml::from_str(contents).map_err(|_| unrecognized()),
+    }
+}
+
+fn unrecognized() -> String {
+    "Unrecognized file format".to_string()
+}
+
+fn import_value(value: &Value) -> Result<ImportResult, String> {
+    let mut result = import_document(value)?;
+    if result.merge {
+        if let Some(collection) = &mut result.collection {
+            record_baseline(&mut collection.items);
+        }
+    }
+    Ok(result)
+}
+
+fn import_document(value: &Value) -> Result<ImportResult, String> {
+    if postman::is_collection(value) {
+        postman::import_collection(value)
+    } else if postman::is_environment(value) {
+        postman::import_environment(value)
+    } else if openapi::is_document(value) {
+        openapi::import_document(value)
+    } else {
+        Err(unrecognized())
+    }
+}
+
+/// Remember what the document said about each request, for merging the
+/// next import of it
+fn record_baseline(items: &mut [CollectionItem]) {
+    for item in items {
+        match item {
+            CollectionItem::Request(request) => {
+                request.imported = Some(ImportBaseline::of(request));
+            }
+            CollectionItem::Folder(folder) => record_baseline(&mut folder.items),
+            CollectionItem::Flow(_) => {}
+        }
+    }
+}
+
+/// Merge a re-imported collection into the one imported before
+///
+/// Requests are matched by method and URL, wherever they were moved to.
+/// Matched requests keep everything the user edited and only gain what the
+/// document changed since the last import; unmatched ones are added to the
+/// folder they were imported into. Nothing is removed.
+pub fn merge_collection(
+    existing: &mut Collection,
+    imported: Collection,
+    report: &mut ImportReport,
+) {
+    let mut added = vec![];
+    report.updated = 0;
+    merge_items(
+        &mut existing.items,
+        imported.items,
+        &mut vec![],
+        &mut added,
+        report,
+    );
+
+    report.requests = added.len();
+    for (folders, request) in added {
+        let mut items = &mut existing.items;
+        for name in folders {
+            let index = items
+                .iter()
+                .position(|item| matches!(item, CollectionItem::Folder(f) if f.name == name));
+            let index = index.unwrap_or_else(|| {
+                items.push(CollectionItem::Folder(Folder {
+                    id: Uuid::new_v4(),
+                    name,
+                    items: vec![],
+                    expanded: false,
+                }));
+                items.len() - 1
+            });
+            let CollectionItem::Folder(folder) = &mut items[index] else {
+                unreachable!()
+            };
+            items = &mut folder.items;
+        }
+        items.push(CollectionItem::Request(request));
+    }
+}
+
+fn merge_items(
+    existing: &mut [CollectionItem],
+    imported: Vec<CollectionItem>,
+    folders: &mut Vec<String>,
+    added: &mut Vec<(Vec<String>, Request)>,
+    report: &mut ImportReport,
+) {
+    for item in imported {
+        match item {
+            CollectionItem::Folder(folder) => {
+                folders.push(folder.name);
+                merge_items(existing, folder.items, folders, added, report);
+                folders.pop();
+            }
+            CollectionItem::Request(request) => match find_request(existing, &request) {
+                Some(current) => {
+                    merge_request(current, request);
+                    report.updated += 1;
+                }
+                None => added.push((folders.clone(), request)),
+            },
+            CollectionItem::Flow(_) => {}
+        }
+    }
+}
+
+fn find_request<'a>(items: &'a mut [CollectionItem], target: &Request) -> Option<&'a mut Request> {
+    let path = |url: &str| url.split('?').next().unwrap_or_default().to_string();
+    for item in items {
+        match item {
+            CollectionItem::Request(request)
+                if request.method == target.method && path(&request.url) == path(&target.url) =>
+            {
+                return Some(request);
+            }
+            CollectionItem::Folder(folder) => {
+                if let Some(request) = find_request(&mut folder.items, target) {
+                    return Some(request);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Update the documentation of `current` from `imported`, keeping user edits
+///
+/// Parameters, headers and cookies are only added when the document did not
+/// have them last time, so ones the user deleted stay deleted. The
+/// description and schema follow the document unless the user changed them.
+/// Requests without a baseline, imported before one was kept, are only
+/// filled in where they are empty.
+fn merge_request(current: &mut Request, imported: Request) {
+    let baseline = ImportBaseline::of(&imported);
+    let previous = current.imported.take();
+    let empty = ImportBaseline::default();
+    let before = previous.as_ref().unwrap_or(&empty);
+
+    let lists = [
+        (
+            &mut current.query_params,
+            imported.query_params,
+            &before.query_params,
+        ),
+        (&mut current.headers, imported.headers, &before.headers),
+        (&mut current.cookies, imported.cookies, &before.cookies),
+    ];
+    for (current, imported, documented) in lists {
+        for key_value in imported {
+            if !has_key(current, &key_value.key)
+                && !documented
+                    .iter()
+                    .any(|key| key.eq_ignore_ascii_case(&key_value.key))
+            {
+                current.push(key_value);
+            }
+        }
+    }
+
+    let unedited = match &previous {
+        Some(previous) => current.description == previous.description,
+        None => current.description.is_empty(),
+    };
+    if unedited {
+        current.description = imported.description;
+    }
+    let unedited = match &previous {
+        Some(previous) => current.schema == previous.schema,
+        None => current.schema.is_none(),
+    };
+    if unedited {
+        current.schema = imported.schema;
+    }
+    if current.example.is_none() {
+        current.example = imported.example;
+    }
+    if matches!(current.body, BodyType::None) {
+        current.body = imported.body;
+    }
+    if current.auth.is_empty() {
+        current.auth = imported.auth;
+    }
+    current.imported = Some(baseline);
+}
+
+fn has_key(items: &[KeyValue], key: &str) -> bool {
+    items.iter().any(|kv| kv.key.eq_ignore_ascii_case(key))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_import_contents_detects_format() {
+        let collection = import_contents(r#"{"info": {"name": "A"}, "item": []}"#).unwrap();
+        assert_eq!(collection.report.source, "Postman collection");
+
+        let environment = import_contents(r#"{"name": "Dev", "values": []}"#).unwrap();
+        assert_eq!(environment.report.source, "Postman environment");
+
+        let yaml = "openapi: 3.0.3\ninfo:\n  title: Pets\npaths: {}\n";
+        assert_eq!(
+            import_contents(yaml).unwrap().report.source,
+            "OpenAPI document"
+        );
+
+        assert!(import_contents("{}").is_err());
+        assert!(import_contents("not json").is_err());
+    }
+
+    #[test]
+    fn test_merge_collection_keeps_edits() {
+        let spec = |description: &str, parameters: &str| {
+            format!(
+                r#"{{"openapi": "3.0.0", "info": {{"title": "Pets"}},
+                "paths": {{
+                    "/pets": {{"get": {{"tags": ["pets"], "summary": "{}",
+                        "parameters": [{}]}}}},
+                    "/pets/{{id}}": {{"delete": {{"tags": ["pets"], "summary": "Remove a pet"}}}}
+                }}}}"#,
+                description, parameters
+            )
+        };
+        let limit = r#"{"name": "limit", "in": "query"}"#;
+        let first = import_contents(&spec("List pets", limit)).unwrap();
+        let mut existing = first.collection.unwrap();
+        let CollectionItem::Folder(folder) = &mut existing.items[0] else {
+            panic!("expected a folder");
+        };
+        // The user renames a request, drops its parameter, describes the
+        // other one and moves it out of its folder
+        let CollectionItem::Request(list) = &mut folder.items[0] else {
+            panic!("expected a request");
+        };
+        list.name = "All my pets".to_string();
+        list.query_params.clear();
+        let mut moved = folder.items.remove(1);
+        let CollectionItem::Request(remove) = &mut moved else {
+            panic!("expected a request");
+        };
+        remove.description = "Careful, this is permanent".to_string();
+        existing.items.push(moved);
+
+        let parameters = format!(r#"{}, {{"name": "sort", "in": "query"}}"#, limit);
+        let second = import_contents(&spec("List every pet", &parameters)).unwrap();
+        let mut report = second.report;
+        merge_collection(&mut existing, second.collection.unwrap(), &mut report);
+        assert_eq!(report.updated, 2);
+        assert_eq!(report.requests, 0);
+        assert_eq!(existing.items.len(), 2);
+
+        let CollectionItem::Folder(folder) = &existing.items[0] else {
+            panic!("expected a folder");
+        };
+        let CollectionItem::Request(list) = &folder.items[0] else {
+            panic!("expected a request");
+        };
+        assert_eq!(list.name, "All my pets");
+        // Unedited documentation follows the document
+        assert_eq!(list.description, "List every pet");
+        // The deleted parameter stays deleted, the new one is added
+        let keys: Vec<&str> = list.query_params.iter().map(|kv| kv.key.as_str()).collect();
+        assert_eq!(keys, ["sort"]);
+
+        let CollectionItem::Request(remove) = &existing.items[1] else {
+            panic!("expected a request");
+        };
+        assert_eq!(remove.description, "Careful, this is permanent");
+    }
+
+    #[test]
+    fn test_merge_request_keeps_edited_schema() {
+        let schema = |inline: &str| {
+            Some(crate::models::ResponseSchema {
+                inline: inline.to_string(),
+                ..Default::default()
+            })
+        };
+        let documented = |inline: &str| Request {
+            schema: schema(inline),
+            ..Default::default()
+        };
+
+        let mut current = documented("{}");
+        current.imported = Some(ImportBaseline::of(&current));
+        merge_request(&mut current, documented(r#"{"type": "object"}"#));
+        assert_eq!(current.schema, schema(r#"{"type": "object"}"#));
+
+        current.schema = schema(r#"{"type": "array"}"#);
+        merge_request(&mut current, documented(r#"{"type": "string"}"#));
+        assert_eq!(current.schema, schema(r#"{"type": "array"}"#));
+
+        // Without a baseline only an empty schema is filled in
+        current.imported = None;
+        merge_request(&mut current, documented("{}"));
+        assert_eq!(current.schema, schema(r#"{"type": "array"}"#));
+    }
+}
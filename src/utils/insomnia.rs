@@ -0,0 +1,262 @@
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::models::{AuthScheme, BodyType, CollectionItem, FormDataParamType, KeyValue, Request};
+
+/// Export a collection or folder in Insomnia's export format 4
+///
+/// Everything is placed in a new workspace named `name`. Flows have no
+/// Insomnia equivalent and are left out.
+pub fn export(name: &str, items: &[CollectionItem]) -> Value {
+    let workspace = resource_id("wrk");
+    let mut resources = vec![json!({
+        "_id": workspace,
+        "_type": "workspace",
+        "parentId": null,
+        "name": name,
+        "description": "",
+        "scope": "collection",
+    })];
+    export_items(items, &workspace, &mut resources);
+
+    json!({
+        "_type": "export",
+        "__export_format": 4,
+        "__export_date": chrono::Utc::now().to_rfc3339(),
+        "__export_source": "requiem",
+        "resources": resources,
+    })
+}
+
+fn export_items(items: &[CollectionItem], parent: &str, resources: &mut Vec<Value>) {
+    for (index, item) in items.iter().enumerate() {
+        match item {
+            CollectionItem::Folder(folder) => {
+                let id = resource_id("fld");
+                resources.push(json!({
+                    "_id": id,
+                    "_type": "request_group",
+                    "parentId": parent,
+                    "name": folder.name,
+                    "description": "",
+                    "metaSortKey": index,
+                }));
+                export_items(&folder.items, &id, resources);
+            }
+            CollectionItem::Request(request) => {
+                resources.push(export_request(request, parent, index));
+            }
+            CollectionItem::Flow(_) => {}
+        }
+    }
+}
+
+fn export_request(request: &Request, parent: &str, index: usize) -> Value {
+    let (body, mime_type) = export_body(&request.body);
+    let mut headers = export_pairs(&request.headers);
+    // Insomnia picks the body editor from the Content-Type header
+    if let Some(mime_type) = mime_type {
+        let has_content_type = request
+            .headers
+            .iter()
+            .any(|h| h.key.eq_ignore_ascii_case("content-type"));
+        if !has_content_type {
+            headers.push(json!({ "name": "Content-Type", "value": mime_type }));
+        }
+    }
+
+    json!({
+        "_id": resource_id("req"),
+        "_type": "request",
+        "parentId": parent,
+        "name": request.name,
+        "description": request.description,
+        "method": request.method.as_str(),
+        "url": template(&request.url),
+        "parameters": export_pairs(&request.query_params),
+        "headers": headers,
+        "body": body,
+        "authentication": export_auth(&request.auth),
+        "settingRejectUnauthorized": !request.insecure,
+        "metaSortKey": index,
+    })
+}
+
+fn export_body(body: &BodyType) -> (Value, Option<&'static str>) {
+    let text = |mime_type: &'static str, text: &str| {
+        (
+            json!({ "mimeType": mime_type, "text": template(text) }),
+            Some(mime_type),
+        )
+    };
+    match body {
+        BodyType::None => (json!({}), None),
+        BodyType::Json(body) => text("application/json", body),
+        BodyType::Xml(body) => text("application/xml", body),
+        BodyType::Text(body) => text("text/plain", body),
+        BodyType::FormUrlEncoded(fields) => {
+            let mime_type = "application/x-www-form-urlencoded";
+            let params = export_pairs(fields);
+            (
+                json!({ "mimeType": mime_type, "params": params }),
+                Some(mime_type),
+            )
+        }
+        BodyType::FormData(fields) => {
+            let mime_type = "multipart/form-data";
+            let params: Vec<Value> = fields
+                .iter()
+                .filter(|field| !field.key.is_empty())
+                .map(|field| match field.param_type {
+                    FormDataParamType::Text => json!({
+                        "name": template(&field.key),
+                        "value": template(&field.value),
+                        "disabled": !field.enabled,
+                    }),
+                    FormDataParamType::File => json!({
+                        "name": template(&field.key),
+                        "type": "file",
+                        "fileName": field.value,
+                        "disabled": !field.enabled,
+                    }),
+                })
+                .collect();
+            (
+                json!({ "mimeType": mime_type, "params": params }),
+                Some(mime_type),
+            )
+        }
+        BodyType::Binary(_) => {
+            let mime_type = "application/octet-stream";
+            (json!({ "mimeType": mime_type }), Some(mime_type))
+        }
+    }
+}
+
+fn export_auth(fields: &[KeyValue]) -> Value {
+    match AuthScheme::from_fields(fields) {
+        Some(AuthScheme::Basic { username, password }) => json!({
+            "type": "basic",
+            "username": template(&username),
+            "password": template(&password),
+        }),
+        Some(AuthScheme::Bearer { token }) => json!({
+            "type": "bearer",
+            "token": template(&token),
+            "prefix": "",
+        }),
+        Some(AuthScheme::ApiKey {
+            key,
+            value,
+            in_query,
+        }) => json!({
+            "type": "apikey",
+            "key": template(&key),
+            "value": template(&value),
+            "addTo": if in_query { "queryParams" } else { "header" },
+        }),
+        Some(AuthScheme::Other { .. }) | None => json!({}),
+    }
+}
+
+fn export_pairs(items: &[KeyValue]) -> Vec<Value> {
+    items
+        .iter()
+        .filter(|item| !item.key.is_empty())
+        .map(|item| {
+            json!({
+                "name": template(&item.key),
+                "value": template(&item.value),
+                "disabled": !item.enabled,
+            })
+        })
+        .collect()
+}
+
+fn resource_id(prefix: &str) -> String {
+    format!("{}_{}", prefix, Uuid::new_v4().simple())
+}
+
+/// Rewrite `{{name}}` placeholders as Insomnia environment references
+fn template(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        let Some(end) = rest[start..].find("}}") else {
+            break;
+        };
+        result.push_str(&rest[..start]);
+        let name = rest[start + 2..start + end].trim();
+        let is_identifier = name
+            .chars()
+            .enumerate()
+            .all(|(i, c)| c == '_' || c.is_ascii_alphabetic() || (i > 0 && c.is_ascii_digit()));
+        if name.is_empty() || name.starts_with('$') || name.starts_with("_.") {
+            result.push_str(&rest[start..start + end + 2]);
+        } else if is_identifier {
+            result.push_str(&format!("{{{{ _.{} }}}}", name));
+        } else {
+            result.push_str(&format!("{{{{ _['{}'] }}}}", name.replace('\'', "\\'")));
+        }
+        rest = &rest[start + end + 2..];
+    }
+    result.push_str(rest);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Folder, HttpMethod};
+
+    #[test]
+    fn test_template() {
+        assert_eq!(template("{{baseUrl}}/users"), "{{ _.baseUrl }}/users");
+        assert_eq!(template("{{api-key}}"), "{{ _['api-key'] }}");
+        assert_eq!(template("{{$guid}} {{ _.x }}"), "{{$guid}} {{ _.x }}");
+        assert_eq!(template("{{open"), "{{open");
+    }
+
+    #[test]
+    fn test_export() {
+        let request = Request {
+            name: "Login".to_string(),
+            method: HttpMethod::POST,
+            url: "{{baseUrl}}/login".to_string(),
+            headers: vec![],
+            auth: vec![KeyValue::new("token", "{{token}}")],
+            body: BodyType::FormUrlEncoded(vec![KeyValue::new("user", "me")]),
+            ..Default::default()
+        };
+        let items = vec![
+            CollectionItem::Request(request.clone()),
+            CollectionItem::Folder(Folder {
+                id: Uuid::new_v4(),
+                name: "Auth".to_string(),
+                items: vec![CollectionItem::Request(request)],
+                expanded: false,
+            }),
+        ];
+
+        let exported = export("Shop", &items);
+        assert_eq!(exported["__export_format"], 4);
+        let resources = exported["resources"].as_array().unwrap();
+        let types: Vec<&str> = resources
+            .iter()
+            .map(|r| r["_type"].as_str().unwrap())
+            .collect();
+        assert_eq!(types, ["workspace", "request", "request_group", "request"]);
+        // The nested request belongs to the folder
+        assert_eq!(resources[3]["parentId"], resources[2]["_id"]);
+
+        let login = &resources[1];
+        assert_eq!(login["url"], "{{ _.baseUrl }}/login");
+        assert_eq!(login["authentication"]["type"], "bearer");
+        assert_eq!(login["authentication"]["token"], "{{ _.token }}");
+        assert_eq!(
+            login["body"]["mimeType"],
+            "application/x-www-form-urlencoded"
+        );
+        assert_eq!(login["headers"][0]["name"], "Content-Type");
+    }
+}
@@ -0,0 +1,124 @@
+use serde_json::Value;
+
+/// A single step in a JSONPath expression
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    Key(String),
+    Index(usize),
+}
+
+/// Parse a JSONPath expression like `$.data.items[0]['user-id']`
+///
+/// Only the child and index operators are supported, which covers the
+/// common case of pointing at a single value in a response body.
+fn parse(path: &str) -> Result<Vec<Segment>, String> {
+    let path = path.trim();
+    let rest = path.strip_prefix('$').unwrap_or(path);
+    let chars: Vec<char> = rest.chars().collect();
+    let mut segments = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        match chars[i] {
+            '.' => {
+                i += 1;
+                let start = i;
+                while i < chars.len() && chars[i] != '.' && chars[i] != '[' {
+                    i += 1;
+                }
+                let key: String = chars[start..i].iter().collect();
+                if key.is_empty() {
+                    return Err(format!("Empty key in JSONPath: {}", path));
+                }
+                segments.push(Segment::Key(key));
+            }
+            '[' => {
+                let end = chars[i..]
+                    .iter()
+                    .position(|c| *c == ']')
+                    .map(|pos| i + pos)
+                    .ok_or_else(|| format!("Unclosed bracket in JSONPath: {}", path))?;
+                let inner: String = chars[i + 1..end].iter().collect();
+                let inner = inner.trim();
+                let quoted = (inner.starts_with('\'') && inner.ends_with('\''))
+                    || (inner.starts_with('"') && inner.ends_with('"'));
+                if quoted && inner.len() >= 2 {
+                    segments.push(Segment::Key(inner[1..inner.len() - 1].to_string()));
+                } else {
+                    let index = inner
+                        .parse::<usize>()
+                        .map_err(|_| format!("Invalid index '{}' in JSONPath: {}", inner, path))?;
+                    segments.push(Segment::Index(index));
+                }
+                i = end + 1;
+            }
+            _ if i == 0 => {
+                // Allow paths without the leading `$.`, e.g. `data.token`
+                let start = i;
+                while i < chars.len() && chars[i] != '.' && chars[i] != '[' {
+                    i += 1;
+                }
+                segments.push(Segment::Key(chars[start..i].iter().collect()));
+            }
+            c => return Err(format!("Unexpected '{}' in JSONPath: {}", c, path)),
+        }
+    }
+
+    Ok(segments)
+}
+
+/// Look up the value a JSONPath expression points at
+pub fn query<'a>(value: &'a Value, path: &str) -> Result<Option<&'a Value>, String> {
+    let segments = parse(path)?;
+    let mut current = value;
+
+    for segment in &segments {
+        let next = match segment {
+            Segment::Key(key) => current.get(key.as_str()),
+            Segment::Index(index) => current.get(*index),
+        };
+        match next {
+            Some(v) => current = v,
+            None => return Ok(None),
+        }
+    }
+
+    Ok(Some(current))
+}
+
+/// Render a JSON value as a plain string (strings without quotes)
+pub fn value_to_string(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_query_nested_path() {
+        let value = json!({"data": {"items": [{"id": 7}, {"id": 8}], "user-id": "u1"}});
+
+        let id = query(&value, "$.data.items[1].id").unwrap();
+        assert_eq!(id, Some(&json!(8)));
+
+        let user = query(&value, "$.data['user-id']").unwrap();
+        assert_eq!(user.map(value_to_string), Some("u1".to_string()));
+
+        let short = query(&value, "data.items[0].id").unwrap();
+        assert_eq!(short, Some(&json!(7)));
+    }
+
+    #[test]
+    fn test_query_missing_and_invalid() {
+        let value = json!({"token": "abc"});
+        assert_eq!(query(&value, "$.missing").unwrap(), None);
+        assert!(query(&value, "$.token[").is_err());
+        assert!(query(&value, "$.token[x]").is_err());
+    }
+}
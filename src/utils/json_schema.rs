@@ -0,0 +1,179 @@
+use serde_json::Value;
+use std::path::Path;
+
+use crate::models::{ResponseSchema, SchemaSource, SchemaViolation};
+
+/// Read the schema document of a request
+///
+/// A relative file path is resolved against `base_dir`, the directory the
+/// collection is saved in, so it does not depend on where the app was started.
+pub fn load(schema: &ResponseSchema, base_dir: &Path) -> Result<Value, String> {
+    let text = match schema.source {
+        SchemaSource::Inline => schema.inline.clone(),
+        SchemaSource::File => {
+            let path = schema.file.trim();
+            if path.is_empty() {
+                return Err("No schema file selected".to_string());
+            }
+            let full = base_dir.join(path);
+            if !full.is_file() {
+                return Err(format!("Schema file {} not found", full.display()));
+            }
+            std::fs::read_to_string(&full)
+                .map_err(|e| format!("Failed to read schema file {}: {}", full.display(), e))?
+        }
+    };
+
+    serde_json::from_str(&text).map_err(|e| format!("Invalid schema JSON: {}", e))
+}
+
+/// Validate a response body, returning every violation found
+///
+/// A body that is not JSON at all is reported as a single violation at the root.
+pub fn validate(schema: &Value, body: &str) -> Result<Vec<SchemaViolation>, String> {
+    let validator =
+        jsonschema::validator_for(schema).map_err(|e| format!("Invalid schema: {}", e))?;
+
+    let instance: Value = match serde_json::from_str(body) {
+        Ok(instance) => instance,
+        Err(e) => {
+            return Ok(vec![SchemaViolation {
+                pointer: String::new(),
+                message: format!("Response body is not valid JSON: {}", e),
+            }])
+        }
+    };
+
+    Ok(validator
+        .iter_errors(&instance)
+        .map(|error| SchemaViolation {
+            pointer: error.instance_path.to_string(),
+            message: error.to_string(),
+        })
+        .collect())
+}
+
+/// Load the schema of a request and validate `body` against it
+pub fn check(
+    schema: &ResponseSchema,
+    body: &str,
+    base_dir: &Path,
+) -> Result<Vec<SchemaViolation>, String> {
+    validate(&load(schema, base_dir)?, body)
+}
+
+/// Path of a picked schema file as stored in the request: relative to
+/// `base_dir` when the file is inside it, absolute otherwise
+pub fn relative_path(path: &str, base_dir: &Path) -> String {
+    let path = Path::new(path);
+    let canonical = base_dir.canonicalize().ok();
+    let relative = [Some(base_dir), canonical.as_deref()]
+        .into_iter()
+        .flatten()
+        .find_map(|base| path.strip_prefix(base).ok())
+        .filter(|relative| !relative.as_os_str().is_empty())
+        .unwrap_or(path);
+    relative.to_string_lossy().to_string()
+}
+
+/// One-line summary such as `/id: "x" is not of type "integer"; ...`
+pub fn summarize(violations: &[SchemaViolation]) -> String {
+    violations
+        .iter()
+        .map(|v| {
+            let pointer = if v.pointer.is_empty() {
+                "(root)"
+            } else {
+                &v.pointer
+            };
+            format!("{}: {}", pointer, v.message)
+        })
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_validate() {
+        let schema = json!({
+            "type": "object",
+            "required": ["id", "tags"],
+            "properties": {
+                "id": { "type": "integer" },
+                "tags": { "type": "array", "items": { "type": "string" } }
+            }
+        });
+
+        assert!(validate(&schema, r#"{"id":1,"tags":["a"]}"#)
+            .unwrap()
+            .is_empty());
+
+        let violations = validate(&schema, r#"{"id":"x","tags":["a",2]}"#).unwrap();
+        let pointers: Vec<&str> = violations.iter().map(|v| v.pointer.as_str()).collect();
+        assert_eq!(violations.len(), 2);
+        assert!(pointers.contains(&"/id"));
+        assert!(pointers.contains(&"/tags/1"));
+
+        let missing = validate(&schema, r#"{"id":1}"#).unwrap();
+        assert_eq!(missing.len(), 1);
+        assert_eq!(missing[0].pointer, "");
+
+        assert_eq!(validate(&schema, "not json").unwrap().len(), 1);
+        assert!(validate(&json!({"type": 12}), "{}").is_err());
+    }
+
+    #[test]
+    fn test_load_inline() {
+        let schema = ResponseSchema {
+            source: SchemaSource::Inline,
+            inline: r#"{"type":"string"}"#.to_string(),
+            file: String::new(),
+        };
+        let base_dir = Path::new("");
+        assert_eq!(check(&schema, r#""ok""#, base_dir).unwrap().len(), 0);
+        assert_eq!(check(&schema, "1", base_dir).unwrap().len(), 1);
+
+        let file = ResponseSchema {
+            source: SchemaSource::File,
+            ..schema
+        };
+        assert!(load(&file, base_dir).is_err());
+    }
+
+    #[test]
+    fn test_load_file_from_save_directory() {
+        let dir = std::env::temp_dir().join("requiem_test_schema_file");
+        std::fs::create_dir_all(dir.join("schemas")).unwrap();
+        let picked = dir.join("schemas").join("user.json");
+        std::fs::write(&picked, r#"{"type":"object"}"#).unwrap();
+
+        let file = relative_path(&picked.to_string_lossy(), &dir);
+        assert_eq!(Path::new(&file), Path::new("schemas").join("user.json"));
+        let schema = ResponseSchema {
+            source: SchemaSource::File,
+            inline: String::new(),
+            file,
+        };
+        assert_eq!(check(&schema, "{}", &dir).unwrap().len(), 0);
+
+        // Outside the save directory the path stays absolute
+        let elsewhere = std::env::temp_dir().join("elsewhere.json");
+        assert_eq!(
+            relative_path(&elsewhere.to_string_lossy(), &dir),
+            elsewhere.to_string_lossy()
+        );
+
+        let missing = ResponseSchema {
+            file: "schemas/missing.json".to_string(),
+            ..schema
+        };
+        let error = load(&missing, &dir).unwrap_err();
+        assert!(error.starts_with("Schema file ") && error.ends_with(" not found"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
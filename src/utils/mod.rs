@@ -1,2 +1,21 @@
+pub mod codegen;
+pub mod curl;
+pub mod data_file;
+pub mod docs;
+pub mod extractor;
 pub mod formatter;
+pub mod har;
+pub mod history;
+pub mod http_file;
+pub mod import;
+pub mod insomnia;
+pub mod json_path;
+pub mod json_schema;
 pub mod navigation;
+pub mod openapi;
+pub mod postman;
+pub mod reload;
+pub mod report;
+pub mod snapshot;
+pub mod undo;
+pub mod variables;
@@ -1,4 +1,4 @@
-use crate::models::{Collection, CollectionItem};
+use crate::models::{Collection, CollectionItem, Request};
 
 /// Get an item by its path in the collection hierarchy
 pub fn get_item_by_path<'a>(
@@ -72,5 +72,34 @@ pub fn get_item_name(collections: &[Collection], path: &[usize]) -> Option<Strin
     match item {
         CollectionItem::Request(req) => Some(req.name.clone()),
         CollectionItem::Folder(folder) => Some(folder.name.clone()),
+        CollectionItem::Flow(flow) => Some(flow.name.clone()),
     }
 }
+
+/// Get the child items of a collection (path of length 1) or folder
+pub fn get_items_by_path<'a>(
+    collections: &'a [Collection],
+    path: &[usize],
+) -> Option<&'a [CollectionItem]> {
+    if path.len() == 1 {
+        return collections.get(path[0]).map(|c| c.items.as_slice());
+    }
+
+    match get_item_by_path(collections, path)? {
+        CollectionItem::Folder(folder) => Some(folder.items.as_slice()),
+        CollectionItem::Request(_) | CollectionItem::Flow(_) => None,
+    }
+}
+
+/// Collect all requests under the given items, depth first in display order
+pub fn collect_requests(items: &[CollectionItem]) -> Vec<Request> {
+    let mut requests = Vec::new();
+    for item in items {
+        match item {
+            CollectionItem::Request(req) => requests.push(req.clone()),
+            CollectionItem::Folder(folder) => requests.extend(collect_requests(&folder.items)),
+            CollectionItem::Flow(_) => {}
+        }
+    }
+    requests
+}
@@ -0,0 +1,1512 @@
+use std::collections::{HashMap, HashSet};
+
+use serde_json::{json, Map, Value};
+use uuid::Uuid;
+
+use crate::models::{
+    AuthScheme, BodyType, Collection, CollectionItem, Folder, FormDataParamType, HttpMethod,
+    ImportReport, ImportResult, KeyValue, Request, ResponseExample, ResponseSchema, SchemaSource,
+};
+
+const METHODS: [&str; 8] = [
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
+/// Limit for `$ref` chains and nested schemas, which may be recursive
+const MAX_DEPTH: usize = 12;
+
+/// Whether `value` looks like an OpenAPI 3.x or Swagger 2.0 document
+pub fn is_document(value: &Value) -> bool {
+    version(value, "openapi").is_some_and(|v| v.starts_with("3."))
+        || version(value, "swagger").as_deref() == Some("2.0")
+}
+
+/// Import an OpenAPI 3.x or Swagger 2.0 document
+///
+/// Each operation becomes a request, grouped into a folder named after its
+/// first tag. The server URL is returned as the `baseUrl` variable, along
+/// with an empty variable for each security scheme's credentials.
+pub fn import_document(value: &Value) -> Result<ImportResult, String> {
+    if !is_document(value) {
+        return Err("Not an OpenAPI or Swagger document".to_string());
+    }
+    let swagger = value.get("openapi").is_none();
+    let name = value
+        .pointer("/info/title")
+        .and_then(Value::as_str)
+        .filter(|title| !title.trim().is_empty())
+        .unwrap_or("Imported API");
+
+    let mut importer = Importer {
+        root: value,
+        swagger,
+        location: name.to_string(),
+        report: ImportReport::new(if swagger {
+            "Swagger 2.0 document"
+        } else {
+            "OpenAPI document"
+        }),
+        variables: HashMap::new(),
+        reported: HashSet::new(),
+    };
+    importer.report.name = name.to_string();
+
+    let base_url = importer.base_url();
+    importer.variables.insert("baseUrl".to_string(), base_url);
+    let items = importer.operations();
+    importer.report.variables = importer.variables.len();
+
+    Ok(ImportResult {
+        collection: Some(Collection {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            items,
+            expanded: true,
+            http_file: None,
+        }),
+        variables: importer.variables,
+        environment: None,
+        overwrite: false,
+        merge: true,
+        report: importer.report,
+    })
+}
+
+/// Build an OpenAPI 3 document describing a collection or folder
+///
+/// Folder names become tags and `{{name}}` path segments become path
+/// parameters. A leading variable such as `{{baseUrl}}` becomes a server
+/// variable. Requests sharing a method and path are documented once.
+pub fn export_document(name: &str, items: &[CollectionItem]) -> Value {
+    let mut exporter = Exporter::default();
+    exporter.items(items, &[]);
+
+    let mut document = json!({
+        "openapi": "3.0.3",
+        "info": { "title": name, "version": "1.0.0" },
+        "paths": exporter.paths,
+    });
+    if !exporter.servers.is_empty() {
+        let servers: Vec<Value> = exporter.servers.iter().map(|s| server(s)).collect();
+        document["servers"] = json!(servers);
+    }
+    if !exporter.tags.is_empty() {
+        let tags: Vec<Value> = exporter.tags.iter().map(|t| json!({ "name": t })).collect();
+        document["tags"] = json!(tags);
+    }
+    if !exporter.security_schemes.is_empty() {
+        document["components"] = json!({ "securitySchemes": exporter.security_schemes });
+    }
+    document
+}
+
+#[derive(Default)]
+struct Exporter {
+    paths: Map<String, Value>,
+    servers: Vec<String>,
+    tags: Vec<String>,
+    security_schemes: Map<String, Value>,
+}
+
+impl Exporter {
+    fn items(&mut self, items: &[CollectionItem], folders: &[&str]) {
+        for item in items {
+            match item {
+                CollectionItem::Folder(folder) => {
+                    let mut path = folders.to_vec();
+                    path.push(&folder.name);
+                    self.items(&folder.items, &path);
+                }
+                CollectionItem::Request(request) => {
+                    let tag = (!folders.is_empty()).then(|| folders.join(" / "));
+                    self.operation(request, tag);
+                }
+                CollectionItem::Flow(_) => {}
+            }
+        }
+    }
+
+    fn operation(&mut self, request: &Request, tag: Option<String>) {
+        let (server_url, path, path_parameters) = split_url(&request.url);
+        let method = request.method.as_str().to_lowercase();
+        let exists = self
+            .paths
+            .get(&path)
+            .is_some_and(|item| item.get(&method).is_some());
+        if exists {
+            return;
+        }
+
+        let mut operation = json!({
+            "summary": request.name,
+            "responses": export_responses(request),
+        });
+        if !request.description.trim().is_empty() {
+            operation["description"] = json!(request.description);
+        }
+        if let Some(tag) = tag {
+            if !self.tags.contains(&tag) {
+                self.tags.push(tag.clone());
+            }
+            operation["tags"] = json!([tag]);
+        }
+        // The first server is the document's; requests elsewhere name their own
+        match self.servers.iter().position(|s| *s == server_url) {
+            Some(0) => {}
+            Some(_) => operation["servers"] = json!([server(&server_url)]),
+            None => {
+                if !self.servers.is_empty() {
+                    operation["servers"] = json!([server(&server_url)]);
+                }
+                self.servers.push(server_url);
+            }
+        }
+
+        let mut parameters: Vec<Value> = path_parameters
+            .iter()
+            .map(|name| {
+                json!({
+                    "name": name,
+                    "in": "path",
+                    "required": true,
+                    "schema": { "type": "string" },
+                })
+            })
+            .collect();
+        let ignored = ["accept", "content-type", "authorization"];
+        let lists = [
+            ("query", &request.query_params),
+            ("header", &request.headers),
+            ("cookie", &request.cookies),
+        ];
+        for (location, items) in lists {
+            for item in items {
+                if item.key.is_empty()
+                    || (location == "header" && ignored.contains(&item.key.to_lowercase().as_str()))
+                {
+                    continue;
+                }
+                let mut parameter = json!({
+                    "name": item.key,
+                    "in": location,
+                    "required": item.enabled,
+                    "schema": { "type": "string" },
+                });
+                if !item.value.is_empty() {
+                    parameter["example"] = json!(item.value);
+                }
+                parameters.push(parameter);
+            }
+        }
+        if !parameters.is_empty() {
+            operation["parameters"] = json!(parameters);
+        }
+
+        if let Some(body) = export_request_body(request) {
+            operation["requestBody"] = body;
+        }
+        let security = self.security(request);
+        if !security.is_empty() {
+            let requirement: Map<String, Value> =
+                security.into_iter().map(|name| (name, json!([]))).collect();
+            operation["security"] = json!([requirement]);
+        }
+
+        let item = self.paths.entry(path).or_insert_with(|| json!({}));
+        item[method.as_str()] = operation;
+    }
+
+    /// Security schemes used by a request, from its auth fields or headers
+    fn security(&mut self, request: &Request) -> Vec<String> {
+        let mut schemes = vec![];
+        let mut add = |name: &str, scheme: Value| {
+            self.security_schemes
+                .entry(name.to_string())
+                .or_insert(scheme);
+            schemes.push(name.to_string());
+        };
+        let authorization = request
+            .headers
+            .iter()
+            .find(|h| h.enabled && h.key.eq_ignore_ascii_case("authorization"))
+            .map(|h| h.value.to_lowercase());
+        match authorization.as_deref() {
+            Some(value) if value.starts_with("bearer ") => {
+                add("bearerAuth", json!({ "type": "http", "scheme": "bearer" }))
+            }
+            Some(value) if value.starts_with("basic ") => {
+                add("basicAuth", json!({ "type": "http", "scheme": "basic" }))
+            }
+            _ => {}
+        }
+
+        match AuthScheme::from_fields(&request.auth) {
+            Some(AuthScheme::Basic { .. }) => {
+                add("basicAuth", json!({ "type": "http", "scheme": "basic" }))
+            }
+            Some(AuthScheme::Bearer { .. }) => {
+                add("bearerAuth", json!({ "type": "http", "scheme": "bearer" }))
+            }
+            Some(AuthScheme::ApiKey { key, in_query, .. }) if !key.is_empty() => add(
+                &key,
+                json!({
+                    "type": "apiKey",
+                    "in": if in_query { "query" } else { "header" },
+                    "name": key,
+                }),
+            ),
+            _ => {}
+        }
+        schemes.dedup();
+        schemes
+    }
+}
+
+/// Split a request URL into its server, OpenAPI path and path parameter names
+fn split_url(url: &str) -> (String, String, Vec<String>) {
+    let url = url.split(['?', '#']).next().unwrap_or_default();
+    let split_at = if url.starts_with("{{") {
+        url.find("}}").map(|end| end + 2)
+    } else if let Some(scheme_end) = url.find("://") {
+        let authority = scheme_end + 3;
+        Some(
+            url[authority..]
+                .find('/')
+                .map_or(url.len(), |i| authority + i),
+        )
+    } else {
+        None
+    };
+    let (server, path) = url.split_at(split_at.unwrap_or(0));
+
+    let mut names = vec![];
+    let mut converted = String::new();
+    let mut rest = path;
+    while let Some(start) = rest.find("{{") {
+        let Some(end) = rest[start..].find("}}") else {
+            break;
+        };
+        let name = rest[start + 2..start + end].trim();
+        converted.push_str(&rest[..start]);
+        converted.push_str(&format!("{{{}}}", name));
+        if !names.iter().any(|n| n == name) {
+            names.push(name.to_string());
+        }
+        rest = &rest[start + end + 2..];
+    }
+    converted.push_str(rest);
+    if !converted.starts_with('/') {
+        converted.insert(0, '/');
+    }
+    (server.to_string(), converted, names)
+}
+
+/// Server object for a URL, turning `{{name}}` variables into server variables
+fn server(url: &str) -> Value {
+    let mut variables = Map::new();
+    let mut converted = url.to_string();
+    for name in crate::utils::variables::referenced_variables(url) {
+        converted = converted.replace(&format!("{{{{{}}}}}", name), &format!("{{{}}}", name));
+        variables.insert(
+            name.clone(),
+            json!({
+                "default": "",
+                "description": format!("Value of the {{{{{}}}}} variable", name),
+            }),
+        );
+    }
+    let mut server = json!({ "url": converted });
+    if !variables.is_empty() {
+        server["variables"] = Value::Object(variables);
+    }
+    server
+}
+
+fn export_request_body(request: &Request) -> Option<Value> {
+    let content_type = request
+        .headers
+        .iter()
+        .find(|h| h.enabled && h.key.eq_ignore_ascii_case("content-type"))
+        .map(|h| {
+            h.value
+                .split(';')
+                .next()
+                .unwrap_or_default()
+                .trim()
+                .to_string()
+        });
+    let media_type = |default: &str| content_type.clone().unwrap_or_else(|| default.to_string());
+
+    let (media_type, media) = match &request.body {
+        BodyType::None => return None,
+        BodyType::Json(text) => {
+            let example = serde_json::from_str(text).unwrap_or_else(|_| json!(text));
+            (
+                media_type("application/json"),
+                json!({ "schema": infer_schema(&example), "example": example }),
+            )
+        }
+        BodyType::Xml(text) => (
+            media_type("application/xml"),
+            json!({ "schema": { "type": "string" }, "example": text }),
+        ),
+        BodyType::Text(text) => (
+            media_type("text/plain"),
+            json!({ "schema": { "type": "string" }, "example": text }),
+        ),
+        BodyType::FormUrlEncoded(fields) | BodyType::FormData(fields) => {
+            let multipart = matches!(request.body, BodyType::FormData(_));
+            let mut properties = Map::new();
+            for field in fields.iter().filter(|f| !f.key.is_empty()) {
+                let property = match field.param_type {
+                    FormDataParamType::File => json!({ "type": "string", "format": "binary" }),
+                    FormDataParamType::Text => json!({ "type": "string", "example": field.value }),
+                };
+                properties.insert(field.key.clone(), property);
+            }
+            let media_type = if multipart {
+                "multipart/form-data"
+            } else {
+                "application/x-www-form-urlencoded"
+            };
+            (
+                media_type.to_string(),
+                json!({ "schema": { "type": "object", "properties": properties } }),
+            )
+        }
+        BodyType::Binary(_) => (
+            media_type("application/octet-stream"),
+            json!({ "schema": { "type": "string", "format": "binary" } }),
+        ),
+    };
+    Some(json!({ "content": { media_type: media } }))
+}
+
+/// Responses from the saved example and response schema of a request
+fn export_responses(request: &Request) -> Value {
+    let schema = request
+        .schema
+        .as_ref()
+        .filter(|schema| schema.source == SchemaSource::Inline)
+        .and_then(|schema| serde_json::from_str::<Value>(&schema.inline).ok());
+
+    let Some(example) = &request.example else {
+        return match schema {
+            Some(schema) => json!({ "200": {
+                "description": "Successful response",
+                "content": { "application/json": { "schema": schema } },
+            }}),
+            None => json!({ "default": { "description": "Response" } }),
+        };
+    };
+
+    let content_type = example
+        .headers
+        .iter()
+        .find(|h| h.key.eq_ignore_ascii_case("content-type"))
+        .map(|h| {
+            h.value
+                .split(';')
+                .next()
+                .unwrap_or_default()
+                .trim()
+                .to_string()
+        });
+    let parsed = serde_json::from_str::<Value>(&example.body).ok();
+    let (media_type, example_value, schema) = match parsed {
+        Some(value) if content_type.as_deref().is_none_or(|t| t.contains("json")) => {
+            let schema = schema.unwrap_or_else(|| infer_schema(&value));
+            (
+                content_type.unwrap_or_else(|| "application/json".to_string()),
+                value,
+                schema,
+            )
+        }
+        _ => (
+            content_type.unwrap_or_else(|| "text/plain".to_string()),
+            json!(example.body),
+            json!({ "type": "string" }),
+        ),
+    };
+    json!({ example.status.to_string(): {
+        "description": "Example response",
+        "content": { media_type: { "schema": schema, "example": example_value } },
+    }})
+}
+
+/// A schema describing the shape of an example value
+fn infer_schema(value: &Value) -> Value {
+    match value {
+        Value::Null => json!({}),
+        Value::Bool(_) => json!({ "type": "boolean" }),
+        Value::Number(number) if number.is_f64() => json!({ "type": "number" }),
+        Value::Number(_) => json!({ "type": "integer" }),
+        Value::String(_) => json!({ "type": "string" }),
+        Value::Array(items) => json!({
+            "type": "array",
+            "items": items.first().map(infer_schema).unwrap_or_else(|| json!({})),
+        }),
+        Value::Object(fields) => {
+            let properties: Map<String, Value> = fields
+                .iter()
+                .map(|(key, value)| (key.clone(), infer_schema(value)))
+                .collect();
+            json!({ "type": "object", "properties": properties })
+        }
+    }
+}
+
+struct Importer<'a> {
+    root: &'a Value,
+    swagger: bool,
+    /// Operation being imported, used as the location of reported issues
+    location: String,
+    report: ImportReport,
+    variables: HashMap<String, String>,
+    /// Issues about shared definitions are reported once, not per operation
+    reported: HashSet<String>,
+}
+
+impl<'a> Importer<'a> {
+    fn base_url(&mut self) -> String {
+        let root = self.root;
+        let url = if self.swagger {
+            let base_path = str_field(root, "basePath").unwrap_or_default();
+            match str_field(root, "host") {
+                Some(host) => {
+                    let schemes: Vec<&str> = array(root, "schemes")
+                        .iter()
+                        .filter_map(Value::as_str)
+                        .collect();
+                    let scheme = if schemes.is_empty() || schemes.contains(&"https") {
+                        "https"
+                    } else {
+                        schemes[0]
+                    };
+                    format!("{}://{}{}", scheme, host, base_path)
+                }
+                None => base_path.to_string(),
+            }
+        } else {
+            match array(root, "servers").first() {
+                Some(server) => server_url(server),
+                None => String::new(),
+            }
+        };
+
+        let url = url.trim_end_matches('/').to_string();
+        if !url.contains("://") {
+            self.issue(
+                "The document has no absolute server URL, set the baseUrl variable before sending",
+            );
+        }
+        url
+    }
+
+    fn operations(&mut self) -> Vec<CollectionItem> {
+        let root = self.root;
+        // Declared tags keep their order, others follow in order of use
+        let mut folders: Vec<(String, Vec<CollectionItem>)> = array(root, "tags")
+            .iter()
+            .filter_map(|tag| str_field(tag, "name"))
+            .map(|name| (name.to_string(), vec![]))
+            .collect();
+        let mut untagged = vec![];
+
+        let Some(paths) = root.get("paths").and_then(Value::as_object) else {
+            self.issue("The document has no paths");
+            return vec![];
+        };
+        for (path, path_item) in paths {
+            self.location = path.clone();
+            let Some(path_item) = self.resolve(path_item) else {
+                continue;
+            };
+            for method in METHODS {
+                let Some(operation) = path_item.get(method) else {
+                    continue;
+                };
+                self.location = format!("{} {}", method.to_uppercase(), path);
+                let Ok(http_method) = method.to_uppercase().parse::<HttpMethod>() else {
+                    self.issue(format!(
+                        "{} requests are not supported and were skipped",
+                        method.to_uppercase()
+                    ));
+                    continue;
+                };
+
+                let request = self.request(path, path_item, operation, http_method);
+                self.report.requests += 1;
+                let tag = array(operation, "tags").first().and_then(Value::as_str);
+                match tag {
+                    Some(tag) => {
+                        let index = match folders.iter().position(|(name, _)| name == tag) {
+                            Some(index) => index,
+                            None => {
+                                folders.push((tag.to_string(), vec![]));
+                                folders.len() - 1
+                            }
+                        };
+                        folders[index].1.push(CollectionItem::Request(request));
+                    }
+                    None => untagged.push(CollectionItem::Request(request)),
+                }
+            }
+        }
+
+        let mut items: Vec<CollectionItem> = folders
+            .into_iter()
+            .filter(|(_, items)| !items.is_empty())
+            .map(|(name, items)| {
+                self.report.folders += 1;
+                CollectionItem::Folder(Folder {
+                    id: Uuid::new_v4(),
+                    name,
+                    items,
+                    expanded: false,
+                })
+            })
+            .collect();
+        items.extend(untagged);
+        items
+    }
+
+    fn request(
+        &mut self,
+        path: &str,
+        path_item: &'a Value,
+        operation: &'a Value,
+        method: HttpMethod,
+    ) -> Request {
+        let summary = non_empty(operation, "summary");
+        let name = summary
+            .or_else(|| non_empty(operation, "operationId"))
+            .map(str::to_string)
+            .unwrap_or_else(|| format!("{} {}", method, path));
+        let mut request = Request {
+            name,
+            method,
+            headers: vec![],
+            description: non_empty(operation, "description")
+                .or(summary)
+                .unwrap_or_default()
+                .to_string(),
+            ..Default::default()
+        };
+
+        // `{id}` path templates become `{{id}}` variables unless an example fills them
+        let mut url_path = path.replace('{', "{{").replace('}', "}}");
+        let mut form_fields = vec![];
+        for parameter in self.parameters(path_item, operation) {
+            let name = str_field(parameter, "name").unwrap_or_default();
+            let example = self.parameter_example(parameter);
+            let required = parameter.get("required") == Some(&Value::Bool(true));
+            match str_field(parameter, "in") {
+                Some("path") => {
+                    if let Some(example) = example {
+                        url_path = url_path.replace(&format!("{{{{{}}}}}", name), &example);
+                    }
+                }
+                Some("query") => request
+                    .query_params
+                    .push(key_value(name, example, required)),
+                Some("header") => {
+                    // Set by the body and auth instead, as OpenAPI specifies
+                    let ignored = ["accept", "content-type", "authorization"];
+                    if !ignored.contains(&name.to_lowercase().as_str()) {
+                        request.headers.push(key_value(name, example, required));
+                    }
+                }
+                Some("cookie") => request.cookies.push(key_value(name, example, required)),
+                Some("body") => {
+                    let schema = parameter.get("schema");
+                    request.body = self.body("application/json", None, schema);
+                }
+                Some("formData") => form_fields.push(parameter),
+                _ => {}
+            }
+        }
+        // Operations served elsewhere than the document's server keep their own URL
+        let own_server = array(operation, "servers")
+            .first()
+            .or_else(|| array(path_item, "servers").first())
+            .map(server_url)
+            .filter(|url| url.contains("://"));
+        let base = own_server.unwrap_or_else(|| "{{baseUrl}}".to_string());
+        request.url = format!("{}{}", base.trim_end_matches('/'), url_path);
+
+        if self.swagger {
+            if !form_fields.is_empty() {
+                request.body = self.swagger_form(operation, &form_fields);
+            }
+        } else {
+            self.request_body(operation, &mut request);
+        }
+        let content_type = match &request.body {
+            BodyType::Json(_) => Some("application/json"),
+            BodyType::Xml(_) => Some("application/xml"),
+            BodyType::Text(_) => Some("text/plain"),
+            _ => None,
+        };
+        if let Some(content_type) = content_type {
+            add_header(&mut request, "Content-Type", content_type.to_string());
+        }
+
+        self.security(operation, &mut request);
+        self.response(operation, &mut request);
+        request
+    }
+
+    /// Path-level parameters, overridden by the operation's own
+    fn parameters(&mut self, path_item: &'a Value, operation: &'a Value) -> Vec<&'a Value> {
+        let mut parameters: Vec<&'a Value> = vec![];
+        for parameter in array(path_item, "parameters")
+            .iter()
+            .chain(array(operation, "parameters"))
+        {
+            let Some(parameter) = self.resolve(parameter) else {
+                continue;
+            };
+            if non_empty(parameter, "name").is_none() {
+                continue;
+            }
+            let same = |p: &Value| {
+                str_field(p, "name") == str_field(parameter, "name")
+                    && str_field(p, "in") == str_field(parameter, "in")
+            };
+            parameters.retain(|p| !same(p));
+            parameters.push(parameter);
+        }
+        parameters
+    }
+
+    fn parameter_example(&mut self, parameter: &'a Value) -> Option<String> {
+        if let Some(example) = parameter.get("example") {
+            return Some(scalar_string(Some(example)));
+        }
+        if let Some(example) = self.first_example(parameter) {
+            return Some(scalar_string(Some(&example)));
+        }
+        // Swagger 2 parameters carry their schema keywords inline
+        let schema = if self.swagger {
+            Some(parameter)
+        } else {
+            parameter.get("schema").and_then(|s| self.resolve(s))
+        }?;
+        ["example", "default"]
+            .iter()
+            .find_map(|key| schema.get(*key))
+            .or_else(|| array(schema, "enum").first())
+            .map(|value| scalar_string(Some(value)))
+    }
+
+    /// The value of the first entry in an OpenAPI 3 `examples` map
+    fn first_example(&mut self, value: &'a Value) -> Option<Value> {
+        let example = value.get("examples")?.as_object()?.values().next()?;
+        self.resolve(example)?.get("value").cloned()
+    }
+
+    fn request_body(&mut self, operation: &'a Value, request: &mut Request) {
+        let Some(body) = operation
+            .get("requestBody")
+            .and_then(|body| self.resolve(body))
+        else {
+            return;
+        };
+        let Some((media_type, media)) = body
+            .get("content")
+            .and_then(Value::as_object)
+            .and_then(|content| pick_media_type(content, false))
+        else {
+            return;
+        };
+        let example = match media.get("example") {
+            Some(example) => Some(example.clone()),
+            None => self.first_example(media),
+        };
+        request.body = self.body(media_type, example, media.get("schema"));
+        if let BodyType::Json(_) | BodyType::Xml(_) | BodyType::Text(_) = request.body {
+            if media_type != "application/json" {
+                add_header(request, "Content-Type", media_type.to_string());
+            }
+        }
+    }
+
+    fn body(
+        &mut self,
+        media_type: &str,
+        example: Option<Value>,
+        schema: Option<&'a Value>,
+    ) -> BodyType {
+        let multipart = media_type.starts_with("multipart/");
+        if media_type.contains("json") {
+            let value = match example {
+                Some(example) => example,
+                None => schema.map(|s| self.example(s, 0)).unwrap_or(json!({})),
+            };
+            BodyType::Json(serde_json::to_string_pretty(&value).unwrap_or_default())
+        } else if multipart || media_type == "application/x-www-form-urlencoded" {
+            let fields = self.schema_form(schema, example.as_ref());
+            if multipart {
+                BodyType::FormData(fields)
+            } else {
+                BodyType::FormUrlEncoded(fields)
+            }
+        } else if media_type.contains("xml") {
+            match example {
+                Some(Value::String(example)) => BodyType::Xml(example),
+                _ => {
+                    self.issue("No XML example was given, so the body was left empty");
+                    BodyType::Xml(String::new())
+                }
+            }
+        } else if media_type.starts_with("text/") {
+            BodyType::Text(example.map(|e| scalar_string(Some(&e))).unwrap_or_default())
+        } else {
+            BodyType::Binary(vec![])
+        }
+    }
+
+    /// Form fields from the properties of an object schema
+    fn schema_form(&mut self, schema: Option<&'a Value>, example: Option<&Value>) -> Vec<KeyValue> {
+        let Some(schema) = schema.and_then(|s| self.resolve(s)) else {
+            return vec![];
+        };
+        let Some(properties) = schema.get("properties").and_then(Value::as_object) else {
+            return vec![];
+        };
+        let mut fields = vec![];
+        for (name, property) in properties {
+            let Some(property) = self.resolve(property) else {
+                continue;
+            };
+            let is_file = matches!(str_field(property, "format"), Some("binary" | "base64"));
+            let value = match example.and_then(|e| e.get(name)) {
+                Some(value) => scalar_string(Some(value)),
+                None if is_file => String::new(),
+                None => {
+                    let value = self.example(property, 1);
+                    scalar_string(Some(&value))
+                }
+            };
+            let mut field = KeyValue::new(name, value);
+            if is_file {
+                field.param_type = FormDataParamType::File;
+            }
+            fields.push(field);
+        }
+        fields
+    }
+
+    /// Swagger 2 `formData` parameters, sent as multipart when a file is involved
+    fn swagger_form(&mut self, operation: &'a Value, parameters: &[&'a Value]) -> BodyType {
+        let consumes = array(operation, "consumes")
+            .iter()
+            .chain(array(self.root, "consumes"))
+            .filter_map(Value::as_str)
+            .any(|media_type| media_type.starts_with("multipart/"));
+        let mut multipart = consumes;
+        let mut fields = vec![];
+        for parameter in parameters {
+            let name = str_field(parameter, "name").unwrap_or_default();
+            let is_file = str_field(parameter, "type") == Some("file");
+            multipart |= is_file;
+            let example = if is_file {
+                None
+            } else {
+                self.parameter_example(parameter)
+            };
+            let required = parameter.get("required") == Some(&Value::Bool(true));
+            let mut field = key_value(name, example, required);
+            if is_file {
+                field.param_type = FormDataParamType::File;
+            }
+            fields.push(field);
+        }
+        if multipart {
+            BodyType::FormData(fields)
+        } else {
+            BodyType::FormUrlEncoded(fields)
+        }
+    }
+
+    /// Build an example value from a schema, preferring the values it declares
+    fn example(&mut self, schema: &'a Value, depth: usize) -> Value {
+        if depth > MAX_DEPTH {
+            return Value::Null;
+        }
+        let Some(schema) = self.resolve(schema) else {
+            return Value::Null;
+        };
+        let declared = ["example", "default", "const"]
+            .iter()
+            .find_map(|key| schema.get(*key))
+            .or_else(|| array(schema, "examples").first())
+            .or_else(|| array(schema, "enum").first());
+        if let Some(declared) = declared {
+            return declared.clone();
+        }
+
+        if let Some(parts) = schema.get("allOf").and_then(Value::as_array) {
+            let mut merged = Map::new();
+            for part in parts {
+                if let Value::Object(fields) = self.example(part, depth + 1) {
+                    merged.extend(fields);
+                }
+            }
+            return Value::Object(merged);
+        }
+        for key in ["oneOf", "anyOf"] {
+            if let Some(first) = array(schema, key).first() {
+                return self.example(first, depth + 1);
+            }
+        }
+
+        let kind = match schema.get("type") {
+            Some(Value::String(kind)) => kind.as_str(),
+            Some(Value::Array(kinds)) => kinds
+                .iter()
+                .filter_map(Value::as_str)
+                .find(|kind| *kind != "null")
+                .unwrap_or("null"),
+            _ if schema.get("properties").is_some() => "object",
+            _ if schema.get("items").is_some() => "array",
+            _ => "",
+        };
+        match kind {
+            "object" => {
+                let mut fields = Map::new();
+                if let Some(properties) = schema.get("properties").and_then(Value::as_object) {
+                    for (name, property) in properties {
+                        // Read-only fields are only ever sent by the server
+                        let read_only = self
+                            .resolve(property)
+                            .is_some_and(|p| p.get("readOnly") == Some(&Value::Bool(true)));
+                        if !read_only {
+                            fields.insert(name.clone(), self.example(property, depth + 1));
+                        }
+                    }
+                }
+                Value::Object(fields)
+            }
+            "array" => match schema.get("items") {
+                Some(items) => json!([self.example(items, depth + 1)]),
+                None => json!([]),
+            },
+            "integer" | "number" => json!(0),
+            "boolean" => json!(true),
+            "string" => json!(string_example(str_field(schema, "format"))),
+            _ => Value::Null,
+        }
+    }
+
+    fn security(&mut self, operation: &'a Value, request: &mut Request) {
+        let root = self.root;
+        // An operation's own list, even an empty one, replaces the document's
+        let requirements = operation.get("security").or_else(|| root.get("security"));
+        let Some(requirement) = requirements
+            .and_then(Value::as_array)
+            .and_then(|requirements| requirements.first())
+            .and_then(Value::as_object)
+        else {
+            return;
+        };
+
+        let definitions = if self.swagger {
+            root.get("securityDefinitions")
+        } else {
+            root.pointer("/components/securitySchemes")
+        };
+        for name in requirement.keys() {
+            let Some(scheme) = definitions
+                .and_then(|definitions| definitions.get(name))
+                .and_then(|scheme| self.resolve(scheme))
+            else {
+                self.issue(format!("Security scheme '{}' is not defined", name));
+                continue;
+            };
+            self.apply_scheme(name, scheme, request);
+        }
+    }
+
+    /// Apply a security scheme using a variable named after it for the credential
+    fn apply_scheme(&mut self, name: &str, scheme: &'a Value, request: &mut Request) {
+        let variable = format!("{{{{{}}}}}", name);
+        let kind = str_field(scheme, "type").unwrap_or_default();
+        let http_scheme = str_field(scheme, "scheme")
+            .unwrap_or_default()
+            .to_lowercase();
+
+        match (kind, http_scheme.as_str()) {
+            ("http", "bearer") => {
+                add_header(request, "Authorization", format!("Bearer {}", variable));
+            }
+            ("http", "basic") | ("basic", _) => {
+                // One pair of credentials for every basic scheme in the document
+                request.auth = AuthScheme::Basic {
+                    username: "{{username}}".to_string(),
+                    password: "{{password}}".to_string(),
+                }
+                .to_fields();
+                self.variables.entry("username".to_string()).or_default();
+                self.variables.entry("password".to_string()).or_default();
+                return;
+            }
+            ("apiKey", _) => {
+                let key = str_field(scheme, "name").unwrap_or(name);
+                match str_field(scheme, "in") {
+                    Some("query") => request.query_params.push(KeyValue::new(key, &variable)),
+                    Some("cookie") => request.cookies.push(KeyValue::new(key, &variable)),
+                    _ => add_header(request, key, variable),
+                }
+            }
+            ("oauth2" | "openIdConnect", _) => {
+                add_header(request, "Authorization", format!("Bearer {}", variable));
+                self.issue(format!(
+                    "OAuth flows are not run here, put an access token in the {{{{{}}}}} variable",
+                    name
+                ));
+            }
+            _ => {
+                self.issue(format!(
+                    "Security scheme '{}' ({} {}) is not supported",
+                    name, kind, http_scheme
+                ));
+                return;
+            }
+        }
+        self.variables.entry(name.to_string()).or_default();
+    }
+
+    /// The first successful response gives the schema and example
+    fn response(&mut self, operation: &'a Value, request: &mut Request) {
+        let Some(responses) = operation.get("responses").and_then(Value::as_object) else {
+            return;
+        };
+        let Some((status, response)) = responses
+            .iter()
+            .find(|(status, _)| status.starts_with('2'))
+            .or_else(|| responses.iter().find(|(status, _)| *status == "default"))
+        else {
+            return;
+        };
+        let Some(response) = self.resolve(response) else {
+            return;
+        };
+
+        let (media_type, schema, example) = if self.swagger {
+            let example = response
+                .get("examples")
+                .and_then(Value::as_object)
+                .and_then(|examples| pick_media_type(examples, true))
+                .map(|(_, example)| example.clone());
+            ("application/json", response.get("schema"), example)
+        } else {
+            let Some((media_type, media)) = response
+                .get("content")
+                .and_then(Value::as_object)
+                .and_then(|content| pick_media_type(content, true))
+            else {
+                return;
+            };
+            let example = match media.get("example") {
+                Some(example) => Some(example.clone()),
+                None => self.first_example(media),
+            };
+            (media_type, media.get("schema"), example)
+        };
+
+        if let Some(schema) = schema {
+            let inline = self.inline(schema, 0);
+            request.schema = Some(ResponseSchema {
+                inline: serde_json::to_string_pretty(&inline).unwrap_or_default(),
+                ..Default::default()
+            });
+        }
+        if let Some(example) = example {
+            let body = match example {
+                Value::String(body) => body,
+                other => serde_json::to_string_pretty(&other).unwrap_or_default(),
+            };
+            request.example = Some(ResponseExample {
+                status: status.parse().unwrap_or(200),
+                headers: vec![KeyValue::new("Content-Type", media_type)],
+                body,
+                saved_at: chrono::Local::now().to_rfc3339(),
+                ignore: vec![],
+            });
+        }
+    }
+
+    /// Copy a schema with its references resolved, so it stands on its own
+    fn inline(&mut self, schema: &'a Value, depth: usize) -> Value {
+        if depth > MAX_DEPTH {
+            return json!({});
+        }
+        match schema {
+            Value::Object(fields) if fields.contains_key("$ref") => match self.resolve(schema) {
+                Some(resolved) => self.inline(resolved, depth + 1),
+                None => json!({}),
+            },
+            Value::Object(fields) => {
+                let mut inlined = Map::new();
+                for (key, value) in fields {
+                    inlined.insert(key.clone(), self.inline(value, depth + 1));
+                }
+                // OpenAPI 3.0 marks nullable values with a keyword JSON Schema lacks
+                if inlined.remove("nullable") == Some(Value::Bool(true)) {
+                    if let Some(Value::String(kind)) = inlined.get("type").cloned() {
+                        inlined.insert("type".to_string(), json!([kind, "null"]));
+                    }
+                }
+                Value::Object(inlined)
+            }
+            Value::Array(items) => Value::Array(
+                items
+                    .iter()
+                    .map(|item| self.inline(item, depth + 1))
+                    .collect(),
+            ),
+            other => other.clone(),
+        }
+    }
+
+    /// Follow `$ref` pointers within the document
+    fn resolve(&mut self, value: &'a Value) -> Option<&'a Value> {
+        let mut value = value;
+        for _ in 0..MAX_DEPTH {
+            let Some(reference) = value.get("$ref").and_then(Value::as_str) else {
+                return Some(value);
+            };
+            let Some(pointer) = reference.strip_prefix('#') else {
+                self.issue(format!("External reference {} was not followed", reference));
+                return None;
+            };
+            let Some(target) = self.root.pointer(pointer) else {
+                self.issue(format!("Reference {} was not found", reference));
+                return None;
+            };
+            value = target;
+        }
+        self.issue("A chain of references was too deep to follow");
+        None
+    }
+
+    fn issue(&mut self, message: impl Into<String>) {
+        let message = message.into();
+        if self.reported.insert(message.clone()) {
+            self.report.issue(&self.location, message);
+        }
+    }
+}
+
+/// A server's URL with its variables set to their defaults
+fn server_url(server: &Value) -> String {
+    let mut url = str_field(server, "url").unwrap_or_default().to_string();
+    if let Some(variables) = server.get("variables").and_then(Value::as_object) {
+        for (name, variable) in variables {
+            let default = scalar_string(variable.get("default"));
+            url = url.replace(&format!("{{{}}}", name), &default);
+        }
+    }
+    url
+}
+
+/// The most useful of the declared media types, JSON first
+fn pick_media_type(content: &Map<String, Value>, response: bool) -> Option<(&str, &Value)> {
+    content
+        .iter()
+        .min_by_key(|(media_type, _)| match media_type.as_str() {
+            json if json.contains("json") => 0,
+            _ if response => 1,
+            "application/x-www-form-urlencoded" => 1,
+            form if form.starts_with("multipart/") => 2,
+            xml if xml.contains("xml") => 3,
+            text if text.starts_with("text/") => 4,
+            _ => 5,
+        })
+        .map(|(media_type, media)| (media_type.as_str(), media))
+}
+
+fn string_example(format: Option<&str>) -> &'static str {
+    match format.unwrap_or_default() {
+        "date-time" => "2024-01-01T00:00:00Z",
+        "date" => "2024-01-01",
+        "time" => "00:00:00",
+        "email" => "user@example.com",
+        "uuid" => "00000000-0000-0000-0000-000000000000",
+        "uri" | "url" => "https://example.com",
+        "hostname" => "example.com",
+        "ipv4" => "127.0.0.1",
+        "ipv6" => "::1",
+        "binary" | "byte" | "password" => "",
+        _ => "string",
+    }
+}
+
+/// Optional parameters are added disabled, so they show without being sent
+fn key_value(name: &str, value: Option<String>, required: bool) -> KeyValue {
+    let mut key_value = KeyValue::new(name, value.unwrap_or_default());
+    key_value.enabled = required;
+    key_value
+}
+
+fn add_header(request: &mut Request, key: &str, value: String) {
+    let exists = request
+        .headers
+        .iter()
+        .any(|h| h.key.eq_ignore_ascii_case(key));
+    if !exists {
+        request.headers.push(KeyValue::new(key, value));
+    }
+}
+
+/// Version fields may be parsed from YAML as numbers, e.g. `swagger: 2.0`
+fn version(value: &Value, key: &str) -> Option<String> {
+    match value.get(key)? {
+        Value::String(version) => Some(version.clone()),
+        Value::Number(version) => Some(version.to_string()),
+        _ => None,
+    }
+}
+
+/// Parameter and form values as text; arrays are comma separated
+fn scalar_string(value: Option<&Value>) -> String {
+    match value {
+        None | Some(Value::Null) => String::new(),
+        Some(Value::String(text)) => text.clone(),
+        Some(Value::Array(items)) => items
+            .iter()
+            .map(|item| scalar_string(Some(item)))
+            .collect::<Vec<_>>()
+            .join(","),
+        Some(other) => other.to_string(),
+    }
+}
+
+fn str_field<'v>(value: &'v Value, key: &str) -> Option<&'v str> {
+    value.get(key).and_then(Value::as_str)
+}
+
+fn non_empty<'v>(value: &'v Value, key: &str) -> Option<&'v str> {
+    str_field(value, key).filter(|text| !text.trim().is_empty())
+}
+
+fn array<'v>(value: &'v Value, key: &str) -> &'v [Value] {
+    value
+        .get(key)
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(items: &[CollectionItem], index: usize) -> &Request {
+        match &items[index] {
+            CollectionItem::Request(request) => request,
+            _ => panic!("expected a request"),
+        }
+    }
+
+    const PETSTORE: &str = r##"
+openapi: 3.0.3
+info:
+  title: Petstore
+servers:
+  - url: https://{region}.pets.example.com/v1
+    variables:
+      region:
+        default: eu
+security:
+  - bearerAuth: []
+tags:
+  - name: pets
+paths:
+  /pets/{petId}:
+    parameters:
+      - $ref: "#/components/parameters/PetId"
+    get:
+      tags: [pets]
+      summary: Get a pet
+      parameters:
+        - name: fields
+          in: query
+          schema:
+            type: array
+            items:
+              type: string
+            example: [name, tag]
+        - name: X-Trace
+          in: header
+          required: true
+          schema:
+            type: string
+      responses:
+        "200":
+          description: A pet
+          content:
+            application/json:
+              schema:
+                $ref: "#/components/schemas/Pet"
+              example:
+                id: 1
+                name: Rex
+  /pets:
+    post:
+      tags: [pets]
+      operationId: createPet
+      security:
+        - apiKey: []
+      requestBody:
+        content:
+          application/json:
+            schema:
+              $ref: "#/components/schemas/Pet"
+      responses:
+        "201":
+          description: Created
+  /health:
+    get:
+      security: []
+      responses:
+        default:
+          description: OK
+    trace:
+      responses: {}
+components:
+  parameters:
+    PetId:
+      name: petId
+      in: path
+      required: true
+      schema:
+        type: integer
+  securitySchemes:
+    bearerAuth:
+      type: http
+      scheme: bearer
+    apiKey:
+      type: apiKey
+      in: query
+      name: api_key
+  schemas:
+    Pet:
+      type: object
+      required: [name]
+      properties:
+        id:
+          type: integer
+          readOnly: true
+        name:
+          type: string
+        tag:
+          type: string
+          nullable: true
+        born:
+          type: string
+          format: date
+        owner:
+          $ref: "#/components/schemas/Owner"
+    Owner:
+      allOf:
+        - type: object
+          properties:
+            email:
+              type: string
+              format: email
+        - type: object
+          properties:
+            pets:
+              type: array
+              items:
+                $ref: "#/components/schemas/Pet"
+"##;
+
+    fn petstore() -> ImportResult {
+        let value: Value = serde_yaml::from_str(PETSTORE).unwrap();
+        assert!(is_document(&value));
+        import_document(&value).unwrap()
+    }
+
+    #[test]
+    fn test_import_openapi() {
+        let result = petstore();
+        assert!(result.merge);
+        assert_eq!(
+            result.variables["baseUrl"],
+            "https://eu.pets.example.com/v1"
+        );
+        assert_eq!(result.variables["bearerAuth"], "");
+        assert_eq!(result.variables["apiKey"], "");
+        assert_eq!(result.report.requests, 3);
+        assert_eq!(result.report.folders, 1);
+
+        let collection = result.collection.unwrap();
+        assert_eq!(collection.name, "Petstore");
+        // Folders come first, untagged operations follow
+        let CollectionItem::Folder(pets) = &collection.items[0] else {
+            panic!("expected a folder");
+        };
+        assert_eq!(pets.name, "pets");
+
+        let create = request(&pets.items, 0);
+        assert_eq!(create.name, "createPet");
+        assert_eq!(create.url, "{{baseUrl}}/pets");
+        assert_eq!(create.query_params[0].key, "api_key");
+        assert_eq!(create.query_params[0].value, "{{apiKey}}");
+        assert!(!create.headers.iter().any(|h| h.key == "Authorization"));
+        let BodyType::Json(body) = &create.body else {
+            panic!("expected a JSON body");
+        };
+        let body: Value = serde_json::from_str(body).unwrap();
+        assert!(body.get("id").is_none());
+        assert_eq!(body["born"], "2024-01-01");
+        assert_eq!(body["owner"]["email"], "user@example.com");
+        assert_eq!(body["owner"]["pets"][0]["name"], "string");
+
+        let get = request(&pets.items, 1);
+        assert_eq!(get.name, "Get a pet");
+        assert_eq!(get.url, "{{baseUrl}}/pets/{{petId}}");
+        assert_eq!(get.query_params[0].value, "name,tag");
+        assert!(!get.query_params[0].enabled);
+        assert!(get.headers[0].enabled);
+        assert_eq!(get.headers[1].value, "Bearer {{bearerAuth}}");
+        assert_eq!(get.example.as_ref().unwrap().status, 200);
+        let schema: Value = serde_json::from_str(&get.schema.as_ref().unwrap().inline).unwrap();
+        assert_eq!(
+            schema["properties"]["tag"]["type"],
+            json!(["string", "null"])
+        );
+        assert_eq!(schema["properties"]["owner"]["allOf"][0]["type"], "object");
+
+        let health = request(&collection.items, 1);
+        assert_eq!(health.name, "GET /health");
+        assert!(health.headers.is_empty());
+
+        let issues = &result.report.issues;
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].location, "TRACE /health");
+    }
+
+    #[test]
+    fn test_import_swagger() {
+        let value = json!({
+            "swagger": "2.0",
+            "info": {"title": "Files"},
+            "host": "files.example.com",
+            "basePath": "/api",
+            "schemes": ["http"],
+            "securityDefinitions": {"basic": {"type": "basic"}},
+            "paths": {
+                "/upload/{folder}": {
+                    "post": {
+                        "security": [{"basic": []}],
+                        "parameters": [
+                            {"name": "folder", "in": "path", "type": "string", "default": "inbox"},
+                            {"name": "file", "in": "formData", "type": "file", "required": true},
+                            {"name": "note", "in": "formData", "type": "string"}
+                        ],
+                        "responses": {"200": {
+                            "description": "OK",
+                            "schema": {"type": "object"},
+                            "examples": {"application/json": {"ok": true}}
+                        }}
+                    }
+                }
+            }
+        });
+        let result = import_document(&value).unwrap();
+        assert_eq!(result.variables["baseUrl"], "http://files.example.com/api");
+        assert_eq!(result.variables["username"], "");
+
+        let collection = result.collection.unwrap();
+        let upload = request(&collection.items, 0);
+        assert_eq!(upload.url, "{{baseUrl}}/upload/inbox");
+        let BodyType::FormData(fields) = &upload.body else {
+            panic!("expected a multipart body");
+        };
+        assert_eq!(fields[0].param_type, FormDataParamType::File);
+        assert!(!fields[1].enabled);
+        assert!(matches!(
+            AuthScheme::from_fields(&upload.auth),
+            Some(AuthScheme::Basic { username, .. }) if username == "{{username}}"
+        ));
+        assert!(upload
+            .example
+            .as_ref()
+            .unwrap()
+            .body
+            .contains("\"ok\": true"));
+        assert!(result.report.issues.is_empty());
+    }
+
+    #[test]
+    fn test_export_round_trip() {
+        let mut get = Request {
+            name: "Get pet".to_string(),
+            url: "{{baseUrl}}/pets/{{petId}}".to_string(),
+            headers: vec![KeyValue::new("Authorization", "Bearer {{token}}")],
+            query_params: vec![KeyValue::new("fields", "name")],
+            description: "One pet".to_string(),
+            ..Default::default()
+        };
+        get.example = Some(ResponseExample {
+            status: 200,
+            headers: vec![KeyValue::new("Content-Type", "application/json")],
+            body: r#"{"id": 1, "tags": ["a"], "weight": 1.5}"#.to_string(),
+            saved_at: String::new(),
+            ignore: vec![],
+        });
+        let create = Request {
+            name: "Create pet".to_string(),
+            method: HttpMethod::POST,
+            url: "https://other.example.com/pets".to_string(),
+            headers: vec![],
+            body: BodyType::Json(r#"{"name": "Rex"}"#.to_string()),
+            ..Default::default()
+        };
+        let items = vec![CollectionItem::Folder(Folder {
+            id: Uuid::new_v4(),
+            name: "Pets".to_string(),
+            items: vec![
+                CollectionItem::Request(get.clone()),
+                CollectionItem::Request(create),
+                // Documented once
+                CollectionItem::Request(get),
+            ],
+            expanded: false,
+        })];
+
+        let document = export_document("Petstore", &items);
+        assert!(is_document(&document));
+        assert_eq!(document["servers"][0]["url"], "{baseUrl}");
+        let operation = &document["paths"]["/pets/{petId}"]["get"];
+        assert_eq!(operation["tags"], json!(["Pets"]));
+        assert_eq!(operation["parameters"][0]["in"], "path");
+        assert_eq!(operation["security"], json!([{ "bearerAuth": [] }]));
+        let schema = &operation["responses"]["200"]["content"]["application/json"]["schema"];
+        assert_eq!(schema["properties"]["weight"]["type"], "number");
+        assert_eq!(schema["properties"]["tags"]["items"]["type"], "string");
+        let create = &document["paths"]["/pets"]["post"];
+        assert_eq!(create["servers"][0]["url"], "https://other.example.com");
+
+        let result = import_document(&document).unwrap();
+        assert_eq!(result.report.requests, 2);
+        let collection = result.collection.unwrap();
+        let CollectionItem::Folder(folder) = &collection.items[0] else {
+            panic!("expected a folder");
+        };
+        assert_eq!(
+            request(&folder.items, 0).url,
+            "https://other.example.com/pets"
+        );
+        let imported = request(&folder.items, 1);
+        assert_eq!(imported.url, "{{baseUrl}}/pets/{{petId}}");
+        assert_eq!(imported.query_params[0].value, "name");
+        assert_eq!(imported.description, "One pet");
+        assert_eq!(imported.example.as_ref().unwrap().status, 200);
+    }
+}
@@ -0,0 +1,1165 @@
+use std::collections::HashMap;
+
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::models::{
+    AuthScheme, BodyType, Collection, CollectionItem, Environment, Folder, FormDataParamType,
+    HttpMethod, ImportReport, ImportResult, KeyValue, Request, ResponseExample,
+};
+use crate::utils::variables;
+
+/// Whether `value` looks like a Postman collection export
+pub fn is_collection(value: &Value) -> bool {
+    value.get("info").is_some_and(Value::is_object)
+        && value.get("item").is_some_and(Value::is_array)
+}
+
+/// Whether `value` looks like a Postman environment export
+pub fn is_environment(value: &Value) -> bool {
+    value.get("values").is_some_and(Value::is_array) && value.get("item").is_none()
+}
+
+/// Import a Postman v2.0 or v2.1 collection
+///
+/// Collection variables are returned as environment defaults, since
+/// collections here have no variables of their own.
+pub fn import_collection(value: &Value) -> Result<ImportResult, String> {
+    if !is_collection(value) {
+        return Err("Not a Postman collection".to_string());
+    }
+    let info = &value["info"];
+    if str_field(info, "schema").is_some_and(|schema| schema.contains("/v1.")) {
+        return Err("Postman v1 collections are not supported, export as v2.1".to_string());
+    }
+
+    let name = str_field(info, "name").unwrap_or("Imported collection");
+    let mut importer = Importer {
+        report: ImportReport::new("Postman collection"),
+    };
+    importer.report.name = name.to_string();
+
+    importer.events(value, name);
+    if description(info).is_some() {
+        importer
+            .report
+            .issue(name, "Collection description was not imported");
+    }
+    let items = importer.items(
+        array(value, "item"),
+        active_auth(value.get("auth"), None),
+        &[],
+    );
+
+    let mut variables = HashMap::new();
+    for variable in array(value, "variable") {
+        let Some(key) = str_field(variable, "key").filter(|key| !key.is_empty()) else {
+            continue;
+        };
+        if variable.get("disabled") == Some(&Value::Bool(true)) {
+            importer
+                .report
+                .issue(name, format!("Disabled variable '{}' was skipped", key));
+            continue;
+        }
+        variables.insert(key.to_string(), value_string(variable.get("value")));
+    }
+    importer.report.variables = variables.len();
+
+    Ok(ImportResult {
+        collection: Some(Collection {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            items,
+            expanded: true,
+            http_file: None,
+        }),
+        variables,
+        environment: None,
+        overwrite: false,
+        merge: false,
+        report: importer.report,
+    })
+}
+
+/// Import a Postman environment export
+///
+/// The environment is matched by name (e.g. `Staging`); the caller falls
+/// back to the current environment when nothing matches.
+pub fn import_environment(value: &Value) -> Result<ImportResult, String> {
+    if !is_environment(value) {
+        return Err("Not a Postman environment".to_string());
+    }
+    let name = str_field(value, "name").unwrap_or("Environment");
+    let mut report = ImportReport::new("Postman environment");
+    report.name = name.to_string();
+
+    let mut variables = HashMap::new();
+    for variable in array(value, "values") {
+        let Some(key) = str_field(variable, "key").filter(|key| !key.is_empty()) else {
+            continue;
+        };
+        if variable.get("enabled") == Some(&Value::Bool(false)) {
+            report.issue(name, format!("Disabled variable '{}' was skipped", key));
+            continue;
+        }
+        variables.insert(key.to_string(), value_string(variable.get("value")));
+    }
+    report.variables = variables.len();
+
+    Ok(ImportResult {
+        collection: None,
+        variables,
+        environment: name.parse::<Environment>().ok(),
+        overwrite: true,
+        merge: false,
+        report,
+    })
+}
+
+/// Export a collection or folder as a Postman v2.1 collection
+///
+/// Flows have no Postman equivalent and are left out. Binary bodies are
+/// exported as file bodies without a file, since Postman keeps only a path.
+pub fn export_collection(name: &str, items: &[CollectionItem]) -> Value {
+    json!({
+        "info": {
+            "_postman_id": Uuid::new_v4().to_string(),
+            "name": name,
+            "schema": POSTMAN_SCHEMA,
+        },
+        "item": export_items(items),
+    })
+}
+
+const POSTMAN_SCHEMA: &str = "https://schema.getpostman.com/json/collection/v2.1.0/collection.json";
+
+fn export_items(items: &[CollectionItem]) -> Vec<Value> {
+    items
+        .iter()
+        .filter_map(|item| match item {
+            CollectionItem::Folder(folder) => Some(json!({
+                "name": folder.name,
+                "item": export_items(&folder.items),
+            })),
+            CollectionItem::Request(request) => Some(export_request(request)),
+            CollectionItem::Flow(_) => None,
+        })
+        .collect()
+}
+
+fn export_request(request: &Request) -> Value {
+    let mut exported = json!({
+        "method": request.method.as_str(),
+        "header": export_key_values(&request.headers),
+        "url": export_url(request),
+    });
+    if let Some(body) = export_body(&request.body) {
+        exported["body"] = body;
+    }
+    if let Some(auth) = AuthScheme::from_fields(&request.auth) {
+        exported["auth"] = export_auth(auth);
+    }
+    if !request.description.is_empty() {
+        exported["description"] = json!(request.description);
+    }
+
+    let mut item = json!({ "name": request.name, "request": exported });
+    if request.insecure {
+        item["protocolProfileBehavior"] = json!({ "strictSSL": false });
+    }
+    if let Some(example) = &request.example {
+        item["response"] = json!([{
+            "name": "Example",
+            "code": example.status,
+            "header": export_key_values(&example.headers),
+            "body": example.body,
+        }]);
+    }
+    item
+}
+
+fn export_url(request: &Request) -> Value {
+    let (base, raw_query) = match request.url.split_once('?') {
+        Some((base, query)) => (base, Some(query)),
+        None => (request.url.as_str(), None),
+    };
+
+    let mut query: Vec<KeyValue> = raw_query
+        .into_iter()
+        .flat_map(|query| query.split('&'))
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            KeyValue::new(key, value)
+        })
+        .collect();
+    query.extend(
+        request
+            .query_params
+            .iter()
+            .filter(|p| !p.key.is_empty())
+            .cloned(),
+    );
+
+    let enabled: Vec<String> = query
+        .iter()
+        .filter(|p| p.enabled)
+        .map(|p| format!("{}={}", p.key, p.value))
+        .collect();
+    let raw = if enabled.is_empty() {
+        base.to_string()
+    } else {
+        format!("{}?{}", base, enabled.join("&"))
+    };
+
+    let (protocol, rest) = match base.split_once("://") {
+        Some((protocol, rest)) => (Some(protocol), rest),
+        None => (None, base),
+    };
+    let (authority, path) = rest.split_once('/').unwrap_or((rest, ""));
+    let (host, port) = match authority.rsplit_once(':') {
+        Some((host, port)) if !port.is_empty() && port.chars().all(|c| c.is_ascii_digit()) => {
+            (host, Some(port))
+        }
+        _ => (authority, None),
+    };
+    let host: Vec<&str> = if host.starts_with("{{") {
+        vec![host]
+    } else {
+        host.split('.').collect()
+    };
+
+    let mut url = json!({ "raw": raw, "host": host });
+    if let Some(protocol) = protocol {
+        url["protocol"] = json!(protocol);
+    }
+    if let Some(port) = port {
+        url["port"] = json!(port);
+    }
+    if !path.is_empty() {
+        url["path"] = json!(path.split('/').collect::<Vec<_>>());
+    }
+    if !query.is_empty() {
+        url["query"] = json!(export_key_values(&query));
+    }
+    url
+}
+
+fn export_body(body: &BodyType) -> Option<Value> {
+    let raw = |raw: &str, language: &str| {
+        json!({
+            "mode": "raw",
+            "raw": raw,
+            "options": { "raw": { "language": language } },
+        })
+    };
+    Some(match body {
+        BodyType::None => return None,
+        BodyType::Json(text) => raw(text, "json"),
+        BodyType::Xml(text) => raw(text, "xml"),
+        BodyType::Text(text) => raw(text, "text"),
+        BodyType::FormUrlEncoded(fields) => json!({
+            "mode": "urlencoded",
+            "urlencoded": export_key_values(fields),
+        }),
+        BodyType::FormData(fields) => {
+            let fields: Vec<Value> = fields
+                .iter()
+                .map(|field| {
+                    let mut exported = match field.param_type {
+                        FormDataParamType::Text => {
+                            json!({ "key": field.key, "value": field.value, "type": "text" })
+                        }
+                        FormDataParamType::File => {
+                            json!({ "key": field.key, "src": field.value, "type": "file" })
+                        }
+                    };
+                    if !field.enabled {
+                        exported["disabled"] = json!(true);
+                    }
+                    exported
+                })
+                .collect();
+            json!({ "mode": "formdata", "formdata": fields })
+        }
+        BodyType::Binary(_) => json!({ "mode": "file", "file": { "src": "" } }),
+    })
+}
+
+fn export_auth(auth: AuthScheme) -> Value {
+    let params = |params: Vec<(&str, String)>| -> Vec<Value> {
+        params
+            .into_iter()
+            .map(|(key, value)| json!({ "key": key, "value": value, "type": "string" }))
+            .collect()
+    };
+    match auth {
+        AuthScheme::Basic { username, password } => json!({
+            "type": "basic",
+            "basic": params(vec![("username", username), ("password", password)]),
+        }),
+        AuthScheme::Bearer { token } => json!({
+            "type": "bearer",
+            "bearer": params(vec![("token", token)]),
+        }),
+        AuthScheme::ApiKey {
+            key,
+            value,
+            in_query,
+        } => {
+            let place = if in_query { "query" } else { "header" };
+            json!({
+                "type": "apikey",
+                "apikey": params(vec![("key", key), ("value", value), ("in", place.to_string())]),
+            })
+        }
+        AuthScheme::Other { kind, fields } => {
+            let mut auth = json!({ "type": kind });
+            auth[kind.as_str()] = json!(export_key_values(&fields));
+            auth
+        }
+    }
+}
+
+fn export_key_values(items: &[KeyValue]) -> Vec<Value> {
+    items
+        .iter()
+        .filter(|item| !item.key.is_empty())
+        .map(|item| {
+            let mut exported = json!({ "key": item.key, "value": item.value });
+            if !item.enabled {
+                exported["disabled"] = json!(true);
+            }
+            exported
+        })
+        .collect()
+}
+
+struct Importer {
+    report: ImportReport,
+}
+
+impl Importer {
+    fn items(
+        &mut self,
+        items: &[Value],
+        auth: Option<&Value>,
+        parents: &[&str],
+    ) -> Vec<CollectionItem> {
+        let mut imported = vec![];
+        for item in items {
+            let name = str_field(item, "name").unwrap_or("Untitled");
+            let location = location(parents, name);
+
+            if let Some(children) = item.get("item").and_then(Value::as_array) {
+                self.events(item, &location);
+                if description(item).is_some() {
+                    self.report
+                        .issue(&location, "Folder description was not imported");
+                }
+                let mut path = parents.to_vec();
+                path.push(name);
+                let children = self.items(children, active_auth(item.get("auth"), auth), &path);
+                self.report.folders += 1;
+                imported.push(CollectionItem::Folder(Folder {
+                    id: Uuid::new_v4(),
+                    name: name.to_string(),
+                    items: children,
+                    expanded: false,
+                }));
+            } else if let Some(request) = item.get("request") {
+                let request = self.request(item, request, auth, &location);
+                self.report.requests += 1;
+                imported.push(CollectionItem::Request(request));
+            } else {
+                self.report.issue(
+                    &location,
+                    "Item has no request or sub-items and was skipped",
+                );
+            }
+        }
+        imported
+    }
+
+    fn request(
+        &mut self,
+        item: &Value,
+        request: &Value,
+        auth: Option<&Value>,
+        location: &str,
+    ) -> Request {
+        let mut imported = Request {
+            name: str_field(item, "name").unwrap_or("Untitled").to_string(),
+            headers: vec![],
+            ..Default::default()
+        };
+
+        // A request may be given as a bare URL
+        if let Some(url) = request.as_str() {
+            imported.method = HttpMethod::GET;
+            imported.url = url.to_string();
+            return imported;
+        }
+
+        let method = str_field(request, "method").unwrap_or("GET");
+        imported.method = method.parse().unwrap_or_else(|_| {
+            self.report.issue(
+                location,
+                format!("Method {} is not supported, imported as GET", method),
+            );
+            HttpMethod::GET
+        });
+        self.url(request.get("url"), &mut imported, location);
+        imported.headers = key_values(array(request, "header"));
+        imported.description = description(request)
+            .or_else(|| description(item))
+            .unwrap_or_default();
+        self.body(request.get("body"), &mut imported, location);
+        self.auth(
+            active_auth(request.get("auth"), auth),
+            &mut imported,
+            location,
+        );
+        imported.insecure =
+            item.pointer("/protocolProfileBehavior/strictSSL") == Some(&Value::Bool(false));
+        self.events(item, location);
+        self.responses(array(item, "response"), &mut imported, location);
+        self.dynamic_variables(&imported, location);
+        imported
+    }
+
+    fn url(&mut self, url: Option<&Value>, request: &mut Request, location: &str) {
+        let url = match url {
+            Some(Value::String(raw)) => {
+                request.url = raw.clone();
+                return;
+            }
+            Some(url @ Value::Object(_)) => url,
+            _ => {
+                self.report.issue(location, "Request has no URL");
+                request.url = String::new();
+                return;
+            }
+        };
+
+        let raw = str_field(url, "raw")
+            .map(str::to_string)
+            .unwrap_or_else(|| build_url(url));
+        let query = url.get("query").and_then(Value::as_array);
+        // The query array is authoritative and also carries disabled parameters
+        let base = match query {
+            Some(_) => raw.split('?').next().unwrap_or_default(),
+            None => raw.as_str(),
+        };
+        request.url = self.path_variables(base, array(url, "variable"), location);
+        if let Some(query) = query {
+            request.query_params = key_values(query);
+        }
+    }
+
+    /// Replace `:name` path segments, which this app writes as `{{name}}`
+    fn path_variables(&mut self, url: &str, values: &[Value], location: &str) -> String {
+        url.split('/')
+            .map(|segment| {
+                let Some(name) = segment.strip_prefix(':').filter(|name| !name.is_empty()) else {
+                    return segment.to_string();
+                };
+                let value = values
+                    .iter()
+                    .find(|v| str_field(v, "key") == Some(name))
+                    .map(|v| value_string(v.get("value")))
+                    .filter(|value| !value.is_empty());
+                match value {
+                    Some(value) => value,
+                    None => {
+                        self.report.issue(
+                            location,
+                            format!(
+                                "Path variable :{} has no value, replaced with {{{{{}}}}}",
+                                name, name
+                            ),
+                        );
+                        format!("{{{{{}}}}}", name)
+                    }
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
+    fn body(&mut self, body: Option<&Value>, request: &mut Request, location: &str) {
+        let Some(body) = body.filter(|body| body.is_object()) else {
+            return;
+        };
+        if body.get("disabled") == Some(&Value::Bool(true)) {
+            return;
+        }
+
+        let mode = str_field(body, "mode").unwrap_or("raw");
+        request.body = match mode {
+            "raw" => {
+                let raw = str_field(body, "raw").unwrap_or_default().to_string();
+                let content_type =
+                    header_value(request, "content-type").map(|ct| ct.to_lowercase());
+                let language = body
+                    .pointer("/options/raw/language")
+                    .and_then(Value::as_str);
+                match (language, content_type.as_deref()) {
+                    (Some("json"), _) => BodyType::Json(raw),
+                    (Some("xml"), _) => BodyType::Xml(raw),
+                    (None, Some(ct)) if ct.contains("json") => BodyType::Json(raw),
+                    (None, Some(ct)) if ct.contains("xml") => BodyType::Xml(raw),
+                    _ if raw.is_empty() => BodyType::None,
+                    _ => BodyType::Text(raw),
+                }
+            }
+            "urlencoded" => BodyType::FormUrlEncoded(key_values(array(body, "urlencoded"))),
+            "formdata" => BodyType::FormData(self.form_data(array(body, "formdata"), location)),
+            "file" => {
+                let src = body
+                    .pointer("/file/src")
+                    .and_then(Value::as_str)
+                    .unwrap_or_default();
+                match std::fs::read(src) {
+                    Ok(bytes) => BodyType::Binary(bytes),
+                    Err(e) => {
+                        self.report.issue(
+                            location,
+                            format!("Body file '{}' could not be read: {}", src, e),
+                        );
+                        BodyType::Binary(vec![])
+                    }
+                }
+            }
+            "graphql" => {
+                let query = body
+                    .pointer("/graphql/query")
+                    .and_then(Value::as_str)
+                    .unwrap_or_default();
+                let raw_variables = body
+                    .pointer("/graphql/variables")
+                    .and_then(Value::as_str)
+                    .unwrap_or_default();
+                let graphql_variables = if raw_variables.trim().is_empty() {
+                    json!({})
+                } else {
+                    serde_json::from_str(raw_variables).unwrap_or_else(|_| {
+                        self.report.issue(
+                            location,
+                            "GraphQL variables are not valid JSON and were sent as a string",
+                        );
+                        Value::String(raw_variables.to_string())
+                    })
+                };
+                let payload = json!({ "query": query, "variables": graphql_variables });
+                BodyType::Json(serde_json::to_string_pretty(&payload).unwrap_or_default())
+            }
+            other => {
+                self.report
+                    .issue(location, format!("Body mode '{}' is not supported", other));
+                BodyType::None
+            }
+        };
+
+        // Postman sends these content types without a header in the request
+        let implied = match request.body {
+            BodyType::Json(_) => Some("application/json"),
+            BodyType::Xml(_) => Some("application/xml"),
+            _ => None,
+        };
+        if let Some(content_type) = implied {
+            if header_value(request, "content-type").is_none() {
+                request
+                    .headers
+                    .push(KeyValue::new("Content-Type", content_type));
+            }
+        }
+    }
+
+    fn form_data(&mut self, fields: &[Value], location: &str) -> Vec<KeyValue> {
+        let mut imported = vec![];
+        for field in fields {
+            let mut key_value = key_value(field);
+            if str_field(field, "type") == Some("file") {
+                let paths: Vec<&str> = match field.get("src") {
+                    Some(Value::String(src)) => vec![src.as_str()],
+                    Some(Value::Array(srcs)) => srcs.iter().filter_map(Value::as_str).collect(),
+                    _ => vec![],
+                };
+                if paths.is_empty() {
+                    self.report.issue(
+                        location,
+                        format!("Form file '{}' has no file selected", key_value.key),
+                    );
+                } else if paths.len() > 1 {
+                    self.report.issue(
+                        location,
+                        format!(
+                            "Form file '{}' lists several files, only the first was kept",
+                            key_value.key
+                        ),
+                    );
+                }
+                key_value.value = paths.first().map(|p| p.to_string()).unwrap_or_default();
+                key_value.param_type = FormDataParamType::File;
+            }
+            imported.push(key_value);
+        }
+        imported
+    }
+
+    fn auth(&mut self, auth: Option<&Value>, request: &mut Request, location: &str) {
+        let Some(auth) = auth else {
+            return;
+        };
+        let kind = str_field(auth, "type").unwrap_or("noauth");
+        let param = |name: &str| auth_param(auth, kind, name).unwrap_or_default();
+
+        match kind {
+            "noauth" => {}
+            "bearer" => add_header(
+                request,
+                "Authorization",
+                format!("Bearer {}", param("token")),
+            ),
+            // Sent encoded once variables are resolved
+            "basic" => {
+                request.auth = AuthScheme::Basic {
+                    username: param("username"),
+                    password: param("password"),
+                }
+                .to_fields()
+            }
+            "apikey" => {
+                let key = param("key");
+                let value = param("value");
+                if param("in") == "query" {
+                    request.query_params.push(KeyValue::new(key, value));
+                } else {
+                    add_header(request, &key, value);
+                }
+            }
+            other => {
+                request.auth = vec![KeyValue::new("type", other)];
+                if let Some(params) = auth.get(other).and_then(Value::as_array) {
+                    request.auth.extend(key_values(params));
+                }
+                self.report.issue(
+                    location,
+                    format!(
+                        "{} auth is not supported, its settings were kept in the Auth tab",
+                        other
+                    ),
+                );
+            }
+        }
+    }
+
+    /// Scripts cannot run here, so each one is reported
+    fn events(&mut self, item: &Value, location: &str) {
+        for event in array(item, "event") {
+            let exec = event.pointer("/script/exec");
+            let source = match exec {
+                Some(Value::Array(lines)) => lines
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+                Some(Value::String(source)) => source.clone(),
+                _ => String::new(),
+            };
+            if source.trim().is_empty() {
+                continue;
+            }
+            let message = match str_field(event, "listen") {
+                Some("prerequest") => "Pre-request script was not imported".to_string(),
+                Some("test") => "Test script was not imported".to_string(),
+                Some(other) => format!("'{}' script was not imported", other),
+                None => "Script was not imported".to_string(),
+            };
+            self.report.issue(location, message);
+        }
+    }
+
+    /// The first saved response becomes the request's example
+    fn responses(&mut self, responses: &[Value], request: &mut Request, location: &str) {
+        let Some(first) = responses.first() else {
+            return;
+        };
+        request.example = Some(ResponseExample {
+            status: first
+                .get("code")
+                .and_then(Value::as_u64)
+                .and_then(|code| u16::try_from(code).ok())
+                .unwrap_or(200),
+            headers: key_values(array(first, "header")),
+            body: str_field(first, "body").unwrap_or_default().to_string(),
+            saved_at: chrono::Local::now().to_rfc3339(),
+            ignore: vec![],
+        });
+        if responses.len() > 1 {
+            self.report.issue(
+                location,
+                format!(
+                    "{} more saved responses were not imported, only the first became the example",
+                    responses.len() - 1
+                ),
+            );
+        }
+    }
+
+    fn dynamic_variables(&mut self, request: &Request, location: &str) {
+        let mut texts = vec![request.url.as_str()];
+        texts.extend(request.headers.iter().map(|h| h.value.as_str()));
+        texts.extend(request.query_params.iter().map(|p| p.value.as_str()));
+        if let BodyType::Json(body) | BodyType::Xml(body) | BodyType::Text(body) = &request.body {
+            texts.push(body);
+        }
+
+        let mut names: Vec<String> = vec![];
+        for text in texts {
+            for name in variables::referenced_variables(text) {
+                if name.starts_with('$') && !names.contains(&name) {
+                    names.push(name);
+                }
+            }
+        }
+        if !names.is_empty() {
+            self.report.issue(
+                location,
+                format!(
+                    "Postman dynamic variables are not resolved: {}",
+                    names
+                        .iter()
+                        .map(|name| format!("{{{{{}}}}}", name))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            );
+        }
+    }
+}
+
+/// `None` and `inherit` fall back to the auth of the enclosing folder
+fn active_auth<'a>(auth: Option<&'a Value>, inherited: Option<&'a Value>) -> Option<&'a Value> {
+    match auth.filter(|auth| auth.is_object()) {
+        Some(auth) if str_field(auth, "type") != Some("inherit") => Some(auth),
+        _ => inherited,
+    }
+}
+
+/// Auth parameters are a key/value array in v2.1 and an object in v2.0
+fn auth_param(auth: &Value, kind: &str, name: &str) -> Option<String> {
+    match auth.get(kind)? {
+        Value::Array(params) => params
+            .iter()
+            .find(|p| str_field(p, "key") == Some(name))
+            .map(|p| value_string(p.get("value"))),
+        Value::Object(params) => Some(value_string(params.get(name))),
+        _ => None,
+    }
+}
+
+fn add_header(request: &mut Request, key: &str, value: String) {
+    // An explicit header in the request wins over the one auth would add
+    if header_value(request, key).is_none() {
+        request.headers.push(KeyValue::new(key, value));
+    }
+}
+
+fn header_value<'a>(request: &'a Request, key: &str) -> Option<&'a str> {
+    request
+        .headers
+        .iter()
+        .find(|h| h.enabled && h.key.eq_ignore_ascii_case(key))
+        .map(|h| h.value.as_str())
+}
+
+/// URL from its parts, for exports without `raw`
+fn build_url(url: &Value) -> String {
+    let join = |value: Option<&Value>, separator: &str| match value {
+        Some(Value::String(part)) => part.clone(),
+        Some(Value::Array(parts)) => parts
+            .iter()
+            .map(|part| match part {
+                Value::String(part) => part.clone(),
+                other => value_string(other.get("value")),
+            })
+            .collect::<Vec<_>>()
+            .join(separator),
+        _ => String::new(),
+    };
+
+    let mut built = String::new();
+    if let Some(protocol) = str_field(url, "protocol") {
+        built.push_str(&format!("{}://", protocol));
+    }
+    built.push_str(&join(url.get("host"), "."));
+    if let Some(port) = url.get("port") {
+        built.push_str(&format!(":{}", value_string(Some(port))));
+    }
+    let path = join(url.get("path"), "/");
+    if !path.is_empty() {
+        built.push('/');
+        built.push_str(path.trim_start_matches('/'));
+    }
+    built
+}
+
+fn location(parents: &[&str], name: &str) -> String {
+    let mut parts = parents.to_vec();
+    parts.push(name);
+    parts.join(" / ")
+}
+
+/// Descriptions are a plain string or `{ "content": ... }`
+fn description(value: &Value) -> Option<String> {
+    let text = match value.get("description")? {
+        Value::String(text) => text.as_str(),
+        other => other.get("content")?.as_str()?,
+    };
+    (!text.trim().is_empty()).then(|| text.to_string())
+}
+
+fn key_values(items: &[Value]) -> Vec<KeyValue> {
+    items
+        .iter()
+        .filter(|item| item.is_object())
+        .map(key_value)
+        .collect()
+}
+
+fn key_value(item: &Value) -> KeyValue {
+    let mut key_value = KeyValue::new(
+        str_field(item, "key").unwrap_or_default(),
+        value_string(item.get("value")),
+    );
+    key_value.enabled = item.get("disabled") != Some(&Value::Bool(true));
+    key_value
+}
+
+fn str_field<'a>(value: &'a Value, key: &str) -> Option<&'a str> {
+    value.get(key).and_then(Value::as_str)
+}
+
+fn array<'a>(value: &'a Value, key: &str) -> &'a [Value] {
+    value
+        .get(key)
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+}
+
+/// Variable values may be numbers or booleans in exports
+fn value_string(value: Option<&Value>) -> String {
+    match value {
+        None | Some(Value::Null) => String::new(),
+        Some(Value::String(text)) => text.clone(),
+        Some(other) => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Credential;
+
+    fn collection() -> Value {
+        json!({
+            "info": {
+                "name": "Shop",
+                "schema": "https://schema.getpostman.com/json/collection/v2.1.0/collection.json"
+            },
+            "auth": {"type": "bearer", "bearer": [{"key": "token", "value": "{{token}}", "type": "string"}]},
+            "variable": [
+                {"key": "baseUrl", "value": "https://shop.example.com"},
+                {"key": "old", "value": "x", "disabled": true}
+            ],
+            "event": [{"listen": "prerequest", "script": {"exec": ["console.log(1)"]}}],
+            "item": [
+                {
+                    "name": "Orders",
+                    "item": [
+                        {
+                            "name": "Get order",
+                            "event": [{"listen": "test", "script": {"exec": ["pm.test('ok')"]}}],
+                            "request": {
+                                "method": "GET",
+                                "description": "Fetch one order",
+                                "header": [{"key": "Accept", "value": "application/json"}],
+                                "url": {
+                                    "raw": "{{baseUrl}}/orders/:id?expand=items",
+                                    "host": ["{{baseUrl}}"],
+                                    "path": ["orders", ":id"],
+                                    "query": [
+                                        {"key": "expand", "value": "items"},
+                                        {"key": "debug", "value": "1", "disabled": true}
+                                    ],
+                                    "variable": [{"key": "id", "value": "42"}]
+                                }
+                            },
+                            "response": [
+                                {"name": "ok", "code": 200, "header": [], "body": "{\"id\":42}"},
+                                {"name": "missing", "code": 404, "body": ""}
+                            ]
+                        },
+                        {
+                            "name": "Create order",
+                            "request": {
+                                "method": "POST",
+                                "auth": {"type": "apikey", "apikey": [
+                                    {"key": "key", "value": "X-Api-Key"},
+                                    {"key": "value", "value": "{{apiKey}}"}
+                                ]},
+                                "url": "{{baseUrl}}/orders",
+                                "body": {
+                                    "mode": "raw",
+                                    "raw": "{\"sku\": \"{{$guid}}\"}",
+                                    "options": {"raw": {"language": "json"}}
+                                }
+                            }
+                        }
+                    ]
+                },
+                {
+                    "name": "Upload",
+                    "request": {
+                        "method": "PUT",
+                        "auth": {"type": "basic", "basic": [
+                            {"key": "username", "value": "admin"},
+                            {"key": "password", "value": "secret"}
+                        ]},
+                        "url": "{{baseUrl}}/upload",
+                        "body": {"mode": "formdata", "formdata": [
+                            {"key": "name", "value": "doc", "type": "text"},
+                            {"key": "file", "type": "file", "src": "/tmp/a.pdf"}
+                        ]}
+                    }
+                },
+                {
+                    "name": "Search",
+                    "request": {
+                        "method": "POST",
+                        "auth": {"type": "oauth2", "oauth2": [{"key": "grant_type", "value": "client_credentials"}]},
+                        "url": "{{baseUrl}}/graphql",
+                        "body": {"mode": "graphql", "graphql": {"query": "{ orders { id } }", "variables": "{\"n\": 1}"}}
+                    }
+                },
+                {
+                    "name": "Login",
+                    "request": {
+                        "method": "POST",
+                        "auth": {"type": "noauth"},
+                        "url": "{{baseUrl}}/login",
+                        "body": {"mode": "urlencoded", "urlencoded": [
+                            {"key": "user", "value": "me"},
+                            {"key": "remember", "value": "1", "disabled": true}
+                        ]}
+                    }
+                }
+            ]
+        })
+    }
+
+    fn request(items: &[CollectionItem], index: usize) -> &Request {
+        match &items[index] {
+            CollectionItem::Request(request) => request,
+            _ => panic!("expected a request"),
+        }
+    }
+
+    #[test]
+    fn test_import_collection() {
+        let result = import_collection(&collection()).unwrap();
+        let collection = result.collection.unwrap();
+        assert_eq!(collection.name, "Shop");
+        assert_eq!(result.report.requests, 5);
+        assert_eq!(result.report.folders, 1);
+        assert_eq!(result.variables.len(), 1);
+        assert_eq!(result.variables["baseUrl"], "https://shop.example.com");
+        assert!(!result.overwrite);
+
+        let CollectionItem::Folder(orders) = &collection.items[0] else {
+            panic!("expected a folder");
+        };
+        let get = request(&orders.items, 0);
+        assert_eq!(get.url, "{{baseUrl}}/orders/42");
+        assert_eq!(get.description, "Fetch one order");
+        assert_eq!(get.query_params.len(), 2);
+        assert!(!get.query_params[1].enabled);
+        // Inherited from the collection
+        assert_eq!(get.headers[1].key, "Authorization");
+        assert_eq!(get.headers[1].value, "Bearer {{token}}");
+        assert_eq!(get.example.as_ref().unwrap().body, "{\"id\":42}");
+
+        let create = request(&orders.items, 1);
+        assert_eq!(create.method, HttpMethod::POST);
+        assert!(matches!(create.body, BodyType::Json(_)));
+        let headers: Vec<(&str, &str)> = create
+            .headers
+            .iter()
+            .map(|h| (h.key.as_str(), h.value.as_str()))
+            .collect();
+        assert_eq!(
+            headers,
+            vec![
+                ("Content-Type", "application/json"),
+                ("X-Api-Key", "{{apiKey}}")
+            ]
+        );
+
+        let upload = request(&collection.items, 1);
+        assert!(upload.headers.is_empty());
+        assert!(matches!(
+            AuthScheme::from_fields(&upload.auth),
+            Some(AuthScheme::Basic { username, .. }) if username == "admin"
+        ));
+        let BodyType::FormData(fields) = &upload.body else {
+            panic!("expected multipart body");
+        };
+        assert_eq!(fields[1].value, "/tmp/a.pdf");
+        assert_eq!(fields[1].param_type, FormDataParamType::File);
+
+        let search = request(&collection.items, 2);
+        assert_eq!(search.auth[0].value, "oauth2");
+        let BodyType::Json(graphql) = &search.body else {
+            panic!("expected GraphQL as JSON");
+        };
+        let graphql: Value = serde_json::from_str(graphql).unwrap();
+        assert_eq!(graphql["variables"]["n"], 1);
+
+        let login = request(&collection.items, 3);
+        assert!(login.headers.is_empty());
+        let BodyType::FormUrlEncoded(fields) = &login.body else {
+            panic!("expected urlencoded body");
+        };
+        assert!(!fields[1].enabled);
+    }
+
+    #[test]
+    fn test_import_basic_auth_with_variables() {
+        let value = json!({
+            "info": {"name": "Admin", "schema": "https://schema.getpostman.com/json/collection/v2.1.0/collection.json"},
+            "auth": {"type": "basic", "basic": [
+                {"key": "username", "value": "{{user}}"},
+                {"key": "password", "value": "{{password}}"}
+            ]},
+            "item": [{"name": "Stats", "request": {"method": "GET", "url": "https://admin.example.com/stats"}}]
+        });
+        let result = import_collection(&value).unwrap();
+        assert!(result.report.issues.is_empty());
+        let collection = result.collection.unwrap();
+
+        let values = HashMap::from([
+            ("user".to_string(), "admin".to_string()),
+            ("password".to_string(), "secret".to_string()),
+        ]);
+        let resolved = variables::resolve_request(request(&collection.items, 0), &values);
+        assert_eq!(
+            AuthScheme::from_fields(&resolved.auth).and_then(|auth| auth.credential()),
+            Some(Credential::Header(
+                "Authorization".to_string(),
+                "Basic YWRtaW46c2VjcmV0".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_import_collection_reports_unsupported() {
+        let report = import_collection(&collection()).unwrap().report;
+        let issues: Vec<(&str, &str)> = report
+            .issues
+            .iter()
+            .map(|i| (i.location.as_str(), i.message.as_str()))
+            .collect();
+
+        assert!(issues.contains(&("Shop", "Pre-request script was not imported")));
+        assert!(issues.contains(&("Shop", "Disabled variable 'old' was skipped")));
+        assert!(issues.contains(&("Orders / Get order", "Test script was not imported")));
+        assert!(issues.contains(&(
+            "Orders / Get order",
+            "1 more saved responses were not imported, only the first became the example"
+        )));
+        assert!(issues.contains(&(
+            "Orders / Create order",
+            "Postman dynamic variables are not resolved: {{$guid}}"
+        )));
+        assert!(
+            issues
+                .iter()
+                .any(|(location, message)| *location == "Search"
+                    && message.starts_with("oauth2 auth"))
+        );
+        assert_eq!(issues.len(), 6);
+    }
+
+    #[test]
+    fn test_import_environment() {
+        let value = json!({
+            "name": "Staging",
+            "values": [
+                {"key": "token", "value": "abc", "enabled": true},
+                {"key": "port", "value": 8080, "enabled": true},
+                {"key": "off", "value": "x", "enabled": false}
+            ]
+        });
+        assert!(is_environment(&value));
+        assert!(!is_collection(&value));
+
+        let result = import_environment(&value).unwrap();
+        assert_eq!(result.environment, Some(Environment::Staging));
+        assert!(result.overwrite);
+        assert_eq!(result.variables["port"], "8080");
+        assert_eq!(result.variables.len(), 2);
+        assert_eq!(result.report.issues.len(), 1);
+    }
+
+    #[test]
+    fn test_export_round_trip() {
+        let mut original = Request {
+            name: "Create order".to_string(),
+            method: HttpMethod::POST,
+            url: "https://{{host}}:8443/orders/{{id}}?draft=1".to_string(),
+            headers: vec![KeyValue::new("Accept", "application/json")],
+            query_params: vec![KeyValue::new("expand", "items")],
+            auth: vec![
+                KeyValue::new("key", "X-Api-Key"),
+                KeyValue::new("value", "{{apiKey}}"),
+                KeyValue::new("in", "query"),
+            ],
+            body: BodyType::Json("{\"qty\": 1}".to_string()),
+            description: "Places an order".to_string(),
+            insecure: true,
+            ..Default::default()
+        };
+        original.headers[0].enabled = false;
+        let items = vec![CollectionItem::Folder(Folder {
+            id: Uuid::new_v4(),
+            name: "Orders".to_string(),
+            items: vec![CollectionItem::Request(original)],
+            expanded: false,
+        })];
+
+        let exported = export_collection("Shop", &items);
+        assert!(is_collection(&exported));
+        let url = exported.pointer("/item/0/item/0/request/url").unwrap();
+        assert_eq!(url["port"], "8443");
+        assert_eq!(url["path"], json!(["orders", "{{id}}"]));
+
+        let result = import_collection(&exported).unwrap();
+        let CollectionItem::Folder(folder) = &result.collection.unwrap().items[0] else {
+            panic!("expected a folder");
+        };
+        let imported = request(&folder.items, 0);
+        assert_eq!(imported.name, "Create order");
+        assert_eq!(imported.method, HttpMethod::POST);
+        assert_eq!(imported.url, "https://{{host}}:8443/orders/{{id}}");
+        assert_eq!(imported.query_params.len(), 3);
+        assert_eq!(imported.query_params[2].key, "X-Api-Key");
+        assert!(!imported.headers[0].enabled);
+        assert!(matches!(&imported.body, BodyType::Json(text) if text == "{\"qty\": 1}"));
+        assert_eq!(imported.description, "Places an order");
+        assert!(imported.insecure);
+    }
+}
@@ -0,0 +1,287 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::models::{Collection, CollectionItem, DiffLine, ReloadConflict, Request};
+use crate::utils::navigation::collect_requests;
+
+/// Whether two values serialize identically
+///
+/// Models do not implement `PartialEq`, and comparing their serialized form
+/// matches what ends up on disk.
+pub fn same<T: Serialize>(a: &T, b: &T) -> bool {
+    match (serde_json::to_value(a), serde_json::to_value(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
+
+/// Whether any request of `current` differs from its saved version
+pub fn has_edits(saved: &Collection, current: &Collection) -> bool {
+    let saved = requests_by_id(&saved.items);
+    collect_requests(&current.items)
+        .iter()
+        .any(|request| saved.get(&request.id).is_some_and(|s| !same(s, request)))
+}
+
+/// Carry the unsaved edits of a collection over to its version read from disk
+///
+/// `saved` is the collection as last read or written, `current` the one in
+/// the app and `disk` the one just read. Edits to requests that did not
+/// change on disk are kept. Requests changed on both sides keep their disk
+/// version and are returned as conflicts.
+pub fn merge(
+    saved: &Collection,
+    current: &Collection,
+    mut disk: Collection,
+) -> (Collection, Vec<ReloadConflict>) {
+    let saved = requests_by_id(&saved.items);
+    let theirs = requests_by_id(&disk.items);
+    let mut conflicts = Vec::new();
+
+    for mine in collect_requests(&current.items) {
+        let Some(base) = saved.get(&mine.id) else {
+            continue;
+        };
+        if same(base, &mine) {
+            continue;
+        }
+        match theirs.get(&mine.id) {
+            Some(theirs) if same(theirs, base) => {
+                replace_request(&mut disk.items, &mine);
+            }
+            // The same edit was made on both sides
+            Some(theirs) if same(theirs, &mine) => {}
+            theirs => conflicts.push(ReloadConflict {
+                request_id: mine.id,
+                name: mine.name.clone(),
+                theirs: theirs.cloned(),
+                mine,
+            }),
+        }
+    }
+    (disk, conflicts)
+}
+
+/// Replace the request with the same id, returning `false` if there is none
+pub fn replace_request(items: &mut [CollectionItem], request: &Request) -> bool {
+    items.iter_mut().any(|item| match item {
+        CollectionItem::Request(existing) if existing.id == request.id => {
+            *existing = request.clone();
+            true
+        }
+        CollectionItem::Folder(folder) => replace_request(&mut folder.items, request),
+        _ => false,
+    })
+}
+
+/// Where the item at `path` in `old` ended up in `new`, matched by id
+///
+/// Paths are relative to the items; an empty path stays empty.
+pub fn remap_path(
+    old: &[CollectionItem],
+    new: &[CollectionItem],
+    path: &[usize],
+) -> Option<Vec<usize>> {
+    let mut items = old;
+    let mut id = None;
+    for &index in path {
+        let item = items.get(index)?;
+        id = Some(item_id(item));
+        items = match item {
+            CollectionItem::Folder(folder) => &folder.items,
+            _ => &[],
+        };
+    }
+    match id {
+        Some(id) => find_item(new, id),
+        None => Some(vec![]),
+    }
+}
+
+/// Path of the item with the given id
+pub fn find_item(items: &[CollectionItem], id: Uuid) -> Option<Vec<usize>> {
+    for (index, item) in items.iter().enumerate() {
+        if item_id(item) == id {
+            return Some(vec![index]);
+        }
+        if let CollectionItem::Folder(folder) = item {
+            if let Some(mut path) = find_item(&folder.items, id) {
+                path.insert(0, index);
+                return Some(path);
+            }
+        }
+    }
+    None
+}
+
+fn item_id(item: &CollectionItem) -> Uuid {
+    match item {
+        CollectionItem::Request(request) => request.id,
+        CollectionItem::Folder(folder) => folder.id,
+        CollectionItem::Flow(flow) => flow.id,
+    }
+}
+
+fn requests_by_id(items: &[CollectionItem]) -> HashMap<Uuid, Request> {
+    collect_requests(items)
+        .into_iter()
+        .map(|request| (request.id, request))
+        .collect()
+}
+
+/// Line diff of the two sides of a conflict, as pretty-printed JSON
+pub fn diff(conflict: &ReloadConflict) -> Vec<DiffLine> {
+    let mine = serde_json::to_string_pretty(&conflict.mine).unwrap_or_default();
+    let theirs = conflict
+        .theirs
+        .as_ref()
+        .and_then(|theirs| serde_json::to_string_pretty(theirs).ok())
+        .unwrap_or_default();
+    diff_lines(&mine, &theirs)
+}
+
+/// Longest-common-subsequence diff, which is plenty for a single request
+fn diff_lines(mine: &str, theirs: &str) -> Vec<DiffLine> {
+    let a: Vec<&str> = mine.lines().collect();
+    let b: Vec<&str> = theirs.lines().collect();
+
+    // lengths[i][j] is the length of the common subsequence of a[i..] and b[j..]
+    let mut lengths = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lengths[i][j] = if a[i] == b[j] {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+
+    let mut lines = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        if a[i] == b[j] {
+            lines.push(DiffLine::Both(a[i].to_string()));
+            i += 1;
+            j += 1;
+        } else if lengths[i + 1][j] >= lengths[i][j + 1] {
+            lines.push(DiffLine::Mine(a[i].to_string()));
+            i += 1;
+        } else {
+            lines.push(DiffLine::Theirs(b[j].to_string()));
+            j += 1;
+        }
+    }
+    lines.extend(a[i..].iter().map(|line| DiffLine::Mine(line.to_string())));
+    lines.extend(b[j..].iter().map(|line| DiffLine::Theirs(line.to_string())));
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Folder;
+
+    fn request(name: &str, url: &str) -> Request {
+        Request {
+            name: name.to_string(),
+            url: url.to_string(),
+            ..Request::default()
+        }
+    }
+
+    fn collection(items: Vec<CollectionItem>) -> Collection {
+        Collection {
+            id: Uuid::new_v4(),
+            name: "Api".to_string(),
+            items,
+            expanded: true,
+            http_file: None,
+        }
+    }
+
+    fn edit(collection: &mut Collection, index: usize, url: &str) {
+        if let CollectionItem::Request(request) = &mut collection.items[index] {
+            request.url = url.to_string();
+        }
+    }
+
+    fn url(collection: &Collection, index: usize) -> &str {
+        match &collection.items[index] {
+            CollectionItem::Request(request) => &request.url,
+            _ => "",
+        }
+    }
+
+    #[test]
+    fn test_merge_keeps_unsaved_edits() {
+        let saved = collection(vec![
+            CollectionItem::Request(request("List", "/users")),
+            CollectionItem::Request(request("Get", "/users/1")),
+        ]);
+        let mut current = saved.clone();
+        edit(&mut current, 0, "/users?page=2");
+        let mut disk = saved.clone();
+        edit(&mut disk, 1, "/users/2");
+
+        assert!(has_edits(&saved, &current));
+        let (merged, conflicts) = merge(&saved, &current, disk);
+        assert!(conflicts.is_empty());
+        assert_eq!(url(&merged, 0), "/users?page=2");
+        assert_eq!(url(&merged, 1), "/users/2");
+    }
+
+    #[test]
+    fn test_merge_reports_conflicts() {
+        let saved = collection(vec![
+            CollectionItem::Request(request("List", "/users")),
+            CollectionItem::Request(request("Get", "/users/1")),
+        ]);
+        let mut current = saved.clone();
+        edit(&mut current, 0, "/mine");
+        edit(&mut current, 1, "/mine/1");
+        let mut disk = saved.clone();
+        edit(&mut disk, 0, "/theirs");
+        disk.items.remove(1);
+
+        let (merged, conflicts) = merge(&saved, &current, disk);
+        assert_eq!(conflicts.len(), 2);
+        assert_eq!(url(&merged, 0), "/theirs");
+        assert_eq!(conflicts[0].theirs.as_ref().unwrap().url, "/theirs");
+        assert!(conflicts[1].theirs.is_none());
+    }
+
+    #[test]
+    fn test_remap_path_follows_moves() {
+        let get = request("Get", "/users/1");
+        let old = vec![
+            CollectionItem::Request(request("List", "/users")),
+            CollectionItem::Request(get.clone()),
+        ];
+        let new = vec![CollectionItem::Folder(Folder {
+            id: Uuid::new_v4(),
+            name: "Users".to_string(),
+            items: vec![CollectionItem::Request(get)],
+            expanded: true,
+        })];
+
+        assert_eq!(remap_path(&old, &new, &[1]), Some(vec![0, 0]));
+        assert_eq!(remap_path(&old, &new, &[0]), None);
+        assert_eq!(remap_path(&old, &new, &[]), Some(vec![]));
+    }
+
+    #[test]
+    fn test_diff_lines() {
+        assert_eq!(
+            diff_lines("a\nb\nc", "a\nx\nc"),
+            vec![
+                DiffLine::Both("a".to_string()),
+                DiffLine::Mine("b".to_string()),
+                DiffLine::Theirs("x".to_string()),
+                DiffLine::Both("c".to_string()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,176 @@
+use crate::models::{RequestRunResult, RunReport};
+
+/// Render a run report as JUnit XML, one test suite per iteration
+///
+/// Transport errors become `<error>` elements and failed assertions
+/// `<failure>` elements, which is how CI servers tell them apart.
+pub fn junit_xml(report: &RunReport) -> String {
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str(&format!(
+        "<testsuites name=\"{}\" tests=\"{}\" failures=\"{}\" time=\"{}\">\n",
+        escape(&report.name),
+        report.results.len(),
+        report.failed_count(),
+        seconds(report.duration_ms)
+    ));
+
+    let groups = report.iterations();
+    let numbered = groups.len() > 1;
+    for group in groups {
+        let suite_name = if numbered {
+            format!("{} (iteration {})", report.name, group.iteration)
+        } else {
+            report.name.clone()
+        };
+        let errors = group.results.iter().filter(|r| r.error.is_some()).count();
+        let failures = group
+            .results
+            .iter()
+            .filter(|r| r.error.is_none() && !r.passed())
+            .count();
+        let time: u64 = group.results.iter().map(|r| r.time_ms).sum();
+
+        xml.push_str(&format!(
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"{}\" time=\"{}\" timestamp=\"{}\">\n",
+            escape(&suite_name),
+            group.results.len(),
+            failures,
+            errors,
+            seconds(time),
+            escape(&report.started_at)
+        ));
+        for result in &group.results {
+            xml.push_str(&testcase(&report.name, result));
+        }
+        xml.push_str("  </testsuite>\n");
+    }
+
+    xml.push_str("</testsuites>\n");
+    xml
+}
+
+fn testcase(class_name: &str, result: &RequestRunResult) -> String {
+    let mut xml = format!(
+        "    <testcase name=\"{} {}\" classname=\"{}\" time=\"{}\"",
+        result.method.as_str(),
+        escape(&result.name),
+        escape(class_name),
+        seconds(result.time_ms)
+    );
+
+    if let Some(error) = &result.error {
+        xml.push_str(&format!(
+            ">\n      <error message=\"{}\">{}</error>\n    </testcase>\n",
+            escape(error),
+            escape(&result.url)
+        ));
+        return xml;
+    }
+
+    let failed: Vec<String> = result
+        .assertions
+        .iter()
+        .filter(|a| !a.passed)
+        .map(|a| match &a.message {
+            Some(message) => format!("{}: {}", a.name, message),
+            None => a.name.clone(),
+        })
+        .collect();
+    if failed.is_empty() {
+        xml.push_str("/>\n");
+    } else {
+        xml.push_str(&format!(
+            ">\n      <failure message=\"{}\">{}</failure>\n    </testcase>\n",
+            escape(&failed[0]),
+            escape(&failed.join("\n"))
+        ));
+    }
+    xml
+}
+
+fn seconds(ms: u64) -> String {
+    format!("{:.3}", ms as f64 / 1000.0)
+}
+
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            // Control characters other than whitespace are not valid XML 1.0
+            c if c.is_control() && !matches!(c, '\n' | '\r' | '\t') => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{AssertionResult, HttpMethod, RunOptions};
+
+    fn result(
+        name: &str,
+        assertions: Vec<AssertionResult>,
+        error: Option<&str>,
+    ) -> RequestRunResult {
+        RequestRunResult {
+            iteration: 1,
+            request_id: uuid::Uuid::new_v4(),
+            name: name.to_string(),
+            method: HttpMethod::GET,
+            url: "http://localhost/a?x=1&y=2".to_string(),
+            status: error.is_none().then_some(200),
+            time_ms: 1500,
+            assertions,
+            error: error.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_junit_xml() {
+        let report = RunReport {
+            name: "Users & Auth".to_string(),
+            started_at: "2024-01-01T00:00:00+00:00".to_string(),
+            duration_ms: 4500,
+            options: RunOptions::default(),
+            results: vec![
+                result(
+                    "List",
+                    vec![AssertionResult::pass("Status code is successful")],
+                    None,
+                ),
+                result(
+                    "Get <one>",
+                    vec![AssertionResult::fail(
+                        "Status code is successful",
+                        "Got 404",
+                    )],
+                    None,
+                ),
+                result("Down", vec![], Some("connection refused")),
+            ],
+            aborted: false,
+            data_rows: vec![],
+        };
+
+        let xml = junit_xml(&report);
+        assert!(xml.contains(
+            "<testsuites name=\"Users &amp; Auth\" tests=\"3\" failures=\"2\" time=\"4.500\">"
+        ));
+        assert!(xml.contains("failures=\"1\" errors=\"1\""));
+        assert!(xml.contains(
+            "<testcase name=\"GET List\" classname=\"Users &amp; Auth\" time=\"1.500\"/>"
+        ));
+        assert!(xml.contains("<failure message=\"Status code is successful: Got 404\">"));
+        assert!(xml.contains("name=\"GET Get &lt;one&gt;\""));
+        assert!(xml.contains(
+            "<error message=\"connection refused\">http://localhost/a?x=1&amp;y=2</error>"
+        ));
+    }
+}
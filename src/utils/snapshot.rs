@@ -0,0 +1,278 @@
+use serde_json::Value;
+
+use crate::models::{DiffEntry, DiffKind, Response, ResponseExample, SnapshotDiff};
+
+/// Compare a response with the pinned example of its request
+///
+/// JSON bodies are compared structurally, so key order and formatting do not
+/// matter. Other bodies are compared as text. Headers are too volatile to
+/// compare one by one; only the media type of Content-Type is checked.
+pub fn compare(example: &ResponseExample, response: &Response) -> SnapshotDiff {
+    let mut diff = SnapshotDiff::default();
+
+    if example.status != response.status {
+        diff.status = Some((example.status, response.status));
+    }
+
+    let expected_type = example
+        .headers
+        .iter()
+        .find(|h| h.key.eq_ignore_ascii_case("content-type"))
+        .map(|h| media_type(&h.value))
+        .unwrap_or_default();
+    let actual_type = response
+        .headers
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case("content-type"))
+        .map(|(_, value)| media_type(value))
+        .unwrap_or_default();
+    if expected_type != actual_type {
+        diff.content_type = Some((expected_type, actual_type));
+    }
+
+    match (
+        serde_json::from_str::<Value>(&example.body),
+        serde_json::from_str::<Value>(&response.body),
+    ) {
+        (Ok(expected), Ok(actual)) => {
+            let mut entries = Vec::new();
+            diff_values(&expected, &actual, &mut Vec::new(), &mut entries);
+            let (ignored, kept): (Vec<_>, Vec<_>) = entries
+                .into_iter()
+                .partition(|entry| is_ignored(&entry.pointer, &example.ignore));
+            diff.ignored = ignored.len();
+            diff.body = kept;
+        }
+        _ => {
+            if example.body != response.body {
+                diff.body.push(DiffEntry {
+                    pointer: String::new(),
+                    kind: DiffKind::Changed,
+                    expected: Some(example.body.clone()),
+                    actual: Some(response.body.clone()),
+                });
+            }
+        }
+    }
+
+    diff
+}
+
+/// `application/json; charset=utf-8` -> `application/json`
+fn media_type(content_type: &str) -> String {
+    content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_lowercase()
+}
+
+fn diff_values(expected: &Value, actual: &Value, path: &mut Vec<String>, out: &mut Vec<DiffEntry>) {
+    match (expected, actual) {
+        (Value::Object(expected), Value::Object(actual)) => {
+            for (key, expected_value) in expected {
+                path.push(key.clone());
+                match actual.get(key) {
+                    Some(actual_value) => diff_values(expected_value, actual_value, path, out),
+                    None => out.push(entry(path, DiffKind::Removed, Some(expected_value), None)),
+                }
+                path.pop();
+            }
+            for (key, actual_value) in actual {
+                if !expected.contains_key(key) {
+                    path.push(key.clone());
+                    out.push(entry(path, DiffKind::Added, None, Some(actual_value)));
+                    path.pop();
+                }
+            }
+        }
+        (Value::Array(expected), Value::Array(actual)) => {
+            for index in 0..expected.len().max(actual.len()) {
+                path.push(index.to_string());
+                match (expected.get(index), actual.get(index)) {
+                    (Some(e), Some(a)) => diff_values(e, a, path, out),
+                    (Some(e), None) => out.push(entry(path, DiffKind::Removed, Some(e), None)),
+                    (None, Some(a)) => out.push(entry(path, DiffKind::Added, None, Some(a))),
+                    (None, None) => {}
+                }
+                path.pop();
+            }
+        }
+        _ => {
+            if expected != actual {
+                out.push(entry(path, DiffKind::Changed, Some(expected), Some(actual)));
+            }
+        }
+    }
+}
+
+fn entry(
+    path: &[String],
+    kind: DiffKind,
+    expected: Option<&Value>,
+    actual: Option<&Value>,
+) -> DiffEntry {
+    DiffEntry {
+        pointer: path
+            .iter()
+            .map(|segment| format!("/{}", segment.replace('~', "~0").replace('/', "~1")))
+            .collect(),
+        kind,
+        expected: expected.map(Value::to_string),
+        actual: actual.map(Value::to_string),
+    }
+}
+
+fn pointer_segments(pointer: &str) -> Vec<String> {
+    pointer
+        .split('/')
+        .skip(1)
+        .map(|segment| segment.replace("~1", "/").replace("~0", "~"))
+        .collect()
+}
+
+/// Whether a difference at `pointer` is covered by an ignore rule
+///
+/// A rule starting with `/` is a JSON pointer where `*` matches any key or
+/// index; it also covers everything below it. Any other rule is a field name
+/// ignored at every depth.
+pub fn is_ignored(pointer: &str, rules: &[String]) -> bool {
+    let segments = pointer_segments(pointer);
+
+    rules
+        .iter()
+        .map(|rule| rule.trim())
+        .filter(|rule| !rule.is_empty())
+        .any(|rule| {
+            if rule.starts_with('/') {
+                let rule_segments = pointer_segments(rule);
+                rule_segments.len() <= segments.len()
+                    && rule_segments
+                        .iter()
+                        .zip(&segments)
+                        .all(|(r, s)| r == "*" || r == s)
+            } else {
+                segments.iter().any(|segment| segment == rule)
+            }
+        })
+}
+
+/// One-line summary of the first few differences, for run reports
+pub fn summarize(diff: &SnapshotDiff) -> String {
+    let mut parts = Vec::new();
+    if let Some((expected, actual)) = diff.status {
+        parts.push(format!("status {} -> {}", expected, actual));
+    }
+    if let Some((expected, actual)) = &diff.content_type {
+        parts.push(format!("content type {} -> {}", expected, actual));
+    }
+    for entry in diff.body.iter().take(5) {
+        let pointer = if entry.pointer.is_empty() {
+            "body"
+        } else {
+            &entry.pointer
+        };
+        let kind = match entry.kind {
+            DiffKind::Added => "added",
+            DiffKind::Removed => "removed",
+            DiffKind::Changed => "changed",
+        };
+        parts.push(format!("{} {}", pointer, kind));
+    }
+    if diff.body.len() > 5 {
+        parts.push(format!("{} more", diff.body.len() - 5));
+    }
+    parts.join("; ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::KeyValue;
+    use std::collections::HashMap;
+
+    fn example(body: &str, ignore: &[&str]) -> ResponseExample {
+        ResponseExample {
+            status: 200,
+            headers: vec![KeyValue::new("Content-Type", "application/json")],
+            body: body.to_string(),
+            saved_at: String::new(),
+            ignore: ignore.iter().map(|rule| rule.to_string()).collect(),
+        }
+    }
+
+    fn response(status: u16, body: &str) -> Response {
+        let mut headers = HashMap::new();
+        headers.insert(
+            "content-type".to_string(),
+            "application/json; charset=utf-8".to_string(),
+        );
+        Response::new(status, String::new(), headers, vec![], body.to_string(), 1)
+    }
+
+    #[test]
+    fn test_compare_structural() {
+        let pinned = example(
+            r#"{"id":1,"name":"a","tags":["x","y"],"meta":{"at":"t1"}}"#,
+            &[],
+        );
+
+        let same = response(
+            200,
+            r#"{"meta":{"at":"t1"},"tags":["x","y"],"name":"a","id":1}"#,
+        );
+        assert!(compare(&pinned, &same).is_match());
+
+        let drifted = response(
+            201,
+            r#"{"id":2,"tags":["x"],"meta":{"at":"t1"},"extra":true}"#,
+        );
+        let diff = compare(&pinned, &drifted);
+        assert_eq!(diff.status, Some((200, 201)));
+        let entries: Vec<(&str, DiffKind)> = diff
+            .body
+            .iter()
+            .map(|e| (e.pointer.as_str(), e.kind))
+            .collect();
+        assert_eq!(
+            entries,
+            vec![
+                ("/id", DiffKind::Changed),
+                ("/name", DiffKind::Removed),
+                ("/tags/1", DiffKind::Removed),
+                ("/extra", DiffKind::Added),
+            ]
+        );
+        assert_eq!(diff.body[0].expected.as_deref(), Some("1"));
+        assert_eq!(diff.body[0].actual.as_deref(), Some("2"));
+    }
+
+    #[test]
+    fn test_compare_ignore_rules() {
+        let pinned = example(
+            r#"{"updatedAt":"t1","items":[{"id":"a","v":1}],"meta":{"request":{"id":"r1"}}}"#,
+            &["updatedAt", "/items/*/id", "/meta"],
+        );
+        let later = response(
+            200,
+            r#"{"updatedAt":"t2","items":[{"id":"b","v":1}],"meta":{"request":{"id":"r2"}}}"#,
+        );
+
+        let diff = compare(&pinned, &later);
+        assert!(diff.is_match());
+        assert_eq!(diff.ignored, 3);
+
+        assert!(is_ignored("/a~1b/id", &["/a~1b".to_string()]));
+        assert!(!is_ignored("/items/0/v", &["/items/*/id".to_string()]));
+    }
+
+    #[test]
+    fn test_compare_text_body() {
+        let pinned = example("hello", &[]);
+        assert!(compare(&pinned, &response(200, "hello"))
+            .content_type
+            .is_none());
+        assert_eq!(compare(&pinned, &response(200, "bye")).body.len(), 1);
+    }
+}
@@ -0,0 +1,137 @@
+/// Number of steps that can be undone
+const LIMIT: usize = 100;
+
+/// A state to go back to, and whether going there has to be saved
+#[derive(Debug, Clone)]
+pub struct Step<T> {
+    pub state: T,
+    /// The edit was written to disk, so undoing or redoing it is too
+    pub saved: bool,
+}
+
+/// Undo and redo stacks of states before and after edits
+///
+/// Edits recorded one after the other with the same group, such as the
+/// keystrokes typed into one field, are undone together.
+#[derive(Debug)]
+pub struct UndoStack<T> {
+    undo: Vec<Step<T>>,
+    redo: Vec<Step<T>>,
+    group: Option<String>,
+}
+
+impl<T> Default for UndoStack<T> {
+    fn default() -> Self {
+        Self {
+            undo: Vec::new(),
+            redo: Vec::new(),
+            group: None,
+        }
+    }
+}
+
+impl<T> UndoStack<T> {
+    /// Whether an edit in `group` continues the last recorded one
+    pub fn continues(&self, group: Option<&str>) -> bool {
+        group.is_some() && self.group.as_deref() == group && !self.undo.is_empty()
+    }
+
+    /// Record the state before an edit; anything undone can no longer be redone
+    pub fn record(&mut self, state: T, saved: bool, group: Option<String>) {
+        self.redo.clear();
+        if self.continues(group.as_deref()) {
+            return;
+        }
+        self.undo.push(Step { state, saved });
+        if self.undo.len() > LIMIT {
+            self.undo.remove(0);
+        }
+        self.group = group;
+    }
+
+    /// The state the next undo goes back to
+    pub fn next_undo(&self) -> Option<&T> {
+        self.undo.last().map(|step| &step.state)
+    }
+
+    /// The state the next redo goes forward to
+    pub fn next_redo(&self) -> Option<&T> {
+        self.redo.last().map(|step| &step.state)
+    }
+
+    /// Step back, given the current state to come back to on redo
+    pub fn undo(&mut self, current: T) -> Option<Step<T>> {
+        let step = self.undo.pop()?;
+        self.redo.push(Step {
+            state: current,
+            saved: step.saved,
+        });
+        self.group = None;
+        Some(step)
+    }
+
+    /// Step forward again, given the current state to come back to on undo
+    pub fn redo(&mut self, current: T) -> Option<Step<T>> {
+        let step = self.redo.pop()?;
+        self.undo.push(Step {
+            state: current,
+            saved: step.saved,
+        });
+        self.group = None;
+        Some(step)
+    }
+
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+        self.group = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_undo_and_redo() {
+        let mut stack = UndoStack::default();
+        let mut text = String::new();
+        for (next, group) in [("h", Some("url")), ("hi", Some("url")), ("hi!", None)] {
+            stack.record(text.clone(), false, group.map(String::from));
+            text = next.to_string();
+        }
+
+        // Typing into one field is undone at once
+        assert_eq!(stack.next_undo().map(String::as_str), Some("hi"));
+        let step = stack.undo(text.clone()).unwrap();
+        assert_eq!(step.state, "hi");
+        let step = stack.undo(step.state).unwrap();
+        assert_eq!(step.state, "");
+        assert!(stack.undo(step.state.clone()).is_none());
+
+        assert_eq!(stack.next_redo().map(String::as_str), Some("hi"));
+        let step = stack.redo(step.state).unwrap();
+        assert_eq!(step.state, "hi");
+        // A new edit drops what was undone
+        stack.record(step.state, true, None);
+        assert!(stack.redo("hi?".to_string()).is_none());
+        let step = stack.undo("hi?".to_string()).unwrap();
+        assert_eq!((step.state.as_str(), step.saved), ("hi", true));
+    }
+
+    #[test]
+    fn test_undo_is_limited() {
+        let mut stack = UndoStack::default();
+        for n in 0..LIMIT + 10 {
+            stack.record(n, false, None);
+        }
+        let mut undone = Vec::new();
+        let mut current = LIMIT + 10;
+        while let Some(step) = stack.undo(current) {
+            current = step.state;
+            undone.push(current);
+        }
+        assert_eq!(undone.len(), LIMIT);
+        assert_eq!(current, 10);
+    }
+}
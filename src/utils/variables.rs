@@ -0,0 +1,138 @@
+use std::collections::HashMap;
+
+use crate::models::{BodyType, KeyValue, Request};
+
+/// Replace `{{name}}` placeholders with values from `variables`
+///
+/// Unknown variables are left untouched so they stay visible to the user.
+pub fn substitute(input: &str, variables: &HashMap<String, String>) -> String {
+    if !input.contains("{{") {
+        return input.to_string();
+    }
+
+    let mut output = String::with_capacity(input.len());
+    let mut rest = input;
+
+    while let Some(start) = rest.find("{{") {
+        output.push_str(&rest[..start]);
+        let after_open = &rest[start + 2..];
+
+        match after_open.find("}}") {
+            Some(end) => {
+                let name = after_open[..end].trim();
+                match variables.get(name) {
+                    Some(value) => output.push_str(value),
+                    None => output.push_str(&rest[start..start + 2 + end + 2]),
+                }
+                rest = &after_open[end + 2..];
+            }
+            None => {
+                output.push_str(&rest[start..]);
+                rest = "";
+            }
+        }
+    }
+
+    output.push_str(rest);
+    output
+}
+
+/// Names of all `{{name}}` placeholders referenced in `input`
+pub fn referenced_variables(input: &str) -> Vec<String> {
+    let mut names = Vec::new();
+    let mut rest = input;
+
+    while let Some(start) = rest.find("{{") {
+        let after_open = &rest[start + 2..];
+        match after_open.find("}}") {
+            Some(end) => {
+                let name = after_open[..end].trim().to_string();
+                if !name.is_empty() && !names.contains(&name) {
+                    names.push(name);
+                }
+                rest = &after_open[end + 2..];
+            }
+            None => break,
+        }
+    }
+
+    names
+}
+
+fn substitute_key_values(items: &[KeyValue], variables: &HashMap<String, String>) -> Vec<KeyValue> {
+    items
+        .iter()
+        .map(|kv| KeyValue {
+            key: substitute(&kv.key, variables),
+            value: substitute(&kv.value, variables),
+            ..kv.clone()
+        })
+        .collect()
+}
+
+/// Build a copy of `request` with all placeholders resolved
+pub fn resolve_request(request: &Request, variables: &HashMap<String, String>) -> Request {
+    let body = match &request.body {
+        BodyType::Json(s) => BodyType::Json(substitute(s, variables)),
+        BodyType::Xml(s) => BodyType::Xml(substitute(s, variables)),
+        BodyType::Text(s) => BodyType::Text(substitute(s, variables)),
+        BodyType::FormUrlEncoded(fields) => {
+            BodyType::FormUrlEncoded(substitute_key_values(fields, variables))
+        }
+        BodyType::FormData(fields) => BodyType::FormData(substitute_key_values(fields, variables)),
+        other => other.clone(),
+    };
+
+    Request {
+        url: substitute(&request.url, variables),
+        headers: substitute_key_values(&request.headers, variables),
+        query_params: substitute_key_values(&request.query_params, variables),
+        cookies: substitute_key_values(&request.cookies, variables),
+        auth: substitute_key_values(&request.auth, variables),
+        body,
+        ..request.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars() -> HashMap<String, String> {
+        let mut map = HashMap::new();
+        map.insert("host".to_string(), "api.example.com".to_string());
+        map.insert("token".to_string(), "secret".to_string());
+        map
+    }
+
+    #[test]
+    fn test_substitute() {
+        let result = substitute("https://{{host}}/users?t={{ token }}", &vars());
+        assert_eq!(result, "https://api.example.com/users?t=secret");
+    }
+
+    #[test]
+    fn test_substitute_keeps_unknown_and_unclosed() {
+        assert_eq!(
+            substitute("{{missing}}/{{host}}", &vars()),
+            "{{missing}}/api.example.com"
+        );
+        assert_eq!(substitute("a {{host", &vars()), "a {{host");
+    }
+
+    #[test]
+    fn test_resolve_request() {
+        let request = Request {
+            url: "https://{{host}}/me".to_string(),
+            headers: vec![KeyValue::new("Authorization", "Bearer {{token}}")],
+            body: BodyType::Json(r#"{"t":"{{token}}"}"#.to_string()),
+            ..Request::default()
+        };
+
+        let resolved = resolve_request(&request, &vars());
+        assert_eq!(resolved.url, "https://api.example.com/me");
+        assert_eq!(resolved.headers[0].value, "Bearer secret");
+        assert!(matches!(resolved.body, BodyType::Json(ref s) if s == r#"{"t":"secret"}"#));
+        assert_eq!(resolved.id, request.id);
+    }
+}